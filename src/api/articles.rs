@@ -33,6 +33,8 @@ struct RawArticleResponse {
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
     published_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    mentions: Vec<String>,
 }
 
 // 列表接口返回的文章数据 (没有content等详细信息)
@@ -191,6 +193,7 @@ impl ArticleService {
             is_bookmarked: None,
             is_clapped: None,
             user_clap_count: None,
+            mentions: Vec::new(),
         }).collect();
         
         Ok(ArticleListResponse {
@@ -259,6 +262,7 @@ impl ArticleService {
             is_bookmarked: None,
             is_clapped: None,
             user_clap_count: None,
+            mentions: Vec::new(),
         }).collect();
         
         let article_count = articles.len() as i32;
@@ -336,6 +340,7 @@ impl ArticleService {
             is_bookmarked: None,
             is_clapped: None,
             user_clap_count: None,
+            mentions: Vec::new(),
         }).collect();
         
         let article_count = articles.len() as i32;
@@ -416,6 +421,7 @@ impl ArticleService {
             is_bookmarked: None,
             is_clapped: None,
             user_clap_count: None,
+            mentions: raw_article.mentions,
         })
     }
     
@@ -496,6 +502,7 @@ impl ArticleService {
             is_bookmarked: None,
             is_clapped: None,
             user_clap_count: None,
+            mentions: raw_article.mentions,
         })
     }
     
@@ -546,6 +553,12 @@ impl ArticleService {
         ).await
     }
     
+    /// 撤销当前用户在这篇文章上的全部点赞
+    pub async fn unclap_article(id: &str) -> ApiResult<()> {
+        let article_id = id.trim_start_matches("article:");
+        API_CLIENT.delete(&format!("/blog/articles/by-id/{}/clap", article_id)).await
+    }
+    
     pub async fn bookmark_article(id: &str, note: Option<String>) -> ApiResult<()> {
         #[derive(serde::Serialize)]
         struct BookmarkRequest {
@@ -621,6 +634,7 @@ impl ArticleService {
             is_bookmarked: None,
             is_clapped: None,
             user_clap_count: None,
+            mentions: Vec::new(),
         }).collect();
         
         Ok(ArticleListResponse {
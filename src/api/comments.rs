@@ -1,6 +1,13 @@
 use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
 use super::client::{ApiClient, ApiResult};
-use crate::models::comment::{Comment, CommentWithAuthor};
+use crate::{
+    collab::anchor::TextAnchor,
+    models::{
+        comment::{Comment, CommentWithAuthor},
+        moderation::{BulkModerationRequest, CommentSettings, ModerationFilter, ModerationListResponse},
+    },
+};
 use once_cell::sync::Lazy;
 
 static API_CLIENT: Lazy<ApiClient> = Lazy::new(ApiClient::new);
@@ -10,6 +17,12 @@ pub struct CreateCommentRequest {
     pub article_id: String,
     pub content: String,
     pub parent_id: Option<String>,
+    // 可以接收提醒的被提及用户名
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub mentions: Vec<String>,
+    // 针对正文某一段的评论
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub anchor: Option<TextAnchor>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -17,7 +30,7 @@ pub struct UpdateCommentRequest {
     pub content: String,
 }
 
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, PartialEq)]
 pub struct CommentListResponse {
     pub comments: Vec<CommentWithAuthor>,
     pub total: i64,
@@ -29,18 +42,59 @@ pub struct CommentListResponse {
 pub struct CommentService;
 
 impl CommentService {
+    /// 分页获取顶级评论，每条评论附带前几条回复
     pub async fn get_article_comments(
         article_id: &str,
         page: Option<i32>,
         per_page: Option<i32>,
         sort: Option<&str>,
-    ) -> ApiResult<Vec<CommentWithAuthor>> {
-        let url = format!("/blog/comments/article/{}", article_id);
+    ) -> ApiResult<CommentListResponse> {
+        let mut url = format!("/blog/comments/article/{}", article_id);
+        let mut params = vec![];
+        if let Some(p) = page { params.push(format!("page={}", p)); }
+        if let Some(pp) = per_page { params.push(format!("per_page={}", pp)); }
+        if let Some(s) = sort { params.push(format!("sort={}", s)); }
+        if !params.is_empty() { url = format!("{}?{}", url, params.join("&")); }
         
-        // 后端返回的是嵌套的评论树结构，不需要分页参数
         API_CLIENT.get(&url).await
     }
     
+    /// 分页获取某条评论的直接回复
+    /// since 之后新增或修改过的评论，各层回复也平铺在列表中，用于轮询时发现新回复
+    pub async fn get_article_comments_since(article_id: &str, since: DateTime<Utc>) -> ApiResult<CommentListResponse> {
+        API_CLIENT
+            .get(&format!(
+                "/blog/comments/article/{}?since={}",
+                article_id,
+                urlencoding::encode(&since.to_rfc3339())
+            ))
+            .await
+    }
+    
+    pub async fn get_comment_replies(
+        comment_id: &str,
+        page: Option<i32>,
+        per_page: Option<i32>,
+    ) -> ApiResult<CommentListResponse> {
+        let mut url = format!("/blog/comments/{}/replies", comment_id);
+        let mut params = vec![];
+        if let Some(p) = page { params.push(format!("page={}", p)); }
+        if let Some(pp) = per_page { params.push(format!("per_page={}", pp)); }
+        if !params.is_empty() { url = format!("{}?{}", url, params.join("&")); }
+        
+        API_CLIENT.get(&url).await
+    }
+    
+    /// 所有针对正文段落的顶级评论（附带回复），用于在正文旁显示
+    pub async fn get_inline_comments(article_id: &str) -> ApiResult<Vec<CommentWithAuthor>> {
+        API_CLIENT.get(&format!("/blog/comments/article/{}/inline", article_id)).await
+    }
+    
+    /// 评论所在的整条讨论：返回顶级评论，replies 中只包含通向该评论的路径
+    pub async fn get_comment_thread(comment_id: &str) -> ApiResult<CommentWithAuthor> {
+        API_CLIENT.get(&format!("/blog/comments/{}/thread", comment_id)).await
+    }
+    
     pub async fn create_comment(request: &CreateCommentRequest) -> ApiResult<Comment> {
         API_CLIENT.post("/blog/comments", request).await
     }
@@ -61,6 +115,36 @@ impl CommentService {
         API_CLIENT.delete(&format!("/blog/comments/{}/clap", id)).await
     }
     
+    /// 当前用户所有文章下的评论，可按筛选条件和文章过滤
+    pub async fn get_moderation_comments(
+        filter: ModerationFilter,
+        article_id: Option<&str>,
+        page: Option<i32>,
+        per_page: Option<i32>,
+    ) -> ApiResult<ModerationListResponse> {
+        let mut url = "/blog/comments/moderation".to_string();
+        let mut params = vec![format!("filter={}", filter.as_str())];
+        if let Some(id) = article_id { params.push(format!("article_id={}", id)); }
+        if let Some(p) = page { params.push(format!("page={}", p)); }
+        if let Some(pp) = per_page { params.push(format!("per_page={}", pp)); }
+        url = format!("{}?{}", url, params.join("&"));
+        
+        API_CLIENT.get(&url).await
+    }
+    
+    /// 对多条评论执行同一个管理操作
+    pub async fn moderate_comments(request: &BulkModerationRequest) -> ApiResult<()> {
+        API_CLIENT.post("/blog/comments/moderation/bulk", request).await
+    }
+    
+    pub async fn get_comment_settings(article_id: &str) -> ApiResult<CommentSettings> {
+        API_CLIENT.get(&format!("/blog/comments/settings/{}", article_id)).await
+    }
+    
+    pub async fn update_comment_settings(article_id: &str, settings: &CommentSettings) -> ApiResult<CommentSettings> {
+        API_CLIENT.put(&format!("/blog/comments/settings/{}", article_id), settings).await
+    }
+    
     pub async fn report_comment(id: &str, reason: &str) -> ApiResult<()> {
         #[derive(Serialize)]
         struct ReportRequest {
@@ -0,0 +1,80 @@
+use super::client::{ApiClient, ApiResult};
+use crate::models::editorial::{
+    CreateDraftCommentRequest, CreateSuggestionRequest, DraftComment, DraftCommentReply,
+    ReplyDraftCommentRequest, ResolveDraftCommentRequest, Suggestion, SuggestionStatus,
+    UpdateSuggestionRequest,
+};
+use once_cell::sync::Lazy;
+
+static API_CLIENT: Lazy<ApiClient> = Lazy::new(ApiClient::new);
+
+// 如果 ID 包含 "article:" 前缀，只取 UUID 部分
+fn article_path(article_id: &str) -> String {
+    format!("/blog/articles/by-id/{}", article_id.trim_start_matches("article:"))
+}
+
+/// 出版物草稿的编辑协作：修改建议与批注，后端只对出版物成员开放
+pub struct EditorialService;
+
+impl EditorialService {
+    pub async fn get_suggestions(article_id: &str) -> ApiResult<Vec<Suggestion>> {
+        API_CLIENT.get(&format!("{}/suggestions", article_path(article_id))).await
+    }
+
+    pub async fn create_suggestion(
+        article_id: &str,
+        request: &CreateSuggestionRequest,
+    ) -> ApiResult<Suggestion> {
+        API_CLIENT.post(&format!("{}/suggestions", article_path(article_id)), request).await
+    }
+
+    pub async fn update_suggestion(
+        article_id: &str,
+        suggestion_id: &str,
+        status: SuggestionStatus,
+    ) -> ApiResult<Suggestion> {
+        API_CLIENT
+            .patch(
+                &format!("{}/suggestions/{}", article_path(article_id), suggestion_id),
+                &UpdateSuggestionRequest { status },
+            )
+            .await
+    }
+
+    pub async fn get_comments(article_id: &str) -> ApiResult<Vec<DraftComment>> {
+        API_CLIENT.get(&format!("{}/draft-comments", article_path(article_id))).await
+    }
+
+    pub async fn create_comment(
+        article_id: &str,
+        request: &CreateDraftCommentRequest,
+    ) -> ApiResult<DraftComment> {
+        API_CLIENT.post(&format!("{}/draft-comments", article_path(article_id)), request).await
+    }
+
+    pub async fn reply_comment(
+        article_id: &str,
+        comment_id: &str,
+        body: String,
+    ) -> ApiResult<DraftCommentReply> {
+        API_CLIENT
+            .post(
+                &format!("{}/draft-comments/{}/replies", article_path(article_id), comment_id),
+                &ReplyDraftCommentRequest { body },
+            )
+            .await
+    }
+
+    pub async fn resolve_comment(
+        article_id: &str,
+        comment_id: &str,
+        resolved: bool,
+    ) -> ApiResult<DraftComment> {
+        API_CLIENT
+            .patch(
+                &format!("{}/draft-comments/{}", article_path(article_id), comment_id),
+                &ResolveDraftCommentRequest { resolved },
+            )
+            .await
+    }
+}
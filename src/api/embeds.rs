@@ -0,0 +1,15 @@
+use super::client::{ApiClient, ApiResult};
+use crate::models::embed::LinkPreview;
+use once_cell::sync::Lazy;
+
+static API_CLIENT: Lazy<ApiClient> = Lazy::new(ApiClient::new);
+
+pub struct EmbedService;
+
+impl EmbedService {
+    pub async fn unfurl(url: &str) -> ApiResult<LinkPreview> {
+        API_CLIENT
+            .get(&format!("/blog/embeds/unfurl?url={}", urlencoding::encode(url)))
+            .await
+    }
+}
@@ -0,0 +1,52 @@
+use super::client::{ApiClient, ApiResult};
+use crate::models::highlight::{
+    CreateHighlightRequest, Highlight, HighlightListResponse, HighlightReport, PopularHighlight,
+    UpdateHighlightRequest,
+};
+use once_cell::sync::Lazy;
+
+static API_CLIENT: Lazy<ApiClient> = Lazy::new(ApiClient::new);
+
+// 如果 ID 包含 "article:" 前缀，只取 UUID 部分
+fn article_path(article_id: &str) -> String {
+    format!("/blog/articles/by-id/{}", article_id.trim_start_matches("article:"))
+}
+
+pub struct HighlightService;
+
+impl HighlightService {
+    /// 当前用户在某篇文章中的高亮
+    pub async fn get_article_highlights(article_id: &str) -> ApiResult<Vec<Highlight>> {
+        let response: HighlightListResponse =
+            API_CLIENT.get(&format!("{}/highlights", article_path(article_id))).await?;
+        Ok(response.highlights)
+    }
+
+    pub async fn create_highlight(request: &CreateHighlightRequest) -> ApiResult<Highlight> {
+        API_CLIENT
+            .post(&format!("{}/highlights", article_path(&request.article_id)), request)
+            .await
+    }
+
+    pub async fn update_highlight(id: &str, request: &UpdateHighlightRequest) -> ApiResult<Highlight> {
+        API_CLIENT.patch(&format!("/blog/highlights/{}", id), request).await
+    }
+
+    pub async fn delete_highlight(id: &str) -> ApiResult<()> {
+        API_CLIENT.delete(&format!("/blog/highlights/{}", id)).await
+    }
+
+    /// 高亮人数最多的段落，所有读者可见
+    pub async fn get_top_highlights(article_id: &str, limit: Option<i32>) -> ApiResult<Vec<PopularHighlight>> {
+        let mut url = format!("{}/highlights/top", article_path(article_id));
+        if let Some(l) = limit {
+            url = format!("{}?limit={}", url, l);
+        }
+        API_CLIENT.get(&url).await
+    }
+
+    /// 作者的高亮统计报告
+    pub async fn get_highlight_report(article_id: &str) -> ApiResult<HighlightReport> {
+        API_CLIENT.get(&format!("{}/highlights/report", article_path(article_id))).await
+    }
+}
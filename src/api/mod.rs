@@ -13,3 +13,12 @@ pub mod publications;
 pub mod domains;
 pub mod subscriptions;
 pub mod bookmarks;
+pub mod embeds;
+pub mod realtime;
+pub mod editorial;
+pub mod submissions;
+pub mod highlights;
+pub mod reading;
+pub mod private_notes;
+pub mod notifications;
+pub mod privacy;
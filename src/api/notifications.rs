@@ -0,0 +1,43 @@
+use super::client::{ApiClient, ApiResult};
+use crate::models::notification::{
+    MarkReadRequest, NotificationListResponse, NotificationType, UnreadCountResponse,
+};
+use once_cell::sync::Lazy;
+
+static API_CLIENT: Lazy<ApiClient> = Lazy::new(ApiClient::new);
+
+/// 通知实时推送的 WebSocket 路径
+pub const NOTIFICATION_SOCKET_PATH: &str = "/blog/notifications/live";
+
+pub struct NotificationService;
+
+impl NotificationService {
+    /// 当前用户的通知，按最近事件时间倒序，可按类型筛选
+    pub async fn get_notifications(
+        kind: Option<NotificationType>,
+        unread_only: bool,
+        page: Option<i32>,
+        per_page: Option<i32>,
+    ) -> ApiResult<NotificationListResponse> {
+        let mut url = "/blog/notifications".to_string();
+        let mut params = vec![];
+        if let Some(k) = kind { params.push(format!("type={}", k.as_str())); }
+        if unread_only { params.push("unread=true".to_string()); }
+        if let Some(p) = page { params.push(format!("page={}", p)); }
+        if let Some(pp) = per_page { params.push(format!("per_page={}", pp)); }
+        if !params.is_empty() { url = format!("{}?{}", url, params.join("&")); }
+
+        API_CLIENT.get(&url).await
+    }
+
+    /// 返回标记后的未读数
+    pub async fn mark_read(notification_ids: Vec<String>) -> ApiResult<UnreadCountResponse> {
+        API_CLIENT
+            .post("/blog/notifications/read", &MarkReadRequest { notification_ids })
+            .await
+    }
+
+    pub async fn mark_all_read() -> ApiResult<()> {
+        API_CLIENT.post("/blog/notifications/read-all", &()).await
+    }
+}
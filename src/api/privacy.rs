@@ -0,0 +1,41 @@
+use super::client::{ApiClient, ApiResult};
+use crate::models::privacy::{PrivacySettings, RelatedUserListResponse, RelationKind, UserRelationIds};
+use once_cell::sync::Lazy;
+
+static API_CLIENT: Lazy<ApiClient> = Lazy::new(ApiClient::new);
+
+pub struct PrivacyService;
+
+impl PrivacyService {
+    pub async fn get_settings() -> ApiResult<PrivacySettings> {
+        API_CLIENT.get("/blog/users/me/privacy").await
+    }
+
+    pub async fn update_settings(settings: &PrivacySettings) -> ApiResult<PrivacySettings> {
+        API_CLIENT.put("/blog/users/me/privacy", settings).await
+    }
+
+    /// 所有屏蔽和静音的用户 id
+    pub async fn get_relation_ids() -> ApiResult<UserRelationIds> {
+        API_CLIENT.get("/blog/users/me/relations").await
+    }
+
+    /// 屏蔽或静音列表，按操作时间倒序
+    pub async fn list(kind: RelationKind, page: Option<i32>, per_page: Option<i32>) -> ApiResult<RelatedUserListResponse> {
+        let mut url = format!("/blog/users/me/relations/{}", kind.as_str());
+        let mut params = vec![];
+        if let Some(p) = page { params.push(format!("page={}", p)); }
+        if let Some(pp) = per_page { params.push(format!("per_page={}", pp)); }
+        if !params.is_empty() { url = format!("{}?{}", url, params.join("&")); }
+
+        API_CLIENT.get(&url).await
+    }
+
+    pub async fn add(kind: RelationKind, user_id: &str) -> ApiResult<()> {
+        API_CLIENT.post(&format!("/blog/users/{}/{}", user_id, kind.as_str()), &()).await
+    }
+
+    pub async fn remove(kind: RelationKind, user_id: &str) -> ApiResult<()> {
+        API_CLIENT.delete(&format!("/blog/users/{}/{}", user_id, kind.as_str())).await
+    }
+}
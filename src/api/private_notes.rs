@@ -0,0 +1,39 @@
+use super::client::{ApiClient, ApiResult};
+use crate::models::private_note::{
+    CreatePrivateNoteRequest, NoteStatus, PrivateNote, PrivateNoteListResponse, UpdatePrivateNoteRequest,
+};
+use once_cell::sync::Lazy;
+
+static API_CLIENT: Lazy<ApiClient> = Lazy::new(ApiClient::new);
+
+pub struct PrivateNoteService;
+
+impl PrivateNoteService {
+    /// 读者把选中的文字和留言私下发给作者
+    pub async fn send_note(request: &CreatePrivateNoteRequest) -> ApiResult<PrivateNote> {
+        API_CLIENT.post("/blog/notes", request).await
+    }
+
+    /// 作者收到的留言，可按文章和状态筛选
+    pub async fn get_inbox(
+        article_id: Option<&str>,
+        status: Option<NoteStatus>,
+        page: Option<i32>,
+        per_page: Option<i32>,
+    ) -> ApiResult<PrivateNoteListResponse> {
+        let mut url = "/blog/notes/inbox".to_string();
+        let mut params = vec![];
+        if let Some(id) = article_id { params.push(format!("article_id={}", id)); }
+        if let Some(s) = status { params.push(format!("status={}", s.as_str())); }
+        if let Some(p) = page { params.push(format!("page={}", p)); }
+        if let Some(pp) = per_page { params.push(format!("per_page={}", pp)); }
+        if !params.is_empty() { url = format!("{}?{}", url, params.join("&")); }
+
+        API_CLIENT.get(&url).await
+    }
+
+    /// 标记为已处理 / 重新打开，或回复读者
+    pub async fn update_note(id: &str, request: &UpdatePrivateNoteRequest) -> ApiResult<PrivateNote> {
+        API_CLIENT.patch(&format!("/blog/notes/{}", id), request).await
+    }
+}
@@ -173,6 +173,11 @@ impl PublicationService {
         API_CLIENT.get("/blog/publications/following").await
     }
     
+    // 获取当前用户加入的出版物及角色
+    pub async fn get_my_publications() -> ApiResult<Vec<PublicationMembership>> {
+        API_CLIENT.get("/blog/publications/mine").await
+    }
+    
     // 获取出版物文章
     pub async fn get_publication_articles(
         slug: &str,
@@ -292,6 +297,7 @@ impl PublicationService {
                 is_bookmarked: Some(false),
                 is_clapped: Some(false),
                 user_clap_count: Some(0),
+                mentions: Vec::new(),
             }
         }).collect();
 
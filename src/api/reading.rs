@@ -0,0 +1,81 @@
+use super::client::{ApiClient, ApiResult};
+use crate::models::reading::{
+    ReadingHistoryResponse, ReadingHistorySettings, ReadingRecord, UpdateReadingProgressRequest,
+};
+use once_cell::sync::Lazy;
+
+static API_CLIENT: Lazy<ApiClient> = Lazy::new(ApiClient::new);
+
+// 如果 ID 包含 "article:" 前缀，只取 UUID 部分
+fn article_key(article_id: &str) -> &str {
+    article_id.trim_start_matches("article:")
+}
+
+/// 登录用户的阅读进度与阅读历史
+pub struct ReadingService;
+
+impl ReadingService {
+    pub async fn get_progress(article_id: &str) -> ApiResult<Option<ReadingRecord>> {
+        API_CLIENT
+            .get(&format!("/blog/articles/by-id/{}/reading-progress", article_key(article_id)))
+            .await
+    }
+
+    pub async fn update_progress(article_id: &str, progress: f64) -> ApiResult<()> {
+        API_CLIENT
+            .put(
+                &format!("/blog/articles/by-id/{}/reading-progress", article_key(article_id)),
+                &UpdateReadingProgressRequest { progress },
+            )
+            .await
+    }
+
+    /// 读了一部分还没读完的文章，最近阅读的在前
+    pub async fn get_continue_reading(limit: Option<i32>) -> ApiResult<Vec<ReadingRecord>> {
+        let mut url = String::from("/blog/reading/continue");
+        if let Some(l) = limit {
+            url = format!("{}?limit={}", url, l);
+        }
+        API_CLIENT.get(&url).await
+    }
+
+    /// 系列中读过的文章的阅读记录
+    pub async fn get_series_progress(series_id: &str) -> ApiResult<Vec<ReadingRecord>> {
+        API_CLIENT
+            .get(&format!("/blog/reading/series/{}", series_id.trim_start_matches("series:")))
+            .await
+    }
+
+    pub async fn get_history(
+        query: Option<&str>,
+        page: Option<i32>,
+        limit: Option<i32>,
+    ) -> ApiResult<ReadingHistoryResponse> {
+        let mut url = String::from("/blog/reading/history");
+        let mut params = vec![];
+        if let Some(q) = query.filter(|q| !q.is_empty()) { params.push(format!("q={}", urlencoding::encode(q))); }
+        if let Some(p) = page { params.push(format!("page={}", p)); }
+        if let Some(l) = limit { params.push(format!("limit={}", l)); }
+        if !params.is_empty() { url = format!("{}?{}", url, params.join("&")); }
+
+        API_CLIENT.get(&url).await
+    }
+
+    pub async fn delete_history_entry(article_id: &str) -> ApiResult<()> {
+        API_CLIENT
+            .delete(&format!("/blog/reading/history/{}", article_key(article_id)))
+            .await
+    }
+
+    pub async fn clear_history() -> ApiResult<()> {
+        API_CLIENT.delete("/blog/reading/history").await
+    }
+
+    pub async fn get_history_settings() -> ApiResult<ReadingHistorySettings> {
+        API_CLIENT.get("/blog/reading/history/settings").await
+    }
+
+    pub async fn update_history_settings(settings: &ReadingHistorySettings) -> ApiResult<()> {
+        API_CLIENT.put("/blog/reading/history/settings", settings).await
+    }
+}
@@ -0,0 +1,80 @@
+use super::client::{ApiClient, API_BASE_URL};
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{window, CloseEvent, MessageEvent, WebSocket};
+
+/// 将 API 路径转换为 WebSocket 地址，并通过查询参数携带登录令牌
+pub fn socket_url(path: &str) -> Option<String> {
+    let base = if API_BASE_URL.starts_with("http") {
+        API_BASE_URL.to_string()
+    } else {
+        // 相对路径：使用当前页面的 origin
+        let location = window()?.location();
+        format!("{}{}", location.origin().ok()?, API_BASE_URL)
+    };
+    let base = base
+        .replacen("https://", "wss://", 1)
+        .replacen("http://", "ws://", 1);
+
+    let separator = if path.contains('?') { '&' } else { '?' };
+    Some(match ApiClient::get_token() {
+        Some(token) => format!("{}{}{}token={}", base, path, separator, urlencoding::encode(&token)),
+        None => format!("{}{}", base, path),
+    })
+}
+
+/// 对浏览器 WebSocket 的薄封装，持有回调闭包直到连接关闭
+pub struct RealtimeSocket {
+    socket: WebSocket,
+    _on_open: Closure<dyn FnMut()>,
+    _on_message: Closure<dyn FnMut(MessageEvent)>,
+    _on_close: Closure<dyn FnMut(CloseEvent)>,
+}
+
+impl RealtimeSocket {
+    pub fn connect(
+        path: &str,
+        on_open: impl FnMut() + 'static,
+        mut on_message: impl FnMut(String) + 'static,
+        mut on_close: impl FnMut(u16) + 'static,
+    ) -> Option<Self> {
+        let socket = WebSocket::new(&socket_url(path)?).ok()?;
+
+        let on_open = Closure::<dyn FnMut()>::new(on_open);
+        let on_message = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+            if let Some(text) = event.data().as_string() {
+                on_message(text);
+            }
+        });
+        let on_close = Closure::<dyn FnMut(CloseEvent)>::new(move |event: CloseEvent| {
+            on_close(event.code());
+        });
+
+        socket.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+        socket.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+        socket.set_onclose(Some(on_close.as_ref().unchecked_ref()));
+
+        Some(Self {
+            socket,
+            _on_open: on_open,
+            _on_message: on_message,
+            _on_close: on_close,
+        })
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.socket.ready_state() == WebSocket::OPEN
+    }
+
+    /// 发送文本消息，连接未就绪时返回 false
+    pub fn send(&self, text: &str) -> bool {
+        self.is_open() && self.socket.send_with_str(text).is_ok()
+    }
+
+    /// 主动关闭连接，不再触发 on_close 回调
+    pub fn close(&self) {
+        self.socket.set_onopen(None);
+        self.socket.set_onmessage(None);
+        self.socket.set_onclose(None);
+        self.socket.close().ok();
+    }
+}
@@ -0,0 +1,82 @@
+use super::client::{ApiClient, ApiResult};
+use crate::models::submission::{
+    CreateSubmissionRequest, Submission, SubmissionListResponse, SubmissionStatus,
+    UpdateSubmissionRequest,
+};
+use once_cell::sync::Lazy;
+
+static API_CLIENT: Lazy<ApiClient> = Lazy::new(ApiClient::new);
+
+/// 出版物投稿与审核，角色权限由后端最终校验
+pub struct SubmissionService;
+
+impl SubmissionService {
+    // 作者向出版物投稿
+    pub async fn submit(publication_id: &str, request: &CreateSubmissionRequest) -> ApiResult<Submission> {
+        API_CLIENT
+            .post(&format!("/blog/publications/{}/submissions", publication_id), request)
+            .await
+    }
+
+    // 审核队列（仅所有者与编辑）
+    pub async fn get_submissions(
+        publication_id: &str,
+        status: Option<SubmissionStatus>,
+        page: Option<i32>,
+        limit: Option<i32>,
+    ) -> ApiResult<SubmissionListResponse> {
+        let mut query_params = vec![];
+
+        if let Some(status) = status {
+            query_params.push(format!("status={}", status.as_str()));
+        }
+        if let Some(page) = page {
+            query_params.push(format!("page={}", page));
+        }
+        if let Some(limit) = limit {
+            query_params.push(format!("limit={}", limit));
+        }
+
+        let query = if query_params.is_empty() {
+            String::new()
+        } else {
+            format!("?{}", query_params.join("&"))
+        };
+
+        API_CLIENT
+            .get(&format!("/blog/publications/{}/submissions{}", publication_id, query))
+            .await
+    }
+
+    // 文章当前的投稿（没有投稿时后端返回 404）
+    pub async fn get_article_submission(article_id: &str) -> ApiResult<Submission> {
+        let article_id = article_id.trim_start_matches("article:");
+        API_CLIENT
+            .get(&format!("/blog/articles/by-id/{}/submission", article_id))
+            .await
+    }
+
+    // 变更投稿状态：编辑审核操作，或作者修改后重新投稿
+    pub async fn update_status(
+        publication_id: &str,
+        submission_id: &str,
+        request: &UpdateSubmissionRequest,
+    ) -> ApiResult<Submission> {
+        API_CLIENT
+            .patch(
+                &format!("/blog/publications/{}/submissions/{}", publication_id, submission_id),
+                request,
+            )
+            .await
+    }
+
+    // 编辑将已通过的投稿发布到出版物
+    pub async fn publish(publication_id: &str, submission_id: &str) -> ApiResult<Submission> {
+        API_CLIENT
+            .post(
+                &format!("/blog/publications/{}/submissions/{}/publish", publication_id, submission_id),
+                &(),
+            )
+            .await
+    }
+}
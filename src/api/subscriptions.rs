@@ -134,6 +134,18 @@ impl SubscriptionService {
         }
     }
     
+    // 会员文章的每月免费阅读额度
+    pub async fn get_reading_meter() -> ApiResult<ReadingMeter> {
+        API_CLIENT.get("/blog/subscriptions/meter").await
+    }
+    
+    // 用一次免费额度解锁会员文章，已解锁的文章不重复计数
+    pub async fn unlock_with_meter(article_id: &str) -> ApiResult<ReadingMeter> {
+        API_CLIENT
+            .post(&format!("/blog/subscriptions/meter/articles/{}", ReadingMeter::article_key(article_id)), &())
+            .await
+    }
+    
     // 获取创作者收益统计
     pub async fn get_earnings_stats(creator_id: &str, period: Option<&str>) -> ApiResult<serde_json::Value> {
         let query = if let Some(period) = period {
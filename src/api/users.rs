@@ -2,6 +2,8 @@ use super::client::{ApiClient, ApiResult};
 use crate::models::{
     user::{UserProfileResponse, UserStats, UpdateProfileRequest, UserListResponse},
     article::{ArticleListResponse, Article, Author, Pagination},
+    preferences::{DisplayPreferences, NotificationPreferences},
+    mention::{MentionCandidate, ResolveMentionsRequest},
 };
 use once_cell::sync::Lazy;
 use serde::{Serialize, Deserialize};
@@ -102,6 +104,7 @@ impl UserService {
             is_bookmarked: None,
             is_clapped: None,
             user_clap_count: None,
+            mentions: Vec::new(),
         }).collect();
         Ok(ArticleListResponse { articles, pagination: raw.pagination })
     }
@@ -170,6 +173,7 @@ impl UserService {
             is_bookmarked: None,
             is_clapped: None,
             user_clap_count: None,
+            mentions: Vec::new(),
         }).collect();
         Ok(ArticleListResponse { articles, pagination: raw.pagination })
     }
@@ -182,6 +186,29 @@ impl UserService {
         API_CLIENT.put("/blog/users/me", updates).await
     }
     
+    // 主题和文章排版偏好，随账号在设备间同步
+    pub async fn get_display_preferences() -> ApiResult<DisplayPreferences> {
+        API_CLIENT.get("/blog/users/me/preferences/display").await
+    }
+    
+    pub async fn update_display_preferences(preferences: &DisplayPreferences) -> ApiResult<DisplayPreferences> {
+        API_CLIENT.put("/blog/users/me/preferences/display", preferences).await
+    }
+    
+    // 每个事件在站内、邮件和浏览器推送渠道的通知设置
+    pub async fn get_notification_preferences() -> ApiResult<NotificationPreferences> {
+        API_CLIENT.get("/blog/users/me/preferences/notifications").await
+    }
+    
+    pub async fn update_notification_preferences(preferences: &NotificationPreferences) -> ApiResult<NotificationPreferences> {
+        API_CLIENT.put("/blog/users/me/preferences/notifications", preferences).await
+    }
+    
+    // 一键关闭所有邮件、推送和摘要，站内通知不受影响
+    pub async fn unsubscribe_all_notifications() -> ApiResult<NotificationPreferences> {
+        API_CLIENT.post("/blog/users/me/preferences/notifications/unsubscribe-all", &()).await
+    }
+    
     pub async fn follow_user(user_id: &str) -> ApiResult<()> {
         API_CLIENT.post(&format!("/blog/follows/user/{}/follow", user_id), &()).await
     }
@@ -246,4 +273,23 @@ impl UserService {
         
         Ok(response.is_following)
     }
+    
+    /// @提及自动补全：按用户名或昵称前缀搜索，结果中标明哪些用户不能被提及
+    pub async fn search_mention_candidates(query: &str, limit: Option<i32>) -> ApiResult<Vec<MentionCandidate>> {
+        let mut url = format!("/blog/users/mentions/search?q={}", urlencoding::encode(query));
+        if let Some(l) = limit {
+            url = format!("{}&limit={}", url, l);
+        }
+        API_CLIENT.get(&url).await
+    }
+    
+    /// 发布前检查文中提及的用户：不存在的用户不返回
+    pub async fn resolve_mentions(usernames: &[String]) -> ApiResult<Vec<MentionCandidate>> {
+        API_CLIENT
+            .post(
+                "/blog/users/mentions/resolve",
+                &ResolveMentionsRequest { usernames: usernames.to_vec() },
+            )
+            .await
+    }
 }
@@ -1,5 +1,8 @@
 use super::client::{ApiClient, ApiResult};
-use crate::models::version::{ArticleVersion, ArticleVersionComparison, CreateVersionRequest, RestoreVersionRequest};
+use crate::models::version::{
+    ArticleVersion, ArticleVersionComparison, CreateVersionRequest, RestoreVersionRequest,
+    UpdateVersionRequest,
+};
 use once_cell::sync::Lazy;
 
 static API_CLIENT: Lazy<ApiClient> = Lazy::new(ApiClient::new);
@@ -36,4 +39,13 @@ impl VersionService {
             &()
         ).await
     }
-}
\ No newline at end of file
+    
+    /// 命名或固定版本
+    pub async fn update_version(
+        article_id: &str,
+        version_id: &str,
+        request: &UpdateVersionRequest
+    ) -> ApiResult<ArticleVersion> {
+        API_CLIENT.patch(&format!("/blog/articles/{}/versions/{}", article_id, version_id), request).await
+    }
+}
@@ -0,0 +1,186 @@
+// 文本锚点：用原文引用（前后文 + 选中文字）加字符位置定位一段文字，
+// 正文被修改后仍能找回原来的位置
+
+use serde::{Deserialize, Serialize};
+
+// 记录的前后文长度（字符数）
+const CONTEXT_CHARS: usize = 32;
+// 模糊匹配允许的编辑距离占选中文字长度的比例（1/4）
+const FUZZY_RATIO: usize = 4;
+// 超过这个长度的选区不做模糊匹配，避免长文中的计算量过大
+const FUZZY_MAX_CHARS: usize = 2000;
+// 短于这个长度的选区不做模糊匹配，只靠前后文定位，避免挂到文中其他位置的同一个词上
+const FUZZY_MIN_CHARS: usize = 16;
+// 原位置已不是这段文字时，前后文至少要吻合的字符数（前后文本身更短时以其长度为准）
+const MIN_CONTEXT_CHARS: usize = 8;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TextAnchor {
+    /// 创建时选区起点的字符下标
+    pub start: usize,
+    pub exact: String,
+    #[serde(default)]
+    pub prefix: String,
+    #[serde(default)]
+    pub suffix: String,
+}
+
+impl TextAnchor {
+    /// 为 text 中 [start, end) 的字符区间创建锚点
+    pub fn capture(text: &str, start: usize, end: usize) -> Self {
+        let chars: Vec<char> = text.chars().collect();
+        let end = end.min(chars.len());
+        let start = start.min(end);
+        Self {
+            start,
+            exact: chars[start..end].iter().collect(),
+            prefix: chars[start.saturating_sub(CONTEXT_CHARS)..start].iter().collect(),
+            suffix: chars[end..(end + CONTEXT_CHARS).min(chars.len())].iter().collect(),
+        }
+    }
+
+    /// 在（可能已被修改的）文本中重新定位，返回字符区间
+    pub fn locate(&self, text: &str) -> Option<(usize, usize)> {
+        let chars: Vec<char> = text.chars().collect();
+        let exact: Vec<char> = self.exact.chars().collect();
+        let prefix: Vec<char> = self.prefix.chars().collect();
+        let suffix: Vec<char> = self.suffix.chars().collect();
+
+        if exact.len() > chars.len() {
+            return None;
+        }
+
+        let mut best: Option<(usize, usize, usize)> = None; // (位置, 前后文匹配数, 距离)
+        for position in 0..=chars.len() - exact.len() {
+            if chars[position..position + exact.len()] != exact[..] {
+                continue;
+            }
+            let before = prefix
+                .iter()
+                .rev()
+                .zip(chars[..position].iter().rev())
+                .take_while(|(a, b)| a == b)
+                .count();
+            let after = suffix
+                .iter()
+                .zip(chars[position + exact.len()..].iter())
+                .take_while(|(a, b)| a == b)
+                .count();
+            let score = before + after;
+            let distance = position.abs_diff(self.start);
+            let better = match best {
+                None => true,
+                Some((_, best_score, best_distance)) => {
+                    score > best_score || (score == best_score && distance < best_distance)
+                }
+            };
+            if better {
+                best = Some((position, score, distance));
+            }
+        }
+
+        let (position, score, _) = best?;
+        // 位置变了的匹配必须有前后文支持，否则 "the" 这类短选区会挂到无关的文字上
+        let context = prefix.len() + suffix.len();
+        if position != self.start && score < context.min(MIN_CONTEXT_CHARS) {
+            return None;
+        }
+        // 空选区（纯插入）只靠前后文定位，至少要匹配一半
+        if exact.is_empty() && !(prefix.is_empty() && suffix.is_empty()) && score * 2 < prefix.len() + suffix.len() {
+            return None;
+        }
+        Some((position, position + exact.len()))
+    }
+
+    /// 先精确定位，找不到时（选中文字本身被修改过）按编辑距离做近似匹配。
+    /// 仍然找不到说明这段文字已被删除或改得面目全非
+    pub fn locate_fuzzy(&self, text: &str) -> Option<(usize, usize)> {
+        if let Some(range) = self.locate(text) {
+            return Some(range);
+        }
+
+        let pattern: Vec<char> = self.exact.chars().collect();
+        let max_cost = pattern.len() / FUZZY_RATIO;
+        if max_cost == 0 || pattern.len() < FUZZY_MIN_CHARS || pattern.len() > FUZZY_MAX_CHARS {
+            return None;
+        }
+
+        // Sellers 算法：模式串可以从文本任意位置开始匹配，同时记录每个匹配的起点
+        let chars: Vec<char> = text.chars().collect();
+        let mut cost: Vec<usize> = (0..=pattern.len()).collect();
+        let mut origin = vec![0usize; pattern.len() + 1];
+        let mut best: Option<(usize, usize, usize, usize, usize)> = None; // (编辑距离, 距离, 长度差, 起点, 终点)
+
+        for (j, ch) in chars.iter().enumerate() {
+            let mut diagonal = (cost[0], origin[0]);
+            cost[0] = 0;
+            origin[0] = j + 1;
+            for i in 1..=pattern.len() {
+                let substitute = (diagonal.0 + usize::from(pattern[i - 1] != *ch), diagonal.1);
+                let skip_text = (cost[i] + 1, origin[i]);
+                let skip_pattern = (cost[i - 1] + 1, origin[i - 1]);
+                diagonal = (cost[i], origin[i]);
+                let next = substitute.min(skip_text).min(skip_pattern);
+                cost[i] = next.0;
+                origin[i] = next.1;
+            }
+
+            let (end_cost, start) = (cost[pattern.len()], origin[pattern.len()]);
+            if end_cost > max_cost {
+                continue;
+            }
+            // 编辑距离相同时取离原位置最近、长度最接近原文的
+            let candidate = (end_cost, start.abs_diff(self.start), (j + 1 - start).abs_diff(pattern.len()), start, j + 1);
+            if best.is_none_or(|b| (candidate.0, candidate.1, candidate.2) < (b.0, b.1, b.2)) {
+                best = Some(candidate);
+            }
+        }
+
+        best.map(|(_, _, _, start, end)| (start, end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locate_prefers_matching_context() {
+        let text = "one cat here, another cat there";
+        let anchor = TextAnchor::capture(text, 22, 25);
+        assert_eq!(anchor.exact, "cat");
+        let edited = format!("prefix {}", text);
+        assert_eq!(anchor.locate(&edited), Some((29, 32)));
+    }
+
+    #[test]
+    fn locate_orphans_short_quote_without_context() {
+        let text = "Once upon a time the fox slept. The end.";
+        let anchor = TextAnchor::capture(text, 17, 20);
+        assert_eq!(anchor.exact, "the");
+        // 原句被改写，文中其他位置仍有 "the"，但前后文对不上
+        let edited = "Completely new opening sentence here, and then the story ends differently.";
+        assert_eq!(anchor.locate(edited), None);
+        assert_eq!(anchor.locate_fuzzy(edited), None);
+        // 原位置不变时直接采用
+        assert_eq!(anchor.locate(text), Some((17, 20)));
+    }
+
+    #[test]
+    fn locate_fuzzy_finds_slightly_edited_text() {
+        let text = "开头。The quick brown fox jumps over the lazy dog. 结尾";
+        let anchor = TextAnchor::capture(text, 3, 47);
+        let edited = "开头。The quick brown cat jumps over the lazy dog. 结尾";
+        assert_eq!(anchor.locate(edited), None);
+        assert_eq!(anchor.locate_fuzzy(edited), Some((3, 47)));
+    }
+
+    #[test]
+    fn locate_fuzzy_gives_up_on_rewritten_text() {
+        let anchor = TextAnchor::capture("keep this sentence intact please", 0, 32);
+        assert_eq!(anchor.locate_fuzzy("something entirely different now"), None);
+        // 太短的选区不做模糊匹配
+        let short = TextAnchor::capture("abc", 0, 3);
+        assert_eq!(short.locate_fuzzy("abd"), None);
+    }
+}
@@ -0,0 +1,344 @@
+// Myers 差异算法：对任意序列（行、单词）计算最短编辑脚本
+
+// 编辑距离上限，超过后不再求最短编辑脚本，避免回溯记录占用过多内存
+const MAX_EDIT_DISTANCE: usize = 1000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffTag {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// 一段连续的相同操作；Delete 只占用旧序列，Insert 只占用新序列
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiffOp {
+    pub tag: DiffTag,
+    pub old_index: usize,
+    pub new_index: usize,
+    pub len: usize,
+}
+
+impl DiffOp {
+    pub fn old_range(&self) -> std::ops::Range<usize> {
+        match self.tag {
+            DiffTag::Insert => self.old_index..self.old_index,
+            _ => self.old_index..self.old_index + self.len,
+        }
+    }
+
+    pub fn new_range(&self) -> std::ops::Range<usize> {
+        match self.tag {
+            DiffTag::Delete => self.new_index..self.new_index,
+            _ => self.new_index..self.new_index + self.len,
+        }
+    }
+}
+
+pub fn diff<T: PartialEq>(old: &[T], new: &[T]) -> Vec<DiffOp> {
+    // 先去掉公共前后缀，缩小搜索范围
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let (old_middle, new_middle) = (&old[prefix..old.len() - suffix], &new[prefix..new.len() - suffix]);
+    let mut ops = Vec::new();
+    push(&mut ops, DiffTag::Equal, 0, 0, prefix);
+    match myers(old_middle, new_middle) {
+        Some(edits) => {
+            for (tag, old_index, new_index) in edits {
+                push(&mut ops, tag, old_index + prefix, new_index + prefix, 1);
+            }
+        }
+        // 差异过大时不再逐项比较，中间部分整段视为删除后插入
+        None => {
+            push(&mut ops, DiffTag::Delete, prefix, prefix, old_middle.len());
+            push(&mut ops, DiffTag::Insert, old.len() - suffix, prefix, new_middle.len());
+        }
+    }
+    push(&mut ops, DiffTag::Equal, old.len() - suffix, new.len() - suffix, suffix);
+    ops
+}
+
+fn push(ops: &mut Vec<DiffOp>, tag: DiffTag, old_index: usize, new_index: usize, len: usize) {
+    if len == 0 {
+        return;
+    }
+    if let Some(last) = ops.last_mut() {
+        let contiguous = match tag {
+            DiffTag::Equal => last.old_index + last.len == old_index && last.new_index + last.len == new_index,
+            DiffTag::Delete => last.old_index + last.len == old_index,
+            DiffTag::Insert => last.new_index + last.len == new_index,
+        };
+        if last.tag == tag && contiguous {
+            last.len += len;
+            return;
+        }
+    }
+    ops.push(DiffOp { tag, old_index, new_index, len });
+}
+
+// 返回按顺序排列的单元素编辑 (类型, 旧下标, 新下标)，编辑距离超过上限时返回 None
+fn myers<T: PartialEq>(a: &[T], b: &[T]) -> Option<Vec<(DiffTag, usize, usize)>> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let max = (n + m) as usize;
+    let offset = max as isize + 1;
+    let mut v = vec![0isize; 2 * max + 3];
+    // 每一步 d 结束后 v[-d..=d] 的快照，回溯时使用，占用内存随 d 平方增长
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+    let mut found = false;
+
+    'search: for d in 0..=max.min(MAX_EDIT_DISTANCE) as isize {
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && v[(offset + k - 1) as usize] < v[(offset + k + 1) as usize]) {
+                v[(offset + k + 1) as usize]
+            } else {
+                v[(offset + k - 1) as usize] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[(offset + k) as usize] = x;
+            if x >= n && y >= m {
+                trace.push(v[(offset - d) as usize..=(offset + d) as usize].to_vec());
+                found = true;
+                break 'search;
+            }
+        }
+        trace.push(v[(offset - d) as usize..=(offset + d) as usize].to_vec());
+    }
+    if !found {
+        return None;
+    }
+
+    // 从终点回溯
+    let mut edits = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for d in (1..trace.len() as isize).rev() {
+        let previous = &trace[(d - 1) as usize];
+        let at = |k: isize| previous[(k + d - 1) as usize];
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && at(k - 1) < at(k + 1)) { k + 1 } else { k - 1 };
+        let prev_x = at(prev_k);
+        let prev_y = prev_x - prev_k;
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            edits.push((DiffTag::Equal, x as usize, y as usize));
+        }
+        if x == prev_x {
+            edits.push((DiffTag::Insert, x as usize, prev_y as usize));
+        } else {
+            edits.push((DiffTag::Delete, prev_x as usize, y as usize));
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+    while x > 0 && y > 0 {
+        x -= 1;
+        y -= 1;
+        edits.push((DiffTag::Equal, x as usize, y as usize));
+    }
+    edits.reverse();
+    Some(edits)
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c as u32,
+        0x3040..=0x30FF | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xAC00..=0xD7AF | 0xF900..=0xFAFF)
+}
+
+/// 按单词切分文本：连续的字母数字为一个词，中日韩文字逐字切分，空白与标点单独成词
+pub fn words(text: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = None;
+    for (i, c) in text.char_indices() {
+        let word_char = c.is_alphanumeric() && !is_cjk(c);
+        if word_char {
+            if start.is_none() {
+                start = Some(i);
+            }
+            continue;
+        }
+        if let Some(s) = start.take() {
+            tokens.push(&text[s..i]);
+        }
+        tokens.push(&text[i..i + c.len_utf8()]);
+    }
+    if let Some(s) = start {
+        tokens.push(&text[s..]);
+    }
+    tokens
+}
+
+/// 一处文本修改：从旧文本第 start 个字符起，把 deleted 替换为 inserted
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextChange {
+    pub start: usize,
+    pub deleted: String,
+    pub inserted: String,
+}
+
+/// 以单词为粒度比较两段文本，相邻的删除与插入合并为一处修改
+pub fn text_changes(old: &str, new: &str) -> Vec<TextChange> {
+    let old_words = words(old);
+    let new_words = words(new);
+    let mut changes: Vec<TextChange> = Vec::new();
+    let mut char_offset = 0;
+    let mut in_change = false;
+
+    for op in diff(&old_words, &new_words) {
+        match op.tag {
+            DiffTag::Equal => {
+                in_change = false;
+                char_offset += old_words[op.old_range()].iter().map(|w| w.chars().count()).sum::<usize>();
+            }
+            DiffTag::Delete | DiffTag::Insert => {
+                if !in_change {
+                    changes.push(TextChange { start: char_offset, deleted: String::new(), inserted: String::new() });
+                    in_change = true;
+                }
+                let change = changes.last_mut().expect("change pushed above");
+                if op.tag == DiffTag::Delete {
+                    let deleted: String = old_words[op.old_range()].concat();
+                    char_offset += deleted.chars().count();
+                    change.deleted.push_str(&deleted);
+                } else {
+                    change.inserted.push_str(&new_words[op.new_range()].concat());
+                }
+            }
+        }
+    }
+    changes
+}
+
+/// 按行比较时的结果块：一段未修改的行，或一处修改（旧行区间被新行区间替换）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffBlock {
+    Equal { old_index: usize, new_index: usize, len: usize },
+    Change { old: std::ops::Range<usize>, new: std::ops::Range<usize> },
+}
+
+/// 把相邻的删除与插入合并成修改块
+pub fn blocks(ops: &[DiffOp]) -> Vec<DiffBlock> {
+    let mut blocks = Vec::new();
+    for op in ops {
+        if op.tag == DiffTag::Equal {
+            blocks.push(DiffBlock::Equal { old_index: op.old_index, new_index: op.new_index, len: op.len });
+            continue;
+        }
+        if let Some(DiffBlock::Change { old, new }) = blocks.last_mut() {
+            old.end = old.end.max(op.old_range().end);
+            new.end = new.end.max(op.new_range().end);
+        } else {
+            blocks.push(DiffBlock::Change { old: op.old_range(), new: op.new_range() });
+        }
+    }
+    blocks
+}
+
+/// 一行文本切成的片段及其是否被修改
+pub type Segments = Vec<(String, bool)>;
+
+/// 单词级比较两行文本，返回两侧的片段及其是否被修改，用于行内高亮
+pub fn word_segments(old: &str, new: &str) -> (Segments, Segments) {
+    fn append(segments: &mut Vec<(String, bool)>, text: String, changed: bool) {
+        match segments.last_mut() {
+            Some((last, flag)) if *flag == changed => last.push_str(&text),
+            _ => segments.push((text, changed)),
+        }
+    }
+
+    let old_words = words(old);
+    let new_words = words(new);
+    let mut old_segments = Vec::new();
+    let mut new_segments = Vec::new();
+    for op in diff(&old_words, &new_words) {
+        match op.tag {
+            DiffTag::Equal => {
+                let text = old_words[op.old_range()].concat();
+                append(&mut old_segments, text.clone(), false);
+                append(&mut new_segments, text, false);
+            }
+            DiffTag::Delete => append(&mut old_segments, old_words[op.old_range()].concat(), true),
+            DiffTag::Insert => append(&mut new_segments, new_words[op.new_range()].concat(), true),
+        }
+    }
+    (old_segments, new_segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 按编辑脚本由旧序列重建新序列
+    fn apply<T: Clone>(old: &[T], new: &[T], ops: &[DiffOp]) -> Vec<T> {
+        let mut result = Vec::new();
+        for op in ops {
+            match op.tag {
+                DiffTag::Equal => result.extend_from_slice(&old[op.old_range()]),
+                DiffTag::Insert => result.extend_from_slice(&new[op.new_range()]),
+                DiffTag::Delete => {}
+            }
+        }
+        result
+    }
+
+    #[test]
+    fn diff_produces_shortest_script() {
+        let old: Vec<char> = "ABCABBA".chars().collect();
+        let new: Vec<char> = "CBABAC".chars().collect();
+        let ops = diff(&old, &new);
+        assert_eq!(apply(&old, &new, &ops), new);
+        let edits: usize = ops.iter().filter(|op| op.tag != DiffTag::Equal).map(|op| op.len).sum();
+        assert_eq!(edits, 5);
+    }
+
+    #[test]
+    fn diff_handles_empty_sides() {
+        assert!(diff::<char>(&[], &[]).is_empty());
+        assert_eq!(diff(&[], &['a', 'b']), vec![DiffOp { tag: DiffTag::Insert, old_index: 0, new_index: 0, len: 2 }]);
+        assert_eq!(diff(&['a'], &[]), vec![DiffOp { tag: DiffTag::Delete, old_index: 0, new_index: 0, len: 1 }]);
+    }
+
+    #[test]
+    fn diff_falls_back_to_block_replacement() {
+        let old: Vec<usize> = (0..5000).collect();
+        let new: Vec<usize> = (0..5000).map(|i| if i % 2 == 0 { i } else { i + 10_000 }).collect();
+        let ops = diff(&old, &new);
+        assert_eq!(apply(&old, &new, &ops), new);
+        assert_eq!(
+            ops,
+            vec![
+                DiffOp { tag: DiffTag::Equal, old_index: 0, new_index: 0, len: 1 },
+                DiffOp { tag: DiffTag::Delete, old_index: 1, new_index: 1, len: 4999 },
+                DiffOp { tag: DiffTag::Insert, old_index: 5000, new_index: 1, len: 4999 },
+            ]
+        );
+    }
+
+    #[test]
+    fn text_changes_merge_adjacent_edits() {
+        let changes = text_changes("the quick brown fox", "the slow brown cat");
+        assert_eq!(
+            changes,
+            vec![
+                TextChange { start: 4, deleted: "quick".to_string(), inserted: "slow".to_string() },
+                TextChange { start: 16, deleted: "fox".to_string(), inserted: "cat".to_string() },
+            ]
+        );
+    }
+
+    #[test]
+    fn text_changes_split_cjk_by_character() {
+        let changes = text_changes("今天天气很好", "今天天气不好");
+        assert_eq!(changes, vec![TextChange { start: 4, deleted: "很".to_string(), inserted: "不".to_string() }]);
+        assert!(text_changes("相同", "相同").is_empty());
+    }
+}
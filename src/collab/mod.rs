@@ -0,0 +1,89 @@
+// 协作编辑：文本 CRDT 与 WebSocket 消息协议，以及修改建议、批注、高亮用到的差异比较和文本锚点
+
+pub mod anchor;
+pub mod diff;
+pub mod text_crdt;
+
+pub use text_crdt::{Element, Op, OpId, TextDoc};
+
+use serde::{Deserialize, Serialize};
+
+/// 协作者的光标，用 CRDT 字符 id 锚定，不受其他人并发编辑影响
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct CursorAnchor {
+    pub anchor: Option<OpId>,
+    pub head: Option<OpId>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Peer {
+    pub site: u64,
+    pub user_id: String,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+}
+
+impl Peer {
+    pub fn name(&self) -> &str {
+        self.display_name.as_deref().unwrap_or(&self.username)
+    }
+
+    /// 按站点分配固定的光标颜色
+    pub fn color(&self) -> &'static str {
+        const COLORS: [&str; 8] = [
+            "#e11d48", "#2563eb", "#16a34a", "#d97706",
+            "#7c3aed", "#0891b2", "#db2777", "#65a30d",
+        ];
+        COLORS[(self.site % COLORS.len() as u64) as usize]
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    Join { site: u64 },
+    Ops { ops: Vec<Op> },
+    Cursor { cursor: Option<CursorAnchor> },
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    Snapshot { elements: Vec<Element>, peers: Vec<Peer> },
+    Ops { site: u64, ops: Vec<Op> },
+    Presence { peers: Vec<Peer> },
+    Cursor { site: u64, cursor: Option<CursorAnchor> },
+    Left { site: u64 },
+}
+
+/// 文本中第 index 个字符所在的行与列（均从 1 开始）
+pub fn line_column(text: &str, index: usize) -> (usize, usize) {
+    let mut line = 1;
+    let mut column = 1;
+    for ch in text.chars().take(index) {
+        if ch == '\n' {
+            line += 1;
+            column = 1;
+        } else {
+            column += 1;
+        }
+    }
+    (line, column)
+}
+
+/// 浏览器输入框的选区以 UTF-16 码元计数，与字符下标互相换算
+pub fn utf16_to_char_index(text: &str, offset: usize) -> usize {
+    let mut units = 0;
+    for (i, ch) in text.chars().enumerate() {
+        if units >= offset {
+            return i;
+        }
+        units += ch.len_utf16();
+    }
+    text.chars().count()
+}
+
+pub fn char_to_utf16_index(text: &str, index: usize) -> usize {
+    text.chars().take(index).map(char::len_utf16).sum()
+}
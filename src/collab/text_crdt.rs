@@ -0,0 +1,380 @@
+// 基于 RGA（Replicated Growable Array）的纯文本 CRDT
+//
+// 每个字符有全局唯一的 OpId（Lamport 计数 + 站点），插入时记录左侧的原始位置（origin），
+// 删除只打墓碑标记。同一组操作以任意顺序、任意次数应用后结果一致。
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+
+// 由已保存的 content 初始化文档时使用的固定站点，多个客户端同时初始化得到相同的操作
+pub const SEED_SITE: u64 = 0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct OpId {
+    pub counter: u64,
+    pub site: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Op {
+    Insert {
+        id: OpId,
+        origin: Option<OpId>,
+        ch: char,
+    },
+    Delete {
+        target: OpId,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Element {
+    pub id: OpId,
+    pub origin: Option<OpId>,
+    pub ch: char,
+    pub deleted: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct TextDoc {
+    site: u64,
+    clock: u64,
+    elements: Vec<Element>,
+    // 已知元素 id 到下标的缓存：valid_until 之前的下标准确，之后的可能因中间插入而过期，查询时按需重建
+    index: HashMap<OpId, usize>,
+    valid_until: usize,
+    // 依赖尚未到达的操作（乱序到达时暂存）
+    pending: VecDeque<Op>,
+}
+
+impl TextDoc {
+    pub fn new(site: u64) -> Self {
+        Self {
+            site,
+            clock: 0,
+            elements: Vec::new(),
+            index: HashMap::new(),
+            valid_until: 0,
+            pending: VecDeque::new(),
+        }
+    }
+
+    pub fn site(&self) -> u64 {
+        self.site
+    }
+
+    pub fn text(&self) -> String {
+        self.elements
+            .iter()
+            .filter(|e| !e.deleted)
+            .map(|e| e.ch)
+            .collect()
+    }
+
+    /// 由已持久化的正文生成确定性的初始操作
+    pub fn seed_ops(content: &str) -> Vec<Op> {
+        let mut ops = Vec::with_capacity(content.len());
+        let mut origin = None;
+        for (i, ch) in content.chars().enumerate() {
+            let id = OpId { counter: i as u64 + 1, site: SEED_SITE };
+            ops.push(Op::Insert { id, origin, ch });
+            origin = Some(id);
+        }
+        ops
+    }
+
+    /// 把快照（按文档顺序排列的元素）还原为可重放的操作
+    pub fn snapshot_ops(elements: &[Element]) -> Vec<Op> {
+        elements
+            .iter()
+            .map(|e| Op::Insert { id: e.id, origin: e.origin, ch: e.ch })
+            .chain(
+                elements
+                    .iter()
+                    .filter(|e| e.deleted)
+                    .map(|e| Op::Delete { target: e.id }),
+            )
+            .collect()
+    }
+
+    /// 合并快照，已知元素会被跳过
+    pub fn merge_snapshot(&mut self, elements: &[Element]) {
+        self.apply_all(&Self::snapshot_ops(elements));
+    }
+
+    /// 本地文档有而快照缺少的插入与删除，按文档顺序排列，可直接重放
+    pub fn ops_missing_from(&self, snapshot: &[Element]) -> Vec<Op> {
+        let remote: HashMap<OpId, bool> = snapshot.iter().map(|e| (e.id, e.deleted)).collect();
+        self.elements
+            .iter()
+            .filter(|e| !remote.contains_key(&e.id))
+            .map(|e| Op::Insert { id: e.id, origin: e.origin, ch: e.ch })
+            .chain(
+                self.elements
+                    .iter()
+                    .filter(|e| e.deleted && remote.get(&e.id) != Some(&true))
+                    .map(|e| Op::Delete { target: e.id }),
+            )
+            .collect()
+    }
+
+    pub fn apply_all(&mut self, ops: &[Op]) {
+        for op in ops {
+            self.apply(op.clone());
+        }
+    }
+
+    /// 应用一个操作（本地或远端），幂等
+    pub fn apply(&mut self, op: Op) {
+        if self.try_apply(&op) {
+            // 新操作可能解除了暂存操作的依赖
+            let mut progressed = true;
+            while progressed && !self.pending.is_empty() {
+                progressed = false;
+                for _ in 0..self.pending.len() {
+                    if let Some(next) = self.pending.pop_front() {
+                        if self.try_apply(&next) {
+                            progressed = true;
+                        } else {
+                            self.pending.push_back(next);
+                        }
+                    }
+                }
+            }
+        } else {
+            self.pending.push_back(op);
+        }
+    }
+
+    // 依赖缺失时返回 false
+    fn try_apply(&mut self, op: &Op) -> bool {
+        match op {
+            Op::Insert { id, origin, ch } => {
+                if self.index.contains_key(id) {
+                    return true;
+                }
+                let start = match origin {
+                    None => 0,
+                    Some(origin) => match self.position(origin) {
+                        Some(index) => index + 1,
+                        None => return false,
+                    },
+                };
+
+                // 跳过时间戳更大的元素（并发插入到同一位置或其后代）
+                let mut index = start;
+                while index < self.elements.len() && self.elements[index].id > *id {
+                    index += 1;
+                }
+
+                self.elements.insert(index, Element { id: *id, origin: *origin, ch: *ch, deleted: false });
+                self.index.insert(*id, index);
+                // 插入点之后的元素整体后移；只有在末尾追加且缓存全部有效时才能继续保持有效
+                if index == self.valid_until && index + 1 == self.elements.len() {
+                    self.valid_until += 1;
+                } else {
+                    self.valid_until = self.valid_until.min(index);
+                }
+                self.clock = self.clock.max(id.counter);
+                true
+            }
+            Op::Delete { target } => match self.position(target) {
+                Some(index) => {
+                    self.elements[index].deleted = true;
+                    true
+                }
+                None => false,
+            },
+        }
+    }
+
+    // 缓存的下标处仍是该元素时直接返回，顺序追加（初始化）和连续输入都走这条路径
+    fn position(&mut self, id: &OpId) -> Option<usize> {
+        let cached = *self.index.get(id)?;
+        if self.elements.get(cached).is_some_and(|e| e.id == *id) {
+            return Some(cached);
+        }
+        for (i, element) in self.elements.iter().enumerate().skip(self.valid_until) {
+            self.index.insert(element.id, i);
+        }
+        self.valid_until = self.elements.len();
+        self.index.get(id).copied()
+    }
+
+    fn next_id(&mut self) -> OpId {
+        self.clock += 1;
+        OpId { counter: self.clock, site: self.site }
+    }
+
+    // 第 index 个可见字符在元素数组中的位置
+    fn visible_position(&self, index: usize) -> Option<usize> {
+        self.elements
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| !e.deleted)
+            .nth(index)
+            .map(|(i, _)| i)
+    }
+
+    /// 光标锚点：光标左侧字符的 id（None 表示文档开头）
+    pub fn anchor_at(&self, index: usize) -> Option<OpId> {
+        if index == 0 {
+            return None;
+        }
+        self.visible_position(index - 1).map(|i| self.elements[i].id)
+    }
+
+    /// 将锚点换算回可见字符下标；锚点字符被删除时落在其左侧最近的可见字符之后
+    pub fn index_of_anchor(&self, anchor: Option<OpId>) -> usize {
+        let Some(anchor) = anchor else {
+            return 0;
+        };
+        let mut visible = 0;
+        for element in &self.elements {
+            if element.id == anchor {
+                return if element.deleted { visible } else { visible + 1 };
+            }
+            if !element.deleted {
+                visible += 1;
+            }
+        }
+        visible
+    }
+
+    /// 将本地编辑（编辑前后的完整文本）转换为操作并应用到本地文档
+    pub fn local_edit(&mut self, new_text: &str) -> Vec<Op> {
+        let old: Vec<char> = self.text().chars().collect();
+        let new: Vec<char> = new_text.chars().collect();
+
+        let prefix = old.iter().zip(new.iter()).take_while(|(a, b)| a == b).count();
+        let max_suffix = old.len().min(new.len()) - prefix;
+        let suffix = old
+            .iter()
+            .rev()
+            .zip(new.iter().rev())
+            .take(max_suffix)
+            .take_while(|(a, b)| a == b)
+            .count();
+
+        let mut ops = Vec::new();
+
+        // 删除 old[prefix..old.len() - suffix]
+        let delete_count = old.len() - suffix - prefix;
+        let targets: Vec<OpId> = self
+            .elements
+            .iter()
+            .filter(|e| !e.deleted)
+            .skip(prefix)
+            .take(delete_count)
+            .map(|e| e.id)
+            .collect();
+        for target in targets {
+            let op = Op::Delete { target };
+            self.apply(op.clone());
+            ops.push(op);
+        }
+
+        // 插入 new[prefix..new.len() - suffix]
+        let mut origin = self.anchor_at(prefix);
+        for ch in &new[prefix..new.len() - suffix] {
+            let id = self.next_id();
+            let op = Op::Insert { id, origin, ch: *ch };
+            self.apply(op.clone());
+            ops.push(op);
+            origin = Some(id);
+        }
+
+        ops
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_ops_resync_lost_edits() {
+        let mut server = TextDoc::new(1);
+        server.apply_all(&TextDoc::seed_ops("hello"));
+
+        let mut local = TextDoc::new(2);
+        local.apply_all(&TextDoc::seed_ops("hello"));
+        // 这些操作在断线前发出但服务器没有收到
+        local.local_edit("help!");
+
+        let missing = local.ops_missing_from(&server.elements);
+        server.apply_all(&missing);
+        assert_eq!(server.text(), "help!");
+        assert!(server.ops_missing_from(&local.elements).is_empty());
+        assert!(local.ops_missing_from(&server.elements).is_empty());
+    }
+
+    fn replicas(content: &str) -> (TextDoc, TextDoc) {
+        let mut a = TextDoc::new(1);
+        let mut b = TextDoc::new(2);
+        a.apply_all(&TextDoc::seed_ops(content));
+        b.apply_all(&TextDoc::seed_ops(content));
+        (a, b)
+    }
+
+    #[test]
+    fn concurrent_inserts_at_same_position_converge() {
+        let (mut a, mut b) = replicas("ab");
+        let from_a = a.local_edit("aXYb");
+        let from_b = b.local_edit("aZb");
+
+        a.apply_all(&from_b);
+        b.apply_all(&from_a);
+        assert_eq!(a.text(), b.text());
+        assert_eq!(a.text().len(), 5);
+        // 同一站点连续输入的字符不会被对方插入打散
+        assert!(a.text().contains("XY"));
+    }
+
+    #[test]
+    fn out_of_order_ops_wait_in_pending() {
+        let (mut a, mut b) = replicas("");
+        let mut ops = a.local_edit("hello");
+        ops.extend(a.local_edit("he world"));
+        ops.reverse();
+
+        b.apply_all(&ops);
+        assert!(b.pending.is_empty());
+        assert_eq!(b.text(), "he world");
+        assert_eq!(a.text(), b.text());
+    }
+
+    #[test]
+    fn delete_racing_insert_converges() {
+        let (mut a, mut b) = replicas("abc");
+        // a 删除 b 的同时 b 在其后插入
+        let from_a = a.local_edit("ac");
+        let from_b = b.local_edit("abXc");
+
+        a.apply_all(&from_b);
+        b.apply_all(&from_a);
+        assert_eq!(a.text(), "aXc");
+        assert_eq!(b.text(), "aXc");
+    }
+
+    #[test]
+    fn middle_edits_keep_positions_consistent() {
+        let (mut a, mut b) = replicas("0123456789");
+        let mut ops = a.local_edit("01ab23456789");
+        ops.extend(a.local_edit("01ab2345cd6789"));
+        ops.extend(a.local_edit("x01ab2345cd678"));
+
+        b.apply_all(&ops);
+        assert_eq!(a.text(), "x01ab2345cd678");
+        assert_eq!(b.text(), a.text());
+    }
+
+    #[test]
+    fn seeds_large_documents() {
+        let content = "协作编辑".repeat(10_000);
+        let mut doc = TextDoc::new(1);
+        doc.apply_all(&TextDoc::seed_ops(&content));
+        assert_eq!(doc.text(), content);
+    }
+}
@@ -92,6 +92,22 @@ pub fn ArticleCard(article: Article) -> Element {
                     // 底部元数据
                     div {
                         class: "flex items-center text-sm text-gray-500",
+                        // 会员专享文章
+                        if article.is_paid_content {
+                            span {
+                                class: "flex items-center mr-2 text-yellow-600",
+                                title: "会员专享文章",
+                                svg {
+                                    class: "w-4 h-4 mr-1",
+                                    fill: "currentColor",
+                                    view_box: "0 0 20 20",
+                                    path {
+                                        d: "M9.049 2.927c.3-.921 1.603-.921 1.902 0l1.07 3.292a1 1 0 00.95.69h3.462c.969 0 1.371 1.24.588 1.81l-2.8 2.034a1 1 0 00-.364 1.118l1.07 3.292c.3.921-.755 1.688-1.54 1.118l-2.8-2.034a1 1 0 00-1.175 0l-2.8 2.034c-.784.57-1.838-.197-1.539-1.118l1.07-3.292a1 1 0 00-.364-1.118L2.98 8.72c-.783-.57-.38-1.81.588-1.81h3.461a1 1 0 00.951-.69l1.07-3.292z"
+                                    }
+                                }
+                                span { class: "text-xs font-medium", "会员专享" }
+                            }
+                        }
                         span { {published_date} }
                         span { class: "mx-2", "·" }
                         span { {reading_time_text} }
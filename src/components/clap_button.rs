@@ -0,0 +1,222 @@
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+use gloo_timers::future::TimeoutFuture;
+use crate::{
+    api::articles::{ArticleService, ClapResponse},
+    hooks::use_auth,
+    Route,
+};
+
+// 每位读者对一篇文章最多点赞次数
+pub const MAX_CLAPS_PER_USER: i32 = 50;
+// 最后一次点赞后等待这么久再把累计的次数一次发出
+const FLUSH_DELAY_MS: f64 = 800.0;
+const FLUSH_CHECK_MS: u32 = 200;
+// 按住超过这个时间开始连续点赞
+const HOLD_DELAY_MS: u32 = 400;
+const HOLD_INTERVAL_MS: u32 = 120;
+// 浮动计数在最后一次点赞后保留的时间
+const BURST_VISIBLE_MS: f64 = 1000.0;
+
+/// Medium 风格的点赞按钮：单击或按住连续点赞，合并成一次请求发送，失败时回滚
+#[component]
+pub fn ClapButton(
+    article_id: String,
+    clap_count: i32,
+    user_clap_count: i32,
+    // 服务器确认后的 (我的点赞数, 总点赞数)
+    on_change: EventHandler<(i32, i32)>,
+) -> Element {
+    let auth = use_auth();
+    let navigator = use_navigator();
+    // 已点击但还没发出的次数
+    let mut pending = use_signal(|| 0);
+    let mut sending = use_signal(|| false);
+    // 正在发送的次数，仍计入 pending，直到请求返回
+    let mut in_flight = use_hook(|| CopyValue::new(0));
+    // 本轮连续点赞的次数，用于浮动计数
+    let mut burst = use_signal(|| 0);
+    let mut error = use_signal(|| None::<String>);
+    let mut holding = use_signal(|| false);
+    // 最近一次点赞的时间（毫秒），用于防抖
+    let mut last_clap = use_hook(|| CopyValue::new(0.0));
+    let article_id = use_hook(|| CopyValue::new(article_id.clone()));
+
+    let remaining = MAX_CLAPS_PER_USER - user_clap_count - pending();
+    let shown_user = user_clap_count + pending();
+    let shown_total = clap_count + pending();
+
+    // 停止点赞一段时间后把累计的次数一次发出；请求在这里依次执行，不会并发
+    use_future(move || async move {
+        loop {
+            TimeoutFuture::new(FLUSH_CHECK_MS).await;
+            if *holding.peek() {
+                continue;
+            }
+            let idle = js_sys::Date::now() - *last_clap.peek();
+            if *burst.peek() > 0 && idle >= BURST_VISIBLE_MS {
+                burst.set(0);
+            }
+            let count = *pending.peek();
+            if count == 0 || idle < FLUSH_DELAY_MS {
+                continue;
+            }
+            sending.set(true);
+            in_flight.set(count);
+            let id = article_id.peek().clone();
+            match ArticleService::clap_article(&id, count).await {
+                Ok(ClapResponse { user_clap_count, total_claps }) => {
+                    on_change.call((user_clap_count, total_claps as i32));
+                }
+                Err(e) => {
+                    error.set(Some(format!("点赞失败，已撤销 {} 次: {}", count, e.message)));
+                }
+            }
+            // 成功时计入服务器返回的数字，失败时回滚；发送期间新增的点赞留到下一轮
+            pending -= count;
+            in_flight.set(0);
+            sending.set(false);
+        }
+    });
+
+    let mut clap_once = move || -> bool {
+        if MAX_CLAPS_PER_USER - user_clap_count - *pending.peek() <= 0 {
+            return false;
+        }
+        pending += 1;
+        burst += 1;
+        error.set(None);
+        last_clap.set(js_sys::Date::now());
+        true
+    };
+
+    let start = move |_| {
+        if !auth.read().is_authenticated {
+            navigator.push(Route::Login {});
+            return;
+        }
+        if !clap_once() {
+            return;
+        }
+        holding.set(true);
+        spawn(async move {
+            TimeoutFuture::new(HOLD_DELAY_MS).await;
+            while *holding.peek() {
+                if !clap_once() {
+                    break;
+                }
+                TimeoutFuture::new(HOLD_INTERVAL_MS).await;
+            }
+        });
+    };
+
+    let mut stop = move || {
+        if *holding.peek() {
+            holding.set(false);
+            last_clap.set(js_sys::Date::now());
+        }
+    };
+
+    let undo = move |_| {
+        let id = article_id.peek().clone();
+        spawn(async move {
+            sending.set(true);
+            match ArticleService::unclap_article(&id).await {
+                Ok(_) => on_change.call((0, clap_count - user_clap_count)),
+                Err(e) => error.set(Some(format!("撤销失败: {}", e.message))),
+            }
+            sending.set(false);
+        });
+    };
+
+    // 离开页面时发出还没发送的点赞；正在发送的那一批已经在路上，不能重复发
+    use_drop(move || {
+        let count = *pending.peek() - *in_flight.peek();
+        if count > 0 {
+            let id = article_id.peek().clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                let _ = ArticleService::clap_article(&id, count).await;
+            });
+        }
+    });
+
+    rsx! {
+        div {
+            class: "relative flex items-center space-x-2",
+
+            // 浮动计数
+            if burst() > 0 {
+                span {
+                    key: "{burst}",
+                    class: "clap-float absolute -top-8 left-2 px-2 py-0.5 text-xs font-semibold text-white bg-green-600 rounded-full pointer-events-none",
+                    {format!("+{}", shown_user)}
+                }
+            }
+
+            button {
+                class: "flex items-center space-x-2 px-4 py-2 rounded-full hover:bg-gray-100 transition-colors select-none disabled:opacity-50",
+                title: if remaining <= 0 {
+                    format!("每篇文章最多点赞 {} 次", MAX_CLAPS_PER_USER)
+                } else {
+                    "单击点赞，按住连续点赞".to_string()
+                },
+                disabled: remaining <= 0 && !holding(),
+                onpointerdown: start,
+                onpointerup: move |_| stop(),
+                onpointerleave: move |_| stop(),
+                onpointercancel: move |_| stop(),
+                // 键盘操作时每次按键点赞一次
+                onkeydown: move |e: KeyboardEvent| {
+                    if e.key() == Key::Enter || e.key() == Key::Character(" ".to_string()) {
+                        e.prevent_default();
+                        if auth.read().is_authenticated {
+                            clap_once();
+                        }
+                    }
+                },
+
+                svg {
+                    class: if shown_user > 0 { "w-6 h-6 text-gray-900" } else { "w-6 h-6 text-gray-500" },
+                    fill: if shown_user > 0 { "currentColor" } else { "none" },
+                    stroke: "currentColor",
+                    view_box: "0 0 24 24",
+                    path {
+                        stroke_linecap: "round",
+                        stroke_linejoin: "round",
+                        stroke_width: "2",
+                        d: "M14 10h4.764a2 2 0 011.789 2.894l-3.5 7A2 2 0 0115.263 21h-4.017c-.163 0-.326-.02-.485-.06L7 20m7-10V5a2 2 0 00-2-2h-.095c-.5 0-.905.405-.905.905 0 .714-.211 1.412-.608 2.006L7 11v9m7-10h-2M7 20H5a2 2 0 01-2-2v-6a2 2 0 012-2h2.5"
+                    }
+                }
+
+                span {
+                    class: "text-sm font-medium",
+                    {shown_total.to_string()}
+                }
+
+                if shown_user > 0 {
+                    span {
+                        class: "text-xs text-gray-500",
+                        "(+{shown_user})"
+                    }
+                }
+            }
+
+            // 服务器上已有点赞且没有待发送的点赞时可以撤销
+            if user_clap_count > 0 && pending() == 0 && burst() == 0 {
+                button {
+                    class: "text-xs text-gray-400 hover:text-gray-600 disabled:opacity-50",
+                    disabled: sending(),
+                    onclick: undo,
+                    "撤销点赞"
+                }
+            }
+
+            if let Some(err) = error() {
+                span {
+                    class: "text-xs text-red-600",
+                    {err}
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use dioxus::prelude::*;
+use crate::hooks::{Collab, CollabStatus};
+
+/// 协作状态条：在线协作者头像与他们的光标位置
+#[component]
+pub fn CollabPresence(collab: Collab) -> Element {
+    let status = (collab.status)();
+    if status == CollabStatus::Disabled {
+        return rsx! {};
+    }
+
+    let peers = (collab.peers)();
+    let positions = collab.peer_positions();
+
+    let (dot_class, status_text) = match status {
+        CollabStatus::Online => ("bg-green-500", "实时协作中"),
+        CollabStatus::Connecting => ("bg-yellow-400", "正在连接协作服务..."),
+        _ => ("bg-gray-400", "离线编辑，恢复连接后自动合并"),
+    };
+
+    rsx! {
+        div {
+            class: "flex flex-wrap items-center gap-3 mb-4 text-sm text-gray-600 dark:text-gray-400",
+
+            span {
+                class: "flex items-center space-x-2",
+                span { class: "inline-block w-2 h-2 rounded-full {dot_class}" }
+                span { {status_text} }
+            }
+
+            // 在线协作者头像
+            if !peers.is_empty() {
+                div {
+                    class: "flex -space-x-2",
+                    for peer in peers.iter() {
+                        if let Some(avatar) = &peer.avatar_url {
+                            img {
+                                key: "{peer.site}",
+                                src: "{avatar}",
+                                alt: "{peer.name()}",
+                                title: "{peer.name()}",
+                                class: "w-7 h-7 rounded-full border-2 object-cover",
+                                style: "border-color: {peer.color()};"
+                            }
+                        } else {
+                            span {
+                                key: "{peer.site}",
+                                title: "{peer.name()}",
+                                class: "w-7 h-7 rounded-full border-2 border-white flex items-center justify-center text-xs font-medium text-white",
+                                style: "background-color: {peer.color()};",
+                                {peer.name().chars().next().map(|c| c.to_uppercase().to_string()).unwrap_or_default()}
+                            }
+                        }
+                    }
+                }
+            }
+
+            // 协作者光标位置，点击跳转
+            for (peer, line, column) in positions {
+                button {
+                    key: "cursor-{peer.site}",
+                    class: "flex items-center space-x-1 px-2 py-0.5 rounded-full text-xs text-white",
+                    style: "background-color: {peer.color()};",
+                    title: "跳转到 {peer.name()} 的光标位置",
+                    onclick: move |_| collab.jump_to_peer(peer.site),
+                    span { {peer.name().to_string()} }
+                    span { class: "opacity-80", "第 {line} 行 · 第 {column} 列" }
+                }
+            }
+        }
+    }
+}
@@ -1,50 +1,115 @@
 use dioxus::prelude::*;
+use gloo_storage::{SessionStorage, Storage};
+use std::collections::HashSet;
 use crate::{
-    models::comment::{Comment, CommentWithAuthor},
-    api::comments::{CommentService, CreateCommentRequest},
-    hooks::use_auth,
+    models::comment::{comment_path, Comment, CommentSort, CommentWithAuthor},
+    models::{mention::{extract_mentions, MentionCandidate}, moderation::CommentSettings},
+    api::{client::ApiResult, comments::{CommentService, CreateCommentRequest}, users::UserService},
+    collab::anchor::TextAnchor,
+    components::{scroll_to_anchor, MentionMenu},
+    hooks::{use_auth, use_live_comments, use_mentions, use_user_relations, LiveComments, LiveStatus},
+    rendering::link_mentions,
 };
 use chrono::{DateTime, Utc};
 use gloo_timers::future::TimeoutFuture;
 use web_sys;
 
+// 本次会话中折叠的讨论
+const COLLAPSED_KEY: &str = "collapsed_comments";
+pub(crate) const PERMALINK_PREFIX: &str = "comment-";
+// 通过固定链接打开的评论高亮时间
+const HIGHLIGHT_MS: u32 = 3000;
+// 段落评论引用原文时最多显示的字符数
+const EXCERPT_CHARS: usize = 120;
+
+/// 评论列表的显示状态：折叠的讨论、固定链接高亮的评论和文章的评论设置
+#[derive(Clone, Copy, PartialEq)]
+struct CommentView {
+    collapsed: Signal<HashSet<String>>,
+    highlighted: Signal<Option<String>>,
+    settings: Signal<CommentSettings>,
+}
+
+impl CommentView {
+    fn is_collapsed(&self, comment_id: &str) -> bool {
+        self.collapsed.read().contains(comment_id)
+    }
+
+    fn toggle(&mut self, comment_id: &str) {
+        {
+            let mut collapsed = self.collapsed.write();
+            if !collapsed.remove(comment_id) {
+                collapsed.insert(comment_id.to_string());
+            }
+        }
+        let _ = SessionStorage::set(COLLAPSED_KEY, &*self.collapsed.peek());
+    }
+
+    fn expand_all(&mut self, ids: &[String]) {
+        {
+            let mut collapsed = self.collapsed.write();
+            for id in ids {
+                collapsed.remove(id);
+            }
+        }
+        let _ = SessionStorage::set(COLLAPSED_KEY, &*self.collapsed.peek());
+    }
+}
+
 #[component]
 pub fn CommentSection(article_id: String) -> Element {
-    let mut comments = use_signal(|| Vec::<CommentWithAuthor>::new());
-    let mut loading = use_signal(|| true);
-    let mut sort_by = use_signal(|| "newest");
+    let mut live = use_context_provider(|| use_live_comments(article_id.clone()));
+    let comments = live.comments;
+    let loading = live.loading;
     let mut show_reply_form = use_signal(|| None::<String>);
+    let mut view = use_context_provider(|| CommentView {
+        collapsed: Signal::new(SessionStorage::get::<HashSet<String>>(COLLAPSED_KEY).unwrap_or_default()),
+        highlighted: Signal::new(None),
+        settings: Signal::new(CommentSettings::default()),
+    });
+    // 固定链接只在首次加载完成后定位一次
+    let mut permalink_handled = use_hook(|| CopyValue::new(false));
     
     let auth = use_auth();
-    let article_id_for_effect = article_id.clone();
     
-    // 初始加载和处理排序变化
+    // 作者可以关闭评论或只允许关注者、订阅者评论
+    let article_id_for_settings = article_id.clone();
     use_effect(move || {
-        let article_id = article_id_for_effect.clone();
+        let _ = auth.read().is_authenticated;
+        let article_id = article_id_for_settings.clone();
         spawn(async move {
-            loading.set(true);
-            
-            match CommentService::get_article_comments(&article_id, None, None, None).await {
-                Ok(comment_list) => {
-                    comments.set(comment_list);
-                }
-                Err(_) => {
-                    // 处理错误
-                }
+            if let Ok(settings) = CommentService::get_comment_settings(&article_id).await {
+                view.settings.set(settings);
             }
-            
-            loading.set(false);
         });
     });
+    let settings = (view.settings)();
     
-    // 计算总评论数（包括所有层级）
-    fn count_all_comments(comments: &[CommentWithAuthor]) -> usize {
-        comments.iter().map(|c| {
-            1 + count_all_comments(&c.replies)
-        }).sum()
-    }
+    // 打开 #comment-xxx 固定链接时定位并高亮该评论，不在已加载页中时单独加载其所在讨论
+    use_effect(move || {
+        if loading() || *permalink_handled.peek() {
+            return;
+        }
+        permalink_handled.set(true);
+        let Some(comment_id) = permalink_target() else {
+            return;
+        };
+        spawn(async move {
+            if !live.reveal(&comment_id).await {
+                return;
+            }
+            if let Some(path) = comment_path(&comments.peek(), &comment_id) {
+                view.expand_all(&path);
+            }
+            view.highlighted.set(Some(comment_id.clone()));
+            TimeoutFuture::new(0).await;
+            scroll_to_comment(&comment_id);
+            TimeoutFuture::new(HIGHLIGHT_MS).await;
+            view.highlighted.set(None);
+        });
+    });
     
-    let total_comments = count_all_comments(&comments());
+    let total_comments = (live.total)();
     
     rsx! {
         div {
@@ -53,53 +118,59 @@ pub fn CommentSection(article_id: String) -> Element {
             // 评论头部
             div {
                 class: "flex items-center justify-between mb-6",
-                h3 {
-                    class: "text-lg font-semibold text-gray-900 dark:text-white",
-                    "评论 ({total_comments})"
+                div {
+                    class: "flex items-center space-x-3",
+                    h3 {
+                        class: "text-lg font-semibold text-gray-900 dark:text-white",
+                        "评论 ({total_comments})"
+                    }
+                    match (live.status)() {
+                        LiveStatus::Live => rsx! {
+                            span {
+                                class: "text-xs text-green-600",
+                                title: "新评论会实时出现",
+                                "● 实时"
+                            }
+                        },
+                        LiveStatus::Polling => rsx! {
+                            span {
+                                class: "text-xs text-gray-400",
+                                title: "无法建立实时连接，每 20 秒自动刷新",
+                                "● 自动刷新"
+                            }
+                        },
+                        LiveStatus::Connecting => rsx! {},
+                    }
                 }
                 
                 // 排序选项
                 div {
-                    class: "flex items-center space-x-4",
-                    button {
-                        class: if sort_by() == "newest" { 
-                            "font-medium text-gray-900 dark:text-white" 
-                        } else { 
-                            "text-gray-500 dark:text-gray-400 hover:text-gray-700 dark:hover:text-gray-300" 
-                        },
-                        onclick: move |_| sort_by.set("newest"),
-                        "最新"
-                    }
-                    button {
-                        class: if sort_by() == "popular" { 
-                            "font-medium text-gray-900 dark:text-white" 
-                        } else { 
-                            "text-gray-500 dark:text-gray-400 hover:text-gray-700 dark:hover:text-gray-300" 
-                        },
-                        onclick: move |_| sort_by.set("popular"),
-                        "热门"
+                    class: "flex items-center space-x-4 text-sm",
+                    for sort in CommentSort::ALL {
+                        button {
+                            class: if (live.sort)() == sort { 
+                                "font-medium text-gray-900 dark:text-white" 
+                            } else { 
+                                "text-gray-500 dark:text-gray-400 hover:text-gray-700 dark:hover:text-gray-300" 
+                            },
+                            onclick: move |_| live.set_sort(sort),
+                            {sort.label()}
+                        }
                     }
                 }
             }
             
             // 评论输入框（顶级评论）
-            if auth.read().is_authenticated {
+            if !settings.viewer_can_comment {
+                div {
+                    class: "text-center py-6 bg-gray-50 dark:bg-gray-800 rounded-lg text-gray-600 dark:text-gray-400",
+                    {settings.permission.restriction_notice()}
+                }
+            } else if auth.read().is_authenticated {
                 CommentForm {
                     article_id: article_id.clone(),
                     parent_id: None,
-                    on_success: {
-                        let article_id = article_id.clone();
-                        move |_| {
-                            let article_id = article_id.clone();
-                            spawn(async move {
-                                loading.set(true);
-                                if let Ok(comment_list) = CommentService::get_article_comments(&article_id, None, None, None).await {
-                                    comments.set(comment_list);
-                                }
-                                loading.set(false);
-                            });
-                        }
-                    }
+                    on_success: move |_| live.reload()
                 }
             } else {
                 div {
@@ -123,6 +194,25 @@ pub fn CommentSection(article_id: String) -> Element {
                 }
             }
             
+            // 新评论提示：点击后再插入，阅读时内容不会跳动
+            if !(live.pending)().is_empty() {
+                div {
+                    class: "sticky top-20 z-10 flex justify-center mt-6",
+                    button {
+                        class: "px-4 py-1.5 text-sm font-medium text-white bg-blue-600 rounded-full shadow hover:bg-blue-700",
+                        onclick: move |_| live.show_pending(),
+                        {
+                            let count = (live.pending)().len();
+                            if live.pending_replies() == count {
+                                format!("↑ {} 条新回复", count)
+                            } else {
+                                format!("↑ {} 条新评论", count)
+                            }
+                        }
+                    }
+                }
+            }
+            
             // 评论列表
             if loading() {
                 div {
@@ -144,26 +234,28 @@ pub fn CommentSection(article_id: String) -> Element {
                     class: "space-y-6 mt-6",
                     for comment in comments() {
                         CommentThread {
+                            key: "{comment.comment.id}",
                             comment_with_replies: comment.clone(),
                             article_id: article_id.clone(),
                             show_reply_form: show_reply_form.clone(),
-                            on_reply_success: {
-                                let article_id = article_id.clone();
-                                move || {
-                                    let article_id = article_id.clone();
-                                    spawn(async move {
-                                        loading.set(true);
-                                        if let Ok(comment_list) = CommentService::get_article_comments(&article_id, None, None, None).await {
-                                            comments.set(comment_list);
-                                        }
-                                        loading.set(false);
-                                    });
-                                }
-                            },
+                            on_reply_success: move |_| live.reload(),
                             depth: 0,
                         }
                     }
                 }
+                
+                // 下一页顶级评论
+                if (live.has_more)() {
+                    div {
+                        class: "flex justify-center mt-8",
+                        button {
+                            class: "px-4 py-2 text-sm text-gray-700 dark:text-gray-300 border border-gray-300 dark:border-gray-600 rounded-full hover:bg-gray-50 dark:hover:bg-gray-800 disabled:opacity-50",
+                            disabled: (live.loading_more)(),
+                            onclick: move |_| live.load_more(),
+                            if (live.loading_more)() { "加载中..." } else { "加载更多评论" }
+                        }
+                    }
+                }
             }
         }
     }
@@ -179,6 +271,12 @@ fn CommentThread(
 ) -> Element {
     let comment = comment_with_replies.comment.clone();
     let replies = comment_with_replies.replies.clone();
+    let remaining = comment_with_replies.remaining_replies();
+    let mut live = use_context::<LiveComments>();
+    let mut view = use_context::<CommentView>();
+    let collapsed = view.is_collapsed(&comment.id);
+    let mut loading_replies = use_signal(|| false);
+    let comment_id = comment.id.clone();
     
     // 限制最大缩进深度，超过后不再缩进但仍可继续回复
     let max_indent_depth = 5;
@@ -206,12 +304,29 @@ fn CommentThread(
                 depth: depth,
             }
             
+            // 折叠或展开这条评论下的讨论
+            if !replies.is_empty() || remaining > 0 {
+                button {
+                    class: "mt-2 ml-[3.25rem] text-xs text-gray-500 dark:text-gray-400 hover:text-gray-700 dark:hover:text-gray-300",
+                    onclick: {
+                        let comment_id = comment_id.clone();
+                        move |_| view.toggle(&comment_id)
+                    },
+                    if collapsed {
+                        {format!("[+] 展开 {} 条回复", comment_with_replies.reply_count.max(replies.len() as i64))}
+                    } else {
+                        "[–] 收起回复"
+                    }
+                }
+            }
+            
             // 嵌套的回复
-            if !replies.is_empty() {
+            if !replies.is_empty() && !collapsed {
                 div {
                     class: "mt-4 space-y-4",
                     for reply in replies {
                         CommentThread {
+                            key: "{reply.comment.id}",
                             comment_with_replies: reply,
                             article_id: article_id.clone(),
                             show_reply_form: show_reply_form.clone(),
@@ -221,6 +336,29 @@ fn CommentThread(
                     }
                 }
             }
+            
+            // 分页加载更多回复
+            if remaining > 0 && !collapsed {
+                button {
+                    class: "mt-3 ml-[3.25rem] text-sm text-blue-600 hover:underline disabled:opacity-50",
+                    disabled: loading_replies(),
+                    onclick: move |_| {
+                        let comment_id = comment_id.clone();
+                        spawn(async move {
+                            loading_replies.set(true);
+                            if let Err(e) = live.load_replies(comment_id).await {
+                                log::warn!("加载回复失败: {}", e);
+                            }
+                            loading_replies.set(false);
+                        });
+                    },
+                    if loading_replies() {
+                        "加载中..."
+                    } else {
+                        {format!("查看更多回复（{}）", remaining)}
+                    }
+                }
+            }
         }
     }
 }
@@ -234,8 +372,18 @@ fn CommentItem(
     on_reply_success: EventHandler<()>,
     depth: usize,
 ) -> Element {
-    let mut is_liked = use_signal(|| comment_with_author.user_has_clapped);
-    let mut like_count = use_signal(|| comment.clap_count);
+    // 点赞状态来自实时更新的评论树
+    let mut live = use_context::<LiveComments>();
+    let view = use_context::<CommentView>();
+    let mut link_copied = use_signal(|| false);
+    let highlighted = (view.highlighted)().as_deref() == Some(comment.id.as_str());
+    // 屏蔽的用户的评论始终折叠；静音的可以点击临时显示。回复仍然正常显示
+    let relations = use_user_relations();
+    let mut revealed = use_signal(|| false);
+    let blocked = relations.is_blocked(&comment.author_id);
+    let concealed = blocked || (relations.is_muted(&comment.author_id) && !revealed());
+    let is_liked = comment_with_author.user_has_clapped;
+    let like_count = comment.clap_count;
     
     let comment_id = comment.id.clone();
     let comment_id_for_like = comment_id.clone();
@@ -256,7 +404,7 @@ fn CommentItem(
         }
         
         let comment_id = comment_id_for_like.clone();
-        let liked = is_liked();
+        let liked = is_liked;
         
         spawn(async move {
             let result = if liked {
@@ -266,19 +414,43 @@ fn CommentItem(
             };
             
             if result.is_ok() {
-                is_liked.set(!liked);
-                if liked {
-                    like_count.set(like_count() - 1);
-                } else {
-                    like_count.set(like_count() + 1);
-                }
+                let count = if liked { like_count - 1 } else { like_count + 1 };
+                live.set_clapped(&comment_id, count, !liked);
             }
         });
     };
     
+    // 复制指向这条评论的固定链接
+    let copy_permalink = {
+        let comment_id = comment_id.clone();
+        move |_| {
+            let Some(window) = web_sys::window() else { return };
+            let location = window.location();
+            let url = format!(
+                "{}{}#{}{}",
+                location.origin().unwrap_or_default(),
+                location.pathname().unwrap_or_default(),
+                PERMALINK_PREFIX,
+                comment_id
+            );
+            let clipboard = window.navigator().clipboard();
+            spawn(async move {
+                let _ = wasm_bindgen_futures::JsFuture::from(clipboard.write_text(&url)).await;
+                link_copied.set(true);
+                TimeoutFuture::new(2000).await;
+                link_copied.set(false);
+            });
+        }
+    };
+    
     rsx! {
         div {
-            class: "flex space-x-3",
+            id: "{PERMALINK_PREFIX}{comment.id}",
+            class: if highlighted {
+                "flex space-x-3 -mx-2 p-2 rounded-lg bg-yellow-50 dark:bg-yellow-900/20 ring-2 ring-yellow-300 transition-colors"
+            } else {
+                "flex space-x-3 transition-colors"
+            },
             
             // 用户头像
             if let Some(avatar_url) = &comment_with_author.author_avatar {
@@ -331,66 +503,130 @@ fn CommentItem(
                             "（已编辑）"
                         }
                     }
+                    
+                    if comment.is_pinned && depth == 0 {
+                        span {
+                            class: "px-1.5 py-0.5 rounded text-xs bg-blue-50 dark:bg-blue-900/30 text-blue-700 dark:text-blue-300",
+                            "作者置顶"
+                        }
+                    }
+                    if comment.is_author_response {
+                        span {
+                            class: "px-1.5 py-0.5 rounded text-xs bg-green-50 dark:bg-green-900/30 text-green-700 dark:text-green-300",
+                            "作者回应"
+                        }
+                    }
+                    if comment.is_hidden {
+                        span {
+                            class: "px-1.5 py-0.5 rounded text-xs bg-gray-100 dark:bg-gray-700 text-gray-600 dark:text-gray-300",
+                            title: "作者隐藏了这条评论，只有你和作者能看到",
+                            "已隐藏"
+                        }
+                    }
+                }
+                
+                // 针对正文段落的评论：引用原文，点击回到正文
+                if let Some(anchor) = comment.anchor.clone().filter(|_| depth == 0) {
+                    blockquote {
+                        class: "mb-2 pl-3 border-l-2 border-green-500 text-sm text-gray-600 dark:text-gray-400 italic line-clamp-2 cursor-pointer hover:text-gray-900 dark:hover:text-gray-200",
+                        title: "在正文中查看",
+                        onclick: move |_| scroll_to_anchor(&anchor),
+                        {excerpt(&anchor.exact)}
+                    }
                 }
                 
                 // 评论内容
-                p {
-                    class: "text-gray-800 dark:text-gray-200 whitespace-pre-wrap break-words",
-                    dangerous_inner_html: "{markdown_to_html(&comment.content)}"
+                if comment.is_deleted {
+                    p {
+                        class: "text-gray-400 dark:text-gray-500 italic",
+                        "该评论已删除"
+                    }
+                } else if blocked {
+                    p {
+                        class: "text-gray-400 dark:text-gray-500 italic",
+                        "你已屏蔽该用户，评论已隐藏"
+                    }
+                } else if concealed {
+                    p {
+                        class: "text-gray-400 dark:text-gray-500 italic",
+                        "你已静音该用户"
+                        button {
+                            class: "ml-2 not-italic text-sm text-gray-500 dark:text-gray-400 hover:text-gray-700 dark:hover:text-gray-300 underline",
+                            onclick: move |_| revealed.set(true),
+                            "仍然显示"
+                        }
+                    }
+                } else {
+                    p {
+                        class: "text-gray-800 dark:text-gray-200 whitespace-pre-wrap break-words",
+                        dangerous_inner_html: "{link_mentions(&markdown_to_html(&comment.content), &comment.mentions)}"
+                    }
                 }
                 
-                // 操作按钮
-                div {
-                    class: "flex items-center space-x-4 mt-2",
+                // 操作按钮，折叠的评论不能互动
+                if !concealed {
+                    div {
+                        class: "flex items-center space-x-4 mt-2",
                     
-                    // 点赞按钮
-                    button {
-                        class: "flex items-center space-x-1 text-sm text-gray-500 dark:text-gray-400 hover:text-gray-700 dark:hover:text-gray-300",
-                        onclick: handle_like,
-                        svg {
-                            class: if is_liked() { 
-                                "w-4 h-4 text-red-500 fill-current" 
-                            } else { 
-                                "w-4 h-4" 
-                            },
-                            fill: if is_liked() { "currentColor" } else { "none" },
-                            stroke: "currentColor",
-                            view_box: "0 0 24 24",
-                            path {
-                                stroke_linecap: "round",
-                                stroke_linejoin: "round",
-                                stroke_width: "2",
-                                d: "M4.318 6.318a4.5 4.5 0 000 6.364L12 20.364l7.682-7.682a4.5 4.5 0 00-6.364-6.364L12 7.636l-1.318-1.318a4.5 4.5 0 00-6.364 0z"
+                        // 点赞按钮
+                        button {
+                            class: "flex items-center space-x-1 text-sm text-gray-500 dark:text-gray-400 hover:text-gray-700 dark:hover:text-gray-300",
+                            onclick: handle_like,
+                            svg {
+                                class: if is_liked { 
+                                    "w-4 h-4 text-red-500 fill-current" 
+                                } else { 
+                                    "w-4 h-4" 
+                                },
+                                fill: if is_liked { "currentColor" } else { "none" },
+                                stroke: "currentColor",
+                                view_box: "0 0 24 24",
+                                path {
+                                    stroke_linecap: "round",
+                                    stroke_linejoin: "round",
+                                    stroke_width: "2",
+                                    d: "M4.318 6.318a4.5 4.5 0 000 6.364L12 20.364l7.682-7.682a4.5 4.5 0 00-6.364-6.364L12 7.636l-1.318-1.318a4.5 4.5 0 00-6.364 0z"
+                                }
                             }
+                            span { {like_count.to_string()} }
                         }
-                        span { {like_count().to_string()} }
-                    }
                     
-                    // 回复按钮 - 所有评论都可以被回复
-                    button {
-                        class: "text-sm text-gray-500 dark:text-gray-400 hover:text-gray-700 dark:hover:text-gray-300",
-                        onclick: {
-                            let comment_id = comment_id_for_reply.clone();
-                            move |_| {
-                                if auth.read().is_authenticated {
-                                    show_reply_form.set(Some(comment_id.clone()));
-                                } else {
-                                    web_sys::window()
-                                        .unwrap()
-                                        .location()
-                                        .set_href("/login")
-                                        .ok();
-                                }
+                        // 回复按钮 - 有评论权限时所有评论都可以被回复
+                        if (view.settings)().viewer_can_comment {
+                            button {
+                                class: "text-sm text-gray-500 dark:text-gray-400 hover:text-gray-700 dark:hover:text-gray-300",
+                                onclick: {
+                                    let comment_id = comment_id_for_reply.clone();
+                                    move |_| {
+                                        if auth.read().is_authenticated {
+                                            show_reply_form.set(Some(comment_id.clone()));
+                                        } else {
+                                            web_sys::window()
+                                                .unwrap()
+                                                .location()
+                                                .set_href("/login")
+                                                .ok();
+                                        }
+                                    }
+                                },
+                                "回复"
                             }
-                        },
-                        "回复"
-                    }
+                        }
                     
-                    // 回复数量显示
-                    if !comment_with_author.replies.is_empty() {
-                        span {
-                            class: "text-sm text-gray-500 dark:text-gray-400",
-                            "{comment_with_author.replies.len()} 条回复"
+                        // 回复数量显示
+                        if comment_with_author.reply_count > 0 || !comment_with_author.replies.is_empty() {
+                            span {
+                                class: "text-sm text-gray-500 dark:text-gray-400",
+                                {format!("{} 条回复", comment_with_author.reply_count.max(comment_with_author.replies.len() as i64))}
+                            }
+                        }
+                    
+                        // 固定链接
+                        button {
+                            class: "text-sm text-gray-400 dark:text-gray-500 hover:text-gray-700 dark:hover:text-gray-300",
+                            title: "复制这条评论的链接",
+                            onclick: copy_permalink,
+                            if link_copied() { "已复制链接" } else { "链接" }
                         }
                     }
                 }
@@ -414,19 +650,30 @@ fn CommentItem(
     }
 }
 
+/// 评论输入框；传入 anchor 时发布针对正文某一段的评论
 #[component]
-fn CommentForm(
+pub fn CommentForm(
     article_id: String,
     parent_id: Option<String>,
+    anchor: Option<TextAnchor>,
     on_success: EventHandler<Comment>,
 ) -> Element {
     let mut content = use_signal(|| String::new());
     let mut submitting = use_signal(|| false);
     let mut error = use_signal(|| None::<String>);
+    // 发布后提示哪些被提及的用户不会收到提醒
+    let mut skipped_mentions = use_signal(Vec::<MentionCandidate>::new);
+    let textarea_id = match (&parent_id, &anchor) {
+        (Some(parent_id), _) => format!("comment-input-{}", parent_id),
+        (None, Some(_)) => "comment-input-inline".to_string(),
+        (None, None) => "comment-input-root".to_string(),
+    };
+    let mut mentions = use_mentions(textarea_id.clone());
     
     // 克隆需要在多个闭包中使用的值
     let article_id_for_submit = article_id.clone();
     let parent_id_for_submit = parent_id.clone();
+    let anchor_for_submit = anchor.clone();
     
     let parent_id_clone = parent_id.clone();
     let _handle_submit = move |e: Event<FormData>| {
@@ -449,16 +696,15 @@ fn CommentForm(
         submitting.set(true);
         error.set(None);
         
-        let request = CreateCommentRequest {
-            article_id: article_id_for_submit.clone(),
-            content: comment_content,
-            parent_id: parent_id_for_submit.clone(),
-        };
+        let article_id = article_id_for_submit.clone();
+        let parent_id = parent_id_for_submit.clone();
+        let anchor = anchor_for_submit.clone();
         
         spawn(async move {
-            match CommentService::create_comment(&request).await {
-                Ok(comment) => {
+            match create_comment_with_mentions(article_id, parent_id, anchor, comment_content).await {
+                Ok((comment, skipped)) => {
                     content.set(String::new());
+                    skipped_mentions.set(skipped);
                     on_success.call(comment);
                 }
                 Err(e) => {
@@ -474,17 +720,56 @@ fn CommentForm(
             // 移除 onsubmit，使用按钮的 onclick 代替
             class: "space-y-4",
             
-            textarea {
-                value: "{content}",
-                oninput: move |e| content.set(e.value()),
-                placeholder: if parent_id.is_some() { 
-                    "写下你的回复..." 
-                } else { 
-                    "写下你的评论..." 
-                },
-                class: "w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg resize-none focus:outline-none focus:ring-2 focus:ring-blue-500 dark:bg-gray-800 dark:text-white",
-                rows: "3",
-                disabled: submitting()
+            div {
+                class: "relative",
+                textarea {
+                    id: "{textarea_id}",
+                    value: "{content}",
+                    oninput: move |e| {
+                        let value = e.value();
+                        mentions.update(&value);
+                        content.set(value);
+                        skipped_mentions.set(Vec::new());
+                    },
+                    onkeydown: move |e| {
+                        let text = content.peek().clone();
+                        if let Some(updated) = mentions.on_keydown(&e, &text) {
+                            content.set(updated);
+                        }
+                    },
+                    onblur: move |_| mentions.close(),
+                    placeholder: if parent_id.is_some() { 
+                        "写下你的回复，输入 @ 提及用户..." 
+                    } else { 
+                        "写下你的评论，输入 @ 提及用户..." 
+                    },
+                    class: "w-full px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg resize-none focus:outline-none focus:ring-2 focus:ring-blue-500 dark:bg-gray-800 dark:text-white",
+                    rows: "3",
+                    disabled: submitting()
+                }
+                MentionMenu {
+                    mentions,
+                    on_select: move |candidate: MentionCandidate| {
+                        let text = content.peek().clone();
+                        if let Some(updated) = mentions.select(&candidate, &text) {
+                            content.set(updated);
+                        }
+                    }
+                }
+            }
+            
+            if !skipped_mentions().is_empty() {
+                div {
+                    class: "text-sm text-amber-700 dark:text-amber-400 bg-amber-50 dark:bg-amber-900/20 rounded-lg px-3 py-2",
+                    p { "评论已发布，以下用户不会收到提醒：" }
+                    for candidate in skipped_mentions() {
+                        p {
+                            key: "{candidate.id}",
+                            class: "text-xs",
+                            {format!("@{}（{}）", candidate.username, candidate.unavailable_reason())}
+                        }
+                    }
+                }
             }
             
             if let Some(err) = error() {
@@ -518,6 +803,7 @@ fn CommentForm(
                         onclick: {
                             let article_id = article_id.clone();
                             let parent_id = parent_id.clone();
+                            let anchor = anchor.clone();
                             let on_success = on_success.clone();
                             
                             move |e: Event<MouseData>| {
@@ -535,16 +821,15 @@ fn CommentForm(
                                 submitting.set(true);
                                 error.set(None);
                                 
-                                let request = CreateCommentRequest {
-                                    article_id: article_id.clone(),
-                                    content: comment_content,
-                                    parent_id: parent_id.clone(),
-                                };
+                                let article_id = article_id.clone();
+                                let parent_id = parent_id.clone();
+                                let anchor = anchor.clone();
                             
                                 spawn(async move {
-                                    match CommentService::create_comment(&request).await {
-                                        Ok(comment) => {
+                                    match create_comment_with_mentions(article_id, parent_id, anchor, comment_content).await {
+                                        Ok((comment, skipped)) => {
                                             content.set(String::new());
+                                            skipped_mentions.set(skipped);
                                             on_success.call(comment);
                                         }
                                         Err(e) => {
@@ -567,6 +852,70 @@ fn CommentForm(
     }
 }
 
+// 发布评论并通知被提及的用户；返回评论和不会收到提醒的用户
+async fn create_comment_with_mentions(
+    article_id: String,
+    parent_id: Option<String>,
+    anchor: Option<TextAnchor>,
+    content: String,
+) -> ApiResult<(Comment, Vec<MentionCandidate>)> {
+    let usernames = extract_mentions(&content);
+    let (mentions, skipped) = if usernames.is_empty() {
+        (Vec::new(), Vec::new())
+    } else {
+        match UserService::resolve_mentions(&usernames).await {
+            Ok(candidates) => {
+                let (allowed, skipped): (Vec<_>, Vec<_>) =
+                    candidates.into_iter().partition(|c| c.can_mention);
+                (allowed.into_iter().map(|c| c.username).collect(), skipped)
+            }
+            // 查询失败时交给服务端判断
+            Err(_) => (usernames, Vec::new()),
+        }
+    };
+    
+    let request = CreateCommentRequest {
+        article_id,
+        content,
+        parent_id,
+        mentions,
+        anchor,
+    };
+    let comment = CommentService::create_comment(&request).await?;
+    Ok((comment, skipped))
+}
+
+// 引用的原文
+fn excerpt(text: &str) -> String {
+    let text = text.trim();
+    if text.chars().count() > EXCERPT_CHARS {
+        format!("“{}…”", text.chars().take(EXCERPT_CHARS).collect::<String>())
+    } else {
+        format!("“{}”", text)
+    }
+}
+
+// 地址中 #comment-xxx 指向的评论
+fn permalink_target() -> Option<String> {
+    let hash = web_sys::window()?.location().hash().ok()?;
+    let id = hash.trim_start_matches('#').strip_prefix(PERMALINK_PREFIX)?;
+    (!id.is_empty()).then(|| id.to_string())
+}
+
+// 把评论滚动到视口中间
+fn scroll_to_comment(comment_id: &str) {
+    let Some(win) = web_sys::window() else { return };
+    let Some(element) = win
+        .document()
+        .and_then(|d| d.get_element_by_id(&format!("{}{}", PERMALINK_PREFIX, comment_id)))
+    else {
+        return;
+    };
+    let viewport = win.inner_height().ok().and_then(|h| h.as_f64()).unwrap_or(0.0);
+    let top = element.get_bounding_client_rect().top() + win.scroll_y().unwrap_or(0.0);
+    win.scroll_to_with_x_and_y(0.0, (top - viewport / 2.0).max(0.0));
+}
+
 fn format_relative_time(datetime: &DateTime<Utc>) -> String {
     let now = Utc::now();
     let duration = now.signed_duration_since(*datetime);
@@ -586,7 +935,7 @@ fn format_relative_time(datetime: &DateTime<Utc>) -> String {
     }
 }
 
-fn markdown_to_html(markdown: &str) -> String {
+pub(crate) fn markdown_to_html(markdown: &str) -> String {
     // 简单的 Markdown 处理，实际应该使用更完整的 Markdown 解析器
     markdown
         .replace("&", "&amp;")
@@ -0,0 +1,80 @@
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+use crate::{
+    hooks::{use_auth, ReadingStore},
+    models::reading::ReadingRecord,
+    Route,
+};
+
+const SHELF_LIMIT: usize = 4;
+
+/// 首页的“继续阅读”书架：读到一半的文章及剩余时间
+#[component]
+pub fn ContinueReadingShelf() -> Element {
+    let auth = use_auth();
+    let mut records = use_signal(Vec::<ReadingRecord>::new);
+
+    use_effect(move || {
+        let authenticated = auth.read().is_authenticated;
+        spawn(async move {
+            records.set(ReadingStore::continue_reading(authenticated, SHELF_LIMIT).await);
+        });
+    });
+
+    if records().is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        section {
+            class: "py-6 border-b border-gray-100",
+            div {
+                class: "flex items-center justify-between mb-4",
+                h2 {
+                    class: "text-sm font-bold text-gray-900 uppercase tracking-wide",
+                    "继续阅读"
+                }
+                Link {
+                    to: Route::ReadingHistory {},
+                    class: "text-sm text-green-600 hover:text-green-700",
+                    "阅读历史 →"
+                }
+            }
+            div {
+                class: "grid grid-cols-1 sm:grid-cols-2 gap-4",
+                for record in records() {
+                    Link {
+                        key: "{record.article_id}",
+                        to: Route::Article { slug: record.article_slug.clone() },
+                        class: "flex items-center p-3 border border-gray-100 rounded-lg hover:bg-gray-50",
+                        if let Some(cover) = &record.article_cover_image {
+                            img {
+                                src: "{cover}",
+                                alt: "{record.article_title}",
+                                class: "w-16 h-16 object-cover rounded mr-3 shrink-0"
+                            }
+                        }
+                        div {
+                            class: "flex-1 min-w-0",
+                            h3 {
+                                class: "text-sm font-medium text-gray-900 truncate",
+                                {record.article_title.clone()}
+                            }
+                            p {
+                                class: "text-xs text-gray-500 mb-2",
+                                {format!("{} · 还剩 {} 分钟", record.author_name, record.minutes_left())}
+                            }
+                            div {
+                                class: "h-1 bg-gray-100 rounded-full overflow-hidden",
+                                div {
+                                    class: "h-full bg-green-600",
+                                    style: format!("width: {}%", record.progress * 100.0)
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
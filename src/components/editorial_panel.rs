@@ -0,0 +1,530 @@
+use dioxus::prelude::*;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use crate::{
+    api::editorial::EditorialService,
+    collab::{anchor::TextAnchor, diff},
+    hooks::{editor_selection, select_in_editor},
+    models::editorial::{
+        CreateDraftCommentRequest, CreateSuggestionRequest, DraftComment, Suggestion,
+        SuggestionStatus,
+    },
+};
+
+/// 出版物草稿的编辑侧栏：修改建议与锚定在文字上的批注
+#[component]
+pub fn EditorialPanel(
+    article_id: String,
+    content: String,
+    // 建议模式：(进入模式时的正文, 工作副本)
+    draft: Option<(String, String)>,
+    is_author: bool,
+    on_apply: EventHandler<String>,
+    // 逐条提交时把已提交的修改从工作副本中撤回，失败后重试不会重复提交
+    on_draft_change: EventHandler<String>,
+    on_draft_submitted: EventHandler<()>,
+) -> Element {
+    let mut suggestions = use_signal(Vec::<Suggestion>::new);
+    let mut comments = use_signal(Vec::<DraftComment>::new);
+    let mut loading = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+    let mut show_resolved = use_signal(|| false);
+    let mut pending_anchor = use_signal(|| None::<TextAnchor>);
+    let mut comment_body = use_signal(String::new);
+    let mut reply_bodies = use_signal(HashMap::<String, String>::new);
+    let mut reload = use_signal(|| 0u32);
+
+    // 加载建议与批注
+    let id_for_load = article_id.clone();
+    use_effect(move || {
+        reload();
+        let article_id = id_for_load.clone();
+        spawn(async move {
+            loading.set(true);
+            match EditorialService::get_suggestions(&article_id).await {
+                Ok(list) => suggestions.set(list),
+                Err(e) => error.set(Some(format!("加载修改建议失败: {}", e.message))),
+            }
+            match EditorialService::get_comments(&article_id).await {
+                Ok(list) => comments.set(list),
+                Err(e) => error.set(Some(format!("加载批注失败: {}", e.message))),
+            }
+            loading.set(false);
+        });
+    });
+
+    // 工作副本与正文的差异，即待提交的建议
+    let draft_changes = draft
+        .as_ref()
+        .map(|(base, working)| diff::text_changes(base, working))
+        .unwrap_or_default();
+
+    // 提交建议，锚点基于进入建议模式时的正文
+    let submit_draft = {
+        let article_id = article_id.clone();
+        let (content, working) = draft.clone().unwrap_or_default();
+        let changes = draft_changes.clone();
+        move |_| {
+            let article_id = article_id.clone();
+            let content = content.clone();
+            let mut working: Vec<char> = working.chars().collect();
+            let changes = changes.clone();
+            spawn(async move {
+                error.set(None);
+                for change in changes {
+                    let end = change.start + change.deleted.chars().count();
+                    let request = CreateSuggestionRequest {
+                        anchor: TextAnchor::capture(&content, change.start, end),
+                        replacement: change.inserted.clone(),
+                    };
+                    match EditorialService::create_suggestion(&article_id, &request).await {
+                        Ok(suggestion) => {
+                            suggestions.write().push(suggestion);
+                            // 之前的修改都已撤回，这处修改在工作副本中的位置与正文一致
+                            let inserted_end = change.start + change.inserted.chars().count();
+                            working.splice(change.start..inserted_end, change.deleted.chars());
+                            on_draft_change.call(working.iter().collect());
+                        }
+                        Err(e) => {
+                            error.set(Some(format!("提交建议失败: {}", e.message)));
+                            return;
+                        }
+                    }
+                }
+                on_draft_submitted.call(());
+            });
+        }
+    };
+
+    // 接受或拒绝建议（仅作者）
+    let review_suggestion = {
+        let article_id = article_id.clone();
+        let content = content.clone();
+        move |suggestion: Suggestion, accept: bool| {
+            let article_id = article_id.clone();
+            let content = content.clone();
+            spawn(async move {
+                error.set(None);
+                let updated_content = if accept {
+                    let Some((start, end)) = suggestion.anchor.locate(&content) else {
+                        error.set(Some("原文已变化，无法定位这条建议".to_string()));
+                        return;
+                    };
+                    let mut chars: Vec<char> = content.chars().collect();
+                    chars.splice(start..end, suggestion.replacement.chars());
+                    Some(chars.into_iter().collect::<String>())
+                } else {
+                    None
+                };
+
+                let status = if accept { SuggestionStatus::Accepted } else { SuggestionStatus::Rejected };
+                match EditorialService::update_suggestion(&article_id, &suggestion.id, status).await {
+                    Ok(updated) => {
+                        if let Some(item) = suggestions.write().iter_mut().find(|s| s.id == updated.id) {
+                            *item = updated;
+                        }
+                        if let Some(updated_content) = updated_content {
+                            on_apply.call(updated_content);
+                        }
+                    }
+                    Err(e) => error.set(Some(format!("操作失败: {}", e.message))),
+                }
+            });
+        }
+    };
+
+    // 为选中的文字创建批注
+    let capture_selection = {
+        // 选区下标对应输入框中显示的文本，建议模式下是工作副本
+        let displayed = draft.as_ref().map(|(_, working)| working.clone()).unwrap_or_else(|| content.clone());
+        move |_| match editor_selection() {
+            Some((start, end)) if start < end => {
+                pending_anchor.set(Some(TextAnchor::capture(&displayed, start, end)));
+                error.set(None);
+            }
+            _ => error.set(Some("请先在正文中选中要批注的文字".to_string())),
+        }
+    };
+
+    let submit_comment = {
+        let article_id = article_id.clone();
+        move |_| {
+            let (Some(anchor), body) = (pending_anchor(), comment_body().trim().to_string()) else {
+                return;
+            };
+            if body.is_empty() {
+                return;
+            }
+            let article_id = article_id.clone();
+            spawn(async move {
+                match EditorialService::create_comment(&article_id, &CreateDraftCommentRequest { anchor, body }).await {
+                    Ok(comment) => {
+                        comments.write().push(comment);
+                        pending_anchor.set(None);
+                        comment_body.set(String::new());
+                    }
+                    Err(e) => error.set(Some(format!("添加批注失败: {}", e.message))),
+                }
+            });
+        }
+    };
+
+    let submit_reply = {
+        let article_id = article_id.clone();
+        move |comment_id: String| {
+            let body = reply_bodies.read().get(&comment_id).cloned().unwrap_or_default();
+            let body = body.trim().to_string();
+            if body.is_empty() {
+                return;
+            }
+            let article_id = article_id.clone();
+            spawn(async move {
+                match EditorialService::reply_comment(&article_id, &comment_id, body).await {
+                    Ok(reply) => {
+                        if let Some(comment) = comments.write().iter_mut().find(|c| c.id == comment_id) {
+                            comment.replies.push(reply);
+                        }
+                        reply_bodies.write().remove(&comment_id);
+                    }
+                    Err(e) => error.set(Some(format!("回复失败: {}", e.message))),
+                }
+            });
+        }
+    };
+
+    let toggle_resolved = {
+        let article_id = article_id.clone();
+        move |comment: DraftComment| {
+            let article_id = article_id.clone();
+            spawn(async move {
+                match EditorialService::resolve_comment(&article_id, &comment.id, !comment.resolved).await {
+                    Ok(updated) => {
+                        if let Some(item) = comments.write().iter_mut().find(|c| c.id == updated.id) {
+                            *item = updated;
+                        }
+                    }
+                    Err(e) => error.set(Some(format!("操作失败: {}", e.message))),
+                }
+            });
+        }
+    };
+
+    let pending: Vec<Suggestion> = suggestions
+        .read()
+        .iter()
+        .filter(|s| s.status == SuggestionStatus::Pending)
+        .cloned()
+        .collect();
+    let visible_comments: Vec<DraftComment> = comments
+        .read()
+        .iter()
+        .filter(|c| show_resolved() || !c.resolved)
+        .cloned()
+        .collect();
+    let resolved_count = comments.read().iter().filter(|c| c.resolved).count();
+
+    rsx! {
+        aside {
+            class: "mt-8 xl:mt-0 xl:fixed xl:top-24 xl:right-8 xl:w-80 xl:max-h-[80vh] xl:overflow-y-auto space-y-6 text-sm",
+
+            div {
+                class: "flex items-center justify-between",
+                h3 {
+                    class: "text-xs font-semibold uppercase tracking-wide text-gray-500",
+                    "编辑协作"
+                }
+                button {
+                    class: "text-xs text-gray-500 hover:text-gray-900 dark:hover:text-white",
+                    disabled: loading(),
+                    onclick: move |_| reload.set(reload() + 1),
+                    if loading() { "加载中..." } else { "刷新" }
+                }
+            }
+
+            if let Some(err) = error() {
+                div {
+                    class: "bg-red-50 dark:bg-red-900/20 text-red-700 dark:text-red-400 px-3 py-2 rounded",
+                    {err}
+                }
+            }
+
+            // 建议模式下尚未提交的修改
+            if draft.is_some() {
+                div {
+                    class: "border border-green-200 dark:border-green-800 rounded-lg p-3 space-y-2",
+                    p {
+                        class: "text-gray-700 dark:text-gray-300",
+                        "建议模式：你的修改不会直接写入正文"
+                    }
+                    for (i, change) in draft_changes.iter().enumerate() {
+                        div {
+                            key: "{i}",
+                            class: "text-xs break-words",
+                            SuggestionDiff { deleted: change.deleted.clone(), inserted: change.inserted.clone() }
+                        }
+                    }
+                    button {
+                        class: "w-full px-3 py-1.5 text-xs text-white bg-green-600 rounded-full hover:bg-green-700 disabled:opacity-50",
+                        disabled: draft_changes.is_empty(),
+                        onclick: submit_draft,
+                        "提交 {draft_changes.len()} 处建议"
+                    }
+                }
+            }
+
+            // 待处理的建议
+            div {
+                class: "space-y-3",
+                h4 {
+                    class: "font-medium text-gray-900 dark:text-white",
+                    "修改建议 ({pending.len()})"
+                }
+                if pending.is_empty() {
+                    p { class: "text-gray-500", "暂无待处理的建议" }
+                }
+                for suggestion in pending {
+                    div {
+                        key: "{suggestion.id}",
+                        class: "border border-gray-200 dark:border-gray-700 rounded-lg p-3 space-y-2",
+                        div {
+                            class: "flex items-center justify-between text-xs text-gray-500",
+                            span { {suggestion.author.display_name.clone()} }
+                            span { {format_relative_time(&suggestion.created_at)} }
+                        }
+                        button {
+                            class: "block w-full text-left break-words",
+                            title: "在正文中定位",
+                            onclick: {
+                                let anchor = suggestion.anchor.clone();
+                                let content = content.clone();
+                                move |_| {
+                                    if let Some((start, end)) = anchor.locate(&content) {
+                                        select_in_editor(start, end);
+                                    }
+                                }
+                            },
+                            SuggestionDiff {
+                                deleted: suggestion.anchor.exact.clone(),
+                                inserted: suggestion.replacement.clone()
+                            }
+                        }
+                        if suggestion.anchor.locate(&content).is_none() {
+                            p { class: "text-xs text-yellow-600", "原文已变化，无法定位" }
+                        }
+                        if is_author {
+                            div {
+                                class: "flex space-x-2",
+                                button {
+                                    class: "px-3 py-1 text-xs text-white bg-green-600 rounded-full hover:bg-green-700",
+                                    onclick: {
+                                        let suggestion = suggestion.clone();
+                                        let review = review_suggestion.clone();
+                                        move |_| review(suggestion.clone(), true)
+                                    },
+                                    "接受"
+                                }
+                                button {
+                                    class: "px-3 py-1 text-xs text-gray-700 dark:text-gray-300 border border-gray-300 dark:border-gray-600 rounded-full",
+                                    onclick: {
+                                        let suggestion = suggestion.clone();
+                                        let review = review_suggestion.clone();
+                                        move |_| review(suggestion.clone(), false)
+                                    },
+                                    "拒绝"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            // 批注
+            div {
+                class: "space-y-3",
+                div {
+                    class: "flex items-center justify-between",
+                    h4 {
+                        class: "font-medium text-gray-900 dark:text-white",
+                        "批注 ({visible_comments.len()})"
+                    }
+                    if resolved_count > 0 {
+                        label {
+                            class: "flex items-center space-x-1 text-xs text-gray-500",
+                            input {
+                                r#type: "checkbox",
+                                checked: show_resolved(),
+                                onchange: move |e| show_resolved.set(e.checked())
+                            }
+                            span { "显示已解决 ({resolved_count})" }
+                        }
+                    }
+                }
+
+                if let Some(anchor) = pending_anchor() {
+                    div {
+                        class: "border border-blue-200 dark:border-blue-800 rounded-lg p-3 space-y-2",
+                        blockquote {
+                            class: "border-l-2 border-blue-400 pl-2 text-xs text-gray-600 dark:text-gray-400 line-clamp-3",
+                            {anchor.exact.clone()}
+                        }
+                        textarea {
+                            class: "w-full h-20 p-2 text-sm bg-transparent border border-gray-300 dark:border-gray-600 rounded outline-none resize-none text-gray-900 dark:text-white",
+                            placeholder: "写下批注...",
+                            value: "{comment_body}",
+                            oninput: move |e| comment_body.set(e.value())
+                        }
+                        div {
+                            class: "flex justify-end space-x-2",
+                            button {
+                                class: "px-3 py-1 text-xs text-gray-600 dark:text-gray-400",
+                                onclick: move |_| pending_anchor.set(None),
+                                "取消"
+                            }
+                            button {
+                                class: "px-3 py-1 text-xs text-white bg-gray-900 rounded-full disabled:opacity-50",
+                                disabled: comment_body().trim().is_empty(),
+                                onclick: submit_comment,
+                                "发表"
+                            }
+                        }
+                    }
+                } else {
+                    button {
+                        class: "w-full px-3 py-1.5 text-xs text-gray-700 dark:text-gray-300 border border-dashed border-gray-300 dark:border-gray-600 rounded-lg hover:border-gray-500",
+                        onclick: capture_selection,
+                        "为选中的文字添加批注"
+                    }
+                }
+
+                for comment in visible_comments {
+                    div {
+                        key: "{comment.id}",
+                        class: if comment.resolved {
+                            "border border-gray-200 dark:border-gray-700 rounded-lg p-3 space-y-2 opacity-60"
+                        } else {
+                            "border border-gray-200 dark:border-gray-700 rounded-lg p-3 space-y-2"
+                        },
+                        button {
+                            class: "block w-full text-left border-l-2 border-yellow-400 pl-2 text-xs text-gray-600 dark:text-gray-400 line-clamp-2",
+                            title: "在正文中定位",
+                            onclick: {
+                                let anchor = comment.anchor.clone();
+                                let content = content.clone();
+                                move |_| {
+                                    if let Some((start, end)) = anchor.locate(&content) {
+                                        select_in_editor(start, end);
+                                    }
+                                }
+                            },
+                            {comment.anchor.exact.clone()}
+                        }
+                        if comment.anchor.locate(&content).is_none() {
+                            p { class: "text-xs text-yellow-600", "批注的文字已被修改或删除" }
+                        }
+                        DraftCommentBody {
+                            author: comment.author.display_name.clone(),
+                            body: comment.body.clone(),
+                            created_at: comment.created_at
+                        }
+                        for reply in comment.replies.iter() {
+                            div {
+                                key: "{reply.id}",
+                                class: "ml-3 pl-3 border-l border-gray-200 dark:border-gray-700",
+                                DraftCommentBody {
+                                    author: reply.author.display_name.clone(),
+                                    body: reply.body.clone(),
+                                    created_at: reply.created_at
+                                }
+                            }
+                        }
+                        if !comment.resolved {
+                            div {
+                                class: "flex space-x-2",
+                                input {
+                                    class: "flex-1 min-w-0 px-2 py-1 text-xs bg-transparent border border-gray-300 dark:border-gray-600 rounded outline-none text-gray-900 dark:text-white",
+                                    placeholder: "回复...",
+                                    value: reply_bodies.read().get(&comment.id).cloned().unwrap_or_default(),
+                                    oninput: {
+                                        let id = comment.id.clone();
+                                        move |e: FormEvent| {
+                                            reply_bodies.write().insert(id.clone(), e.value());
+                                        }
+                                    },
+                                    onkeydown: {
+                                        let id = comment.id.clone();
+                                        let submit = submit_reply.clone();
+                                        move |e: KeyboardEvent| {
+                                            if e.key() == Key::Enter {
+                                                submit(id.clone());
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        button {
+                            class: "text-xs text-gray-500 hover:text-gray-900 dark:hover:text-white",
+                            onclick: {
+                                let comment = comment.clone();
+                                let toggle = toggle_resolved.clone();
+                                move |_| toggle(comment.clone())
+                            },
+                            if comment.resolved { "重新打开" } else { "标记为已解决" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn SuggestionDiff(deleted: String, inserted: String) -> Element {
+    rsx! {
+        if !deleted.is_empty() {
+            del {
+                class: "bg-red-50 dark:bg-red-900/30 text-red-700 dark:text-red-400",
+                {deleted}
+            }
+        }
+        if !inserted.is_empty() {
+            ins {
+                class: "no-underline bg-green-50 dark:bg-green-900/30 text-green-700 dark:text-green-400",
+                {inserted}
+            }
+        }
+    }
+}
+
+#[component]
+fn DraftCommentBody(author: String, body: String, created_at: DateTime<Utc>) -> Element {
+    rsx! {
+        div {
+            div {
+                class: "flex items-center justify-between text-xs text-gray-500",
+                span { class: "font-medium text-gray-700 dark:text-gray-300", {author} }
+                span { {format_relative_time(&created_at)} }
+            }
+            p {
+                class: "mt-1 text-gray-800 dark:text-gray-200 whitespace-pre-wrap break-words",
+                {body}
+            }
+        }
+    }
+}
+
+fn format_relative_time(datetime: &DateTime<Utc>) -> String {
+    let duration = Utc::now().signed_duration_since(*datetime);
+
+    if duration.num_seconds() < 60 {
+        "刚刚".to_string()
+    } else if duration.num_minutes() < 60 {
+        format!("{} 分钟前", duration.num_minutes())
+    } else if duration.num_hours() < 24 {
+        format!("{} 小时前", duration.num_hours())
+    } else if duration.num_days() < 7 {
+        format!("{} 天前", duration.num_days())
+    } else {
+        datetime.format("%Y-%m-%d %H:%M").to_string()
+    }
+}
@@ -1,185 +1,328 @@
+use std::collections::HashSet;
 use dioxus::prelude::*;
-use web_sys::{window, Selection, Range, HtmlElement};
+use web_sys::{window, Node};
 use wasm_bindgen::JsCast;
-use crate::models::highlight::{Highlight, CreateHighlightRequest};
+use crate::{
+    api::{highlights::HighlightService, private_notes::PrivateNoteService},
+    collab::{anchor::TextAnchor, char_to_utf16_index},
+    models::{
+        highlight::{CreateHighlightRequest, Highlight, HighlightReport, PopularHighlight, UpdateHighlightRequest},
+        private_note::CreatePrivateNoteRequest,
+    },
+};
 use gloo_storage::{LocalStorage, Storage};
-use std::rc::Rc;
-use std::cell::RefCell;
+use gloo_timers::future::TimeoutFuture;
 
 // 高亮颜色选项
 const HIGHLIGHT_COLORS: &[(&str, &str)] = &[
     ("yellow", "#FEF3C7"),
-    ("green", "#D1FAE5"), 
+    ("green", "#D1FAE5"),
     ("blue", "#DBEAFE"),
     ("purple", "#E9D5FF"),
     ("red", "#FEE2E2"),
 ];
 
+// 正文容器，高亮的字符位置都相对于它的纯文本
+pub const HIGHLIGHT_ROOT_ID: &str = "article-highlight-root";
+// 正文中最多标出的热门高亮段落数
+const TOP_HIGHLIGHT_LIMIT: i32 = 3;
+const HIDE_OTHERS_HIGHLIGHTS_KEY: &str = "hide_others_highlights";
+
+/// 读者设置：是否隐藏其他读者的热门高亮
+pub fn others_highlights_hidden() -> bool {
+    LocalStorage::get::<bool>(HIDE_OTHERS_HIGHLIGHTS_KEY).unwrap_or(false)
+}
+
+pub fn set_others_highlights_hidden(hidden: bool) {
+    let _ = LocalStorage::set(HIDE_OTHERS_HIGHLIGHTS_KEY, hidden);
+}
+
+fn color_value(name: &str) -> &'static str {
+    HIGHLIGHT_COLORS
+        .iter()
+        .find(|(color, _)| *color == name)
+        .map(|(_, value)| *value)
+        .unwrap_or(HIGHLIGHT_COLORS[0].1)
+}
+
+// 当前选区在正文纯文本中的位置
+#[derive(Debug, Clone, PartialEq)]
+struct SelectionInfo {
+    anchor: TextAnchor,
+    end: usize,
+    start_path: String,
+    end_path: String,
+}
+
+/// 正文与高亮：所有读者都能看到热门高亮，登录用户（can_highlight）还可以添加自己的高亮和笔记。
+/// 传入 on_comment 时工具栏中可以针对选中的文字发起段落评论；
+/// 传入 note_recipient（作者名）时可以把选中的文字连同留言私下发给作者
 #[component]
 pub fn HighlightSystem(
-    article_id: String,
-    article_html: String,
+    article_id: ReadOnlySignal<String>,
+    article_html: ReadOnlySignal<String>,
+    can_highlight: ReadOnlySignal<bool>,
+    on_comment: Option<EventHandler<TextAnchor>>,
+    note_recipient: Option<String>,
 ) -> Element {
-    let article_id = article_id.clone();
-    let mut highlights = use_signal(|| Vec::<Highlight>::new());
+    let mut highlights = use_signal(Vec::<Highlight>::new);
+    let mut top_highlights = use_signal(Vec::<PopularHighlight>::new);
+    let mut hide_others = use_signal(others_highlights_hidden);
+    // 文章修改后无法重新定位的高亮
+    let mut orphaned = use_signal(HashSet::<String>::new);
+    // 已经把重新定位结果同步给服务器的 (id, 起点, 终点, 是否失效)，避免重复请求
+    let synced = use_hook(|| CopyValue::new(HashSet::<(String, usize, usize, bool)>::new()));
     let mut show_toolbar = use_signal(|| false);
-    let mut selected_text = use_signal(|| String::new());
     let mut toolbar_position = use_signal(|| (0.0, 0.0));
     let mut show_note_modal = use_signal(|| false);
     let mut current_highlight_color = use_signal(|| "yellow".to_string());
-    let mut note_text = use_signal(|| String::new());
+    let mut note_text = use_signal(String::new);
     let mut selection_info = use_signal(|| None::<SelectionInfo>);
+    let mut error = use_signal(|| None::<String>);
+    let mut show_private_note = use_signal(|| false);
+    let mut private_note_text = use_signal(String::new);
+    let mut sending_note = use_signal(|| false);
+    let mut notice = use_signal(|| None::<String>);
 
-    // 加载保存的高亮
-    use_effect({
-        let article_id = article_id.clone();
-        let mut highlights = highlights.clone();
-        move || {
-            let key = format!("highlights_{}", article_id);
-            if let Ok(saved_highlights) = LocalStorage::get::<Vec<Highlight>>(&key) {
-                highlights.set(saved_highlights);
+    // 热门高亮
+    use_effect(move || {
+        let article_id = article_id();
+        spawn(async move {
+            if let Ok(list) = HighlightService::get_top_highlights(&article_id, Some(TOP_HIGHLIGHT_LIMIT)).await {
+                top_highlights.set(list);
+            }
+        });
+    });
+
+    // 从服务器加载高亮，并迁移早期保存在 LocalStorage 中的高亮
+    use_effect(move || {
+        let article_id = article_id();
+        if !can_highlight() {
+            highlights.set(Vec::new());
+            return;
+        }
+        spawn(async move {
+            let mut list = match HighlightService::get_article_highlights(&article_id).await {
+                Ok(list) => list,
+                Err(e) => {
+                    error.set(Some(format!("加载高亮失败: {}", e.message)));
+                    return;
+                }
+            };
+            list.extend(migrate_local_highlights(&article_id).await);
+            highlights.set(list);
+        });
+    });
+
+    // 正文或高亮变化后重新定位并绘制
+    use_effect(move || {
+        let _ = article_html();
+        let list = highlights();
+        let top = if hide_others() { Vec::new() } else { top_highlights() };
+        let Some(root) = highlight_root() else { return };
+        clear_highlights(&root);
+        let text = root.text_content().unwrap_or_default();
+
+        // 先标出热门段落，自己的高亮包裹在内层
+        for passage in &top {
+            let Some((start, end)) = passage.anchor().locate_fuzzy(&text) else { continue };
+            let label = format!("{} 人高亮了这段文字", passage.count);
+            let spans = wrap_range(&root, start, end, &|span| {
+                span.set_class_name("top-highlight-span");
+                span.set_attribute("title", &label).ok();
+            });
+            if let Some(last) = spans.last() {
+                last.set_attribute("data-top-count", &format!("{} 人高亮", passage.count)).ok();
+            }
+        }
+
+        let mut lost = HashSet::new();
+        for highlight in &list {
+            let placement = highlight.anchor().locate_fuzzy(&text);
+            match placement {
+                Some((start, end)) => {
+                    wrap_range(&root, start, end, &|span| {
+                        span.set_class_name("highlight-span");
+                        span.set_attribute("data-highlight-id", &highlight.id).ok();
+                        span.set_attribute(
+                            "style",
+                            &format!("background-color: {}; padding: 2px 0; cursor: pointer;", color_value(&highlight.color)),
+                        ).ok();
+                    });
+                }
+                None => {
+                    lost.insert(highlight.id.clone());
+                }
             }
+
+            // 位置变化或失效状态变化时告知服务器
+            let (start, end, is_orphaned) = match placement {
+                Some((start, end)) => (start, end, false),
+                None => (highlight.start_offset, highlight.end_offset, true),
+            };
+            let unchanged = start == highlight.start_offset
+                && end == highlight.end_offset
+                && is_orphaned == highlight.orphaned;
+            let key = (highlight.id.clone(), start, end, is_orphaned);
+            if unchanged || !synced.write_unchecked().insert(key) {
+                continue;
+            }
+            let id = highlight.id.clone();
+            let request = UpdateHighlightRequest {
+                start_offset: Some(start),
+                end_offset: Some(end),
+                orphaned: Some(is_orphaned),
+                ..Default::default()
+            };
+            spawn(async move {
+                let _ = HighlightService::update_highlight(&id, &request).await;
+            });
         }
+        orphaned.set(lost);
     });
 
     // 监听文本选择事件
     let handle_selection = move |_| {
-        if let Some(window) = window() {
-            if let Ok(selection) = window.get_selection() {
-                if let Some(selection) = selection {
-                    if !selection.is_collapsed() {
-                        if let Some(text) = selection.to_string().as_string() {
-                            if !text.trim().is_empty() {
-                                selected_text.set(text);
-                                
-                                // 获取选择范围的位置
-                                if let Some(range) = selection.get_range_at(0).ok() {
-                                    let rect = range.get_bounding_client_rect();
-                                    let x = rect.left() + rect.width() / 2.0;
-                                    let y = rect.top() + window.scroll_y().unwrap_or(0.0);
-                                    toolbar_position.set((x, y));
-                                    show_toolbar.set(true);
-                                    
-                                    // 保存选择信息
-                                    selection_info.set(Some(SelectionInfo {
-                                        range,
-                                        selection,
-                                    }));
-                                }
-                            }
-                        }
-                    } else {
-                        show_toolbar.set(false);
-                    }
+        if !can_highlight() {
+            return;
+        }
+        match current_selection() {
+            Some((info, (x, y))) => {
+                toolbar_position.set((x, y));
+                selection_info.set(Some(info));
+                show_toolbar.set(true);
+            }
+            None => {
+                if !show_note_modal() && !show_private_note() {
+                    selection_info.set(None);
                 }
+                show_toolbar.set(false);
             }
         }
     };
 
     // 添加高亮
-    let add_highlight = Rc::new({
-        let article_id = article_id.clone();
-        let selection_info = selection_info.clone();
-        let selected_text = selected_text.clone();
-        let highlights = highlights.clone();
-        let show_toolbar = show_toolbar.clone();
-        let show_note_modal = show_note_modal.clone();
-        let current_highlight_color = current_highlight_color.clone();
-        let note_text = note_text.clone();
-        
-        move |color: String, with_note: bool| {
-            let selection_info = selection_info.clone();
-            let selected_text = selected_text.clone();
-            let mut highlights = highlights.clone();
-            let mut show_toolbar = show_toolbar.clone();
-            let mut show_note_modal = show_note_modal.clone();
-            let mut note_text = note_text.clone();
-            
-            if selection_info.read().is_some() {
-                let content = selected_text();
-            
-            // 创建新的高亮
-            let new_highlight = Highlight {
-                id: format!("hl_{}", chrono::Utc::now().timestamp_millis()),
-                user_id: "current_user".to_string(), // 实际应该从auth获取
-                article_id: article_id.clone(),
-                content: content.clone(),
-                note: if with_note { Some(note_text()) } else { None },
-                start_offset: 0, // 实际应该计算偏移量
-                end_offset: content.len(),
-                start_container_path: "".to_string(), // 实际应该计算路径
-                end_container_path: "".to_string(),
-                color: color.clone(),
-                created_at: chrono::Utc::now(),
-                updated_at: chrono::Utc::now(),
-            };
-            
-            // 添加到列表
-            let mut current_highlights = highlights();
-            current_highlights.push(new_highlight);
-            highlights.set(current_highlights.clone());
-            
-            // 保存到LocalStorage
-            let key = format!("highlights_{}", article_id);
-            let _ = LocalStorage::set(&key, &current_highlights);
-            
-            // 应用高亮样式
-            if let Some(info) = &*selection_info.read() {
-                apply_highlight_style(&info.range, &color);
-                
-                // 清除选择
-                info.selection.remove_all_ranges().ok();
+    let mut add_highlight = move |color: String, with_note: bool| {
+        let Some(info) = selection_info() else { return };
+        let request = CreateHighlightRequest {
+            article_id: article_id(),
+            content: info.anchor.exact.clone(),
+            note: if with_note && !note_text().trim().is_empty() { Some(note_text()) } else { None },
+            start_offset: info.anchor.start,
+            end_offset: info.end,
+            start_container_path: info.start_path.clone(),
+            end_container_path: info.end_path.clone(),
+            prefix: info.anchor.prefix.clone(),
+            suffix: info.anchor.suffix.clone(),
+            color: Some(color),
+            orphaned: false,
+        };
+        show_toolbar.set(false);
+        show_note_modal.set(false);
+        selection_info.set(None);
+        note_text.set(String::new());
+        clear_selection();
+
+        spawn(async move {
+            match HighlightService::create_highlight(&request).await {
+                Ok(highlight) => highlights.write().push(highlight),
+                Err(e) => error.set(Some(format!("保存高亮失败: {}", e.message))),
             }
-            show_toolbar.set(false);
-            show_note_modal.set(false);
-            note_text.set(String::new());
-        }
+        });
+    };
+
+    // 私下发给作者
+    let send_private_note = move |_| {
+        let Some(info) = selection_info() else { return };
+        let message = private_note_text().trim().to_string();
+        if message.is_empty() {
+            return;
         }
-    });
+        let request = CreatePrivateNoteRequest {
+            article_id: article_id(),
+            anchor: info.anchor,
+            message,
+        };
+        spawn(async move {
+            sending_note.set(true);
+            match PrivateNoteService::send_note(&request).await {
+                Ok(_) => {
+                    show_private_note.set(false);
+                    private_note_text.set(String::new());
+                    selection_info.set(None);
+                    clear_selection();
+                    notice.set(Some("已私下发送给作者".to_string()));
+                    TimeoutFuture::new(3000).await;
+                    notice.set(None);
+                }
+                Err(e) => error.set(Some(format!("发送失败: {}", e.message))),
+            }
+            sending_note.set(false);
+        });
+    };
 
-    // 显示高亮笔记
-    let show_highlight_note = move |highlight: &Highlight| {
-        // TODO: 显示笔记的逻辑
+    let delete_highlight = move |id: String| {
+        spawn(async move {
+            match HighlightService::delete_highlight(&id).await {
+                Ok(_) => highlights.write().retain(|h| h.id != id),
+                Err(e) => error.set(Some(format!("删除高亮失败: {}", e.message))),
+            }
+        });
     };
 
     rsx! {
         div {
             class: "relative",
             onmouseup: handle_selection,
-            
+
             // 文章内容（带高亮）
             div {
-                class: "highlight-container",
+                id: HIGHLIGHT_ROOT_ID,
+                class: "highlight-container prose prose-lg max-w-none mb-12",
                 dangerous_inner_html: "{article_html}"
             }
-            
+
+            if let Some(err) = error() {
+                div {
+                    class: "fixed bottom-4 left-1/2 -translate-x-1/2 z-50 px-4 py-2 bg-red-600 text-white text-sm rounded-lg shadow-lg cursor-pointer",
+                    onclick: move |_| error.set(None),
+                    {err}
+                }
+            }
+
+            if let Some(message) = notice() {
+                div {
+                    class: "fixed bottom-4 left-1/2 -translate-x-1/2 z-50 px-4 py-2 bg-gray-900 text-white text-sm rounded-lg shadow-lg",
+                    {message}
+                }
+            }
+
             // 高亮工具栏
             if show_toolbar() {
                 div {
                     class: "fixed z-50 bg-gray-800 text-white rounded-lg shadow-lg p-2 flex items-center space-x-2",
                     style: "left: {toolbar_position().0}px; top: {toolbar_position().1 - 50.0}px; transform: translateX(-50%);",
-                    
+                    // 避免点击工具栏时清除选区
+                    onmousedown: move |e| e.prevent_default(),
+                    onmouseup: move |e| e.stop_propagation(),
+
                     // 颜色选择器
                     for (name, color) in HIGHLIGHT_COLORS {
                         button {
                             class: "w-6 h-6 rounded-full border-2 border-white",
                             style: "background-color: {color}",
-                            onclick: {
-                                let add_highlight = add_highlight.clone();
-                                let color_name = name.to_string();
-                                move |_| {
-                                    current_highlight_color.set(color_name.clone());
-                                    (add_highlight.as_ref())(color_name.clone(), false);
-                                }
+                            onclick: move |_| {
+                                current_highlight_color.set(name.to_string());
+                                add_highlight(name.to_string(), false);
                             }
                         }
                     }
-                    
+
                     // 分隔线
                     div {
                         class: "w-px h-6 bg-gray-600 mx-1"
                     }
-                    
+
                     // 添加笔记按钮
                     button {
                         class: "px-2 py-1 text-sm hover:bg-gray-700 rounded",
@@ -201,33 +344,120 @@ pub fn HighlightSystem(
                         }
                         "笔记"
                     }
+
+                    // 针对选中文字发表评论
+                    if let Some(on_comment) = on_comment {
+                        button {
+                            class: "px-2 py-1 text-sm hover:bg-gray-700 rounded",
+                            onclick: move |_| {
+                                if let Some(info) = selection_info() {
+                                    on_comment.call(info.anchor);
+                                }
+                                show_toolbar.set(false);
+                                selection_info.set(None);
+                                clear_selection();
+                            },
+                            "评论"
+                        }
+                    }
+
+                    // 私下告诉作者（如错别字），不公开显示
+                    if note_recipient.is_some() {
+                        button {
+                            class: "px-2 py-1 text-sm hover:bg-gray-700 rounded",
+                            title: "只有作者能看到",
+                            onclick: move |_| {
+                                show_private_note.set(true);
+                                show_toolbar.set(false);
+                            },
+                            "私信作者"
+                        }
+                    }
                 }
             }
-            
+
+            // 私信作者
+            if show_private_note() {
+                div {
+                    class: "fixed inset-0 z-50 flex items-center justify-center bg-black bg-opacity-50",
+                    onclick: move |_| show_private_note.set(false),
+                    onmouseup: move |e| e.stop_propagation(),
+
+                    div {
+                        class: "bg-white dark:bg-gray-800 rounded-lg p-6 w-96 max-w-full mx-4",
+                        onclick: move |e| e.stop_propagation(),
+
+                        h3 {
+                            class: "text-lg font-semibold mb-1",
+                            {format!("私信 {}", note_recipient.clone().unwrap_or_default())}
+                        }
+                        p {
+                            class: "text-sm text-gray-500 dark:text-gray-400 mb-4",
+                            "只有作者能看到，适合指出错别字或事实错误"
+                        }
+
+                        div {
+                            class: "mb-4 p-3 bg-gray-100 dark:bg-gray-700 rounded",
+                            p {
+                                class: "text-sm italic line-clamp-4",
+                                {format!("\"{}\"", selection_info().map(|info| info.anchor.exact).unwrap_or_default())}
+                            }
+                        }
+
+                        textarea {
+                            class: "w-full p-3 border border-gray-300 dark:border-gray-600 rounded-lg focus:outline-none focus:ring-2 focus:ring-blue-500 dark:bg-gray-900",
+                            placeholder: "例如：“其”应为“期”",
+                            rows: "4",
+                            value: "{private_note_text}",
+                            oninput: move |e| private_note_text.set(e.value())
+                        }
+
+                        div {
+                            class: "mt-4 flex justify-end space-x-3",
+                            button {
+                                class: "px-4 py-2 text-gray-600 hover:text-gray-800",
+                                onclick: move |_| {
+                                    show_private_note.set(false);
+                                    private_note_text.set(String::new());
+                                },
+                                "取消"
+                            }
+                            button {
+                                class: "px-4 py-2 bg-blue-600 text-white rounded-lg hover:bg-blue-700 disabled:opacity-50",
+                                disabled: sending_note() || private_note_text().trim().is_empty(),
+                                onclick: send_private_note,
+                                if sending_note() { "发送中..." } else { "发送" }
+                            }
+                        }
+                    }
+                }
+            }
+
             // 笔记模态框
             if show_note_modal() {
                 div {
                     class: "fixed inset-0 z-50 flex items-center justify-center bg-black bg-opacity-50",
                     onclick: move |_| show_note_modal.set(false),
-                    
+                    onmouseup: move |e| e.stop_propagation(),
+
                     div {
                         class: "bg-white dark:bg-gray-800 rounded-lg p-6 w-96 max-w-full mx-4",
                         onclick: move |e| e.stop_propagation(),
-                        
+
                         h3 {
                             class: "text-lg font-semibold mb-4",
                             "添加笔记"
                         }
-                        
+
                         // 显示选中的文本
                         div {
                             class: "mb-4 p-3 bg-gray-100 dark:bg-gray-700 rounded",
                             p {
                                 class: "text-sm italic",
-                                "\"{selected_text}\""
+                                {format!("\"{}\"", selection_info().map(|info| info.anchor.exact).unwrap_or_default())}
                             }
                         }
-                        
+
                         // 颜色选择
                         div {
                             class: "mb-4",
@@ -250,7 +480,7 @@ pub fn HighlightSystem(
                                 }
                             }
                         }
-                        
+
                         // 笔记输入
                         textarea {
                             class: "w-full p-3 border border-gray-300 dark:border-gray-600 rounded-lg focus:outline-none focus:ring-2 focus:ring-blue-500",
@@ -259,7 +489,7 @@ pub fn HighlightSystem(
                             value: "{note_text}",
                             oninput: move |e| note_text.set(e.value())
                         }
-                        
+
                         // 按钮
                         div {
                             class: "mt-4 flex justify-end space-x-3",
@@ -273,73 +503,236 @@ pub fn HighlightSystem(
                             }
                             button {
                                 class: "px-4 py-2 bg-blue-600 text-white rounded-lg hover:bg-blue-700",
-                                onclick: {
-                                    let add_highlight = add_highlight.clone();
-                                    move |_| {
-                                        (add_highlight.as_ref())(current_highlight_color(), true);
-                                    }
-                                },
+                                onclick: move |_| add_highlight(current_highlight_color(), true),
                                 "保存"
                             }
                         }
                     }
                 }
             }
-            
+
             // 高亮列表侧边栏
-            HighlightsSidebar {
-                highlights: highlights(),
-                on_delete: move |id| {
-                    let mut current = highlights();
-                    current.retain(|h| h.id != id);
-                    highlights.set(current.clone());
-                    
-                    // 更新LocalStorage
-                    let key = format!("highlights_{}", article_id);
-                    let _ = LocalStorage::set(&key, &current);
+            if can_highlight() {
+                HighlightsSidebar {
+                    highlights: highlights(),
+                    orphaned: orphaned(),
+                    hide_others: hide_others(),
+                    on_toggle_others: move |hidden: bool| {
+                        set_others_highlights_hidden(hidden);
+                        hide_others.set(hidden);
+                    },
+                    on_delete: delete_highlight
                 }
             }
         }
     }
 }
 
-// 选择信息结构
-struct SelectionInfo {
-    range: Range,
-    selection: Selection,
+fn highlight_root() -> Option<web_sys::Element> {
+    window()?.document()?.get_element_by_id(HIGHLIGHT_ROOT_ID)
+}
+
+// 读取当前选区：位于正文内且非空时返回锚点与工具栏位置
+fn current_selection() -> Option<(SelectionInfo, (f64, f64))> {
+    let selection = window()?.get_selection().ok()??;
+    if selection.is_collapsed() {
+        return None;
+    }
+    let range = selection.get_range_at(0).ok()?;
+    let root = highlight_root()?;
+    if !root.contains(Some(&range.common_ancestor_container().ok()?)) {
+        return None;
+    }
+
+    let start_container = range.start_container().ok()?;
+    let end_container = range.end_container().ok()?;
+    let start = char_offset(&root, &start_container, range.start_offset().ok()?)?;
+    let end = char_offset(&root, &end_container, range.end_offset().ok()?)?;
+    let text = root.text_content().unwrap_or_default();
+    let anchor = TextAnchor::capture(&text, start, end);
+    if anchor.exact.trim().is_empty() {
+        return None;
+    }
+
+    let rect = range.get_bounding_client_rect();
+    let info = SelectionInfo {
+        anchor,
+        end,
+        start_path: node_path(&root, &start_container),
+        end_path: node_path(&root, &end_container),
+    };
+    Some((info, (rect.left() + rect.width() / 2.0, rect.top())))
 }
 
-// 应用高亮样式
-fn apply_highlight_style(range: &Range, color: &str) {
-    if let Some(window) = window() {
-        if let Some(document) = window.document() {
-            if let Ok(span) = document.create_element("span") {
-                let color_value = match color {
-                    "yellow" => "#FEF3C7",
-                    "green" => "#D1FAE5",
-                    "blue" => "#DBEAFE",
-                    "purple" => "#E9D5FF",
-                    "red" => "#FEE2E2",
-                    _ => "#FEF3C7",
-                };
-                
-                span.set_class_name("highlight-span");
-                span.set_attribute("style", &format!("background-color: {}; padding: 2px 0; cursor: pointer;", color_value)).ok();
-                
-                // 将选中的内容包裹在 span 中
-                range.surround_contents(&span).ok();
+fn clear_selection() {
+    if let Some(selection) = window().and_then(|w| w.get_selection().ok().flatten()) {
+        selection.remove_all_ranges().ok();
+    }
+}
+
+// 从正文开头到 (container, offset) 的字符数
+fn char_offset(root: &web_sys::Element, container: &Node, offset: u32) -> Option<usize> {
+    let range = window()?.document()?.create_range().ok()?;
+    range.select_node_contents(root).ok()?;
+    range.set_end(container, offset).ok()?;
+    Some(String::from(range.to_string()).chars().count())
+}
+
+// 节点相对正文容器的子节点下标路径，如 "3/0/1"
+fn node_path(root: &web_sys::Element, node: &Node) -> String {
+    let root: &Node = root.as_ref();
+    let mut indices = Vec::new();
+    let mut current = node.clone();
+    while !current.is_same_node(Some(root)) {
+        let Some(parent) = current.parent_node() else { break };
+        let children = parent.child_nodes();
+        let index = (0..children.length())
+            .find(|i| children.get(*i).is_some_and(|child| child.is_same_node(Some(&current))))
+            .unwrap_or(0);
+        indices.push(index.to_string());
+        current = parent;
+    }
+    indices.reverse();
+    indices.join("/")
+}
+
+// 按文档顺序收集所有文本节点，与 textContent 的拼接顺序一致
+fn text_nodes(node: &Node, out: &mut Vec<web_sys::Text>) {
+    let children = node.child_nodes();
+    for i in 0..children.length() {
+        let Some(child) = children.get(i) else { continue };
+        match child.dyn_into::<web_sys::Text>() {
+            Ok(text) => out.push(text),
+            Err(child) => text_nodes(&child, out),
+        }
+    }
+}
+
+// 去掉已绘制的高亮，恢复原始文本节点
+fn clear_highlights(root: &web_sys::Element) {
+    let Ok(spans) = root.query_selector_all("span.highlight-span, span.top-highlight-span") else { return };
+    for i in 0..spans.length() {
+        let Some(span) = spans.get(i) else { continue };
+        let Some(parent) = span.parent_node() else { continue };
+        while let Some(child) = span.first_child() {
+            parent.insert_before(&child, Some(&span)).ok();
+        }
+        parent.remove_child(&span).ok();
+    }
+    root.normalize();
+}
+
+// 把 [start, end) 字符区间内的文字逐个文本节点包裹起来，可以跨越多个段落；返回创建的 span
+fn wrap_range(
+    root: &web_sys::Element,
+    start: usize,
+    end: usize,
+    decorate: &dyn Fn(&web_sys::Element),
+) -> Vec<web_sys::Element> {
+    let mut spans = Vec::new();
+    let Some(document) = window().and_then(|w| w.document()) else { return spans };
+    let mut nodes = Vec::new();
+    text_nodes(root, &mut nodes);
+
+    let mut position = 0;
+    for node in nodes {
+        let data = node.data();
+        let len = data.chars().count();
+        let (node_start, node_end) = (position, position + len);
+        position = node_end;
+        if node_end <= start || node_start >= end {
+            continue;
+        }
+
+        let local_start = start.saturating_sub(node_start);
+        let local_end = end.min(node_end) - node_start;
+        let piece: String = data.chars().skip(local_start).take(local_end - local_start).collect();
+        // 段落之间的空白不包裹，否则会破坏排版
+        if piece.trim().is_empty() {
+            continue;
+        }
+
+        let mut target = node;
+        if local_start > 0 {
+            match target.split_text(char_to_utf16_index(&data, local_start) as u32) {
+                Ok(rest) => target = rest,
+                Err(_) => continue,
             }
         }
+        if local_end < len {
+            target.split_text(piece.encode_utf16().count() as u32).ok();
+        }
+
+        let (Ok(span), Some(parent)) = (document.create_element("span"), target.parent_node()) else { continue };
+        decorate(&span);
+        parent.replace_child(&span, &target).ok();
+        span.append_child(&target).ok();
+        spans.push(span);
+    }
+    spans
+}
+
+// 早期版本把高亮存在 LocalStorage 且没有记录位置，按原文在正文中重新定位后上传。
+// 找不到原文的作为失效高亮上传，上传失败的留在本地下次再试
+async fn migrate_local_highlights(article_id: &str) -> Vec<Highlight> {
+    let key = format!("highlights_{}", article_id);
+    let Ok(saved) = LocalStorage::get::<Vec<Highlight>>(&key) else { return Vec::new() };
+    let text = highlight_root().and_then(|root| root.text_content()).unwrap_or_default();
+
+    let mut migrated = Vec::new();
+    let mut failed = Vec::new();
+    for old in saved {
+        let located = TextAnchor { start: 0, exact: old.content.clone(), prefix: String::new(), suffix: String::new() }
+            .locate(&text);
+        let (anchor, orphaned) = match located {
+            Some((start, end)) => (TextAnchor::capture(&text, start, end), false),
+            None => (old.anchor(), true),
+        };
+        let end_offset = anchor.start + anchor.exact.chars().count();
+        let request = CreateHighlightRequest {
+            article_id: article_id.to_string(),
+            content: anchor.exact,
+            note: old.note.clone(),
+            start_offset: anchor.start,
+            end_offset,
+            start_container_path: String::new(),
+            end_container_path: String::new(),
+            prefix: anchor.prefix,
+            suffix: anchor.suffix,
+            color: Some(old.color.clone()),
+            orphaned,
+        };
+        match HighlightService::create_highlight(&request).await {
+            Ok(highlight) => migrated.push(highlight),
+            Err(_) => failed.push(old),
+        }
+    }
+
+    if failed.is_empty() {
+        LocalStorage::delete(&key);
+    } else {
+        LocalStorage::set(&key, &failed).ok();
+    }
+    migrated
+}
+
+fn scroll_to_highlight(id: &str) {
+    let selector = format!("span[data-highlight-id=\"{}\"]", id);
+    if let Some(span) = highlight_root().and_then(|root| root.query_selector(&selector).ok().flatten()) {
+        span.scroll_into_view();
     }
 }
 
 #[component]
 fn HighlightsSidebar(
     highlights: Vec<Highlight>,
+    orphaned: HashSet<String>,
+    hide_others: bool,
+    on_toggle_others: EventHandler<bool>,
     on_delete: EventHandler<String>,
 ) -> Element {
     let mut show_sidebar = use_signal(|| false);
-    
+
     rsx! {
         div {
             // 切换按钮
@@ -365,7 +758,7 @@ fn HighlightsSidebar(
                     }
                 }
             }
-            
+
             // 侧边栏
             div {
                 class: if show_sidebar() {
@@ -373,7 +766,7 @@ fn HighlightsSidebar(
                 } else {
                     "fixed right-0 top-0 h-full w-80 bg-white dark:bg-gray-800 shadow-lg transform translate-x-full transition-transform z-50"
                 },
-                
+
                 // 头部
                 div {
                     class: "p-4 border-b border-gray-200 dark:border-gray-700",
@@ -400,8 +793,18 @@ fn HighlightsSidebar(
                             }
                         }
                     }
+                    label {
+                        class: "flex items-center mt-3 text-sm text-gray-600 dark:text-gray-400 cursor-pointer",
+                        input {
+                            r#type: "checkbox",
+                            class: "mr-2",
+                            checked: !hide_others,
+                            onchange: move |e| on_toggle_others.call(!e.checked())
+                        }
+                        "显示其他读者的热门高亮"
+                    }
                 }
-                
+
                 // 高亮列表
                 div {
                     class: "overflow-y-auto h-full pb-20",
@@ -416,6 +819,8 @@ fn HighlightsSidebar(
                             class: "p-4 space-y-3",
                             for highlight in highlights {
                                 HighlightCard {
+                                    key: "{highlight.id}",
+                                    orphaned: orphaned.contains(&highlight.id),
                                     highlight: highlight.clone(),
                                     on_delete: move |id| on_delete.call(id)
                                 }
@@ -431,33 +836,39 @@ fn HighlightsSidebar(
 #[component]
 fn HighlightCard(
     highlight: Highlight,
+    orphaned: bool,
     on_delete: EventHandler<String>,
 ) -> Element {
-    let color_value = match highlight.color.as_str() {
-        "yellow" => "#FEF3C7",
-        "green" => "#D1FAE5",
-        "blue" => "#DBEAFE",
-        "purple" => "#E9D5FF",
-        "red" => "#FEE2E2",
-        _ => "#FEF3C7",
-    };
-    
+    let color_value = color_value(&highlight.color);
     let note_text = highlight.note.clone();
-    
+    let id = highlight.id.clone();
+
     rsx! {
         div {
             class: "border border-gray-200 dark:border-gray-700 rounded-lg p-3 hover:shadow-md transition-shadow",
-            
-            // 高亮文本
+
+            // 高亮文本，点击跳转到正文中的位置
             div {
-                class: "mb-2",
+                class: if orphaned { "mb-2 opacity-60" } else { "mb-2 cursor-pointer" },
                 style: "background-color: {color_value}; padding: 4px 8px; border-radius: 4px;",
+                onclick: move |_| {
+                    if !orphaned {
+                        scroll_to_highlight(&id);
+                    }
+                },
                 p {
                     class: "text-sm",
                     "\"{highlight.content}\""
                 }
             }
-            
+
+            if orphaned {
+                p {
+                    class: "mb-2 text-xs text-amber-600 dark:text-amber-400",
+                    "文章已修改，无法在正文中找到这段文字"
+                }
+            }
+
             // 笔记
             if let Some(note) = note_text {
                 div {
@@ -468,7 +879,7 @@ fn HighlightCard(
                     }
                 }
             }
-            
+
             // 底部操作
             div {
                 class: "flex items-center justify-between text-xs text-gray-500",
@@ -483,4 +894,66 @@ fn HighlightCard(
             }
         }
     }
-}
\ No newline at end of file
+}
+
+/// 作者查看的高亮报告：读者高亮最多的段落
+#[component]
+pub fn HighlightReportPanel(article_id: String) -> Element {
+    let mut report = use_signal(|| None::<HighlightReport>);
+    let mut loading = use_signal(|| true);
+
+    use_effect(move || {
+        let article_id = article_id.clone();
+        spawn(async move {
+            if let Ok(data) = HighlightService::get_highlight_report(&article_id).await {
+                report.set(Some(data));
+            }
+            loading.set(false);
+        });
+    });
+
+    rsx! {
+        section {
+            class: "mb-8 border border-gray-200 dark:border-gray-700 rounded-lg p-6",
+            h3 {
+                class: "text-lg font-semibold text-gray-900 dark:text-white mb-1",
+                "读者高亮最多的段落"
+            }
+            if loading() {
+                p { class: "text-sm text-gray-500", "加载中..." }
+            } else if let Some(data) = report() {
+                p {
+                    class: "text-sm text-gray-500 dark:text-gray-400 mb-4",
+                    {format!("{} 位读者共添加了 {} 处高亮", data.readers, data.total_highlights)}
+                }
+                if data.passages.is_empty() {
+                    p { class: "text-sm text-gray-500", "还没有读者高亮这篇文章" }
+                } else {
+                    ol {
+                        class: "space-y-3",
+                        for (index, passage) in data.passages.iter().enumerate() {
+                            li {
+                                key: "{index}",
+                                class: "flex items-start space-x-3",
+                                span {
+                                    class: "w-6 shrink-0 text-sm font-medium text-gray-400",
+                                    {format!("{}.", index + 1)}
+                                }
+                                blockquote {
+                                    class: "flex-1 text-sm text-gray-700 dark:text-gray-300 border-l-2 border-gray-300 dark:border-gray-600 pl-3",
+                                    {passage.content.clone()}
+                                }
+                                span {
+                                    class: "shrink-0 text-sm text-gray-500 dark:text-gray-400",
+                                    {format!("{} 人", passage.count)}
+                                }
+                            }
+                        }
+                    }
+                }
+            } else {
+                p { class: "text-sm text-gray-500", "暂时无法获取高亮统计" }
+            }
+        }
+    }
+}
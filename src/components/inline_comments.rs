@@ -0,0 +1,376 @@
+use std::{cell::RefCell, rc::Rc};
+use dioxus::prelude::*;
+use gloo_timers::future::TimeoutFuture;
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::window;
+use crate::{
+    api::comments::CommentService,
+    collab::anchor::TextAnchor,
+    components::{comment_v2::{markdown_to_html, CommentForm}, HIGHLIGHT_ROOT_ID},
+    hooks::use_auth,
+    models::{comment::CommentWithAuthor, moderation::CommentSettings},
+    rendering::link_mentions,
+};
+
+// 正文旁的评论气泡层，气泡位置相对于它计算
+const INLINE_LAYER_ID: &str = "inline-comment-layer";
+// 图片和嵌入内容加载后段落位置会变化，稍后再测量一次
+const RELAYOUT_DELAY_MS: u32 = 800;
+
+// 正文中的一个段落：纯文本中的字符区间和相对气泡层的位置
+#[derive(Debug, Clone, PartialEq)]
+struct Block {
+    start: usize,
+    end: usize,
+    top: f64,
+}
+
+/// 段落评论：正文右侧显示每段的评论数，点击打开只包含该段评论的讨论面板。
+/// draft 由高亮工具栏设置，为选中的文字发起评论
+#[component]
+pub fn InlineComments(
+    article_id: ReadOnlySignal<String>,
+    article_html: ReadOnlySignal<String>,
+    draft: Signal<Option<TextAnchor>>,
+) -> Element {
+    let auth = use_auth();
+    let mut comments = use_signal(Vec::<CommentWithAuthor>::new);
+    let mut settings = use_signal(CommentSettings::default);
+    let mut blocks = use_signal(Vec::<Block>::new);
+    let mut text = use_signal(String::new);
+    let mut layout_version = use_signal(|| 0u32);
+    // 打开的段落和新评论的锚点（整段或选中的文字）
+    let mut open = use_signal(|| None::<usize>);
+    let mut new_anchor = use_signal(|| None::<TextAnchor>);
+    let mut reply_to = use_signal(|| None::<String>);
+    let listener = use_hook(|| Rc::new(RefCell::new(None::<Closure<dyn FnMut()>>)));
+
+    let reload = move || {
+        let article_id = article_id.peek().clone();
+        spawn(async move {
+            if let Ok(list) = CommentService::get_inline_comments(&article_id).await {
+                comments.set(list);
+            }
+        });
+    };
+
+    use_effect(move || {
+        let article_id = article_id();
+        let _ = auth.read().is_authenticated;
+        spawn(async move {
+            if let Ok(list) = CommentService::get_inline_comments(&article_id).await {
+                comments.set(list);
+            }
+            if let Ok(data) = CommentService::get_comment_settings(&article_id).await {
+                settings.set(data);
+            }
+        });
+    });
+
+    // 正文渲染或窗口大小变化后重新测量段落位置
+    use_effect(move || {
+        let _ = article_html();
+        let _ = layout_version();
+        spawn(async move {
+            for delay in [0, RELAYOUT_DELAY_MS] {
+                TimeoutFuture::new(delay).await;
+                if let Some((content, measured)) = measure_blocks() {
+                    text.set(content);
+                    blocks.set(measured);
+                }
+            }
+        });
+    });
+
+    use_effect({
+        let listener = listener.clone();
+        move || {
+            let Some(win) = window() else { return };
+            let closure = Closure::<dyn FnMut()>::new(move || {
+                let next = *layout_version.peek() + 1;
+                layout_version.set(next);
+            });
+            win.add_event_listener_with_callback("resize", closure.as_ref().unchecked_ref())
+                .ok();
+            if let Some(previous) = listener.borrow_mut().replace(closure) {
+                win.remove_event_listener_with_callback("resize", previous.as_ref().unchecked_ref())
+                    .ok();
+            }
+        }
+    });
+
+    use_drop({
+        let listener = listener.clone();
+        move || {
+            if let (Some(win), Some(closure)) = (window(), listener.borrow_mut().take()) {
+                win.remove_event_listener_with_callback("resize", closure.as_ref().unchecked_ref())
+                    .ok();
+            }
+        }
+    });
+
+    // 每段的评论；找不到原文的评论只在评论区显示
+    let groups = use_memo(move || {
+        let text = text();
+        let blocks = blocks();
+        let mut groups = vec![Vec::<CommentWithAuthor>::new(); blocks.len()];
+        for comment in comments() {
+            let Some(anchor) = &comment.comment.anchor else { continue };
+            let Some((start, _)) = anchor.locate_fuzzy(&text) else { continue };
+            if let Some(index) = block_at(&blocks, start) {
+                groups[index].push(comment);
+            }
+        }
+        groups
+    });
+
+    // 高亮工具栏中点击“评论”：打开选中文字所在段落
+    use_effect(move || {
+        let Some(anchor) = draft() else { return };
+        draft.set(None);
+        if let Some(index) = block_at(&blocks.peek(), anchor.start) {
+            reply_to.set(None);
+            new_anchor.set(Some(anchor));
+            open.set(Some(index));
+        }
+    });
+
+    let mut open_block = move |index: usize| {
+        let Some(block) = blocks.peek().get(index).cloned() else { return };
+        let anchor = TextAnchor::capture(&text.peek(), block.start, block.end);
+        reply_to.set(None);
+        new_anchor.set(Some(anchor));
+        open.set(Some(index));
+    };
+
+    let can_comment = auth.read().is_authenticated && settings().viewer_can_comment;
+    let open_index = open();
+    let thread = open_index.and_then(|index| groups().get(index).cloned()).unwrap_or_default();
+    // 有评论的段落显示评论数，可以评论时其余段落悬停显示“+”
+    let bubbles: Vec<(usize, f64, usize)> = blocks()
+        .iter()
+        .zip(groups().iter())
+        .enumerate()
+        .map(|(index, (block, group))| (index, block.top, thread_size(group)))
+        .filter(|(_, _, count)| *count > 0 || can_comment)
+        .collect();
+
+    rsx! {
+        div {
+            id: INLINE_LAYER_ID,
+            class: "absolute inset-0 pointer-events-none",
+            for (index, top, count) in bubbles {
+                button {
+                    key: "{index}",
+                    class: if count > 0 {
+                        "pointer-events-auto absolute right-0 md:-right-12 flex items-center justify-center min-w-[1.75rem] h-7 px-1.5 rounded-full border border-gray-300 dark:border-gray-600 bg-white dark:bg-gray-800 text-xs text-gray-700 dark:text-gray-300 hover:border-gray-500 shadow-sm"
+                    } else {
+                        "pointer-events-auto absolute md:-right-12 hidden md:flex items-center justify-center w-7 h-7 rounded-full text-gray-400 opacity-0 hover:opacity-100 focus:opacity-100 hover:bg-gray-100 dark:hover:bg-gray-800 transition-opacity"
+                    },
+                    style: "top: {top}px",
+                    title: if count > 0 { "查看这段的评论" } else { "评论这一段" },
+                    onclick: move |_| open_block(index),
+                    if count > 0 {
+                        {count.to_string()}
+                    } else {
+                        "+"
+                    }
+                }
+            }
+        }
+
+        // 段落讨论面板
+        if open_index.is_some() {
+            div {
+                class: "fixed right-0 top-0 h-full w-full sm:w-96 bg-white dark:bg-gray-800 shadow-lg z-50 flex flex-col",
+                div {
+                    class: "flex items-center justify-between p-4 border-b border-gray-200 dark:border-gray-700",
+                    h3 {
+                        class: "text-lg font-semibold text-gray-900 dark:text-white",
+                        if thread.is_empty() { "评论这一段" } else { {format!("段落评论（{}）", thread_size(&thread))} }
+                    }
+                    button {
+                        class: "p-1 text-gray-500 hover:text-gray-900 dark:hover:text-white",
+                        onclick: move |_| open.set(None),
+                        "✕"
+                    }
+                }
+
+                div {
+                    class: "flex-1 overflow-y-auto p-4 space-y-5",
+                    for comment in thread {
+                        div {
+                            key: "{comment.comment.id}",
+                            InlineCommentBody { comment: comment.clone() }
+                            div {
+                                class: "ml-9 mt-3 space-y-3 border-l border-gray-200 dark:border-gray-700 pl-3",
+                                for reply in flatten_replies(&comment) {
+                                    InlineCommentBody { key: "{reply.comment.id}", comment: reply }
+                                }
+                                if can_comment {
+                                    if reply_to().as_deref() == Some(comment.comment.id.as_str()) {
+                                        CommentForm {
+                                            article_id: article_id(),
+                                            parent_id: Some(comment.comment.id.clone()),
+                                            on_success: move |_| {
+                                                reply_to.set(None);
+                                                reload();
+                                            }
+                                        }
+                                    } else {
+                                        button {
+                                            class: "text-sm text-gray-500 dark:text-gray-400 hover:text-gray-700 dark:hover:text-gray-300",
+                                            onclick: {
+                                                let id = comment.comment.id.clone();
+                                                move |_| reply_to.set(Some(id.clone()))
+                                            },
+                                            "回复"
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+
+                // 新评论
+                div {
+                    class: "p-4 border-t border-gray-200 dark:border-gray-700",
+                    if let Some(anchor) = new_anchor() {
+                        blockquote {
+                            class: "mb-3 pl-3 border-l-2 border-green-500 text-sm text-gray-600 dark:text-gray-400 italic line-clamp-3",
+                            {anchor.exact.trim().to_string()}
+                        }
+                        if can_comment {
+                            CommentForm {
+                                key: "{anchor.start}-{anchor.exact.len()}",
+                                article_id: article_id(),
+                                parent_id: None,
+                                anchor: Some(anchor.clone()),
+                                on_success: move |_| reload()
+                            }
+                        } else if !auth.read().is_authenticated {
+                            p {
+                                class: "text-sm text-gray-600 dark:text-gray-400",
+                                "登录后可以评论这一段"
+                            }
+                        } else {
+                            p {
+                                class: "text-sm text-gray-600 dark:text-gray-400",
+                                {settings().permission.restriction_notice()}
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn InlineCommentBody(comment: CommentWithAuthor) -> Element {
+    rsx! {
+        div {
+            class: "flex space-x-2",
+            if let Some(avatar_url) = &comment.author_avatar {
+                img {
+                    src: "{avatar_url}",
+                    alt: "{comment.author_username}",
+                    class: "w-7 h-7 rounded-full flex-shrink-0"
+                }
+            } else {
+                div { class: "w-7 h-7 rounded-full bg-gray-200 dark:bg-gray-600 flex-shrink-0" }
+            }
+            div {
+                class: "min-w-0",
+                p {
+                    class: "text-sm",
+                    a {
+                        href: "/@{comment.author_username}",
+                        class: "font-medium text-gray-900 dark:text-white hover:underline",
+                        {comment.author_name.clone()}
+                    }
+                    span {
+                        class: "ml-2 text-gray-500 dark:text-gray-400",
+                        {comment.comment.created_at.format("%Y-%m-%d %H:%M").to_string()}
+                    }
+                }
+                if comment.comment.is_deleted {
+                    p { class: "text-sm text-gray-400 italic", "该评论已删除" }
+                } else {
+                    p {
+                        class: "text-sm text-gray-800 dark:text-gray-200 whitespace-pre-wrap break-words",
+                        dangerous_inner_html: "{link_mentions(&markdown_to_html(&comment.comment.content), &comment.comment.mentions)}"
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// 滚动到评论引用的正文段落
+pub fn scroll_to_anchor(anchor: &TextAnchor) {
+    let Some((content, blocks)) = measure_blocks() else { return };
+    let Some((start, _)) = anchor.locate_fuzzy(&content) else { return };
+    let Some(block) = block_at(&blocks, start).and_then(|index| blocks.get(index)) else { return };
+    let Some(win) = window() else { return };
+    let Some(layer) = win.document().and_then(|d| d.get_element_by_id(INLINE_LAYER_ID)) else { return };
+    let viewport = win.inner_height().ok().and_then(|h| h.as_f64()).unwrap_or(0.0);
+    let top = layer.get_bounding_client_rect().top() + win.scroll_y().unwrap_or(0.0) + block.top;
+    win.scroll_to_with_x_and_y(0.0, (top - viewport / 3.0).max(0.0));
+}
+
+// 正文纯文本和各个段落的位置
+fn measure_blocks() -> Option<(String, Vec<Block>)> {
+    let document = window()?.document()?;
+    let root = document.get_element_by_id(HIGHLIGHT_ROOT_ID)?;
+    let layer_top = document
+        .get_element_by_id(INLINE_LAYER_ID)
+        .map(|layer| layer.get_bounding_client_rect().top())
+        .unwrap_or_else(|| root.get_bounding_client_rect().top());
+
+    let children = root.child_nodes();
+    let mut blocks = Vec::new();
+    let mut position = 0;
+    for i in 0..children.length() {
+        let Some(child) = children.get(i) else { continue };
+        let content = child.text_content().unwrap_or_default();
+        let len = content.chars().count();
+        let start = position;
+        position += len;
+        if content.trim().is_empty() {
+            continue;
+        }
+        let Ok(element) = child.dyn_into::<web_sys::Element>() else { continue };
+        blocks.push(Block {
+            start,
+            end: position,
+            top: element.get_bounding_client_rect().top() - layer_top,
+        });
+    }
+    Some((root.text_content().unwrap_or_default(), blocks))
+}
+
+fn block_at(blocks: &[Block], position: usize) -> Option<usize> {
+    blocks
+        .iter()
+        .position(|block| position >= block.start && position < block.end)
+        .or_else(|| blocks.iter().rposition(|block| block.start <= position))
+}
+
+// 讨论中的全部回复，按时间顺序平铺
+fn flatten_replies(comment: &CommentWithAuthor) -> Vec<CommentWithAuthor> {
+    let mut replies = Vec::new();
+    let mut stack: Vec<&CommentWithAuthor> = comment.replies.iter().rev().collect();
+    while let Some(reply) = stack.pop() {
+        replies.push(reply.clone());
+        stack.extend(reply.replies.iter().rev());
+    }
+    replies.sort_by_key(|reply| reply.comment.created_at);
+    replies
+}
+
+// 段落中的评论和回复总数
+fn thread_size(group: &[CommentWithAuthor]) -> usize {
+    group.iter().map(|comment| 1 + flatten_replies(comment).len()).sum()
+}
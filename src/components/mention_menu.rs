@@ -0,0 +1,74 @@
+use dioxus::prelude::*;
+use crate::{hooks::Mentions, models::mention::MentionCandidate};
+
+/// 输入 @ 时弹出的候选用户列表，放在 relative 容器中、输入框之后
+#[component]
+pub fn MentionMenu(mentions: Mentions, on_select: EventHandler<MentionCandidate>) -> Element {
+    if (mentions.query)().is_none() {
+        return rsx! {};
+    }
+    let candidates = (mentions.candidates)();
+    if candidates.is_empty() {
+        return rsx! {};
+    }
+    let active = (mentions.active)();
+
+    rsx! {
+        ul {
+            class: "absolute left-0 top-full mt-1 z-30 w-72 max-h-64 overflow-y-auto bg-white dark:bg-gray-800 border border-gray-200 dark:border-gray-700 rounded-lg shadow-lg py-1",
+            for (index, candidate) in candidates.into_iter().enumerate() {
+                li {
+                    key: "{candidate.id}",
+                    class: match (candidate.can_mention, index == active) {
+                        (false, _) => "flex items-center px-3 py-2 opacity-50 cursor-not-allowed",
+                        (true, true) => "flex items-center px-3 py-2 bg-gray-100 dark:bg-gray-700 cursor-pointer",
+                        (true, false) => "flex items-center px-3 py-2 hover:bg-gray-50 dark:hover:bg-gray-700 cursor-pointer",
+                    },
+                    // 用 mousedown 并阻止默认行为，避免输入框失去焦点
+                    onmousedown: {
+                        let candidate = candidate.clone();
+                        move |e: Event<MouseData>| {
+                            e.prevent_default();
+                            if candidate.can_mention {
+                                on_select.call(candidate.clone());
+                            }
+                        }
+                    },
+                    onmouseenter: {
+                        let mut active = mentions.active;
+                        move |_| active.set(index)
+                    },
+
+                    if let Some(avatar_url) = &candidate.avatar_url {
+                        img {
+                            src: "{avatar_url}",
+                            alt: "{candidate.username}",
+                            class: "w-7 h-7 rounded-full flex-shrink-0"
+                        }
+                    } else {
+                        div {
+                            class: "w-7 h-7 rounded-full bg-gray-200 dark:bg-gray-600 flex-shrink-0"
+                        }
+                    }
+                    div {
+                        class: "ml-2 min-w-0",
+                        p {
+                            class: "text-sm text-gray-900 dark:text-white truncate",
+                            {candidate.name().to_string()}
+                            span {
+                                class: "ml-1 text-gray-500 dark:text-gray-400",
+                                "@{candidate.username}"
+                            }
+                        }
+                        if !candidate.can_mention {
+                            p {
+                                class: "text-xs text-gray-500 dark:text-gray-400",
+                                {candidate.unavailable_reason().to_string()}
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -2,19 +2,49 @@ pub mod route_guard;
 pub mod article_card;
 pub mod comment_v2;
 pub mod version_history;
+pub mod version_diff;
 pub mod image_upload;
 pub mod share_modal;
 pub mod highlight_system;
 pub mod recommendations;
 pub mod subscription_widget;
+pub mod table_of_contents;
+pub mod collab_presence;
+pub mod editorial_panel;
+pub mod submission_panel;
+pub mod continue_reading;
+pub mod reader_settings;
+pub mod offline_button;
+pub mod clap_button;
+pub mod series_navigation;
+pub mod mention_menu;
+pub mod inline_comments;
+pub mod private_notes;
+pub mod notification_bell;
+pub mod user_relation_menu;
 
 pub use route_guard::*;
 pub use article_card::*;
 // 使用新的评论组件
 pub use comment_v2::CommentSection;
 pub use version_history::*;
+pub use version_diff::*;
 pub use image_upload::*;
 pub use share_modal::*;
 pub use highlight_system::*;
 pub use recommendations::*;
-pub use subscription_widget::*;
\ No newline at end of file
+pub use subscription_widget::*;
+pub use table_of_contents::*;
+pub use collab_presence::*;
+pub use editorial_panel::*;
+pub use submission_panel::*;
+pub use continue_reading::*;
+pub use reader_settings::*;
+pub use offline_button::*;
+pub use clap_button::*;
+pub use series_navigation::*;
+pub use mention_menu::*;
+pub use inline_comments::*;
+pub use private_notes::*;
+pub use notification_bell::*;
+pub use user_relation_menu::*;
@@ -0,0 +1,250 @@
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+use chrono::{DateTime, Utc};
+use crate::{
+    components::comment_v2::PERMALINK_PREFIX,
+    hooks::{use_auth, use_notifications},
+    models::notification::{Notification, NotificationType},
+    Route,
+};
+
+/// 导航栏中的通知铃铛：未读角标和最近通知下拉框，未登录时不显示
+#[component]
+pub fn NotificationBell() -> Element {
+    let auth = use_auth();
+    let notifications = use_notifications();
+    let mut open = use_signal(|| false);
+
+    if !auth.read().is_authenticated {
+        return rsx! {};
+    }
+
+    let unread = (notifications.unread)();
+    let recent = (notifications.recent)();
+
+    rsx! {
+        div {
+            class: "relative",
+            button {
+                class: "relative p-1 text-gray-700 hover:text-gray-900 dark:text-gray-300 dark:hover:text-white",
+                title: "通知",
+                onclick: move |_| open.toggle(),
+                svg {
+                    class: "w-6 h-6",
+                    fill: "none",
+                    stroke: "currentColor",
+                    view_box: "0 0 24 24",
+                    path {
+                        stroke_linecap: "round",
+                        stroke_linejoin: "round",
+                        stroke_width: "2",
+                        d: "M15 17h5l-1.405-1.405A2.032 2.032 0 0118 14.158V11a6.002 6.002 0 00-4-5.659V5a2 2 0 10-4 0v.341C7.67 6.165 6 8.388 6 11v3.159c0 .538-.214 1.055-.595 1.436L4 17h5m6 0v1a3 3 0 11-6 0v-1m6 0H9"
+                    }
+                }
+                if unread > 0 {
+                    span {
+                        class: "absolute -top-1 -right-1 min-w-[1.125rem] h-[1.125rem] px-1 flex items-center justify-center text-[10px] font-medium text-white bg-red-500 rounded-full",
+                        {if unread > 99 { "99+".to_string() } else { unread.to_string() }}
+                    }
+                }
+            }
+
+            if open() {
+                // 点击下拉框外部时关闭
+                div {
+                    class: "fixed inset-0 z-30",
+                    onclick: move |_| open.set(false),
+                }
+                div {
+                    class: "absolute right-0 mt-2 w-96 max-w-[calc(100vw-2rem)] bg-white dark:bg-gray-800 border border-gray-200 dark:border-gray-700 rounded-lg shadow-lg z-40",
+                    div {
+                        class: "flex items-center justify-between px-4 py-3 border-b border-gray-100 dark:border-gray-700",
+                        h3 {
+                            class: "text-sm font-semibold text-gray-900 dark:text-white",
+                            "通知"
+                        }
+                        if unread > 0 {
+                            button {
+                                class: "text-xs text-gray-500 hover:text-gray-900 dark:text-gray-400 dark:hover:text-white",
+                                onclick: move |_| notifications.mark_all_read(),
+                                "全部标为已读"
+                            }
+                        }
+                    }
+
+                    div {
+                        class: "max-h-96 overflow-y-auto",
+                        if (notifications.loading)() && recent.is_empty() {
+                            div {
+                                class: "flex justify-center py-8",
+                                div {
+                                    class: "animate-spin rounded-full h-6 w-6 border-b-2 border-gray-900 dark:border-white"
+                                }
+                            }
+                        } else if recent.is_empty() {
+                            p {
+                                class: "py-8 text-center text-sm text-gray-500 dark:text-gray-400",
+                                "还没有通知"
+                            }
+                        } else {
+                            for notification in recent {
+                                NotificationItem {
+                                    key: "{notification.id}",
+                                    notification: notification.clone(),
+                                    compact: true,
+                                    on_open: move |_| open.set(false),
+                                }
+                            }
+                        }
+                    }
+
+                    Link {
+                        to: Route::Notifications {},
+                        class: "block px-4 py-3 text-center text-sm text-gray-700 dark:text-gray-300 hover:bg-gray-50 dark:hover:bg-gray-700 border-t border-gray-100 dark:border-gray-700 rounded-b-lg",
+                        onclick: move |_| open.set(false),
+                        "查看全部通知"
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// 一条通知；点击后标为已读并跳转到相关的文章、评论、用户或出版物
+#[component]
+pub fn NotificationItem(
+    notification: Notification,
+    compact: Option<bool>,
+    // 点击通知之后调用，参数为通知 id
+    on_open: Option<EventHandler<String>>,
+) -> Element {
+    let notifications = use_notifications();
+    let navigator = use_navigator();
+    let compact = compact.unwrap_or(false);
+
+    let open_notification = {
+        let notification = notification.clone();
+        move |_| {
+            if !notification.is_read {
+                notifications.mark_read(&notification.id);
+            }
+            if let Some(handler) = &on_open {
+                handler.call(notification.id.clone());
+            }
+            if let Some(route) = notification_route(&notification) {
+                navigator.push(route);
+                // 回复和提及定位到具体评论
+                if let (Some(comment_id), Some(window)) = (&notification.comment_id, web_sys::window()) {
+                    let _ = window
+                        .location()
+                        .set_hash(&format!("{}{}", PERMALINK_PREFIX, comment_id));
+                }
+            }
+        }
+    };
+
+    let avatars: Vec<_> = notification.actors.iter().take(3).cloned().collect();
+
+    rsx! {
+        div {
+            class: if notification.is_read {
+                "flex items-start gap-3 px-4 py-3 cursor-pointer hover:bg-gray-50 dark:hover:bg-gray-700"
+            } else {
+                "flex items-start gap-3 px-4 py-3 cursor-pointer bg-blue-50 dark:bg-blue-900/20 hover:bg-blue-100 dark:hover:bg-blue-900/30"
+            },
+            onclick: open_notification,
+
+            div {
+                class: "relative flex-shrink-0",
+                div {
+                    class: "flex -space-x-2",
+                    for (index, actor) in avatars.into_iter().enumerate() {
+                        if let Some(avatar_url) = &actor.avatar_url {
+                            img {
+                                key: "{index}",
+                                src: "{avatar_url}",
+                                alt: "{actor.username}",
+                                class: "w-8 h-8 rounded-full ring-2 ring-white dark:ring-gray-800"
+                            }
+                        } else {
+                            div {
+                                key: "{index}",
+                                class: "w-8 h-8 rounded-full ring-2 ring-white dark:ring-gray-800 bg-gray-200 dark:bg-gray-600 flex items-center justify-center text-xs text-gray-600 dark:text-gray-300",
+                                {actor.display_name.chars().next().map(|c| c.to_string()).unwrap_or_default()}
+                            }
+                        }
+                    }
+                }
+                span {
+                    class: "absolute -bottom-1 -right-1 text-xs",
+                    {notification.kind.icon()}
+                }
+            }
+
+            div {
+                class: "flex-1 min-w-0",
+                p {
+                    class: if compact {
+                        "text-sm text-gray-900 dark:text-white line-clamp-2"
+                    } else {
+                        "text-sm text-gray-900 dark:text-white"
+                    },
+                    {notification.summary()}
+                }
+                if let Some(excerpt) = &notification.excerpt {
+                    p {
+                        class: "mt-1 text-sm text-gray-500 dark:text-gray-400 truncate",
+                        {excerpt.clone()}
+                    }
+                }
+                p {
+                    class: "mt-1 text-xs text-gray-400",
+                    {format_relative_time(&notification.created_at)}
+                }
+            }
+
+            if !notification.is_read {
+                span {
+                    class: "mt-2 w-2 h-2 flex-shrink-0 rounded-full bg-blue-500"
+                }
+            }
+        }
+    }
+}
+
+// 通知指向的页面
+fn notification_route(notification: &Notification) -> Option<Route> {
+    match notification.kind {
+        NotificationType::Clap | NotificationType::Reply | NotificationType::Mention => notification
+            .target_slug
+            .clone()
+            .map(|slug| Route::Article { slug }),
+        NotificationType::PublicationInvite => notification
+            .target_slug
+            .clone()
+            .map(|slug| Route::PublicationDetail { slug }),
+        NotificationType::Follow | NotificationType::Subscription => notification
+            .actors
+            .first()
+            .map(|actor| Route::Profile { username: actor.username.clone() }),
+    }
+}
+
+fn format_relative_time(datetime: &DateTime<Utc>) -> String {
+    let now = Utc::now();
+    let duration = now.signed_duration_since(*datetime);
+
+    if duration.num_seconds() < 60 {
+        "刚刚".to_string()
+    } else if duration.num_minutes() < 60 {
+        format!("{} 分钟前", duration.num_minutes())
+    } else if duration.num_hours() < 24 {
+        format!("{} 小时前", duration.num_hours())
+    } else if duration.num_days() < 30 {
+        format!("{} 天前", duration.num_days())
+    } else if duration.num_days() < 365 {
+        format!("{} 个月前", duration.num_days() / 30)
+    } else {
+        format!("{} 年前", duration.num_days() / 365)
+    }
+}
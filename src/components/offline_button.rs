@@ -0,0 +1,65 @@
+use dioxus::prelude::*;
+use crate::{
+    api::articles::ArticleService,
+    hooks::OfflineStore,
+};
+
+/// 收藏文章的“离线保存”按钮：缓存文章内容和图片，再次点击移除
+#[component]
+pub fn OfflineSaveButton(article_id: String, slug: String) -> Element {
+    let mut saved = use_signal(|| OfflineStore::is_saved(&article_id));
+    let mut working = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+
+    let toggle = move |e: Event<MouseData>| {
+        // 按钮可能在链接卡片内
+        e.prevent_default();
+        e.stop_propagation();
+        if working() {
+            return;
+        }
+        let article_id = article_id.clone();
+        let slug = slug.clone();
+        spawn(async move {
+            working.set(true);
+            error.set(None);
+            let result = if saved() {
+                OfflineStore::remove(&article_id).await
+            } else {
+                match ArticleService::get_article(&slug).await {
+                    Ok(article) => OfflineStore::save(&article).await.map(|_| ()),
+                    Err(e) => Err(e.message),
+                }
+            };
+            match result {
+                Ok(_) => saved.set(OfflineStore::is_saved(&article_id)),
+                Err(e) => error.set(Some(format!("离线保存失败: {}", e))),
+            }
+            working.set(false);
+        });
+    };
+
+    rsx! {
+        button {
+            class: if saved() {
+                "px-3 py-1 text-sm border border-green-600 text-green-700 dark:text-green-400 rounded hover:bg-green-50 dark:hover:bg-green-900/20 disabled:opacity-50"
+            } else {
+                "px-3 py-1 text-sm border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-300 rounded hover:bg-gray-100 dark:hover:bg-gray-700 disabled:opacity-50"
+            },
+            title: error().unwrap_or_else(|| {
+                if saved() { "已保存，可离线阅读；点击移除".to_string() } else { "保存文章和图片，无网络时也能阅读".to_string() }
+            }),
+            disabled: working(),
+            onclick: toggle,
+            if working() {
+                "保存中..."
+            } else if error().is_some() {
+                "保存失败，重试"
+            } else if saved() {
+                "✓ 已离线保存"
+            } else {
+                "离线保存"
+            }
+        }
+    }
+}
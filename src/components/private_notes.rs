@@ -0,0 +1,235 @@
+use dioxus::prelude::*;
+use crate::{
+    api::private_notes::PrivateNoteService,
+    models::private_note::{NoteStatus, PrivateNote, UpdatePrivateNoteRequest},
+};
+
+const PAGE_SIZE: i32 = 20;
+
+/// 作者收到的读者私信；传入 article_id 时只显示这篇文章的
+#[component]
+pub fn PrivateNoteInbox(article_id: ReadOnlySignal<Option<String>>) -> Element {
+    let mut notes = use_signal(Vec::<PrivateNote>::new);
+    // None 表示全部
+    let mut status_filter = use_signal(|| Some(NoteStatus::Open));
+    let mut open_count = use_signal(|| 0i64);
+    let mut loading = use_signal(|| true);
+    let mut error = use_signal(|| None::<String>);
+
+    use_effect(move || {
+        let article_id = article_id();
+        let status = status_filter();
+        spawn(async move {
+            loading.set(true);
+            match PrivateNoteService::get_inbox(article_id.as_deref(), status, Some(1), Some(PAGE_SIZE)).await {
+                Ok(resp) => {
+                    notes.set(resp.notes);
+                    open_count.set(resp.open_count);
+                }
+                Err(e) => error.set(Some(format!("加载私信失败: {}", e.message))),
+            }
+            loading.set(false);
+        });
+    });
+
+    let update = move |(id, request): (String, UpdatePrivateNoteRequest)| {
+        spawn(async move {
+            error.set(None);
+            match PrivateNoteService::update_note(&id, &request).await {
+                Ok(updated) => {
+                    let previous = notes.peek().iter().find(|n| n.id == updated.id).map(|n| n.status);
+                    if previous != Some(updated.status) {
+                        let delta = if updated.status == NoteStatus::Open { 1 } else { -1 };
+                        open_count.set((open_count() + delta).max(0));
+                    }
+                    notes.with_mut(|list| {
+                        if let Some(note) = list.iter_mut().find(|n| n.id == updated.id) {
+                            *note = updated;
+                        }
+                    });
+                }
+                Err(e) => error.set(Some(format!("操作失败: {}", e.message))),
+            }
+        });
+    };
+
+    let filters = [(Some(NoteStatus::Open), "待处理"), (Some(NoteStatus::Resolved), "已处理"), (None, "全部")];
+
+    rsx! {
+        section {
+            class: "mb-8 border border-gray-200 dark:border-gray-700 rounded-lg p-6",
+            div {
+                class: "flex flex-wrap items-center justify-between gap-2 mb-4",
+                div {
+                    h3 {
+                        class: "text-lg font-semibold text-gray-900 dark:text-white",
+                        "读者私信"
+                    }
+                    p {
+                        class: "text-sm text-gray-500 dark:text-gray-400",
+                        {format!("{} 条待处理 · 只有你能看到", open_count())}
+                    }
+                }
+                div {
+                    class: "flex gap-2",
+                    for (status, label) in filters {
+                        button {
+                            key: "{label}",
+                            class: if status_filter() == status {
+                                "px-3 py-1 text-sm rounded-full bg-gray-900 text-white"
+                            } else {
+                                "px-3 py-1 text-sm rounded-full border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-300"
+                            },
+                            onclick: move |_| status_filter.set(status),
+                            {label}
+                        }
+                    }
+                }
+            }
+
+            if let Some(err) = error() {
+                p { class: "mb-3 text-sm text-red-600 dark:text-red-400", {err} }
+            }
+
+            if loading() {
+                p { class: "text-sm text-gray-500", "加载中..." }
+            } else if notes().is_empty() {
+                p {
+                    class: "text-sm text-gray-500 dark:text-gray-400",
+                    if status_filter() == Some(NoteStatus::Open) { "没有待处理的私信" } else { "还没有读者私信" }
+                }
+            } else {
+                div {
+                    class: "divide-y divide-gray-200 dark:divide-gray-700",
+                    for note in notes() {
+                        PrivateNoteCard {
+                            key: "{note.id}",
+                            show_article: article_id().is_none(),
+                            note: note.clone(),
+                            on_update: update
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn PrivateNoteCard(
+    note: PrivateNote,
+    show_article: bool,
+    on_update: EventHandler<(String, UpdatePrivateNoteRequest)>,
+) -> Element {
+    let mut replying = use_signal(|| false);
+    let mut reply_text = use_signal(String::new);
+    let resolved = note.status == NoteStatus::Resolved;
+
+    rsx! {
+        div {
+            class: if resolved { "py-4 opacity-70" } else { "py-4" },
+            div {
+                class: "flex flex-wrap items-center gap-x-2 text-sm",
+                a {
+                    href: "/@{note.sender.username}",
+                    class: "font-medium text-gray-900 dark:text-white hover:underline",
+                    {note.sender.display_name.clone()}
+                }
+                span {
+                    class: "text-gray-500 dark:text-gray-400",
+                    {note.created_at.format("%Y-%m-%d %H:%M").to_string()}
+                }
+                if show_article {
+                    a {
+                        href: "/article/{note.article_slug}",
+                        class: "text-gray-500 dark:text-gray-400 hover:underline truncate",
+                        {format!("《{}》", note.article_title)}
+                    }
+                }
+                span {
+                    class: if resolved {
+                        "ml-auto px-2 py-0.5 rounded-full text-xs bg-green-100 text-green-800"
+                    } else {
+                        "ml-auto px-2 py-0.5 rounded-full text-xs bg-yellow-100 text-yellow-800"
+                    },
+                    {note.status.label()}
+                }
+            }
+            blockquote {
+                class: "mt-2 pl-3 border-l-2 border-gray-300 dark:border-gray-600 text-sm text-gray-600 dark:text-gray-400 italic",
+                {note.anchor.exact.trim().to_string()}
+            }
+            p {
+                class: "mt-2 text-gray-800 dark:text-gray-200 whitespace-pre-wrap break-words",
+                {note.message.clone()}
+            }
+            if let Some(reply) = &note.reply {
+                div {
+                    class: "mt-2 p-3 rounded bg-gray-50 dark:bg-gray-800 text-sm",
+                    p {
+                        class: "text-xs text-gray-500 dark:text-gray-400 mb-1",
+                        {format!("你的回复 · {}", note.replied_at.map(|at| at.format("%Y-%m-%d %H:%M").to_string()).unwrap_or_default())}
+                    }
+                    p { class: "text-gray-800 dark:text-gray-200 whitespace-pre-wrap", {reply.clone()} }
+                }
+            }
+
+            if replying() {
+                div {
+                    class: "mt-3",
+                    textarea {
+                        class: "w-full p-3 text-sm border border-gray-300 dark:border-gray-600 rounded-lg bg-transparent text-gray-900 dark:text-white outline-none resize-none",
+                        rows: "3",
+                        placeholder: "私下回复读者...",
+                        value: "{reply_text}",
+                        oninput: move |e| reply_text.set(e.value())
+                    }
+                    div {
+                        class: "mt-2 flex justify-end gap-2",
+                        button {
+                            class: "px-3 py-1.5 text-sm text-gray-600 dark:text-gray-400",
+                            onclick: move |_| replying.set(false),
+                            "取消"
+                        }
+                        button {
+                            class: "px-3 py-1.5 text-sm rounded-full bg-gray-900 text-white disabled:opacity-50",
+                            disabled: reply_text().trim().is_empty(),
+                            onclick: {
+                                let id = note.id.clone();
+                                move |_| {
+                                    on_update.call((id.clone(), UpdatePrivateNoteRequest {
+                                        status: None,
+                                        reply: Some(reply_text().trim().to_string()),
+                                    }));
+                                    replying.set(false);
+                                    reply_text.set(String::new());
+                                }
+                            },
+                            "发送回复"
+                        }
+                    }
+                }
+            } else {
+                div {
+                    class: "mt-3 flex gap-4 text-sm",
+                    button {
+                        class: "text-gray-500 dark:text-gray-400 hover:text-gray-900 dark:hover:text-white",
+                        onclick: move |_| replying.set(true),
+                        if note.reply.is_some() { "再次回复" } else { "回复" }
+                    }
+                    button {
+                        class: "text-gray-500 dark:text-gray-400 hover:text-gray-900 dark:hover:text-white",
+                        onclick: {
+                            let id = note.id.clone();
+                            move |_| {
+                                let status = if resolved { NoteStatus::Open } else { NoteStatus::Resolved };
+                                on_update.call((id.clone(), UpdatePrivateNoteRequest { status: Some(status), reply: None }));
+                            }
+                        },
+                        if resolved { "重新打开" } else { "标记为已处理" }
+                    }
+                }
+            }
+        }
+    }
+}
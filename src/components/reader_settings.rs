@@ -0,0 +1,130 @@
+use dioxus::prelude::*;
+use crate::{
+    hooks::{use_theme, Theme},
+    models::preferences::{ReaderFont, ReaderPreferences, ReaderWidth, LINE_HEIGHTS},
+};
+
+/// 主题与正文排版设置，文章页的“Aa”菜单和阅读设置页共用
+#[component]
+pub fn ReaderSettingsPanel() -> Element {
+    let mut theme = use_theme();
+    let current = theme.current();
+    let reader = theme.reader();
+
+    let option_class = |active: bool| {
+        if active {
+            "px-3 py-1.5 text-sm rounded-md border border-green-600 text-green-700 bg-green-50 dark:bg-green-900/20 dark:text-green-400"
+        } else {
+            "px-3 py-1.5 text-sm rounded-md border border-gray-200 dark:border-gray-600 text-gray-700 dark:text-gray-300 hover:bg-gray-50 dark:hover:bg-gray-700"
+        }
+    };
+
+    rsx! {
+        div {
+            class: "space-y-5",
+
+            div {
+                h3 { class: "text-xs font-semibold text-gray-500 dark:text-gray-400 uppercase mb-2", "主题" }
+                div {
+                    class: "flex flex-wrap gap-2",
+                    for option in Theme::ALL {
+                        button {
+                            key: "{option.as_str()}",
+                            class: option_class(option == current),
+                            onclick: move |_| theme.set(option),
+                            if option == Theme::System {
+                                {format!("{}（{}）", option.label(), theme.system_theme().label())}
+                            } else {
+                                {option.label()}
+                            }
+                        }
+                    }
+                }
+            }
+
+            div {
+                h3 { class: "text-xs font-semibold text-gray-500 dark:text-gray-400 uppercase mb-2", "字体" }
+                div {
+                    class: "flex gap-2",
+                    for font in ReaderFont::ALL {
+                        button {
+                            key: "{font.label()}",
+                            class: option_class(font == reader.font),
+                            style: format!("font-family: {}", font.font_family()),
+                            onclick: {
+                                let reader = reader.clone();
+                                move |_| theme.set_reader(ReaderPreferences { font, ..reader.clone() })
+                            },
+                            {font.label()}
+                        }
+                    }
+                }
+            }
+
+            div {
+                h3 { class: "text-xs font-semibold text-gray-500 dark:text-gray-400 uppercase mb-2", "字号" }
+                div {
+                    class: "flex items-center gap-3",
+                    button {
+                        class: option_class(false),
+                        title: "缩小",
+                        onclick: {
+                            let reader = reader.clone();
+                            move |_| theme.set_reader(ReaderPreferences { font_scale: reader.step_font_scale(-1), ..reader.clone() })
+                        },
+                        "A-"
+                    }
+                    span {
+                        class: "text-sm text-gray-600 dark:text-gray-400 w-12 text-center",
+                        {format!("{}%", (reader.font_scale * 100.0).round())}
+                    }
+                    button {
+                        class: option_class(false),
+                        title: "放大",
+                        onclick: {
+                            let reader = reader.clone();
+                            move |_| theme.set_reader(ReaderPreferences { font_scale: reader.step_font_scale(1), ..reader.clone() })
+                        },
+                        "A+"
+                    }
+                }
+            }
+
+            div {
+                h3 { class: "text-xs font-semibold text-gray-500 dark:text-gray-400 uppercase mb-2", "行距" }
+                div {
+                    class: "flex gap-2",
+                    for line_height in LINE_HEIGHTS {
+                        button {
+                            key: "{line_height}",
+                            class: option_class((line_height - reader.line_height).abs() < f64::EPSILON),
+                            onclick: {
+                                let reader = reader.clone();
+                                move |_| theme.set_reader(ReaderPreferences { line_height, ..reader.clone() })
+                            },
+                            {format!("{}", line_height)}
+                        }
+                    }
+                }
+            }
+
+            div {
+                h3 { class: "text-xs font-semibold text-gray-500 dark:text-gray-400 uppercase mb-2", "栏宽" }
+                div {
+                    class: "flex gap-2",
+                    for width in ReaderWidth::ALL {
+                        button {
+                            key: "{width.label()}",
+                            class: option_class(width == reader.width),
+                            onclick: {
+                                let reader = reader.clone();
+                                move |_| theme.set_reader(ReaderPreferences { width, ..reader.clone() })
+                            },
+                            {width.label()}
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -3,13 +3,14 @@ use dioxus_router::prelude::*;
 use crate::{
     api::recommendations::{RecommendationService, RecommendationParams, RecommendedArticle, TrendingArticle},
     components::ArticleCard,
-    hooks::use_auth,
+    hooks::{use_auth, use_user_relations},
     Route,
 };
 
 #[component]
 pub fn PersonalizedRecommendations() -> Element {
     let mut recommendations = use_signal(|| Vec::<RecommendedArticle>::new());
+    let relations = use_user_relations();
     let mut loading = use_signal(|| true);
     let mut algorithm = use_signal(|| "hybrid".to_string());
     let auth = use_auth();
@@ -102,7 +103,8 @@ pub fn PersonalizedRecommendations() -> Element {
             } else {
                 div {
                     class: "grid gap-6",
-                    for rec in recommendations.read().iter().cloned() {
+                    // 不显示屏蔽和静音的作者
+                    for rec in recommendations.read().iter().filter(|rec| !relations.is_hidden(&rec.article.author.id)).cloned() {
                         div {
                             class: "relative",
                             
@@ -150,6 +152,7 @@ pub fn TrendingArticles(period: String) -> Element {
     let period = period.clone();
     let mut articles = use_signal(|| Vec::<TrendingArticle>::new());
     let mut loading = use_signal(|| true);
+    let relations = use_user_relations();
     
     use_effect({
         let period = period.clone();
@@ -202,7 +205,7 @@ pub fn TrendingArticles(period: String) -> Element {
             } else {
                 div {
                     class: "space-y-3",
-                    for (i, article) in articles().iter().enumerate() {
+                    for (i, article) in articles().iter().filter(|a| !relations.is_hidden(&a.author.id)).enumerate() {
                         Link {
                             to: Route::Article { slug: article.slug.clone() },
                             class: "block group",
@@ -0,0 +1,145 @@
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+use crate::{
+    hooks::{use_auth, SeriesState},
+    Route,
+};
+
+/// 文章顶部的系列横幅：第几篇、阅读进度，以及可展开的全部文章列表
+#[component]
+pub fn SeriesBanner(series: SeriesState, article_id: String) -> Element {
+    let auth = use_auth();
+    let mut expanded = use_signal(|| false);
+
+    let Some(data) = (series.data)() else {
+        return rsx! {};
+    };
+    let parts = data.parts();
+    let Some(position) = data.position(&article_id) else {
+        return rsx! {};
+    };
+    let finished = parts.iter().filter(|part| series.is_finished(&part.article.id)).count();
+    let show_progress = auth.read().is_authenticated;
+
+    rsx! {
+        div {
+            class: "mb-8 border border-gray-200 dark:border-gray-700 rounded-lg",
+            div {
+                class: "flex items-center justify-between px-4 py-3",
+                div {
+                    class: "min-w-0",
+                    p {
+                        class: "text-xs uppercase tracking-wide text-gray-500 dark:text-gray-400",
+                        {format!("系列 · 第 {} 篇，共 {} 篇", position + 1, parts.len())}
+                    }
+                    Link {
+                        to: Route::SeriesDetail { slug: data.series.slug.clone() },
+                        class: "block font-medium text-gray-900 dark:text-white hover:underline truncate",
+                        {data.series.title.clone()}
+                    }
+                }
+                button {
+                    class: "ml-4 shrink-0 text-sm text-gray-600 dark:text-gray-400 hover:text-gray-900 dark:hover:text-white",
+                    onclick: move |_| expanded.toggle(),
+                    if expanded() { "收起目录 ▲" } else { "全部文章 ▼" }
+                }
+            }
+
+            // 登录读者的系列阅读进度
+            if show_progress {
+                div {
+                    class: "px-4 pb-3",
+                    div {
+                        class: "h-1.5 bg-gray-100 dark:bg-gray-700 rounded-full overflow-hidden",
+                        div {
+                            class: "h-full bg-green-600",
+                            style: format!("width: {}%", finished * 100 / parts.len().max(1))
+                        }
+                    }
+                    p {
+                        class: "mt-1 text-xs text-gray-500 dark:text-gray-400",
+                        {format!("已读完 {} / {} 篇", finished, parts.len())}
+                    }
+                }
+            }
+
+            if expanded() {
+                ol {
+                    class: "border-t border-gray-200 dark:border-gray-700 py-2",
+                    for (index, part) in parts.iter().enumerate() {
+                        li {
+                            key: "{part.article.id}",
+                            class: if index == position {
+                                "flex items-center px-4 py-2 text-sm bg-gray-50 dark:bg-gray-800 font-medium text-gray-900 dark:text-white"
+                            } else {
+                                "flex items-center px-4 py-2 text-sm text-gray-700 dark:text-gray-300"
+                            },
+                            span {
+                                class: "w-6 shrink-0 text-gray-400",
+                                "{index + 1}"
+                            }
+                            if index == position {
+                                span { class: "flex-1 truncate", {part.article.title.clone()} }
+                            } else {
+                                Link {
+                                    to: Route::Article { slug: part.article.slug.clone() },
+                                    class: "flex-1 truncate hover:underline",
+                                    {part.article.title.clone()}
+                                }
+                            }
+                            if show_progress && series.is_finished(&part.article.id) {
+                                span {
+                                    class: "ml-2 text-green-600",
+                                    title: "已读完",
+                                    "✓"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// 文章底部的上一篇 / 下一篇链接
+#[component]
+pub fn SeriesPager(series: SeriesState, article_id: String) -> Element {
+    let Some(data) = (series.data)() else {
+        return rsx! {};
+    };
+    let parts = data.parts();
+    let Some(position) = data.position(&article_id) else {
+        return rsx! {};
+    };
+    let previous = position.checked_sub(1).and_then(|i| parts.get(i)).map(|part| part.article.clone());
+    let next = parts.get(position + 1).map(|part| part.article.clone());
+
+    if previous.is_none() && next.is_none() {
+        return rsx! {};
+    }
+
+    rsx! {
+        nav {
+            class: "grid grid-cols-2 gap-4 mb-8",
+            if let Some(article) = previous {
+                Link {
+                    to: Route::Article { slug: article.slug.clone() },
+                    class: "p-4 border border-gray-200 dark:border-gray-700 rounded-lg hover:bg-gray-50 dark:hover:bg-gray-800",
+                    p { class: "text-xs text-gray-500 dark:text-gray-400", "← 上一篇" }
+                    p { class: "mt-1 font-medium text-gray-900 dark:text-white line-clamp-2", {article.title.clone()} }
+                }
+            } else {
+                div {}
+            }
+            if let Some(article) = next {
+                Link {
+                    to: Route::Article { slug: article.slug.clone() },
+                    class: "p-4 text-right border border-gray-200 dark:border-gray-700 rounded-lg hover:bg-gray-50 dark:hover:bg-gray-800",
+                    p { class: "text-xs text-gray-500 dark:text-gray-400", "下一篇 →" }
+                    p { class: "mt-1 font-medium text-gray-900 dark:text-white line-clamp-2", {article.title.clone()} }
+                }
+            }
+        }
+    }
+}
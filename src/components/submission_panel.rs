@@ -0,0 +1,206 @@
+use dioxus::prelude::*;
+use crate::{
+    api::{publications::PublicationService, submissions::SubmissionService},
+    models::{
+        publication::PublicationMembership,
+        submission::{CreateSubmissionRequest, Submission},
+    },
+};
+
+/// 编辑器顶部的投稿状态条，需要修改时作者可以附言重新投稿
+#[component]
+pub fn SubmissionBanner(submission: Submission, on_resubmit: EventHandler<String>) -> Element {
+    let mut note = use_signal(String::new);
+    let status = submission.status;
+
+    rsx! {
+        div {
+            class: "mb-6 border border-gray-200 dark:border-gray-700 rounded-lg px-4 py-3 text-sm space-y-2",
+            div {
+                class: "flex items-center space-x-2",
+                span { class: "text-gray-600 dark:text-gray-400", "投稿状态" }
+                span {
+                    class: "px-2 py-0.5 rounded-full text-xs font-medium {status.badge_class()}",
+                    {status.label()}
+                }
+                if let Some(at) = submission.scheduled_at {
+                    span {
+                        class: "text-xs text-gray-500",
+                        {format!("计划于 {} 发布", at.format("%Y-%m-%d %H:%M"))}
+                    }
+                }
+            }
+            if let Some(event) = submission.latest_note() {
+                blockquote {
+                    class: "border-l-2 border-gray-300 pl-3 text-gray-700 dark:text-gray-300",
+                    p { class: "whitespace-pre-wrap", {event.note.clone().unwrap_or_default()} }
+                    p {
+                        class: "text-xs text-gray-500 mt-1",
+                        {format!("— {} · {}", event.actor.display_name, event.created_at.format("%Y-%m-%d %H:%M"))}
+                    }
+                }
+            }
+            if status.can_resubmit() {
+                div {
+                    class: "flex space-x-2",
+                    input {
+                        class: "flex-1 min-w-0 px-3 py-1.5 text-sm bg-transparent border border-gray-300 dark:border-gray-600 rounded outline-none text-gray-900 dark:text-white",
+                        placeholder: "说明做了哪些修改（可选）",
+                        value: "{note}",
+                        oninput: move |e| note.set(e.value())
+                    }
+                    button {
+                        class: "px-4 py-1.5 text-sm text-white bg-gray-900 rounded-full hover:bg-gray-700",
+                        onclick: move |_| {
+                            on_resubmit.call(note());
+                            note.set(String::new());
+                        },
+                        "重新投稿"
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// 选择出版物并投稿
+#[component]
+pub fn SubmitToPublicationModal(
+    article_id: String,
+    // 文章已属于某个出版物时只能投给它
+    publication_id: Option<String>,
+    on_close: EventHandler<()>,
+    on_submitted: EventHandler<Submission>,
+) -> Element {
+    let mut memberships = use_signal(Vec::<PublicationMembership>::new);
+    let mut selected = use_signal(|| publication_id.clone().unwrap_or_default());
+    let mut note = use_signal(String::new);
+    let mut loading = use_signal(|| true);
+    let mut submitting = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+
+    let fixed_publication = publication_id.clone();
+    use_effect(move || {
+        let fixed_publication = fixed_publication.clone();
+        spawn(async move {
+            match PublicationService::get_my_publications().await {
+                Ok(list) => {
+                    let list: Vec<PublicationMembership> = list
+                        .into_iter()
+                        .filter(|m| fixed_publication.as_ref().is_none_or(|id| &m.publication.id == id))
+                        .collect();
+                    if selected.peek().is_empty() {
+                        if let Some(first) = list.first() {
+                            selected.set(first.publication.id.clone());
+                        }
+                    }
+                    memberships.set(list);
+                }
+                Err(e) => error.set(Some(format!("加载出版物失败: {}", e.message))),
+            }
+            loading.set(false);
+        });
+    });
+
+    let submit = move |_| {
+        let publication_id = selected();
+        if publication_id.is_empty() {
+            return;
+        }
+        let request = CreateSubmissionRequest {
+            article_id: article_id.clone(),
+            note: if note().trim().is_empty() { None } else { Some(note().trim().to_string()) },
+        };
+        spawn(async move {
+            submitting.set(true);
+            error.set(None);
+            match SubmissionService::submit(&publication_id, &request).await {
+                Ok(submission) => on_submitted.call(submission),
+                Err(e) => error.set(Some(format!("投稿失败: {}", e.message))),
+            }
+            submitting.set(false);
+        });
+    };
+
+    rsx! {
+        div {
+            class: "fixed inset-0 z-50 flex items-center justify-center bg-black bg-opacity-50",
+            onclick: move |_| on_close.call(()),
+            div {
+                class: "w-full max-w-md mx-4 bg-white dark:bg-gray-800 rounded-lg shadow-xl p-6 space-y-4",
+                onclick: move |e| e.stop_propagation(),
+                h2 {
+                    class: "text-lg font-semibold text-gray-900 dark:text-white",
+                    "投稿到出版物"
+                }
+
+                if let Some(err) = error() {
+                    div {
+                        class: "bg-red-50 dark:bg-red-900/20 text-red-700 dark:text-red-400 px-3 py-2 rounded text-sm",
+                        {err}
+                    }
+                }
+
+                if loading() {
+                    p { class: "text-sm text-gray-500", "加载中..." }
+                } else if memberships().is_empty() {
+                    p {
+                        class: "text-sm text-gray-500",
+                        "你还没有加入任何出版物，请先联系出版物的编辑邀请你成为作者"
+                    }
+                } else {
+                    div {
+                        class: "space-y-2",
+                        for membership in memberships() {
+                            label {
+                                key: "{membership.publication.id}",
+                                class: "flex items-center justify-between p-3 border border-gray-200 dark:border-gray-700 rounded-lg cursor-pointer",
+                                div {
+                                    class: "flex items-center space-x-3",
+                                    input {
+                                        r#type: "radio",
+                                        name: "publication",
+                                        checked: selected() == membership.publication.id,
+                                        onchange: {
+                                            let id = membership.publication.id.clone();
+                                            move |_| selected.set(id.clone())
+                                        }
+                                    }
+                                    span {
+                                        class: "text-gray-900 dark:text-white",
+                                        {membership.publication.name.clone()}
+                                    }
+                                }
+                                span {
+                                    class: "text-xs text-gray-500",
+                                    {membership.role.label()}
+                                }
+                            }
+                        }
+                    }
+                    textarea {
+                        class: "w-full h-24 p-3 text-sm bg-transparent border border-gray-300 dark:border-gray-600 rounded-lg outline-none resize-none text-gray-900 dark:text-white",
+                        placeholder: "给编辑的留言（可选）",
+                        value: "{note}",
+                        oninput: move |e| note.set(e.value())
+                    }
+                }
+
+                div {
+                    class: "flex justify-end space-x-3",
+                    button {
+                        class: "px-4 py-2 text-sm text-gray-700 dark:text-gray-300",
+                        onclick: move |_| on_close.call(()),
+                        "取消"
+                    }
+                    button {
+                        class: "px-4 py-2 text-sm text-white bg-green-600 rounded-full hover:bg-green-700 disabled:opacity-50",
+                        disabled: submitting() || selected().is_empty(),
+                        onclick: submit,
+                        if submitting() { "投稿中..." } else { "投稿" }
+                    }
+                }
+            }
+        }
+    }
+}
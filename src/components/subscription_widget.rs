@@ -11,6 +11,8 @@ use crate::{
 pub fn SubscriptionWidget(
     creator: User,
     is_premium_content: Option<bool>,
+    // 订阅成功后通知页面解锁内容
+    on_subscribed: Option<EventHandler<Subscription>>,
 ) -> Element {
     let creator_id = creator.id.clone();
     let mut subscription_status = use_signal(|| None::<Subscription>);
@@ -78,8 +80,11 @@ pub fn SubscriptionWidget(
             };
             
             if let Ok(subscription) = SubscriptionService::create_subscription(&request).await {
-                subscription_status.set(Some(subscription));
+                subscription_status.set(Some(subscription.clone()));
                 show_plans_modal.set(false);
+                if let Some(handler) = on_subscribed {
+                    handler.call(subscription);
+                }
             }
         });
     };
@@ -106,8 +111,8 @@ pub fn SubscriptionWidget(
     }
     
     rsx! {
-        // 付费内容锁定提示
-        if is_premium && subscription_status().is_none() {
+        // 付费内容锁定提示（未订阅或订阅已失效），查询订阅状态期间不显示，避免订阅者看到提示闪现
+        if is_premium && !loading() && !subscription_status().is_some_and(|s| matches!(s.status, SubscriptionStatus::Active)) {
             div {
                 class: "bg-gradient-to-r from-blue-50 to-indigo-50 dark:from-blue-900/20 dark:to-indigo-900/20 border border-blue-200 dark:border-blue-800 rounded-lg p-6 my-8",
                 
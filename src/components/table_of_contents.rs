@@ -0,0 +1,180 @@
+use dioxus::prelude::*;
+use gloo_timers::future::TimeoutFuture;
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::window;
+use crate::rendering::toc::TocEntry;
+
+// 标题进入视口顶部这个距离内即视为当前章节（留出顶部导航栏的高度）
+const ACTIVE_OFFSET: f64 = 120.0;
+
+#[component]
+pub fn TableOfContents(entries: ReadOnlySignal<Vec<TocEntry>>) -> Element {
+    let mut active_id = use_signal(|| None::<String>);
+    let mut show_drawer = use_signal(|| false);
+    let listener = use_hook(|| Rc::new(RefCell::new(None::<Closure<dyn FnMut()>>)));
+
+    // 滚动监听：找出最后一个已经滚过顶部的标题
+    // 正文变化后目录项随之更新，重新订阅新的标题
+    use_effect({
+        let listener = listener.clone();
+        move || {
+            let ids: Vec<String> = entries.read().iter().map(|e| e.id.clone()).collect();
+            let update_active = move || {
+                let Some(document) = window().and_then(|w| w.document()) else {
+                    return;
+                };
+                let mut current = ids.first().cloned();
+                for id in &ids {
+                    if let Some(element) = document.get_element_by_id(id) {
+                        if element.get_bounding_client_rect().top() <= ACTIVE_OFFSET {
+                            current = Some(id.clone());
+                        } else {
+                            break;
+                        }
+                    }
+                }
+                if *active_id.peek() != current {
+                    active_id.set(current);
+                }
+            };
+
+            let Some(win) = window() else {
+                return;
+            };
+            let closure = Closure::<dyn FnMut()>::new(update_active);
+            win.add_event_listener_with_callback("scroll", closure.as_ref().unchecked_ref())
+                .ok();
+            if let Some(previous) = listener.borrow_mut().replace(closure) {
+                win.remove_event_listener_with_callback("scroll", previous.as_ref().unchecked_ref())
+                    .ok();
+            }
+
+            // 打开带 #锚点 的分享链接时，正文渲染完成后再定位
+            spawn(async move {
+                TimeoutFuture::new(0).await;
+                if let Some(win) = window() {
+                    let hash = win.location().hash().unwrap_or_default();
+                    let id = urlencoding::decode(hash.trim_start_matches('#'))
+                        .map(|s| s.into_owned())
+                        .unwrap_or_default();
+                    if !id.is_empty() {
+                        if let Some(element) = win.document().and_then(|d| d.get_element_by_id(&id)) {
+                            element.scroll_into_view();
+                        }
+                    }
+                }
+            });
+        }
+    });
+
+    use_drop({
+        let listener = listener.clone();
+        move || {
+            if let (Some(win), Some(closure)) = (window(), listener.borrow_mut().take()) {
+                win.remove_event_listener_with_callback("scroll", closure.as_ref().unchecked_ref())
+                    .ok();
+            }
+        }
+    });
+
+    let entries = entries();
+
+    // 标题太少时不显示目录
+    if entries.len() < 2 {
+        return rsx! {};
+    }
+
+    let min_level = entries.iter().map(|e| e.level).min().unwrap_or(2);
+
+    let render_list = move |entries: Vec<TocEntry>, close_drawer: bool| {
+        rsx! {
+            ul {
+                class: "space-y-1 text-sm",
+                for entry in entries {
+                    li {
+                        key: "{entry.id}",
+                        style: "padding-left: {(entry.level - min_level) as f32 * 0.75}rem;",
+                        a {
+                            href: "#{entry.id}",
+                            class: if active_id().as_deref() == Some(entry.id.as_str()) {
+                                "block py-1 pl-3 border-l-2 border-gray-900 dark:border-gray-100 text-gray-900 dark:text-gray-100 font-medium"
+                            } else {
+                                "block py-1 pl-3 border-l-2 border-transparent text-gray-500 dark:text-gray-400 hover:text-gray-900 dark:hover:text-gray-100"
+                            },
+                            onclick: {
+                                let id = entry.id.clone();
+                                move |_| {
+                                    active_id.set(Some(id.clone()));
+                                    if close_drawer {
+                                        show_drawer.set(false);
+                                    }
+                                }
+                            },
+                            {entry.text.clone()}
+                        }
+                    }
+                }
+            }
+        }
+    };
+
+    rsx! {
+        // 宽屏：左侧固定目录
+        aside {
+            class: "hidden xl:block fixed top-24 left-8 w-60 max-h-[70vh] overflow-y-auto z-10",
+            h3 {
+                class: "text-xs font-semibold uppercase tracking-wide text-gray-500 dark:text-gray-400 mb-3",
+                "目录"
+            }
+            {render_list(entries.clone(), false)}
+        }
+
+        // 窄屏：底部抽屉
+        div {
+            class: "xl:hidden",
+            button {
+                class: "fixed bottom-6 left-6 z-30 flex items-center space-x-2 px-4 py-2 bg-white dark:bg-gray-800 border border-gray-200 dark:border-gray-700 rounded-full shadow-lg text-sm text-gray-700 dark:text-gray-300",
+                onclick: move |_| show_drawer.set(!show_drawer()),
+                svg {
+                    class: "w-4 h-4",
+                    fill: "none",
+                    stroke: "currentColor",
+                    view_box: "0 0 24 24",
+                    path {
+                        stroke_linecap: "round",
+                        stroke_linejoin: "round",
+                        stroke_width: "2",
+                        d: "M4 6h16M4 12h10M4 18h14"
+                    }
+                }
+                span { "目录" }
+            }
+
+            if show_drawer() {
+                div {
+                    class: "fixed inset-0 z-40 bg-black bg-opacity-30",
+                    onclick: move |_| show_drawer.set(false),
+                    div {
+                        class: "absolute bottom-0 left-0 right-0 max-h-[60vh] overflow-y-auto bg-white dark:bg-gray-800 rounded-t-2xl p-6",
+                        onclick: move |e| e.stop_propagation(),
+                        div {
+                            class: "flex items-center justify-between mb-4",
+                            h3 {
+                                class: "text-base font-semibold text-gray-900 dark:text-gray-100",
+                                "目录"
+                            }
+                            button {
+                                class: "p-1 text-gray-500 dark:text-gray-400 hover:text-gray-900 dark:hover:text-gray-100",
+                                onclick: move |_| show_drawer.set(false),
+                                "✕"
+                            }
+                        }
+                        {render_list(entries.clone(), true)}
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use dioxus::prelude::*;
+use crate::{hooks::use_user_relations, models::privacy::RelationKind};
+
+/// 个人主页上的“⋯”菜单：静音或屏蔽这位用户
+#[component]
+pub fn UserRelationMenu(user_id: String, username: String) -> Element {
+    let relations = use_user_relations();
+    let mut open = use_signal(|| false);
+    let mut busy = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+
+    let toggle = {
+        let user_id = user_id.clone();
+        let username = username.clone();
+        move |kind: RelationKind| {
+            let enabled = !relations.contains(kind, &user_id);
+            if enabled {
+                let confirmed = web_sys::window()
+                    .and_then(|w| w.confirm_with_message(&kind.confirm_message(&username)).ok())
+                    .unwrap_or(false);
+                if !confirmed {
+                    return;
+                }
+            }
+            open.set(false);
+            let user_id = user_id.clone();
+            spawn(async move {
+                busy.set(true);
+                error.set(None);
+                if let Err(e) = relations.set(kind, &user_id, enabled).await {
+                    error.set(Some(format!("{}失败: {}", kind.label(), e.message)));
+                }
+                busy.set(false);
+            });
+        }
+    };
+
+    rsx! {
+        div {
+            class: "relative",
+            button {
+                class: "flex items-center px-4 py-3 bg-gray-100 text-gray-700 rounded-full font-medium hover:bg-gray-200 transition-all duration-200 disabled:opacity-50",
+                title: "更多操作",
+                disabled: busy(),
+                onclick: move |_| open.toggle(),
+                "⋯"
+            }
+            if open() {
+                // 点击菜单外部时关闭
+                div {
+                    class: "fixed inset-0 z-30",
+                    onclick: move |_| open.set(false),
+                }
+                div {
+                    class: "absolute right-0 mt-2 w-48 py-1 bg-white dark:bg-gray-800 border border-gray-200 dark:border-gray-700 rounded-lg shadow-lg z-40",
+                    for kind in [RelationKind::Mute, RelationKind::Block] {
+                        button {
+                            key: "{kind.as_str()}",
+                            class: if kind == RelationKind::Block {
+                                "block w-full px-4 py-2 text-left text-sm text-red-600 hover:bg-gray-50 dark:hover:bg-gray-700"
+                            } else {
+                                "block w-full px-4 py-2 text-left text-sm text-gray-700 dark:text-gray-300 hover:bg-gray-50 dark:hover:bg-gray-700"
+                            },
+                            onclick: {
+                                let mut toggle = toggle.clone();
+                                move |_| toggle(kind)
+                            },
+                            if relations.contains(kind, &user_id) {
+                                {format!("{} @{}", kind.undo_label(), username)}
+                            } else {
+                                {format!("{} @{}", kind.label(), username)}
+                            }
+                        }
+                    }
+                }
+            }
+            if let Some(message) = error() {
+                p {
+                    class: "absolute right-0 mt-2 w-64 text-xs text-red-600",
+                    {message}
+                }
+            }
+        }
+    }
+}
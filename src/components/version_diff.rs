@@ -0,0 +1,388 @@
+use std::{collections::HashSet, rc::Rc};
+use dioxus::prelude::*;
+use crate::{
+    collab::diff::{self, DiffBlock},
+    models::version::{VersionField, VersionSnapshot},
+};
+
+// 修改块前后保留的未修改行数，更长的未修改区域默认折叠
+const CONTEXT_LINES: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DiffLayout {
+    SideBySide,
+    Inline,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum LineKind {
+    Context,
+    Deleted,
+    Inserted,
+    Empty,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct DiffLine {
+    number: Option<usize>,
+    kind: LineKind,
+    segments: Vec<(String, bool)>,
+}
+
+impl DiffLine {
+    fn empty() -> Self {
+        Self { number: None, kind: LineKind::Empty, segments: Vec::new() }
+    }
+}
+
+enum DiffRow {
+    Context { old_number: usize, new_number: usize, text: String },
+    Fold { block: usize, hidden: usize },
+    Hunk {
+        old: std::ops::Range<usize>,
+        new: std::ops::Range<usize>,
+        // 并排显示时每行左右两侧
+        pairs: Vec<(DiffLine, DiffLine)>,
+    },
+}
+
+/// 两组字段的差异：元数据逐项比较，正文按行比较并在修改行内标出改动的单词。
+/// `restorable` 为真时 old 是历史版本、new 是当前草稿，可以把单个字段或单处修改恢复到草稿
+#[component]
+pub fn VersionDiffView(
+    old: VersionSnapshot,
+    new: VersionSnapshot,
+    old_label: String,
+    new_label: String,
+    restorable: bool,
+    on_close: EventHandler<()>,
+    on_restore_field: EventHandler<(VersionField, String)>,
+) -> Element {
+    let mut layout = use_signal(|| DiffLayout::SideBySide);
+    let mut expanded = use_signal(HashSet::<usize>::new);
+
+    let changed_fields: Vec<VersionField> = VersionField::METADATA
+        .into_iter()
+        .filter(|field| old.field(*field) != new.field(*field))
+        .collect();
+    let rows = content_rows(&old.content, &new.content, &expanded.read());
+    let content_changed = old.content != new.content;
+    // 恢复单处修改时才拼出新的正文，各修改块共享这两份文本
+    let old_content = Rc::new(old.content.clone());
+    let new_content = Rc::new(new.content.clone());
+
+    rsx! {
+        div {
+            class: "absolute inset-0 flex items-center justify-center p-4",
+            div {
+                class: "absolute inset-0 bg-black bg-opacity-50",
+                onclick: move |_| on_close.call(())
+            }
+            div {
+                class: "relative w-full max-w-6xl max-h-full flex flex-col bg-white dark:bg-gray-800 rounded-lg shadow-xl",
+
+                // 头部
+                div {
+                    class: "flex items-center justify-between px-6 py-4 border-b border-gray-200 dark:border-gray-700",
+                    div {
+                        h2 {
+                            class: "text-lg font-semibold text-gray-900 dark:text-white",
+                            "版本差异"
+                        }
+                        p {
+                            class: "text-sm text-gray-500 dark:text-gray-400",
+                            span { class: "text-red-600 dark:text-red-400", {old_label.clone()} }
+                            " → "
+                            span { class: "text-green-600 dark:text-green-400", {new_label.clone()} }
+                        }
+                    }
+                    div {
+                        class: "flex items-center space-x-3",
+                        div {
+                            class: "flex text-sm border border-gray-300 dark:border-gray-600 rounded overflow-hidden",
+                            button {
+                                class: if layout() == DiffLayout::SideBySide {
+                                    "px-3 py-1 bg-gray-900 text-white"
+                                } else {
+                                    "px-3 py-1 text-gray-600 dark:text-gray-300"
+                                },
+                                onclick: move |_| layout.set(DiffLayout::SideBySide),
+                                "并排"
+                            }
+                            button {
+                                class: if layout() == DiffLayout::Inline {
+                                    "px-3 py-1 bg-gray-900 text-white"
+                                } else {
+                                    "px-3 py-1 text-gray-600 dark:text-gray-300"
+                                },
+                                onclick: move |_| layout.set(DiffLayout::Inline),
+                                "行内"
+                            }
+                        }
+                        button {
+                            class: "p-2 rounded-full hover:bg-gray-100 dark:hover:bg-gray-700 text-gray-500",
+                            onclick: move |_| on_close.call(()),
+                            "✕"
+                        }
+                    }
+                }
+
+                div {
+                    class: "flex-1 overflow-y-auto p-6 space-y-6",
+
+                    if changed_fields.is_empty() && !content_changed {
+                        p {
+                            class: "text-center text-gray-500 dark:text-gray-400 py-12",
+                            "两个版本完全相同"
+                        }
+                    }
+
+                    // 元数据字段
+                    for field in changed_fields {
+                        {
+                            let (old_segments, new_segments) = diff::word_segments(old.field(field), new.field(field));
+                            let value = old.field(field).to_string();
+                            rsx! {
+                                div {
+                                    key: "{field.label()}",
+                                    class: "border border-gray-200 dark:border-gray-700 rounded-lg",
+                                    div {
+                                        class: "flex items-center justify-between px-4 py-2 bg-gray-50 dark:bg-gray-900 border-b border-gray-200 dark:border-gray-700 rounded-t-lg",
+                                        span {
+                                            class: "text-sm font-medium text-gray-700 dark:text-gray-300",
+                                            {field.label()}
+                                        }
+                                        if restorable {
+                                            button {
+                                                class: "text-sm text-blue-600 dark:text-blue-400 hover:text-blue-500",
+                                                onclick: move |_| on_restore_field.call((field, value.clone())),
+                                                {format!("恢复{}", field.label())}
+                                            }
+                                        }
+                                    }
+                                    div {
+                                        class: "grid grid-cols-2 divide-x divide-gray-200 dark:divide-gray-700 text-sm",
+                                        FieldValue { segments: old_segments, kind: LineKind::Deleted }
+                                        FieldValue { segments: new_segments, kind: LineKind::Inserted }
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    // 正文
+                    if content_changed {
+                        div {
+                            class: "border border-gray-200 dark:border-gray-700 rounded-lg overflow-hidden font-mono text-sm",
+                            div {
+                                class: "px-4 py-2 bg-gray-50 dark:bg-gray-900 border-b border-gray-200 dark:border-gray-700 font-sans text-sm font-medium text-gray-700 dark:text-gray-300",
+                                {VersionField::Content.label()}
+                            }
+                            for row in rows {
+                                match row {
+                                    DiffRow::Context { old_number, new_number, text } => rsx! {
+                                        if layout() == DiffLayout::SideBySide {
+                                            div {
+                                                class: "grid grid-cols-2",
+                                                DiffLineView { line: DiffLine { number: Some(old_number), kind: LineKind::Context, segments: vec![(text.clone(), false)] } }
+                                                DiffLineView { line: DiffLine { number: Some(new_number), kind: LineKind::Context, segments: vec![(text, false)] } }
+                                            }
+                                        } else {
+                                            DiffLineView { line: DiffLine { number: Some(new_number), kind: LineKind::Context, segments: vec![(text, false)] } }
+                                        }
+                                    },
+                                    DiffRow::Fold { block, hidden } => rsx! {
+                                        button {
+                                            class: "w-full py-1 text-xs font-sans text-blue-600 dark:text-blue-400 bg-blue-50 dark:bg-blue-900/20 hover:bg-blue-100 dark:hover:bg-blue-900/40",
+                                            onclick: move |_| {
+                                                expanded.write().insert(block);
+                                            },
+                                            {format!("展开 {} 行未修改内容", hidden)}
+                                        }
+                                    },
+                                    DiffRow::Hunk { old: old_range, new: new_range, pairs } => {
+                                        let (old_content, new_content) = (old_content.clone(), new_content.clone());
+                                        let (old_lines, new_lines) = (old_range.clone(), new_range.clone());
+                                        let header = hunk_header(&old_range, &new_range);
+                                        rsx! {
+                                            div {
+                                                class: "flex items-center justify-between px-4 py-1 bg-gray-100 dark:bg-gray-700 font-sans text-xs text-gray-500 dark:text-gray-400",
+                                                span { {header} }
+                                                if restorable {
+                                                    button {
+                                                        class: "text-blue-600 dark:text-blue-400 hover:text-blue-500",
+                                                        onclick: move |_| {
+                                                            let restored = restore_hunk(&old_content, &new_content, old_lines.clone(), new_lines.clone());
+                                                            on_restore_field.call((VersionField::Content, restored));
+                                                        },
+                                                        "恢复此处"
+                                                    }
+                                                }
+                                            }
+                                            if layout() == DiffLayout::SideBySide {
+                                                for (left, right) in pairs {
+                                                    div {
+                                                        class: "grid grid-cols-2",
+                                                        DiffLineView { line: left }
+                                                        DiffLineView { line: right }
+                                                    }
+                                                }
+                                            } else {
+                                                for (left, _) in pairs.iter().filter(|(left, _)| left.kind != LineKind::Empty).cloned() {
+                                                    DiffLineView { line: left }
+                                                }
+                                                for (_, right) in pairs.iter().filter(|(_, right)| right.kind != LineKind::Empty).cloned() {
+                                                    DiffLineView { line: right }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn FieldValue(segments: Vec<(String, bool)>, kind: LineKind) -> Element {
+    let (background, highlight) = line_colors(kind);
+    rsx! {
+        div {
+            class: "px-4 py-2 whitespace-pre-wrap break-words {background}",
+            if segments.is_empty() {
+                span { class: "text-gray-400 italic", "（空）" }
+            }
+            for (text, changed) in segments {
+                span {
+                    class: if changed { highlight } else { "" },
+                    {text}
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn DiffLineView(line: DiffLine) -> Element {
+    let (background, highlight) = line_colors(line.kind);
+    let marker = match line.kind {
+        LineKind::Deleted => "-",
+        LineKind::Inserted => "+",
+        _ => " ",
+    };
+
+    rsx! {
+        div {
+            class: "flex min-w-0 {background}",
+            span {
+                class: "w-12 shrink-0 pr-2 text-right text-gray-400 select-none",
+                {line.number.map(|n| n.to_string()).unwrap_or_default()}
+            }
+            span {
+                class: "w-4 shrink-0 text-gray-400 select-none",
+                {marker}
+            }
+            span {
+                class: "flex-1 min-w-0 pr-2 whitespace-pre-wrap break-words text-gray-800 dark:text-gray-200",
+                for (text, changed) in line.segments {
+                    span {
+                        class: if changed { highlight } else { "" },
+                        {text}
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn line_colors(kind: LineKind) -> (&'static str, &'static str) {
+    match kind {
+        LineKind::Deleted => ("bg-red-50 dark:bg-red-900/20", "bg-red-200 dark:bg-red-800/60 rounded-sm"),
+        LineKind::Inserted => ("bg-green-50 dark:bg-green-900/20", "bg-green-200 dark:bg-green-800/60 rounded-sm"),
+        LineKind::Empty => ("bg-gray-50 dark:bg-gray-900/40", ""),
+        LineKind::Context => ("", ""),
+    }
+}
+
+fn hunk_header(old: &std::ops::Range<usize>, new: &std::ops::Range<usize>) -> String {
+    fn describe(range: &std::ops::Range<usize>) -> String {
+        match range.len() {
+            0 => "无".to_string(),
+            1 => format!("第 {} 行", range.start + 1),
+            _ => format!("第 {}-{} 行", range.start + 1, range.end),
+        }
+    }
+    format!("旧版 {} → 新版 {}", describe(old), describe(new))
+}
+
+/// 把正文拆成显示用的行：未修改区域两端保留几行上下文，中间折叠
+fn content_rows(old: &str, new: &str, expanded: &HashSet<usize>) -> Vec<DiffRow> {
+    let old_lines: Vec<&str> = old.split('\n').collect();
+    let new_lines: Vec<&str> = new.split('\n').collect();
+    let blocks = diff::blocks(&diff::diff(&old_lines, &new_lines));
+    let last = blocks.len().saturating_sub(1);
+    let mut rows = Vec::new();
+
+    for (index, block) in blocks.into_iter().enumerate() {
+        match block {
+            DiffBlock::Equal { old_index, new_index, len } => {
+                let head = if index == 0 { 0 } else { CONTEXT_LINES };
+                let tail = if index == last { 0 } else { CONTEXT_LINES };
+                let fold = !expanded.contains(&index) && len > head + tail + 1;
+                for offset in 0..len {
+                    if fold && offset == head {
+                        rows.push(DiffRow::Fold { block: index, hidden: len - head - tail });
+                    }
+                    if fold && offset >= head && offset < len - tail {
+                        continue;
+                    }
+                    rows.push(DiffRow::Context {
+                        old_number: old_index + offset + 1,
+                        new_number: new_index + offset + 1,
+                        text: old_lines[old_index + offset].to_string(),
+                    });
+                }
+            }
+            DiffBlock::Change { old, new } => {
+                let count = old.len().max(new.len());
+                let pairs = (0..count)
+                    .map(|i| {
+                        let old_line = old_lines[old.clone()].get(i);
+                        let new_line = new_lines[new.clone()].get(i);
+                        // 两侧同一位置都有内容时视为同一行的修改，标出改动的单词
+                        let (old_segments, new_segments) = match (old_line, new_line) {
+                            (Some(a), Some(b)) => diff::word_segments(a, b),
+                            (a, b) => (
+                                a.map(|a| vec![(a.to_string(), false)]).unwrap_or_default(),
+                                b.map(|b| vec![(b.to_string(), false)]).unwrap_or_default(),
+                            ),
+                        };
+                        let left = match old_line {
+                            Some(_) => DiffLine { number: Some(old.start + i + 1), kind: LineKind::Deleted, segments: old_segments },
+                            None => DiffLine::empty(),
+                        };
+                        let right = match new_line {
+                            Some(_) => DiffLine { number: Some(new.start + i + 1), kind: LineKind::Inserted, segments: new_segments },
+                            None => DiffLine::empty(),
+                        };
+                        (left, right)
+                    })
+                    .collect();
+                rows.push(DiffRow::Hunk { old, new, pairs });
+            }
+        }
+    }
+    rows
+}
+
+/// 把旧版本中的一处修改恢复到新文本：用旧行替换新文本中对应的行
+fn restore_hunk(old: &str, new: &str, old_range: std::ops::Range<usize>, new_range: std::ops::Range<usize>) -> String {
+    let old_lines: Vec<&str> = old.split('\n').collect();
+    let mut lines: Vec<&str> = new.split('\n').collect();
+    lines.splice(new_range, old_lines[old_range].iter().copied());
+    lines.join("\n")
+}
@@ -1,17 +1,54 @@
 use dioxus::prelude::*;
 use dioxus::events::MouseEvent;
 use crate::{
-    models::version::ArticleVersion,
+    models::version::{ArticleVersion, UpdateVersionRequest, VersionField, VersionSnapshot},
     api::versions::VersionService,
+    components::VersionDiffView,
 };
 use chrono::{DateTime, Utc};
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum VersionFilter {
+    All,
+    Labeled,
+    Auto,
+}
+
+impl VersionFilter {
+    fn label(&self) -> &'static str {
+        match self {
+            VersionFilter::All => "全部",
+            VersionFilter::Labeled => "已命名",
+            VersionFilter::Auto => "自动保存",
+        }
+    }
+
+    fn matches(&self, version: &ArticleVersion) -> bool {
+        match self {
+            VersionFilter::All => true,
+            VersionFilter::Labeled => version.is_labeled() || version.pinned,
+            VersionFilter::Auto => version.is_automatic() && !version.is_labeled(),
+        }
+    }
+}
+
+// 打开的差异视图：历史版本与当前草稿，或两个历史版本之间
+#[derive(Debug, Clone, PartialEq)]
+enum DiffTarget {
+    Draft(Box<ArticleVersion>),
+    Versions(Box<ArticleVersion>, Box<ArticleVersion>),
+}
+
 #[component]
 pub fn VersionHistory(
     article_id: String,
     show: bool,
+    // 编辑器中的当前内容，用于与历史版本比较
+    draft: VersionSnapshot,
     on_close: EventHandler<()>,
     on_restore: EventHandler<ArticleVersion>,
+    // 只恢复某个字段或正文中的某一处修改
+    on_restore_field: EventHandler<(VersionField, String)>,
 ) -> Element {
     let mut versions = use_signal(|| Vec::<ArticleVersion>::new());
     let mut loading = use_signal(|| false);
@@ -19,7 +56,26 @@ pub fn VersionHistory(
     let mut comparing = use_signal(|| false);
     let mut compare_version_a = use_signal(|| None::<String>);
     let mut compare_version_b = use_signal(|| None::<String>);
-    let article_id_for_button = article_id.clone();
+    let mut diff_target = use_signal(|| None::<DiffTarget>);
+    let mut filter = use_signal(|| VersionFilter::All);
+    let mut update_error = use_signal(|| None::<String>);
+    let article_id_for_update = article_id.clone();
+    
+    // 命名或固定版本后就地替换列表中的条目
+    let update_version = move |(version_id, request): (String, UpdateVersionRequest)| {
+        let article_id = article_id_for_update.clone();
+        spawn(async move {
+            update_error.set(None);
+            match VersionService::update_version(&article_id, &version_id, &request).await {
+                Ok(updated) => {
+                    if let Some(existing) = versions.write().iter_mut().find(|v| v.id == updated.id) {
+                        *existing = updated;
+                    }
+                }
+                Err(e) => update_error.set(Some(format!("更新版本失败: {}", e.message))),
+            }
+        });
+    };
     
     // 加载版本历史
     use_effect(move || {
@@ -105,18 +161,15 @@ pub fn VersionHistory(
                         if comparing() && compare_version_a().is_some() && compare_version_b().is_some() {
                             button {
                                 class: "px-3 py-1 bg-blue-600 text-white text-sm rounded hover:bg-blue-700",
-                                onclick: {
-                                    let article_id = article_id_for_button.clone();
-                                    move |_| {
-                                        // 打开对比视图
-                                        if let (Some(a), Some(b)) = (compare_version_a(), compare_version_b()) {
-                                            web_sys::window()
-                                                .unwrap()
-                                                .open_with_url_and_target(
-                                                    &format!("/article/{}/versions/compare?a={}&b={}", article_id, a, b),
-                                                    "_blank"
-                                                )
-                                                .ok();
+                                onclick: move |_| {
+                                    let list = versions();
+                                    let find = |id: Option<String>| list.iter().find(|v| Some(&v.id) == id.as_ref()).cloned();
+                                    if let (Some(a), Some(b)) = (find(compare_version_a()), find(compare_version_b())) {
+                                        // 总是从较早的版本比较到较新的版本
+                                        if a.version_number <= b.version_number {
+                                            diff_target.set(Some(DiffTarget::Versions(Box::new(a), Box::new(b))));
+                                        } else {
+                                            diff_target.set(Some(DiffTarget::Versions(Box::new(b), Box::new(a))));
                                         }
                                     }
                                 },
@@ -124,6 +177,26 @@ pub fn VersionHistory(
                             }
                         }
                     }
+                    div {
+                        class: "flex space-x-4 mt-3 text-sm",
+                        for option in [VersionFilter::All, VersionFilter::Labeled, VersionFilter::Auto] {
+                            button {
+                                class: if filter() == option {
+                                    "font-medium text-gray-900 dark:text-white border-b-2 border-gray-900 dark:border-white"
+                                } else {
+                                    "text-gray-500 dark:text-gray-400 hover:text-gray-900 dark:hover:text-white"
+                                },
+                                onclick: move |_| filter.set(option),
+                                {option.label()}
+                            }
+                        }
+                    }
+                    if let Some(err) = update_error() {
+                        p {
+                            class: "mt-2 text-sm text-red-600 dark:text-red-400",
+                            {err}
+                        }
+                    }
                 }
                 
                 // 版本列表
@@ -149,10 +222,11 @@ pub fn VersionHistory(
                         div {
                             class: "divide-y divide-gray-200 dark:divide-gray-700",
                             
-                            for (index, version) in versions().iter().enumerate() {
+                            for version in versions().iter().filter(|v| filter().matches(v)) {
                                 VersionItem {
+                                    key: "{version.id}",
                                     version: version.clone(),
-                                    is_current: index == 0,
+                                    is_current: versions().first().map(|v| &v.id) == Some(&version.id),
                                     is_selected: selected_version() == Some(version.id.clone()),
                                     comparing: comparing(),
                                     is_compare_a: compare_version_a() == Some(version.id.clone()),
@@ -172,13 +246,42 @@ pub fn VersionHistory(
                                             selected_version.set(Some(v.id.clone()));
                                         }
                                     },
-                                    on_restore: move |v| on_restore.call(v)
+                                    on_restore: move |v| on_restore.call(v),
+                                    on_diff: move |v| diff_target.set(Some(DiffTarget::Draft(Box::new(v)))),
+                                    on_update: update_version.clone()
                                 }
                             }
                         }
                     }
                 }
             }
+
+            // 差异视图覆盖在侧边栏之上
+            match diff_target() {
+                Some(DiffTarget::Draft(version)) => rsx! {
+                    VersionDiffView {
+                        old: VersionSnapshot::from(&*version),
+                        new: draft.clone(),
+                        old_label: format!("版本 {}", version.version_number),
+                        new_label: "当前草稿".to_string(),
+                        restorable: true,
+                        on_close: move |_| diff_target.set(None),
+                        on_restore_field: move |change| on_restore_field.call(change)
+                    }
+                },
+                Some(DiffTarget::Versions(a, b)) => rsx! {
+                    VersionDiffView {
+                        old: VersionSnapshot::from(&*a),
+                        new: VersionSnapshot::from(&*b),
+                        old_label: format!("版本 {}", a.version_number),
+                        new_label: format!("版本 {}", b.version_number),
+                        restorable: false,
+                        on_close: move |_| diff_target.set(None),
+                        on_restore_field: move |_| {}
+                    }
+                },
+                None => rsx! {},
+            }
         }
     }
 }
@@ -193,7 +296,20 @@ fn VersionItem(
     is_compare_b: bool,
     on_select: EventHandler<ArticleVersion>,
     on_restore: EventHandler<ArticleVersion>,
+    on_diff: EventHandler<ArticleVersion>,
+    on_update: EventHandler<(String, UpdateVersionRequest)>,
 ) -> Element {
+    // 正在编辑的版本名称
+    let mut editing_label = use_signal(|| None::<String>);
+    let version_id = version.id.clone();
+    let mut save_label = move || {
+        if let Some(label) = editing_label() {
+            on_update.call((version_id.clone(), UpdateVersionRequest { label: Some(label.trim().to_string()), pinned: None }));
+            editing_label.set(None);
+        }
+    };
+    let mut save_label_on_enter = save_label.clone();
+
     rsx! {
         div {
             class: {
@@ -214,33 +330,81 @@ fn VersionItem(
                 class: "flex items-start justify-between",
                 
                 div {
-                    class: "flex-1",
+                    class: "flex-1 min-w-0",
                     div {
-                        class: "flex items-center mb-1",
+                        class: "flex flex-wrap items-center gap-2 mb-1",
                         h4 {
                             class: "text-sm font-medium text-gray-900 dark:text-white",
-                            {format!("版本 {}", version.version_number)}
+                            if version.is_labeled() {
+                                {version.label.clone().unwrap_or_default()}
+                            } else {
+                                {format!("版本 {}", version.version_number)}
+                            }
+                        }
+                        if version.is_labeled() {
+                            span {
+                                class: "text-xs text-gray-500 dark:text-gray-400",
+                                {format!("版本 {}", version.version_number)}
+                            }
+                        }
+                        if version.pinned {
+                            span {
+                                class: "text-xs text-gray-500 dark:text-gray-400",
+                                title: "已固定，不会被自动清理",
+                                "📌"
+                            }
+                        }
+                        if version.is_live {
+                            span {
+                                class: "px-2 py-1 text-xs bg-blue-100 dark:bg-blue-900 text-blue-800 dark:text-blue-200 rounded",
+                                "线上版本"
+                            }
                         }
                         if is_current {
                             span {
-                                class: "ml-2 px-2 py-1 text-xs bg-green-100 dark:bg-green-900 text-green-800 dark:text-green-200 rounded",
+                                class: "px-2 py-1 text-xs bg-green-100 dark:bg-green-900 text-green-800 dark:text-green-200 rounded",
                                 "当前版本"
                             }
                         }
                         if is_compare_a {
                             span {
-                                class: "ml-2 px-2 py-1 text-xs bg-yellow-100 dark:bg-yellow-900 text-yellow-800 dark:text-yellow-200 rounded",
+                                class: "px-2 py-1 text-xs bg-yellow-100 dark:bg-yellow-900 text-yellow-800 dark:text-yellow-200 rounded",
                                 "A"
                             }
                         }
                         if is_compare_b {
                             span {
-                                class: "ml-2 px-2 py-1 text-xs bg-yellow-100 dark:bg-yellow-900 text-yellow-800 dark:text-yellow-200 rounded",
+                                class: "px-2 py-1 text-xs bg-yellow-100 dark:bg-yellow-900 text-yellow-800 dark:text-yellow-200 rounded",
                                 "B"
                             }
                         }
                     }
                     
+                    if let Some(label) = editing_label() {
+                        div {
+                            class: "flex items-center space-x-2 mb-1",
+                            onclick: move |e| e.stop_propagation(),
+                            input {
+                                class: "flex-1 min-w-0 px-2 py-1 text-sm bg-transparent border border-gray-300 dark:border-gray-600 rounded outline-none text-gray-900 dark:text-white",
+                                placeholder: "例如：已发给编辑、正式发布 v1",
+                                value: "{label}",
+                                oninput: move |e| editing_label.set(Some(e.value())),
+                                onkeydown: move |e: KeyboardEvent| {
+                                    if e.key() == Key::Enter {
+                                        save_label_on_enter();
+                                    } else if e.key() == Key::Escape {
+                                        editing_label.set(None);
+                                    }
+                                }
+                            }
+                            button {
+                                class: "text-sm text-blue-600 dark:text-blue-400",
+                                onclick: move |_| save_label(),
+                                "保存"
+                            }
+                        }
+                    }
+                    
                     if let Some(summary) = &version.change_summary {
                         p {
                             class: "text-sm text-gray-600 dark:text-gray-400 mb-1",
@@ -254,19 +418,55 @@ fn VersionItem(
                     }
                 }
                 
-                if !is_current && !comparing {
+                if !comparing {
                     div {
-                        class: "ml-4",
+                        class: "ml-4 flex flex-col items-end space-y-1 text-sm",
+                        button {
+                            class: "text-gray-600 dark:text-gray-400 hover:text-gray-900 dark:hover:text-white",
+                            onclick: {
+                                let version_for_diff = version.clone();
+                                move |e: MouseEvent| {
+                                    e.stop_propagation();
+                                    on_diff.call(version_for_diff.clone());
+                                }
+                            },
+                            "差异"
+                        }
+                        button {
+                            class: "text-gray-600 dark:text-gray-400 hover:text-gray-900 dark:hover:text-white",
+                            onclick: {
+                                let label = version.label.clone().unwrap_or_default();
+                                move |e: MouseEvent| {
+                                    e.stop_propagation();
+                                    editing_label.set(Some(label.clone()));
+                                }
+                            },
+                            if version.is_labeled() { "重命名" } else { "命名" }
+                        }
                         button {
-                            class: "text-sm text-blue-600 dark:text-blue-400 hover:text-blue-500",
+                            class: "text-gray-600 dark:text-gray-400 hover:text-gray-900 dark:hover:text-white",
                             onclick: {
-                                let version_for_restore = version.clone();
+                                let version_id = version.id.clone();
+                                let pinned = version.pinned;
                                 move |e: MouseEvent| {
                                     e.stop_propagation();
-                                    on_restore.call(version_for_restore.clone());
+                                    on_update.call((version_id.clone(), UpdateVersionRequest { label: None, pinned: Some(!pinned) }));
                                 }
                             },
-                            "恢复"
+                            if version.pinned { "取消固定" } else { "固定" }
+                        }
+                        if !is_current {
+                            button {
+                                class: "text-blue-600 dark:text-blue-400 hover:text-blue-500",
+                                onclick: {
+                                    let version_for_restore = version.clone();
+                                    move |e: MouseEvent| {
+                                        e.stop_propagation();
+                                        on_restore.call(version_for_restore.clone());
+                                    }
+                                },
+                                "恢复"
+                            }
                         }
                     }
                 }
@@ -1,5 +1,27 @@
 pub mod use_auth;
 pub mod use_theme;
+pub mod use_embeds;
+pub mod use_collab;
+pub mod use_publication_role;
+pub mod use_reading_progress;
+pub mod use_offline;
+pub mod use_paywall;
+pub mod use_series;
+pub mod use_live_comments;
+pub mod use_mentions;
+pub mod use_notifications;
+pub mod use_user_relations;
 
 pub use use_auth::*;
-pub use use_theme::*;
\ No newline at end of file
+pub use use_theme::*;
+pub use use_embeds::*;
+pub use use_collab::*;
+pub use use_publication_role::*;
+pub use use_reading_progress::*;
+pub use use_offline::*;
+pub use use_paywall::*;
+pub use use_series::*;
+pub use use_live_comments::*;
+pub use use_mentions::*;
+pub use use_notifications::*;
+pub use use_user_relations::*;
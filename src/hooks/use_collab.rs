@@ -0,0 +1,456 @@
+use dioxus::prelude::*;
+use futures::StreamExt;
+use gloo_timers::future::TimeoutFuture;
+use std::collections::HashMap;
+use wasm_bindgen::JsCast;
+use web_sys::{window, HtmlTextAreaElement};
+use crate::{
+    api::realtime::RealtimeSocket,
+    collab::{self, ClientMessage, CursorAnchor, Op, Peer, ServerMessage, TextDoc},
+    models::article::Article,
+};
+
+/// 协作编辑的正文输入框 id，用于读取与恢复本地光标
+pub const COLLAB_TEXTAREA_ID: &str = "editor-content";
+
+// 断线重连的最长等待时间
+const MAX_RETRY_DELAY_MS: u32 = 30_000;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CollabStatus {
+    // 非出版物文章，不启用协作
+    Disabled,
+    Connecting,
+    Online,
+    // 离线期间的编辑会在重连后合并
+    Offline,
+}
+
+enum CollabEvent {
+    Connect(String),
+    Opened(u64),
+    Message(u64, String),
+    Closed(u64),
+    Reconnect(u64),
+}
+
+struct CollabSession {
+    article_id: Option<String>,
+    doc: TextDoc,
+    socket: Option<RealtimeSocket>,
+    // 尚未发出的本地操作；已发出的操作若随断线丢失，会在重连收到快照后补发
+    outbox: Vec<Op>,
+    // 当前连接是否已收到快照
+    ready: bool,
+    ever_synced: bool,
+    // 每次建立连接递增，用于丢弃旧连接的事件
+    generation: u64,
+    retries: u32,
+    last_cursor: Option<CursorAnchor>,
+    disposed: bool,
+    // 浏览器回调通过该通道把事件送回协程处理
+    events: Option<UnboundedSender<CollabEvent>>,
+}
+
+impl CollabSession {
+    fn doc_site(&self) -> u64 {
+        self.doc.site()
+    }
+
+    fn send(&self, message: &ClientMessage) -> bool {
+        match (&self.socket, serde_json::to_string(message)) {
+            (Some(socket), Ok(text)) if self.ready => socket.send(&text),
+            _ => false,
+        }
+    }
+
+    fn flush_outbox(&mut self) {
+        if self.outbox.is_empty() {
+            return;
+        }
+        let ops = std::mem::take(&mut self.outbox);
+        if !self.send(&ClientMessage::Ops { ops: ops.clone() }) {
+            self.outbox = ops;
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct Collab {
+    pub status: Signal<CollabStatus>,
+    pub peers: Signal<Vec<Peer>>,
+    pub cursors: Signal<HashMap<u64, CursorAnchor>>,
+    session: CopyValue<CollabSession>,
+}
+
+/// 出版物文章的实时协作：正文以 CRDT 同步，保存时仍写回 content 字段
+pub fn use_collab(article: Signal<Option<Article>>, content: Signal<String>) -> Collab {
+    let mut status = use_signal(|| CollabStatus::Disabled);
+    let mut peers = use_signal(Vec::<Peer>::new);
+    let mut cursors = use_signal(HashMap::<u64, CursorAnchor>::new);
+    let mut session = use_hook(|| {
+        CopyValue::new(CollabSession {
+            article_id: None,
+            doc: TextDoc::new(random_site()),
+            socket: None,
+            outbox: Vec::new(),
+            ready: false,
+            ever_synced: false,
+            generation: 0,
+            retries: 0,
+            last_cursor: None,
+            disposed: false,
+            events: None,
+        })
+    });
+
+    let events = use_coroutine(move |mut rx: UnboundedReceiver<CollabEvent>| async move {
+        while let Some(event) = rx.next().await {
+            match event {
+                CollabEvent::Connect(article_id) => {
+                    {
+                        let mut s = session.write();
+                        if let Some(socket) = s.socket.take() {
+                            socket.close();
+                        }
+                        s.doc = TextDoc::new(random_site());
+                        s.doc.apply_all(&TextDoc::seed_ops(&content.peek()));
+                        s.article_id = Some(article_id);
+                        s.outbox.clear();
+                        s.ever_synced = false;
+                        s.retries = 0;
+                    }
+                    open_socket(session, status);
+                }
+                CollabEvent::Opened(generation) => {
+                    let s = session.peek();
+                    if s.generation == generation {
+                        if let (Some(socket), Ok(text)) = (
+                            &s.socket,
+                            serde_json::to_string(&ClientMessage::Join { site: s.doc_site() }),
+                        ) {
+                            socket.send(&text);
+                        }
+                    }
+                }
+                CollabEvent::Message(generation, text) => {
+                    if session.peek().generation != generation {
+                        continue;
+                    }
+                    let Ok(message) = serde_json::from_str::<ServerMessage>(&text) else {
+                        continue;
+                    };
+                    let own_site = session.peek().doc_site();
+                    match message {
+                        ServerMessage::Snapshot { elements, peers: present } => {
+                            apply_remote(session, content, |s| {
+                                if !elements.is_empty() && !s.ever_synced && s.outbox.is_empty() {
+                                    // 首次连接且没有本地修改，直接采用服务器上的文档
+                                    let mut doc = TextDoc::new(s.doc_site());
+                                    doc.merge_snapshot(&elements);
+                                    s.doc = doc;
+                                } else {
+                                    s.doc.merge_snapshot(&elements);
+                                }
+                                // 服务器不确认收到的操作，断线前发出的可能已丢失：
+                                // 以快照为准补发本地有而服务器缺少的插入和删除（服务器没有文档时即完整上传）
+                                s.outbox = s.doc.ops_missing_from(&elements);
+                                s.ready = true;
+                                s.ever_synced = true;
+                                s.retries = 0;
+                                s.flush_outbox();
+                            });
+                            peers.set(present.into_iter().filter(|p| p.site != own_site).collect());
+                            cursors.write().clear();
+                            status.set(CollabStatus::Online);
+                        }
+                        ServerMessage::Ops { site, ops } => {
+                            if site != own_site {
+                                apply_remote(session, content, |s| s.doc.apply_all(&ops));
+                            }
+                        }
+                        ServerMessage::Presence { peers: present } => {
+                            peers.set(present.into_iter().filter(|p| p.site != own_site).collect());
+                        }
+                        ServerMessage::Cursor { site, cursor } => {
+                            if site != own_site {
+                                match cursor {
+                                    Some(cursor) => cursors.write().insert(site, cursor),
+                                    None => cursors.write().remove(&site),
+                                };
+                            }
+                        }
+                        ServerMessage::Left { site } => {
+                            peers.write().retain(|p| p.site != site);
+                            cursors.write().remove(&site);
+                        }
+                    }
+                }
+                CollabEvent::Closed(generation) => {
+                    let retries = {
+                        let mut s = session.write();
+                        if s.generation != generation || s.disposed {
+                            continue;
+                        }
+                        s.ready = false;
+                        s.socket = None;
+                        s.retries += 1;
+                        s.retries
+                    };
+                    status.set(CollabStatus::Offline);
+                    peers.write().clear();
+                    cursors.write().clear();
+                    schedule_reconnect(session, generation, retries);
+                }
+                CollabEvent::Reconnect(generation) => {
+                    let s = session.peek();
+                    if s.generation == generation && !s.disposed && s.socket.is_none() {
+                        drop(s);
+                        open_socket(session, status);
+                    }
+                }
+            }
+        }
+    });
+
+    use_hook(move || session.write().events = Some(events.tx()));
+
+    // 出版物文章加载后建立协作连接
+    use_effect(move || {
+        let Some(art) = article() else {
+            return;
+        };
+        if art.publication.is_none() {
+            return;
+        }
+        if session.peek().article_id.as_deref() != Some(art.id.as_str()) {
+            events.send(CollabEvent::Connect(art.id.clone()));
+        }
+    });
+
+    use_drop(move || {
+        let mut s = session.write();
+        s.disposed = true;
+        if let Some(socket) = s.socket.take() {
+            socket.close();
+        }
+    });
+
+    Collab { status, peers, cursors, session }
+}
+
+impl Collab {
+    pub fn is_active(&self) -> bool {
+        *self.status.peek() != CollabStatus::Disabled
+    }
+
+    /// 本地输入：转换为操作后广播，离线时暂存
+    pub fn local_edit(&mut self, new_text: &str) {
+        if !self.is_active() {
+            return;
+        }
+        let mut s = self.session.write();
+        let ops = s.doc.local_edit(new_text);
+        if ops.is_empty() {
+            return;
+        }
+        if !s.outbox.is_empty() || !s.send(&ClientMessage::Ops { ops: ops.clone() }) {
+            s.outbox.extend(ops);
+            s.flush_outbox();
+        }
+    }
+
+    /// 把本地光标同步给其他协作者
+    pub fn update_cursor(&mut self) {
+        if !self.is_active() {
+            return;
+        }
+        let mut s = self.session.write();
+        let cursor = textarea_selection().map(|(text, start, end)| CursorAnchor {
+            anchor: s.doc.anchor_at(collab::utf16_to_char_index(&text, start)),
+            head: s.doc.anchor_at(collab::utf16_to_char_index(&text, end)),
+        });
+        if s.last_cursor != cursor && s.send(&ClientMessage::Cursor { cursor }) {
+            s.last_cursor = cursor;
+        }
+    }
+
+    /// 协作者光标当前所在的行列
+    pub fn peer_positions(&self) -> Vec<(Peer, usize, usize)> {
+        let s = self.session.peek();
+        let text = s.doc.text();
+        let cursors = self.cursors.read();
+        self.peers
+            .read()
+            .iter()
+            .filter_map(|peer| {
+                let cursor = cursors.get(&peer.site)?;
+                let index = s.doc.index_of_anchor(cursor.head);
+                let (line, column) = collab::line_column(&text, index);
+                Some((peer.clone(), line, column))
+            })
+            .collect()
+    }
+
+    /// 将本地光标移动到某位协作者的位置
+    pub fn jump_to_peer(&self, site: u64) {
+        let Some(cursor) = self.cursors.peek().get(&site).copied() else {
+            return;
+        };
+        let s = self.session.peek();
+        let text = s.doc.text();
+        let index = collab::char_to_utf16_index(&text, s.doc.index_of_anchor(cursor.head));
+        if let Some(textarea) = textarea() {
+            textarea.focus().ok();
+            textarea.set_selection_range(index as u32, index as u32).ok();
+        }
+    }
+}
+
+fn open_socket(mut session: CopyValue<CollabSession>, mut status: Signal<CollabStatus>) {
+    let mut s = session.write();
+    let (Some(article_id), Some(tx)) = (s.article_id.clone(), s.events.clone()) else {
+        return;
+    };
+    s.generation += 1;
+    s.ready = false;
+    let generation = s.generation;
+
+    let path = format!(
+        "/blog/articles/by-id/{}/collab",
+        article_id.trim_start_matches("article:")
+    );
+    let (open_tx, message_tx, close_tx) = (tx.clone(), tx.clone(), tx);
+    s.socket = RealtimeSocket::connect(
+        &path,
+        move || {
+            open_tx.unbounded_send(CollabEvent::Opened(generation)).ok();
+        },
+        move |text| {
+            message_tx.unbounded_send(CollabEvent::Message(generation, text)).ok();
+        },
+        move |_| {
+            close_tx.unbounded_send(CollabEvent::Closed(generation)).ok();
+        },
+    );
+    if s.socket.is_none() {
+        // 浏览器拒绝建立连接（如地址无效），按断线处理并稍后重试
+        s.retries += 1;
+        let retries = s.retries;
+        drop(s);
+        status.set(CollabStatus::Offline);
+        schedule_reconnect(session, generation, retries);
+        return;
+    }
+    drop(s);
+
+    status.set(if *status.peek() == CollabStatus::Offline {
+        CollabStatus::Offline
+    } else {
+        CollabStatus::Connecting
+    });
+}
+
+// 指数退避重连
+fn schedule_reconnect(session: CopyValue<CollabSession>, generation: u64, retries: u32) {
+    let delay = (1000u32 << retries.min(5)).min(MAX_RETRY_DELAY_MS);
+    let tx = session.peek().events.clone();
+    spawn(async move {
+        TimeoutFuture::new(delay).await;
+        if let Some(tx) = tx {
+            tx.unbounded_send(CollabEvent::Reconnect(generation)).ok();
+        }
+    });
+}
+
+// 应用远端修改并刷新正文，同时保持本地光标落在原来的字符旁边
+fn apply_remote(
+    mut session: CopyValue<CollabSession>,
+    mut content: Signal<String>,
+    update: impl FnOnce(&mut CollabSession),
+) {
+    let mut s = session.write();
+    let focused = textarea_is_focused();
+    let selection = textarea_selection().map(|(text, start, end)| {
+        (
+            s.doc.anchor_at(collab::utf16_to_char_index(&text, start)),
+            s.doc.anchor_at(collab::utf16_to_char_index(&text, end)),
+        )
+    });
+
+    update(&mut s);
+
+    let text = s.doc.text();
+    if *content.peek() == text {
+        return;
+    }
+    let restore = selection.map(|(anchor, head)| {
+        (
+            collab::char_to_utf16_index(&text, s.doc.index_of_anchor(anchor)),
+            collab::char_to_utf16_index(&text, s.doc.index_of_anchor(head)),
+        )
+    });
+    drop(s);
+    content.set(text);
+
+    if let (true, Some((start, end))) = (focused, restore) {
+        spawn(async move {
+            // 等待输入框的值更新后再恢复选区
+            TimeoutFuture::new(0).await;
+            if let Some(textarea) = textarea() {
+                textarea.set_selection_range(start as u32, end as u32).ok();
+            }
+        });
+    }
+}
+
+fn textarea() -> Option<HtmlTextAreaElement> {
+    window()?
+        .document()?
+        .get_element_by_id(COLLAB_TEXTAREA_ID)?
+        .dyn_into::<HtmlTextAreaElement>()
+        .ok()
+}
+
+fn textarea_is_focused() -> bool {
+    let active = window()
+        .and_then(|w| w.document())
+        .and_then(|d| d.active_element());
+    matches!(active, Some(element) if element.id() == COLLAB_TEXTAREA_ID)
+}
+
+// (输入框文本, 选区起点, 选区终点)，以 UTF-16 码元计
+fn textarea_selection() -> Option<(String, usize, usize)> {
+    let textarea = textarea()?;
+    let start = textarea.selection_start().ok()??;
+    let end = textarea.selection_end().ok()??;
+    Some((textarea.value(), start as usize, end as usize))
+}
+
+/// 正文输入框当前选区（字符下标）
+pub fn editor_selection() -> Option<(usize, usize)> {
+    let (text, start, end) = textarea_selection()?;
+    Some((
+        collab::utf16_to_char_index(&text, start),
+        collab::utf16_to_char_index(&text, end),
+    ))
+}
+
+/// 在正文输入框中选中 [start, end) 字符区间
+pub fn select_in_editor(start: usize, end: usize) {
+    if let Some(textarea) = textarea() {
+        let text = textarea.value();
+        textarea.focus().ok();
+        textarea
+            .set_selection_range(
+                collab::char_to_utf16_index(&text, start) as u32,
+                collab::char_to_utf16_index(&text, end) as u32,
+            )
+            .ok();
+    }
+}
+
+fn random_site() -> u64 {
+    // 0 保留给由 content 生成的种子操作
+    (js_sys::Math::random() * (u32::MAX as f64)) as u64 + 1
+}
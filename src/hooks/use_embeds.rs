@@ -0,0 +1,166 @@
+use dioxus::prelude::*;
+use gloo_timers::future::TimeoutFuture;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{window, Element};
+use crate::{
+    api::embeds::EmbedService,
+    models::embed::LinkPreview,
+    rendering::math::escape_xml,
+};
+
+thread_local! {
+    static CLICK_TO_LOAD_INSTALLED: Cell<bool> = const { Cell::new(false) };
+    // 同一会话内已抓取过的链接预览
+    static PREVIEW_CACHE: RefCell<HashMap<String, Option<LinkPreview>>> = RefCell::new(HashMap::new());
+}
+
+/// 渲染后的正文变化时激活其中的嵌入卡片：点击加载媒体、补全链接预览
+pub fn use_embeds(html: Memo<String>) {
+    use_effect(move || {
+        if html.read().is_empty() {
+            return;
+        }
+        spawn(async move {
+            // 等待 dangerous_inner_html 写入 DOM
+            TimeoutFuture::new(0).await;
+            install_click_to_load();
+            unfurl_link_cards().await;
+        });
+    });
+}
+
+// 在 document 上安装一次点击委托，把占位卡片替换为 iframe
+fn install_click_to_load() {
+    if CLICK_TO_LOAD_INSTALLED.with(|installed| installed.replace(true)) {
+        return;
+    }
+    let Some(document) = window().and_then(|w| w.document()) else {
+        return;
+    };
+
+    let handler = Closure::<dyn FnMut(web_sys::Event)>::new(move |event: web_sys::Event| {
+        let Some(target) = event.target().and_then(|t| t.dyn_into::<Element>().ok()) else {
+            return;
+        };
+        let Ok(Some(button)) = target.closest(".embed-load") else {
+            return;
+        };
+        let Ok(Some(card)) = button.closest(".embed-card") else {
+            return;
+        };
+        event.prevent_default();
+        load_embed(&card);
+    });
+
+    document
+        .add_event_listener_with_callback("click", handler.as_ref().unchecked_ref())
+        .ok();
+    handler.forget();
+}
+
+fn load_embed(card: &Element) {
+    let Some(document) = window().and_then(|w| w.document()) else {
+        return;
+    };
+    let Ok(iframe) = document.create_element("iframe") else {
+        return;
+    };
+
+    if let Some(src) = card.get_attribute("data-embed-src") {
+        iframe.set_attribute("src", &src).ok();
+        iframe
+            .set_attribute("allow", "autoplay; encrypted-media; picture-in-picture; fullscreen")
+            .ok();
+        iframe.set_attribute("allowfullscreen", "true").ok();
+    } else if let Some(srcdoc) = card.get_attribute("data-embed-srcdoc") {
+        iframe.set_attribute("srcdoc", &srcdoc).ok();
+        iframe.set_attribute("sandbox", "allow-scripts allow-popups").ok();
+    } else {
+        return;
+    }
+
+    let provider = card.get_attribute("data-embed-provider").unwrap_or_default();
+    iframe.set_attribute("title", &provider).ok();
+    iframe.set_attribute("loading", "lazy").ok();
+    iframe.set_attribute("referrerpolicy", "no-referrer").ok();
+    iframe.set_class_name("embed-frame");
+
+    card.set_inner_html("");
+    card.append_child(&iframe).ok();
+    card.class_list().add_1("embed-loaded").ok();
+}
+
+// 为尚未补全的链接卡片请求 OpenGraph 元数据
+async fn unfurl_link_cards() {
+    let Some(document) = window().and_then(|w| w.document()) else {
+        return;
+    };
+    let Ok(nodes) = document.query_selector_all("[data-unfurl-url]:not([data-unfurled])") else {
+        return;
+    };
+
+    let mut cards = Vec::new();
+    for i in 0..nodes.length() {
+        if let Some(card) = nodes.item(i).and_then(|n| n.dyn_into::<Element>().ok()) {
+            card.set_attribute("data-unfurled", "pending").ok();
+            cards.push(card);
+        }
+    }
+
+    for card in cards {
+        let Some(url) = card.get_attribute("data-unfurl-url") else {
+            continue;
+        };
+
+        let cached = PREVIEW_CACHE.with(|cache| cache.borrow().get(&url).cloned());
+        let preview = match cached {
+            Some(preview) => preview,
+            None => {
+                let preview = EmbedService::unfurl(&url).await.ok();
+                PREVIEW_CACHE.with(|cache| cache.borrow_mut().insert(url.clone(), preview.clone()));
+                preview
+            }
+        };
+
+        if let Some(preview) = preview {
+            card.set_inner_html(&link_card_html(&preview));
+        }
+        card.set_attribute("data-unfurled", "done").ok();
+    }
+}
+
+fn link_card_html(preview: &LinkPreview) -> String {
+    let site = preview
+        .site_name
+        .clone()
+        .unwrap_or_else(|| preview.url.clone());
+    let title = preview.title.clone().unwrap_or_else(|| preview.url.clone());
+
+    let mut html = String::from("<span class=\"link-card-body\">");
+    html.push_str("<span class=\"link-card-site\">");
+    if let Some(favicon) = &preview.favicon_url {
+        html.push_str(&format!(
+            "<img class=\"link-card-favicon\" src=\"{}\" alt=\"\" referrerpolicy=\"no-referrer\">",
+            escape_xml(favicon)
+        ));
+    }
+    html.push_str(&escape_xml(&site));
+    html.push_str("</span>");
+    html.push_str(&format!("<span class=\"link-card-title\">{}</span>", escape_xml(&title)));
+    if let Some(description) = &preview.description {
+        html.push_str(&format!(
+            "<span class=\"link-card-description\">{}</span>",
+            escape_xml(description)
+        ));
+    }
+    html.push_str("</span>");
+    if let Some(image) = &preview.image_url {
+        html.push_str(&format!(
+            "<img class=\"link-card-image\" src=\"{}\" alt=\"\" loading=\"lazy\" referrerpolicy=\"no-referrer\">",
+            escape_xml(image)
+        ));
+    }
+    html
+}
@@ -0,0 +1,391 @@
+use dioxus::prelude::*;
+use chrono::{DateTime, Duration, Utc};
+use futures::StreamExt;
+use gloo_timers::future::TimeoutFuture;
+use crate::{
+    api::{comments::CommentService, realtime::RealtimeSocket},
+    models::comment::{
+        append_comments, contains_comment, find_comment_mut, insert_comment, merge_updates,
+        remove_comment, unknown_comments, CommentEvent, CommentSort, CommentWithAuthor,
+    },
+};
+
+// 连续断线这么多次后改为轮询
+const MAX_SOCKET_RETRIES: u32 = 3;
+const POLL_INTERVAL_MS: u32 = 20_000;
+const COMMENTS_PER_PAGE: i32 = 20;
+const REPLIES_PER_PAGE: i32 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LiveStatus {
+    Connecting,
+    // WebSocket 实时推送
+    Live,
+    // 无法建立 WebSocket 时定时拉取
+    Polling,
+}
+
+enum LiveEvent {
+    Opened(u64),
+    Message(u64, String),
+    Closed(u64),
+}
+
+struct LiveSession {
+    socket: Option<RealtimeSocket>,
+    // 每次建立连接递增，用于丢弃旧连接的事件
+    generation: u64,
+    retries: u32,
+    disposed: bool,
+    events: Option<UnboundedSender<LiveEvent>>,
+}
+
+/// 文章评论的实时状态：已显示的评论树（顶级评论分页加载），以及收到但还没显示的新评论
+#[derive(Clone, Copy, PartialEq)]
+pub struct LiveComments {
+    pub comments: Signal<Vec<CommentWithAuthor>>,
+    // 新评论先放在这里，读者点击提示后再插入，避免内容跳动
+    pub pending: Signal<Vec<CommentWithAuthor>>,
+    pub loading: Signal<bool>,
+    pub status: Signal<LiveStatus>,
+    pub sort: Signal<CommentSort>,
+    // 顶级评论总数和是否还有下一页
+    pub total: Signal<i64>,
+    pub has_more: Signal<bool>,
+    pub loading_more: Signal<bool>,
+    page: CopyValue<i32>,
+    // 上次拉取评论的时间，轮询时只取这之后的变化
+    synced_at: CopyValue<DateTime<Utc>>,
+    article_id: CopyValue<String>,
+}
+
+/// 订阅文章评论的新增、编辑、删除和点赞数变化，WebSocket 不可用时退回轮询
+pub fn use_live_comments(article_id: String) -> LiveComments {
+    let comments = use_signal(Vec::<CommentWithAuthor>::new);
+    let pending = use_signal(Vec::<CommentWithAuthor>::new);
+    let loading = use_signal(|| true);
+    let mut status = use_signal(|| LiveStatus::Connecting);
+    let sort = use_signal(|| CommentSort::Top);
+    let total = use_signal(|| 0);
+    let has_more = use_signal(|| false);
+    let loading_more = use_signal(|| false);
+    let page = use_hook(|| CopyValue::new(1));
+    let synced_at = use_hook(|| CopyValue::new(Utc::now()));
+    let article_id = use_hook(|| CopyValue::new(article_id));
+    let mut session = use_hook(|| {
+        CopyValue::new(LiveSession {
+            socket: None,
+            generation: 0,
+            retries: 0,
+            disposed: false,
+            events: None,
+        })
+    });
+
+    let mut live = LiveComments {
+        comments,
+        pending,
+        loading,
+        status,
+        sort,
+        total,
+        has_more,
+        loading_more,
+        page,
+        synced_at,
+        article_id,
+    };
+
+    let events = use_coroutine(move |mut rx: UnboundedReceiver<LiveEvent>| async move {
+        while let Some(event) = rx.next().await {
+            match event {
+                LiveEvent::Opened(generation) => {
+                    if session.peek().generation == generation {
+                        session.write().retries = 0;
+                        status.set(LiveStatus::Live);
+                    }
+                }
+                LiveEvent::Message(generation, text) => {
+                    if session.peek().generation != generation {
+                        continue;
+                    }
+                    if let Ok(event) = serde_json::from_str::<CommentEvent>(&text) {
+                        live.apply(event);
+                    }
+                }
+                LiveEvent::Closed(generation) => {
+                    let retries = {
+                        let mut s = session.write();
+                        if s.generation != generation || s.disposed {
+                            continue;
+                        }
+                        s.socket = None;
+                        s.retries += 1;
+                        s.retries
+                    };
+                    if retries >= MAX_SOCKET_RETRIES {
+                        status.set(LiveStatus::Polling);
+                        continue;
+                    }
+                    status.set(LiveStatus::Connecting);
+                    TimeoutFuture::new(1000 << retries).await;
+                    // 断线期间可能漏掉推送，重连前补拉一次
+                    live.refresh().await;
+                    open_socket(session, status, &live.article_id.peek());
+                }
+            }
+        }
+    });
+
+    use_hook(move || {
+        session.write().events = Some(events.tx());
+        live.reload();
+        open_socket(session, status, &article_id.peek());
+    });
+
+    // 轮询模式下定时拉取评论
+    use_future(move || async move {
+        loop {
+            TimeoutFuture::new(POLL_INTERVAL_MS).await;
+            if *status.peek() == LiveStatus::Polling {
+                live.refresh().await;
+            }
+        }
+    });
+
+    use_drop(move || {
+        let mut s = session.write();
+        s.disposed = true;
+        if let Some(socket) = s.socket.take() {
+            socket.close();
+        }
+    });
+
+    live
+}
+
+impl LiveComments {
+    /// 从第一页重新加载评论（显示加载状态），用于首次加载、切换排序和自己发表评论之后
+    pub fn reload(&self) {
+        let mut live = *self;
+        spawn(async move {
+            live.loading.set(true);
+            let sort = *live.sort.peek();
+            let requested_at = Utc::now();
+            if let Ok(response) = CommentService::get_article_comments(
+                &live.article_id.peek(),
+                Some(1),
+                Some(COMMENTS_PER_PAGE),
+                Some(sort.as_str()),
+            )
+            .await
+            {
+                live.pending.write().retain(|c| !contains_comment(&response.comments, &c.comment.id));
+                live.comments.set(response.comments);
+                live.total.set(response.total);
+                live.has_more.set(response.has_next);
+                live.page.set(1);
+                live.synced_at.set(requested_at);
+            }
+            live.loading.set(false);
+        });
+    }
+
+    pub fn set_sort(&mut self, sort: CommentSort) {
+        if *self.sort.peek() != sort {
+            self.sort.set(sort);
+            self.reload();
+        }
+    }
+
+    /// 加载下一页顶级评论
+    pub fn load_more(&self) {
+        let mut live = *self;
+        if *live.loading_more.peek() || !*live.has_more.peek() {
+            return;
+        }
+        spawn(async move {
+            live.loading_more.set(true);
+            let next = *live.page.peek() + 1;
+            let sort = *live.sort.peek();
+            if let Ok(response) = CommentService::get_article_comments(
+                &live.article_id.peek(),
+                Some(next),
+                Some(COMMENTS_PER_PAGE),
+                Some(sort.as_str()),
+            )
+            .await
+            {
+                append_comments(&mut live.comments.write(), response.comments);
+                live.total.set(response.total);
+                live.has_more.set(response.has_next);
+                live.page.set(next);
+            }
+            live.loading_more.set(false);
+        });
+    }
+
+    /// 加载某条评论的下一页回复
+    pub async fn load_replies(&mut self, comment_id: String) -> Result<(), String> {
+        let loaded = find_comment_mut(&mut self.comments.write(), &comment_id)
+            .map(|c| c.replies.len() as i32)
+            .unwrap_or(0);
+        let next = loaded / REPLIES_PER_PAGE + 1;
+        let response = CommentService::get_comment_replies(&comment_id, Some(next), Some(REPLIES_PER_PAGE))
+            .await
+            .map_err(|e| e.message)?;
+        if let Some(parent) = find_comment_mut(&mut self.comments.write(), &comment_id) {
+            append_comments(&mut parent.replies, response.comments);
+            parent.reply_count = response.total.max(parent.replies.len() as i64);
+        }
+        Ok(())
+    }
+
+    /// 确保某条评论出现在评论树中（固定链接指向不在已加载页中的评论时使用）
+    pub async fn reveal(&mut self, comment_id: &str) -> bool {
+        if contains_comment(&self.comments.peek(), comment_id) {
+            return true;
+        }
+        let Ok(thread) = CommentService::get_comment_thread(comment_id).await else {
+            return false;
+        };
+        let mut comments = self.comments.write();
+        match find_comment_mut(&mut comments, &thread.comment.id) {
+            // 顶级评论已加载，只是回复还没展开
+            Some(existing) => merge_path(existing, thread),
+            None => comments.insert(0, thread),
+        }
+        contains_comment(&comments, comment_id)
+    }
+
+    /// 静默拉取上次同步之后的变化（含回复）：更新已显示评论的内容，新评论并入待显示列表
+    async fn refresh(&mut self) {
+        // 多取一分钟，避免客户端与服务器的时钟误差漏掉评论，重复的评论会被跳过
+        let since = *self.synced_at.peek() - Duration::minutes(1);
+        let requested_at = Utc::now();
+        let Ok(response) = CommentService::get_article_comments_since(&self.article_id.peek(), since).await else {
+            return;
+        };
+        self.synced_at.set(requested_at);
+        merge_updates(&mut self.comments.write(), &response.comments);
+        let shown = self.comments.peek().clone();
+        // 与推送收到的新评论合并，不能覆盖
+        let mut pending = self.pending.write();
+        for fresh in unknown_comments(&response.comments, &shown) {
+            let existing = pending.iter().position(|c| c.comment.id == fresh.comment.id);
+            match existing {
+                Some(index) if fresh.comment.is_deleted => {
+                    pending.remove(index);
+                }
+                Some(index) => pending[index].comment = fresh.comment,
+                None if !fresh.comment.is_deleted => pending.push(fresh),
+                None => {}
+            }
+        }
+    }
+
+    fn apply(&mut self, event: CommentEvent) {
+        match event {
+            CommentEvent::Created { comment } => {
+                let id = comment.comment.id.clone();
+                if !contains_comment(&self.comments.peek(), &id)
+                    && !self.pending.peek().iter().any(|c| c.comment.id == id)
+                {
+                    self.pending.write().push(CommentWithAuthor { replies: Vec::new(), ..comment });
+                }
+            }
+            CommentEvent::Updated { comment } => {
+                if let Some(existing) = find_comment_mut(&mut self.comments.write(), &comment.id) {
+                    existing.comment = comment;
+                } else if let Some(existing) = self.pending.write().iter_mut().find(|c| c.comment.id == comment.id) {
+                    existing.comment = comment;
+                }
+            }
+            CommentEvent::Deleted { comment_id } => {
+                self.pending.write().retain(|c| c.comment.id != comment_id);
+                remove_comment(&mut self.comments.write(), &comment_id);
+            }
+            CommentEvent::Clapped { comment_id, clap_count } => {
+                if let Some(existing) = find_comment_mut(&mut self.comments.write(), &comment_id) {
+                    existing.comment.clap_count = clap_count;
+                }
+            }
+        }
+    }
+
+    /// 把待显示的新评论插入评论树
+    pub fn show_pending(&mut self) {
+        let fresh = std::mem::take(&mut *self.pending.write());
+        // 按最早排序时新评论属于最后一页，还有下一页时等翻页再显示
+        let oldest_first = *self.sort.peek() == CommentSort::Oldest;
+        if oldest_first && *self.has_more.peek() {
+            let mut comments = self.comments.write();
+            for comment in fresh.into_iter().filter(|c| c.comment.parent_id.is_some()) {
+                insert_comment(&mut comments, comment, false);
+            }
+            return;
+        }
+        let mut comments = self.comments.write();
+        for comment in fresh {
+            // 父评论不在已加载的评论中时丢弃，展开回复时会加载
+            insert_comment(&mut comments, comment, oldest_first);
+        }
+    }
+
+    /// 本地点赞或取消点赞后更新评论树
+    pub fn set_clapped(&mut self, comment_id: &str, clap_count: i64, user_has_clapped: bool) {
+        if let Some(existing) = find_comment_mut(&mut self.comments.write(), comment_id) {
+            existing.comment.clap_count = clap_count;
+            existing.user_has_clapped = user_has_clapped;
+        }
+    }
+
+    /// 待显示新评论中有多少是回复
+    pub fn pending_replies(&self) -> usize {
+        self.pending.read().iter().filter(|c| c.comment.parent_id.is_some()).count()
+    }
+}
+
+fn open_socket(mut session: CopyValue<LiveSession>, mut status: Signal<LiveStatus>, article_id: &str) {
+    let mut s = session.write();
+    let Some(tx) = s.events.clone() else {
+        return;
+    };
+    s.generation += 1;
+    let generation = s.generation;
+
+    let path = format!(
+        "/blog/comments/article/{}/live",
+        article_id.trim_start_matches("article:")
+    );
+    let (open_tx, message_tx, close_tx) = (tx.clone(), tx.clone(), tx);
+    s.socket = RealtimeSocket::connect(
+        &path,
+        move || {
+            open_tx.unbounded_send(LiveEvent::Opened(generation)).ok();
+        },
+        move |text| {
+            message_tx.unbounded_send(LiveEvent::Message(generation, text)).ok();
+        },
+        move |_| {
+            close_tx.unbounded_send(LiveEvent::Closed(generation)).ok();
+        },
+    );
+    let unsupported = s.socket.is_none();
+    drop(s);
+
+    // 浏览器无法创建 WebSocket 时直接轮询
+    if unsupported {
+        status.set(LiveStatus::Polling);
+    }
+}
+
+// 把 get_comment_thread 返回的路径合并进已加载的评论
+fn merge_path(existing: &mut CommentWithAuthor, path: CommentWithAuthor) {
+    for reply in path.replies {
+        match existing.replies.iter_mut().find(|r| r.comment.id == reply.comment.id) {
+            Some(child) => merge_path(child, reply),
+            None => existing.replies.push(reply),
+        }
+    }
+}
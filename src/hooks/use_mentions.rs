@@ -0,0 +1,152 @@
+use dioxus::prelude::*;
+use gloo_timers::future::TimeoutFuture;
+use wasm_bindgen::JsCast;
+use web_sys::{window, HtmlTextAreaElement};
+use crate::{
+    api::users::UserService,
+    collab,
+    models::mention::{insert_mention, mention_query_at, MentionCandidate},
+};
+
+// 停止输入这么久后再搜索
+const SEARCH_DEBOUNCE_MS: u32 = 200;
+const MAX_CANDIDATES: i32 = 6;
+
+/// 输入框中的 @提及自动补全
+#[derive(Clone, Copy, PartialEq)]
+pub struct Mentions {
+    // 正在输入的提及：@ 的位置（字符下标）和已输入的前缀
+    pub query: Signal<Option<(usize, String)>>,
+    pub candidates: Signal<Vec<MentionCandidate>>,
+    pub active: Signal<usize>,
+    textarea_id: CopyValue<String>,
+    // 每次搜索递增，丢弃过期的结果
+    generation: CopyValue<u64>,
+}
+
+pub fn use_mentions(textarea_id: String) -> Mentions {
+    let query = use_signal(|| None::<(usize, String)>);
+    let candidates = use_signal(Vec::<MentionCandidate>::new);
+    let active = use_signal(|| 0usize);
+    let textarea_id = use_hook(|| CopyValue::new(textarea_id));
+    let generation = use_hook(|| CopyValue::new(0u64));
+    Mentions { query, candidates, active, textarea_id, generation }
+}
+
+impl Mentions {
+    pub fn is_open(&self) -> bool {
+        self.query.peek().is_some() && !self.candidates.peek().is_empty()
+    }
+
+    pub fn close(&mut self) {
+        self.query.set(None);
+        self.candidates.set(Vec::new());
+    }
+
+    /// 输入或移动光标后调用：光标处是 @前缀时搜索候选用户
+    pub fn update(&mut self, text: &str) {
+        let found = self.cursor(text).and_then(|cursor| mention_query_at(text, cursor));
+        let Some((at, prefix)) = found else {
+            if self.query.peek().is_some() {
+                self.close();
+            }
+            return;
+        };
+        if self.query.peek().as_ref() == Some(&(at, prefix.clone())) {
+            return;
+        }
+        self.query.set(Some((at, prefix.clone())));
+
+        let generation = *self.generation.peek() + 1;
+        self.generation.set(generation);
+        let mut this = *self;
+        spawn(async move {
+            TimeoutFuture::new(SEARCH_DEBOUNCE_MS).await;
+            if *this.generation.peek() != generation {
+                return;
+            }
+            let results = if prefix.is_empty() {
+                Vec::new()
+            } else {
+                UserService::search_mention_candidates(&prefix, Some(MAX_CANDIDATES))
+                    .await
+                    .unwrap_or_default()
+            };
+            if *this.generation.peek() == generation {
+                this.candidates.set(results);
+                this.active.set(0);
+            }
+        });
+    }
+
+    /// 处理方向键、回车、Tab 和 Esc；选中用户时返回替换后的文本
+    pub fn on_keydown(&mut self, evt: &KeyboardEvent, text: &str) -> Option<String> {
+        if !self.is_open() {
+            return None;
+        }
+        let count = self.candidates.peek().len();
+        match evt.key() {
+            Key::ArrowDown => {
+                evt.prevent_default();
+                let next = (*self.active.peek() + 1) % count;
+                self.active.set(next);
+                None
+            }
+            Key::ArrowUp => {
+                evt.prevent_default();
+                let next = (*self.active.peek() + count - 1) % count;
+                self.active.set(next);
+                None
+            }
+            Key::Enter | Key::Tab => {
+                let candidate = self.candidates.peek().get(*self.active.peek()).cloned()?;
+                if !candidate.can_mention {
+                    return None;
+                }
+                evt.prevent_default();
+                self.select(&candidate, text)
+            }
+            Key::Escape => {
+                evt.prevent_default();
+                self.close();
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// 插入选中的用户名，返回新文本并把光标移到用户名之后
+    pub fn select(&mut self, candidate: &MentionCandidate, text: &str) -> Option<String> {
+        let (at, _) = self.query.peek().clone()?;
+        let cursor = self.cursor(text).unwrap_or(at);
+        let (updated, caret) = insert_mention(text, at, cursor, &candidate.username);
+        self.close();
+
+        let textarea_id = self.textarea_id.peek().clone();
+        let caret_utf16 = collab::char_to_utf16_index(&updated, caret) as u32;
+        spawn(async move {
+            // 等待输入框的值更新后再移动光标
+            TimeoutFuture::new(0).await;
+            if let Some(textarea) = textarea(&textarea_id) {
+                textarea.focus().ok();
+                textarea.set_selection_range(caret_utf16, caret_utf16).ok();
+            }
+        });
+        Some(updated)
+    }
+
+    // 输入框光标位置（字符下标）
+    fn cursor(&self, text: &str) -> Option<usize> {
+        let textarea = textarea(&self.textarea_id.peek())?;
+        let position = textarea.selection_start().ok()??;
+        Some(collab::utf16_to_char_index(text, position as usize))
+    }
+}
+
+fn textarea(id: &str) -> Option<HtmlTextAreaElement> {
+    window()?
+        .document()?
+        .get_element_by_id(id)?
+        .dyn_into::<HtmlTextAreaElement>()
+        .ok()
+}
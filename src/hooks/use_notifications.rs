@@ -0,0 +1,264 @@
+use dioxus::prelude::*;
+use futures::StreamExt;
+use gloo_timers::future::TimeoutFuture;
+use crate::{
+    api::{
+        notifications::{NotificationService, NOTIFICATION_SOCKET_PATH},
+        realtime::RealtimeSocket,
+    },
+    hooks::{AuthState, LiveStatus},
+    models::notification::{mark_read_local, merge_notification, Notification, NotificationEvent},
+};
+
+// 连续断线这么多次后改为轮询
+const MAX_SOCKET_RETRIES: u32 = 3;
+const POLL_INTERVAL_MS: u32 = 30_000;
+// 下拉框中显示的最近通知数
+const RECENT_COUNT: i32 = 8;
+
+enum NotificationSocketEvent {
+    Opened(u64),
+    Message(u64, String),
+    Closed(u64),
+}
+
+struct NotificationSession {
+    socket: Option<RealtimeSocket>,
+    // 每次建立连接递增，用于丢弃旧连接的事件
+    generation: u64,
+    retries: u32,
+    // 已登录并在接收通知
+    active: bool,
+    events: Option<UnboundedSender<NotificationSocketEvent>>,
+}
+
+/// 当前用户的通知：未读数和最近几条通知，登录后通过 WebSocket 实时更新
+#[derive(Clone, Copy, PartialEq)]
+pub struct Notifications {
+    pub recent: Signal<Vec<Notification>>,
+    pub unread: Signal<i64>,
+    pub status: Signal<LiveStatus>,
+    // 最近一次推送来的通知，通知页据此实时插入新通知
+    pub latest: Signal<Option<Notification>>,
+    pub loading: Signal<bool>,
+}
+
+pub fn use_notifications() -> Notifications {
+    use_context::<Notifications>()
+}
+
+/// 在应用根组件中调用，登录后连接通知推送，退出登录后断开
+pub fn use_provide_notifications(auth: Signal<AuthState>) -> Notifications {
+    let recent = use_signal(Vec::<Notification>::new);
+    let unread = use_signal(|| 0);
+    let mut status = use_signal(|| LiveStatus::Connecting);
+    let latest = use_signal(|| None::<Notification>);
+    let loading = use_signal(|| false);
+    let mut session = use_hook(|| {
+        CopyValue::new(NotificationSession {
+            socket: None,
+            generation: 0,
+            retries: 0,
+            active: false,
+            events: None,
+        })
+    });
+
+    let mut notifications = use_context_provider(|| Notifications {
+        recent,
+        unread,
+        status,
+        latest,
+        loading,
+    });
+
+    let events = use_coroutine(move |mut rx: UnboundedReceiver<NotificationSocketEvent>| async move {
+        while let Some(event) = rx.next().await {
+            match event {
+                NotificationSocketEvent::Opened(generation) => {
+                    if session.peek().generation == generation {
+                        session.write().retries = 0;
+                        status.set(LiveStatus::Live);
+                    }
+                }
+                NotificationSocketEvent::Message(generation, text) => {
+                    if session.peek().generation != generation {
+                        continue;
+                    }
+                    if let Ok(event) = serde_json::from_str::<NotificationEvent>(&text) {
+                        notifications.apply(event);
+                    }
+                }
+                NotificationSocketEvent::Closed(generation) => {
+                    let retries = {
+                        let mut s = session.write();
+                        if s.generation != generation || !s.active {
+                            continue;
+                        }
+                        s.socket = None;
+                        s.retries += 1;
+                        s.retries
+                    };
+                    if retries >= MAX_SOCKET_RETRIES {
+                        status.set(LiveStatus::Polling);
+                        continue;
+                    }
+                    status.set(LiveStatus::Connecting);
+                    TimeoutFuture::new(1000 << retries).await;
+                    if !session.peek().active {
+                        continue;
+                    }
+                    // 断线期间可能漏掉推送，重连前补拉一次
+                    notifications.refresh().await;
+                    open_socket(session, status);
+                }
+            }
+        }
+    });
+
+    use_hook(move || {
+        session.write().events = Some(events.tx());
+    });
+
+    // 登录状态变化时连接或断开
+    use_effect(move || {
+        let authenticated = auth.read().is_authenticated;
+        let active = session.peek().active;
+        if authenticated && !active {
+            {
+                let mut s = session.write();
+                s.active = true;
+                s.retries = 0;
+            }
+            spawn(async move {
+                notifications.loading.set(true);
+                notifications.refresh().await;
+                notifications.loading.set(false);
+            });
+            open_socket(session, status);
+        } else if !authenticated && active {
+            close_socket(session);
+            notifications.recent.set(Vec::new());
+            notifications.unread.set(0);
+            notifications.latest.set(None);
+            status.set(LiveStatus::Connecting);
+        }
+    });
+
+    // 轮询模式下定时拉取
+    use_future(move || async move {
+        loop {
+            TimeoutFuture::new(POLL_INTERVAL_MS).await;
+            if session.peek().active && *status.peek() == LiveStatus::Polling {
+                notifications.refresh().await;
+            }
+        }
+    });
+
+    use_drop(move || close_socket(session));
+
+    notifications
+}
+
+impl Notifications {
+    /// 重新拉取未读数和最近通知
+    pub async fn refresh(&mut self) {
+        let Ok(response) = NotificationService::get_notifications(None, false, Some(1), Some(RECENT_COUNT)).await
+        else {
+            return;
+        };
+        self.unread.set(response.unread_count);
+        self.recent.set(response.notifications);
+    }
+
+    fn apply(&mut self, event: NotificationEvent) {
+        match event {
+            NotificationEvent::Created { notification, unread_count } => {
+                self.unread.set(unread_count);
+                {
+                    let mut recent = self.recent.write();
+                    merge_notification(&mut recent, notification.clone());
+                    recent.truncate(RECENT_COUNT as usize);
+                }
+                self.latest.set(Some(notification));
+            }
+            NotificationEvent::Read { notification_ids, unread_count } => {
+                self.unread.set(unread_count);
+                mark_read_local(&mut self.recent.write(), &notification_ids);
+            }
+            NotificationEvent::AllRead => self.mark_all_read_local(),
+        }
+    }
+
+    /// 标记一条未读通知为已读，先更新本地再请求服务端
+    pub fn mark_read(&self, id: &str) {
+        let mut notifications = *self;
+        let ids = vec![id.to_string()];
+        mark_read_local(&mut notifications.recent.write(), &ids);
+        // 通知可能不在最近列表中，以服务端返回的未读数为准
+        let count = *notifications.unread.peek();
+        notifications.unread.set((count - 1).max(0));
+        spawn(async move {
+            if let Ok(response) = NotificationService::mark_read(ids).await {
+                notifications.unread.set(response.unread_count);
+            }
+        });
+    }
+
+    pub fn mark_all_read(&self) {
+        let mut notifications = *self;
+        notifications.mark_all_read_local();
+        spawn(async move {
+            if NotificationService::mark_all_read().await.is_err() {
+                notifications.refresh().await;
+            }
+        });
+    }
+
+    fn mark_all_read_local(&mut self) {
+        for notification in self.recent.write().iter_mut() {
+            notification.is_read = true;
+        }
+        self.unread.set(0);
+    }
+}
+
+fn open_socket(mut session: CopyValue<NotificationSession>, mut status: Signal<LiveStatus>) {
+    let mut s = session.write();
+    let Some(tx) = s.events.clone() else {
+        return;
+    };
+    s.generation += 1;
+    let generation = s.generation;
+
+    let (open_tx, message_tx, close_tx) = (tx.clone(), tx.clone(), tx);
+    s.socket = RealtimeSocket::connect(
+        NOTIFICATION_SOCKET_PATH,
+        move || {
+            open_tx.unbounded_send(NotificationSocketEvent::Opened(generation)).ok();
+        },
+        move |text| {
+            message_tx.unbounded_send(NotificationSocketEvent::Message(generation, text)).ok();
+        },
+        move |_| {
+            close_tx.unbounded_send(NotificationSocketEvent::Closed(generation)).ok();
+        },
+    );
+    let unsupported = s.socket.is_none();
+    drop(s);
+
+    // 浏览器无法创建 WebSocket 时直接轮询
+    if unsupported {
+        status.set(LiveStatus::Polling);
+    }
+}
+
+fn close_socket(mut session: CopyValue<NotificationSession>) {
+    let mut s = session.write();
+    s.active = false;
+    // 让已关闭连接的事件失效
+    s.generation += 1;
+    if let Some(socket) = s.socket.take() {
+        socket.close();
+    }
+}
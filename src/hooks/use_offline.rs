@@ -0,0 +1,261 @@
+use dioxus::prelude::*;
+use chrono::Utc;
+use gloo_storage::{LocalStorage, Storage};
+use js_sys::Promise;
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{window, Cache, RequestInit, RequestMode, Response};
+use crate::models::{
+    article::Article,
+    offline::OfflineArticle,
+};
+
+// 与 sw.js 中的 OFFLINE_CACHE 保持一致，service worker 从这里取离线图片
+const OFFLINE_CACHE: &str = "rainbow-offline-v1";
+const SERVICE_WORKER_URL: &str = "/sw.js";
+const INDEX_KEY: &str = "offline_articles";
+const LIMIT_KEY: &str = "offline_storage_limit_mb";
+const DEFAULT_LIMIT_MB: u64 = 100;
+
+/// 注册 service worker，离线时由它提供应用外壳和已缓存的图片
+pub fn register_service_worker() {
+    let Some(win) = window() else { return };
+    let container = win.navigator().service_worker();
+    wasm_bindgen_futures::spawn_local(async move {
+        if let Err(e) = JsFuture::from(container.register(SERVICE_WORKER_URL)).await {
+            log::warn!("注册 service worker 失败: {:?}", e);
+        }
+    });
+}
+
+/// 离线文章：文章 JSON 和图片存放在 Cache Storage，列表保存在 LocalStorage
+pub struct OfflineStore;
+
+impl OfflineStore {
+    pub fn list() -> Vec<OfflineArticle> {
+        LocalStorage::get::<Vec<OfflineArticle>>(INDEX_KEY).unwrap_or_default()
+    }
+
+    fn save_list(list: &[OfflineArticle]) {
+        let _ = LocalStorage::set(INDEX_KEY, list);
+    }
+
+    pub fn is_saved(article_id: &str) -> bool {
+        Self::list().iter().any(|a| a.article_id == article_id)
+    }
+
+    pub fn total_bytes() -> u64 {
+        Self::list().iter().map(|a| a.size_bytes).sum()
+    }
+
+    pub fn storage_limit_mb() -> u64 {
+        LocalStorage::get::<u64>(LIMIT_KEY).unwrap_or(DEFAULT_LIMIT_MB)
+    }
+
+    pub async fn set_storage_limit_mb(limit: u64) -> Result<(), String> {
+        let _ = LocalStorage::set(LIMIT_KEY, limit);
+        Self::evict_to_limit(None).await
+    }
+
+    /// 缓存文章 JSON、正文及封面中的图片
+    pub async fn save(article: &Article) -> Result<OfflineArticle, String> {
+        let cache = open_cache().await?;
+        let json = serde_json::to_string(article).map_err(|e| e.to_string())?;
+        let response = Response::new_with_opt_str(Some(&json)).map_err(js_error)?;
+        JsFuture::from(cache.put_with_str(&article_url(&article.slug), &response))
+            .await
+            .map_err(js_error)?;
+
+        let mut images = image_sources(&article.content_html);
+        if let Some(cover) = &article.cover_image_url {
+            if !images.contains(cover) {
+                images.insert(0, cover.clone());
+            }
+        }
+
+        let mut size_bytes = json.len() as u64;
+        for url in &images {
+            // 单张图片失败不影响保存文章，离线时该图片不显示
+            match cache_image(&cache, url).await {
+                Ok(bytes) => size_bytes += bytes,
+                Err(e) => log::warn!("缓存图片 {} 失败: {}", url, e),
+            }
+        }
+
+        let entry = OfflineArticle::from_article(article, images, size_bytes);
+        let mut list = Self::list();
+        list.retain(|a| a.article_id != entry.article_id);
+        list.insert(0, entry.clone());
+        Self::save_list(&list);
+        Self::evict_to_limit(Some(&entry.article_id)).await?;
+        Ok(entry)
+    }
+
+    /// 从缓存读取文章，同时记录打开时间
+    pub async fn load(slug: &str) -> Option<Article> {
+        let cache = open_cache().await.ok()?;
+        let matched = JsFuture::from(cache.match_with_str(&article_url(slug))).await.ok()?;
+        let response = matched.dyn_into::<Response>().ok()?;
+        let text = JsFuture::from(response.text().ok()?).await.ok()?.as_string()?;
+        let article = serde_json::from_str::<Article>(&text).ok()?;
+
+        let mut list = Self::list();
+        if let Some(entry) = list.iter_mut().find(|a| a.slug == slug) {
+            entry.last_opened_at = Utc::now();
+            Self::save_list(&list);
+        }
+        Some(article)
+    }
+
+    pub async fn remove(article_id: &str) -> Result<(), String> {
+        let mut list = Self::list();
+        let Some(index) = list.iter().position(|a| a.article_id == article_id) else {
+            return Ok(());
+        };
+        let entry = list.remove(index);
+        Self::save_list(&list);
+
+        let cache = open_cache().await?;
+        let _ = JsFuture::from(cache.delete_with_str(&article_url(&entry.slug))).await;
+        // 其他离线文章仍在使用的图片保留
+        for url in &entry.images {
+            if !list.iter().any(|a| a.images.contains(url)) {
+                let _ = JsFuture::from(cache.delete_with_str(url)).await;
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn clear() -> Result<(), String> {
+        LocalStorage::delete(INDEX_KEY);
+        let caches = window().ok_or("无法访问浏览器窗口")?.caches().map_err(js_error)?;
+        JsFuture::from(caches.delete(OFFLINE_CACHE)).await.map_err(js_error)?;
+        Ok(())
+    }
+
+    /// 超出存储上限时按最久未打开的顺序清理，keep 为刚保存、不应被清理的文章
+    pub async fn evict_to_limit(keep: Option<&str>) -> Result<(), String> {
+        let limit = Self::storage_limit_mb() * 1024 * 1024;
+        let mut list = Self::list();
+        list.sort_by_key(|a| a.last_opened_at);
+        let mut total: u64 = list.iter().map(|a| a.size_bytes).sum();
+        for entry in list {
+            if total <= limit {
+                break;
+            }
+            if Some(entry.article_id.as_str()) == keep {
+                continue;
+            }
+            total -= entry.size_bytes;
+            Self::remove(&entry.article_id).await?;
+        }
+        Ok(())
+    }
+
+    /// 浏览器为本站分配的存储用量和配额（字节）
+    pub async fn storage_estimate() -> Option<(f64, f64)> {
+        let manager = window()?.navigator().storage();
+        let estimate = JsFuture::from(manager.estimate().ok()?).await.ok()?;
+        let estimate = estimate.unchecked_into::<web_sys::StorageEstimate>();
+        Some((estimate.get_usage()?, estimate.get_quota()?))
+    }
+}
+
+/// 当前是否联网，随浏览器的 online/offline 事件更新
+pub fn use_online() -> Signal<bool> {
+    let mut online = use_signal(|| window().map(|w| w.navigator().on_line()).unwrap_or(true));
+    let listeners = use_hook(|| Rc::new(RefCell::new(Vec::<(&'static str, Closure<dyn FnMut()>)>::new())));
+
+    use_hook({
+        let listeners = listeners.clone();
+        move || {
+            let Some(win) = window() else { return };
+            for (event, value) in [("online", true), ("offline", false)] {
+                let closure = Closure::<dyn FnMut()>::new(move || online.set(value));
+                win.add_event_listener_with_callback(event, closure.as_ref().unchecked_ref())
+                    .ok();
+                listeners.borrow_mut().push((event, closure));
+            }
+        }
+    });
+
+    use_drop(move || {
+        let Some(win) = window() else { return };
+        for (event, closure) in listeners.borrow_mut().drain(..) {
+            win.remove_event_listener_with_callback(event, closure.as_ref().unchecked_ref())
+                .ok();
+        }
+    });
+
+    online
+}
+
+// 文章 JSON 在缓存中的地址，不对应真实接口
+fn article_url(slug: &str) -> String {
+    format!("/offline/articles/{}.json", urlencoding::encode(slug))
+}
+
+async fn open_cache() -> Result<Cache, String> {
+    let caches = window().ok_or("无法访问浏览器窗口")?.caches().map_err(js_error)?;
+    let cache = JsFuture::from(caches.open(OFFLINE_CACHE)).await.map_err(js_error)?;
+    Ok(cache.unchecked_into::<Cache>())
+}
+
+// 先按 CORS 请求以便计算大小，跨域不允许时退回 no-cors（不透明响应，大小未知）
+async fn cache_image(cache: &Cache, url: &str) -> Result<u64, String> {
+    let win = window().ok_or("无法访问浏览器窗口")?;
+    let response = match fetch(&win, url, RequestMode::Cors).await {
+        Ok(response) if response.ok() => response,
+        _ => fetch(&win, url, RequestMode::NoCors).await?,
+    };
+    let size = response
+        .headers()
+        .get("content-length")
+        .ok()
+        .flatten()
+        .and_then(|len| len.parse::<u64>().ok())
+        .unwrap_or(0);
+    JsFuture::from(cache.put_with_str(url, &response))
+        .await
+        .map_err(js_error)?;
+    Ok(size)
+}
+
+async fn fetch(win: &web_sys::Window, url: &str, mode: RequestMode) -> Result<Response, String> {
+    let init = RequestInit::new();
+    init.set_mode(mode);
+    let promise: Promise = win.fetch_with_str_and_init(url, &init);
+    let response = JsFuture::from(promise).await.map_err(js_error)?;
+    response.dyn_into::<Response>().map_err(js_error)
+}
+
+fn js_error(value: JsValue) -> String {
+    value.as_string().unwrap_or_else(|| format!("{:?}", value))
+}
+
+// 正文中 <img src="..."> 的地址
+fn image_sources(html: &str) -> Vec<String> {
+    let mut sources = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find("<img") {
+        rest = &rest[start + 4..];
+        let tag_end = rest.find('>').unwrap_or(rest.len());
+        let tag = &rest[..tag_end];
+        if let Some(src_start) = tag.find("src=") {
+            let value = &tag[src_start + 4..];
+            let quote = value.chars().next().unwrap_or(' ');
+            let src = if quote == '"' || quote == '\'' {
+                value[1..].split(quote).next().unwrap_or("")
+            } else {
+                value.split(|c: char| c.is_whitespace()).next().unwrap_or("")
+            };
+            if !src.is_empty() && !src.starts_with("data:") && !sources.iter().any(|s| s == src) {
+                sources.push(src.to_string());
+            }
+        }
+        rest = &rest[tag_end..];
+    }
+    sources
+}
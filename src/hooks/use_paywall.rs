@@ -0,0 +1,98 @@
+use dioxus::prelude::*;
+use crate::{
+    api::{articles::ArticleService, subscriptions::SubscriptionService},
+    hooks::use_auth,
+    models::{
+        article::Article,
+        subscription::{ReadingMeter, SubscriptionStatus},
+    },
+};
+
+/// 读者对一篇文章的访问权限。未解锁时服务器只返回试读部分，客户端不做截断
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArticleAccess {
+    Checking,
+    // 免费文章、作者本人或订阅者
+    Full,
+    // 使用每月免费额度阅读的会员文章
+    Metered(ReadingMeter),
+    // 免费额度已用完，只能试读
+    Locked(ReadingMeter),
+    // 免费额度由服务器按账号记录，未登录时只能试读
+    SignInRequired,
+    // 查询免费额度失败，暂时只能试读，刷新后重试
+    MeterUnavailable,
+}
+
+impl ArticleAccess {
+    pub fn is_locked(&self) -> bool {
+        matches!(
+            self,
+            ArticleAccess::Locked(_) | ArticleAccess::SignInRequired | ArticleAccess::MeterUnavailable
+        )
+    }
+}
+
+/// 判断当前读者能否阅读全文；登录读者打开会员文章时，有额度则自动使用一次免费额度
+pub fn use_article_access(mut article: Signal<Option<Article>>) -> Signal<ArticleAccess> {
+    let auth = use_auth();
+    let mut access = use_signal(|| ArticleAccess::Checking);
+
+    // 只在换了文章时重新判断，点赞等修改文章数据时不重复请求
+    let key = use_memo(move || {
+        article().map(|art| (art.id.clone(), art.slug.clone(), art.is_paid_content, art.author.id.clone()))
+    });
+
+    use_effect(move || {
+        let Some((article_id, slug, is_paid, author_id)) = key() else { return };
+        let auth_state = auth.read().clone();
+        if auth_state.loading {
+            return;
+        }
+        if !is_paid || auth_state.user.as_ref().map(|u| u.id == author_id).unwrap_or(false) {
+            access.set(ArticleAccess::Full);
+            return;
+        }
+        if !auth_state.is_authenticated {
+            access.set(ArticleAccess::SignInRequired);
+            return;
+        }
+        access.set(ArticleAccess::Checking);
+        spawn(async move {
+            if let Ok(Some(subscription)) = SubscriptionService::check_subscription_status(&author_id).await {
+                if matches!(subscription.status, SubscriptionStatus::Active) {
+                    access.set(ArticleAccess::Full);
+                    return;
+                }
+            }
+
+            let Ok(meter) = SubscriptionService::get_reading_meter().await else {
+                access.set(ArticleAccess::MeterUnavailable);
+                return;
+            };
+            if meter.has_unlocked(&article_id) {
+                access.set(ArticleAccess::Metered(meter));
+                return;
+            }
+            if meter.remaining() == 0 {
+                access.set(ArticleAccess::Locked(meter));
+                return;
+            }
+            match SubscriptionService::unlock_with_meter(&article_id).await {
+                Ok(meter) if meter.has_unlocked(&article_id) => {
+                    // 解锁前拿到的只是试读部分，重新获取全文
+                    if let Ok(full) = ArticleService::get_article(&slug).await {
+                        if let Some(art) = article.write().as_mut() {
+                            art.content_html = full.content_html;
+                        }
+                    }
+                    access.set(ArticleAccess::Metered(meter));
+                }
+                Ok(meter) => access.set(ArticleAccess::Locked(meter)),
+                Err(_) => access.set(ArticleAccess::Locked(meter)),
+            }
+        });
+    });
+
+    access
+}
@@ -0,0 +1,34 @@
+use dioxus::prelude::*;
+use crate::{
+    api::publications::PublicationService,
+    hooks::use_auth,
+    models::publication::{MemberRole, MemberStatus},
+};
+
+/// 当前用户在出版物中的角色；未登录、非成员或成员资格未激活时为 None
+pub fn use_publication_role(publication_id: Memo<Option<String>>) -> Signal<Option<MemberRole>> {
+    let auth = use_auth();
+    let mut role = use_signal(|| None::<MemberRole>);
+
+    use_effect(move || {
+        let user_id = auth().user.map(|u| u.id);
+        let (Some(publication_id), Some(user_id)) = (publication_id(), user_id) else {
+            role.set(None);
+            return;
+        };
+        spawn(async move {
+            let found = PublicationService::get_members(&publication_id, None, None, Some(1), Some(100))
+                .await
+                .ok()
+                .and_then(|list| {
+                    list.members
+                        .into_iter()
+                        .find(|m| m.user.id == user_id && m.status == MemberStatus::Active)
+                })
+                .map(|m| m.role);
+            role.set(found);
+        });
+    });
+
+    role
+}
@@ -0,0 +1,274 @@
+use dioxus::prelude::*;
+use gloo_storage::{LocalStorage, Storage};
+use gloo_timers::future::TimeoutFuture;
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::window;
+use crate::{
+    api::{client::ApiResult, reading::ReadingService},
+    hooks::use_auth,
+    models::{
+        article::Article,
+        reading::{ReadingHistorySettings, ReadingRecord},
+    },
+};
+
+// 未登录时阅读记录保存在本地
+const LOCAL_HISTORY_KEY: &str = "reading_history";
+const LOCAL_PAUSED_KEY: &str = "reading_history_paused";
+const LOCAL_HISTORY_LIMIT: usize = 100;
+// 阅读历史每页条数
+const HISTORY_PAGE_SIZE: usize = 20;
+// 滚动时每隔这么久保存一次进度
+const SAVE_INTERVAL_MS: u32 = 5000;
+// 进度变化小于 1% 时不保存
+const SAVE_THRESHOLD: f64 = 0.01;
+
+/// 阅读记录的存取：登录用户同步到服务器，未登录时保存在 LocalStorage
+pub struct ReadingStore;
+
+impl ReadingStore {
+    fn local_records() -> Vec<ReadingRecord> {
+        LocalStorage::get::<Vec<ReadingRecord>>(LOCAL_HISTORY_KEY).unwrap_or_default()
+    }
+
+    fn save_local_records(records: &[ReadingRecord]) {
+        let _ = LocalStorage::set(LOCAL_HISTORY_KEY, records);
+    }
+
+    pub async fn load(article_id: &str, authenticated: bool) -> Option<ReadingRecord> {
+        if authenticated {
+            ReadingService::get_progress(article_id).await.ok().flatten()
+        } else {
+            Self::local_records().into_iter().find(|r| r.article_id == article_id)
+        }
+    }
+
+    pub async fn save(record: ReadingRecord, authenticated: bool) {
+        if Self::paused() {
+            return;
+        }
+        if authenticated {
+            let _ = ReadingService::update_progress(&record.article_id, record.progress).await;
+        } else {
+            let mut records = Self::local_records();
+            records.retain(|r| r.article_id != record.article_id);
+            records.insert(0, record);
+            records.truncate(LOCAL_HISTORY_LIMIT);
+            Self::save_local_records(&records);
+        }
+    }
+
+    /// 读到一半的文章，最近阅读的在前
+    pub async fn continue_reading(authenticated: bool, limit: usize) -> Vec<ReadingRecord> {
+        if authenticated {
+            ReadingService::get_continue_reading(Some(limit as i32)).await.unwrap_or_default()
+        } else {
+            Self::local_records()
+                .into_iter()
+                .filter(|r| r.can_resume())
+                .take(limit)
+                .collect()
+        }
+    }
+
+    /// 第 page 页（从 1 开始）的阅读历史，以及是否还有下一页
+    pub async fn history(query: &str, page: usize, authenticated: bool) -> ApiResult<(Vec<ReadingRecord>, bool)> {
+        if authenticated {
+            let resp = ReadingService::get_history(Some(query), Some(page as i32), Some(HISTORY_PAGE_SIZE as i32)).await?;
+            let has_next = ((page * HISTORY_PAGE_SIZE) as i64) < resp.total;
+            return Ok((resp.records, has_next));
+        }
+        let query = query.trim().to_lowercase();
+        let matched: Vec<ReadingRecord> = Self::local_records()
+            .into_iter()
+            .filter(|r| {
+                query.is_empty()
+                    || r.article_title.to_lowercase().contains(&query)
+                    || r.author_name.to_lowercase().contains(&query)
+            })
+            .collect();
+        let has_next = matched.len() > page * HISTORY_PAGE_SIZE;
+        let records = matched
+            .into_iter()
+            .skip((page - 1) * HISTORY_PAGE_SIZE)
+            .take(HISTORY_PAGE_SIZE)
+            .collect();
+        Ok((records, has_next))
+    }
+
+    pub async fn remove(article_id: &str, authenticated: bool) -> ApiResult<()> {
+        if authenticated {
+            return ReadingService::delete_history_entry(article_id).await;
+        }
+        let mut records = Self::local_records();
+        records.retain(|r| r.article_id != article_id);
+        Self::save_local_records(&records);
+        Ok(())
+    }
+
+    pub async fn clear(authenticated: bool) -> ApiResult<()> {
+        if authenticated {
+            return ReadingService::clear_history().await;
+        }
+        LocalStorage::delete(LOCAL_HISTORY_KEY);
+        Ok(())
+    }
+
+    /// 本地缓存的暂停状态，登录用户以服务器设置为准
+    pub fn paused() -> bool {
+        LocalStorage::get::<bool>(LOCAL_PAUSED_KEY).unwrap_or(false)
+    }
+
+    pub async fn refresh_paused(authenticated: bool) -> bool {
+        if authenticated {
+            if let Ok(settings) = ReadingService::get_history_settings().await {
+                let _ = LocalStorage::set(LOCAL_PAUSED_KEY, settings.paused);
+            }
+        }
+        Self::paused()
+    }
+
+    pub async fn set_paused(paused: bool, authenticated: bool) -> ApiResult<()> {
+        if authenticated {
+            ReadingService::update_history_settings(&ReadingHistorySettings { paused }).await?;
+        }
+        let _ = LocalStorage::set(LOCAL_PAUSED_KEY, paused);
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+pub struct ReadingProgress {
+    // 当前已读比例 0.0 - 1.0
+    pub progress: Signal<f64>,
+    // 上次读到的位置，可以跳转过去继续阅读
+    pub resume_at: Signal<Option<f64>>,
+    body_id: &'static str,
+}
+
+impl ReadingProgress {
+    pub fn resume(&mut self) {
+        if let Some(progress) = self.resume_at.take() {
+            scroll_to_progress(self.body_id, progress);
+        }
+    }
+
+    pub fn dismiss(&mut self) {
+        self.resume_at.set(None);
+    }
+}
+
+/// 跟踪正文（body_id 指定的元素）的阅读进度，定时保存并在打开文章时提示从上次的位置继续
+pub fn use_reading_progress(article: Signal<Option<Article>>, body_id: &'static str) -> ReadingProgress {
+    let auth = use_auth();
+    let mut progress = use_signal(|| 0.0);
+    let mut resume_at = use_signal(|| None::<f64>);
+    // 最近一次保存的进度
+    let mut saved = use_hook(|| CopyValue::new(None::<f64>));
+    let listener = use_hook(|| Rc::new(RefCell::new(None::<Closure<dyn FnMut()>>)));
+
+    // 只在切换文章时重新读取，鼓掌、收藏等修改文章数据不应再次弹出继续阅读的提示
+    let article_id = use_memo(move || article().map(|art| art.id.clone()));
+
+    // 打开文章时读取上次的进度，并同步暂停状态（可能在其他设备上修改过）
+    use_effect(move || {
+        let Some(article_id) = article_id() else { return };
+        let authenticated = auth.read().is_authenticated;
+        saved.set(None);
+        resume_at.set(None);
+        spawn(async move {
+            ReadingStore::refresh_paused(authenticated).await;
+            if let Some(record) = ReadingStore::load(&article_id, authenticated).await {
+                saved.set(Some(record.progress));
+                if record.can_resume() {
+                    resume_at.set(Some(record.progress));
+                }
+            }
+        });
+    });
+
+    // 滚动时更新进度
+    use_effect({
+        let listener = listener.clone();
+        move || {
+            let update = move || {
+                if let Some(value) = measure_progress(body_id) {
+                    progress.set(value);
+                }
+            };
+            let Some(win) = window() else { return };
+            let closure = Closure::<dyn FnMut()>::new(update);
+            win.add_event_listener_with_callback("scroll", closure.as_ref().unchecked_ref())
+                .ok();
+            if let Some(previous) = listener.borrow_mut().replace(closure) {
+                win.remove_event_listener_with_callback("scroll", previous.as_ref().unchecked_ref())
+                    .ok();
+            }
+            if let Some(value) = measure_progress(body_id) {
+                progress.set(value);
+            }
+        }
+    });
+
+    let mut save = move || {
+        let art = article.peek().clone()?;
+        let current = *progress.peek();
+        let changed = saved.peek().map_or(current > 0.0, |last| (current - last).abs() >= SAVE_THRESHOLD);
+        if !changed {
+            return None;
+        }
+        saved.set(Some(current));
+        Some((ReadingRecord::from_article(&art, current), auth.peek().is_authenticated))
+    };
+
+    // 定时保存
+    use_future(move || async move {
+        loop {
+            TimeoutFuture::new(SAVE_INTERVAL_MS).await;
+            if let Some((record, authenticated)) = save() {
+                ReadingStore::save(record, authenticated).await;
+            }
+        }
+    });
+
+    // 离开页面时保存最后的进度；组件已卸载，只能交给浏览器的任务队列执行
+    use_drop({
+        let listener = listener.clone();
+        move || {
+            if let (Some(win), Some(closure)) = (window(), listener.borrow_mut().take()) {
+                win.remove_event_listener_with_callback("scroll", closure.as_ref().unchecked_ref())
+                    .ok();
+            }
+            if let Some((record, authenticated)) = save() {
+                wasm_bindgen_futures::spawn_local(ReadingStore::save(record, authenticated));
+            }
+        }
+    });
+
+    ReadingProgress { progress, resume_at, body_id }
+}
+
+// 正文底部进入视口即视为读完
+fn measure_progress(body_id: &str) -> Option<f64> {
+    let win = window()?;
+    let body = win.document()?.get_element_by_id(body_id)?;
+    let rect = body.get_bounding_client_rect();
+    let viewport = win.inner_height().ok()?.as_f64()?;
+    let scrollable = rect.height() - viewport;
+    if scrollable <= 0.0 {
+        return Some(if rect.top() < viewport { 1.0 } else { 0.0 });
+    }
+    Some((-rect.top() / scrollable).clamp(0.0, 1.0))
+}
+
+fn scroll_to_progress(body_id: &str, progress: f64) {
+    let Some(win) = window() else { return };
+    let Some(body) = win.document().and_then(|d| d.get_element_by_id(body_id)) else { return };
+    let rect = body.get_bounding_client_rect();
+    let viewport = win.inner_height().ok().and_then(|v| v.as_f64()).unwrap_or(0.0);
+    let top = win.scroll_y().unwrap_or(0.0) + rect.top();
+    let offset = (rect.height() - viewport).max(0.0) * progress;
+    win.scroll_to_with_x_and_y(0.0, top + offset);
+}
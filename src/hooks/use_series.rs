@@ -0,0 +1,63 @@
+use dioxus::prelude::*;
+use crate::{
+    api::{reading::ReadingService, series::SeriesService},
+    hooks::use_auth,
+    models::{article::Article, series::SeriesWithArticles},
+};
+
+/// 文章所属系列的全部文章，以及登录读者已读完的文章
+#[derive(Clone, Copy, PartialEq)]
+pub struct SeriesState {
+    pub data: Signal<Option<SeriesWithArticles>>,
+    pub finished_ids: Signal<Vec<String>>,
+}
+
+impl SeriesState {
+    pub fn is_finished(&self, article_id: &str) -> bool {
+        self.finished_ids.read().iter().any(|id| id == article_id)
+    }
+}
+
+pub fn use_series(series_id: Memo<Option<String>>) -> SeriesState {
+    let auth = use_auth();
+    let mut data = use_signal(|| None::<SeriesWithArticles>);
+    let mut finished_ids = use_signal(Vec::<String>::new);
+
+    use_effect(move || {
+        let Some(id) = series_id() else {
+            data.set(None);
+            return;
+        };
+        let authenticated = auth.read().is_authenticated;
+        spawn(async move {
+            match SeriesService::get_series(&id).await {
+                Ok(series) => data.set(Some(series)),
+                Err(e) => log::warn!("加载系列失败: {}", e.message),
+            }
+            if authenticated {
+                if let Ok(records) = ReadingService::get_series_progress(&id).await {
+                    finished_ids.set(
+                        records
+                            .into_iter()
+                            .filter(|r| r.is_finished())
+                            .map(|r| r.article_id)
+                            .collect(),
+                    );
+                }
+            } else {
+                finished_ids.set(Vec::new());
+            }
+        });
+    });
+
+    SeriesState { data, finished_ids }
+}
+
+/// 系列中下一篇没读完的文章，全部读完时为 None
+pub fn next_unfinished<'a>(series: &'a SeriesWithArticles, finished_ids: &[String]) -> Option<&'a Article> {
+    series
+        .parts()
+        .into_iter()
+        .map(|part| &part.article)
+        .find(|article| !finished_ids.contains(&article.id))
+}
@@ -1,103 +1,210 @@
 use dioxus::prelude::*;
 use gloo_storage::{LocalStorage, Storage};
+use std::cell::RefCell;
+use std::rc::Rc;
+use wasm_bindgen::{closure::Closure, JsCast};
+use crate::{
+    api::users::UserService,
+    hooks::{use_auth, AuthState},
+    models::preferences::{DisplayPreferences, ReaderPreferences},
+};
 
-#[derive(Debug, Clone, Copy, PartialEq)]
+const THEME_KEY: &str = "theme";
+const READER_KEY: &str = "reader_preferences";
+const DARK_QUERY: &str = "(prefers-color-scheme: dark)";
+// 所有主题会用到的 HTML class，切换时先全部移除
+const THEME_CLASSES: [&str; 3] = ["dark", "sepia", "high-contrast"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
 pub enum Theme {
     Light,
     Dark,
+    // 跟随系统的深色/浅色设置
+    #[default]
+    System,
+    // 米黄背景、低对比度
+    Sepia,
+    HighContrast,
 }
 
 impl Theme {
+    pub const ALL: [Theme; 5] = [Theme::System, Theme::Light, Theme::Dark, Theme::Sepia, Theme::HighContrast];
+
     pub fn class(&self) -> &'static str {
         match self {
-            Theme::Light => "",
+            Theme::Light | Theme::System => "",
             Theme::Dark => "dark",
+            Theme::Sepia => "sepia",
+            Theme::HighContrast => "high-contrast",
         }
     }
-    
-    pub fn toggle(&self) -> Self {
+
+    pub fn label(&self) -> &'static str {
         match self {
-            Theme::Light => Theme::Dark,
-            Theme::Dark => Theme::Light,
+            Theme::Light => "浅色",
+            Theme::Dark => "深色",
+            Theme::System => "跟随系统",
+            Theme::Sepia => "护眼",
+            Theme::HighContrast => "高对比度",
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Theme::Light => "light",
+            Theme::Dark => "dark",
+            Theme::System => "system",
+            Theme::Sepia => "sepia",
+            Theme::HighContrast => "high-contrast",
         }
     }
-}
 
-impl Default for Theme {
-    fn default() -> Self {
-        Theme::Light
+    pub fn parse(value: &str) -> Option<Self> {
+        Theme::ALL.into_iter().find(|t| t.as_str() == value)
+    }
+
+    /// 跟随系统时按系统设置换成浅色或深色
+    pub fn resolve(&self, system_dark: bool) -> Theme {
+        match self {
+            Theme::System if system_dark => Theme::Dark,
+            Theme::System => Theme::Light,
+            other => *other,
+        }
     }
 }
 
 #[derive(Clone, Copy)]
 pub struct ThemeState {
     theme: Signal<Theme>,
+    system_dark: Signal<bool>,
+    reader: Signal<ReaderPreferences>,
+    auth: Signal<AuthState>,
 }
 
 impl ThemeState {
     pub fn current(&self) -> Theme {
         *self.theme.read()
     }
-    
-    pub fn toggle(&mut self) {
-        let current = *self.theme.read();
-        let new_theme = current.toggle();
-        self.theme.set(new_theme.clone());
-        
-        // 保存到本地存储
-        let _ = LocalStorage::set("theme", match new_theme {
-            Theme::Light => "light",
-            Theme::Dark => "dark",
-        });
-        
-        // 更新HTML元素的class
-        if let Some(window) = web_sys::window() {
-            if let Some(document) = window.document() {
-                if let Some(element) = document.document_element() {
-                    let class_list = element.class_list();
-                    match new_theme {
-                        Theme::Dark => {
-                            let _ = class_list.add_1("dark");
-                        }
-                        Theme::Light => {
-                            let _ = class_list.remove_1("dark");
-                        }
-                    }
-                }
-            }
+
+    /// 系统当前是浅色还是深色
+    pub fn system_theme(&self) -> Theme {
+        Theme::System.resolve(*self.system_dark.read())
+    }
+
+    pub fn set(&mut self, theme: Theme) {
+        self.theme.set(theme);
+        let _ = LocalStorage::set(THEME_KEY, theme.as_str());
+        self.sync();
+    }
+
+    pub fn reader(&self) -> ReaderPreferences {
+        self.reader.read().clone()
+    }
+
+    pub fn set_reader(&mut self, preferences: ReaderPreferences) {
+        let _ = LocalStorage::set(READER_KEY, &preferences);
+        self.reader.set(preferences);
+        self.sync();
+    }
+
+    // 登录用户的偏好保存到个人资料
+    fn sync(&self) {
+        if !self.auth.peek().is_authenticated {
+            return;
         }
+        let preferences = DisplayPreferences {
+            theme: self.theme.peek().as_str().to_string(),
+            reader: self.reader.peek().clone(),
+        };
+        spawn(async move {
+            if let Err(e) = UserService::update_display_preferences(&preferences).await {
+                log::warn!("同步显示偏好失败: {}", e.message);
+            }
+        });
     }
 }
 
 pub fn use_provide_theme() -> ThemeState {
+    let auth = use_auth();
     let mut theme = use_signal(|| {
         // 从本地存储读取主题
-        let stored_theme = LocalStorage::get::<String>("theme").unwrap_or_else(|_| "light".to_string());
-        match stored_theme.as_str() {
-            "dark" => Theme::Dark,
-            _ => Theme::Light,
+        let stored_theme = LocalStorage::get::<String>(THEME_KEY).unwrap_or_default();
+        Theme::parse(&stored_theme).unwrap_or_default()
+    });
+    let mut system_dark = use_signal(|| dark_media_query().map(|q| q.matches()).unwrap_or(false));
+    let mut reader = use_signal(|| LocalStorage::get::<ReaderPreferences>(READER_KEY).unwrap_or_default());
+    let listener = use_hook(|| Rc::new(RefCell::new(None::<Closure<dyn FnMut()>>)));
+
+    // 系统深色模式切换时实时跟随
+    use_hook({
+        let listener = listener.clone();
+        move || {
+            let Some(query) = dark_media_query() else { return };
+            let closure = Closure::<dyn FnMut()>::new(move || {
+                if let Some(query) = dark_media_query() {
+                    system_dark.set(query.matches());
+                }
+            });
+            query.add_event_listener_with_callback("change", closure.as_ref().unchecked_ref())
+                .ok();
+            *listener.borrow_mut() = Some(closure);
+        }
+    });
+
+    use_drop(move || {
+        if let (Some(query), Some(closure)) = (dark_media_query(), listener.borrow_mut().take()) {
+            query.remove_event_listener_with_callback("change", closure.as_ref().unchecked_ref())
+                .ok();
         }
     });
-    
-    // 初始化时设置HTML class
+
+    // 主题变化时更新HTML元素的class
     use_effect(move || {
+        let resolved = theme().resolve(system_dark());
         if let Some(window) = web_sys::window() {
             if let Some(document) = window.document() {
                 if let Some(element) = document.document_element() {
                     let class_list = element.class_list();
-                    if theme() == Theme::Dark {
-                        let _ = class_list.add_1("dark");
+                    for class in THEME_CLASSES {
+                        let _ = class_list.remove_1(class);
+                    }
+                    if !resolved.class().is_empty() {
+                        let _ = class_list.add_1(resolved.class());
                     }
                 }
             }
         }
     });
-    
-    let state = ThemeState { theme };
+
+    let state = ThemeState { theme, system_dark, reader, auth };
+
+    // 登录后以个人资料中保存的偏好为准；还没有保存过时上传本地的偏好
+    use_effect(move || {
+        if !auth.read().is_authenticated {
+            return;
+        }
+        spawn(async move {
+            if let Ok(preferences) = UserService::get_display_preferences().await {
+                let Some(saved) = Theme::parse(&preferences.theme) else {
+                    state.sync();
+                    return;
+                };
+                theme.set(saved);
+                let _ = LocalStorage::set(THEME_KEY, saved.as_str());
+                let _ = LocalStorage::set(READER_KEY, &preferences.reader);
+                reader.set(preferences.reader);
+            }
+        });
+    });
+
     use_context_provider(|| state);
     state
 }
 
 pub fn use_theme() -> ThemeState {
     use_context()
-}
\ No newline at end of file
+}
+
+fn dark_media_query() -> Option<web_sys::MediaQueryList> {
+    web_sys::window()?.match_media(DARK_QUERY).ok().flatten()
+}
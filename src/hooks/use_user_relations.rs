@@ -0,0 +1,112 @@
+use dioxus::prelude::*;
+use gloo_timers::future::TimeoutFuture;
+use std::collections::HashSet;
+use crate::{
+    api::{client::ApiResult, privacy::PrivacyService},
+    hooks::AuthState,
+    models::privacy::RelationKind,
+};
+
+// 加载失败后的重试间隔，每次加倍直到上限
+const RETRY_DELAY_MS: u32 = 2_000;
+const MAX_RETRY_DELAY_MS: u32 = 60_000;
+
+/// 当前用户屏蔽和静音的用户 id，文章列表和评论据此在客户端隐藏内容
+#[derive(Clone, Copy, PartialEq)]
+pub struct UserRelations {
+    pub blocked: Signal<HashSet<String>>,
+    pub muted: Signal<HashSet<String>>,
+}
+
+pub fn use_user_relations() -> UserRelations {
+    use_context::<UserRelations>()
+}
+
+/// 在应用根组件中调用，登录后加载屏蔽和静音列表（失败时持续重试），退出登录后清空
+pub fn use_provide_user_relations(auth: Signal<AuthState>) -> UserRelations {
+    let mut relations = use_context_provider(|| UserRelations {
+        blocked: Signal::new(HashSet::new()),
+        muted: Signal::new(HashSet::new()),
+    });
+    // 本次登录是否已开始加载
+    let mut started = use_hook(|| CopyValue::new(false));
+    // 退出登录时递增，作废仍在进行或等待重试的加载
+    let mut session = use_hook(|| CopyValue::new(0u64));
+
+    use_effect(move || {
+        let authenticated = auth.read().is_authenticated;
+        if authenticated && !*started.peek() {
+            started.set(true);
+            let current = *session.peek();
+            spawn(async move {
+                let mut delay = RETRY_DELAY_MS;
+                loop {
+                    let result = PrivacyService::get_relation_ids().await;
+                    if *session.peek() != current {
+                        return;
+                    }
+                    if let Ok(ids) = result {
+                        relations.blocked.set(ids.blocked.into_iter().collect());
+                        relations.muted.set(ids.muted.into_iter().collect());
+                        return;
+                    }
+                    // 列表为空时被屏蔽用户的内容会重新出现，不能放弃加载
+                    TimeoutFuture::new(delay).await;
+                    delay = (delay * 2).min(MAX_RETRY_DELAY_MS);
+                    if *session.peek() != current {
+                        return;
+                    }
+                }
+            });
+        } else if !authenticated && *started.peek() {
+            started.set(false);
+            let next = *session.peek() + 1;
+            session.set(next);
+            relations.blocked.set(HashSet::new());
+            relations.muted.set(HashSet::new());
+        }
+    });
+
+    relations
+}
+
+impl UserRelations {
+    pub fn is_blocked(&self, user_id: &str) -> bool {
+        self.blocked.read().contains(user_id)
+    }
+
+    pub fn is_muted(&self, user_id: &str) -> bool {
+        self.muted.read().contains(user_id)
+    }
+
+    /// 屏蔽或静音的用户，其内容不出现在文章列表中
+    pub fn is_hidden(&self, user_id: &str) -> bool {
+        self.is_blocked(user_id) || self.is_muted(user_id)
+    }
+
+    pub fn contains(&self, kind: RelationKind, user_id: &str) -> bool {
+        match kind {
+            RelationKind::Block => self.is_blocked(user_id),
+            RelationKind::Mute => self.is_muted(user_id),
+        }
+    }
+
+    /// 屏蔽 / 静音或撤销，成功后更新本地列表
+    pub async fn set(&self, kind: RelationKind, user_id: &str, enabled: bool) -> ApiResult<()> {
+        if enabled {
+            PrivacyService::add(kind, user_id).await?;
+        } else {
+            PrivacyService::remove(kind, user_id).await?;
+        }
+        let mut ids = match kind {
+            RelationKind::Block => self.blocked,
+            RelationKind::Mute => self.muted,
+        };
+        if enabled {
+            ids.write().insert(user_id.to_string());
+        } else {
+            ids.write().remove(user_id);
+        }
+        Ok(())
+    }
+}
@@ -3,10 +3,12 @@ use dioxus::prelude::*;
 use dioxus_router::prelude::*;
 
 mod api;
+mod collab;
 mod components;
 mod hooks;
 mod models;
 mod pages;
+mod rendering;
 
 use components::*;
 use hooks::*;
@@ -22,8 +24,11 @@ fn main() {
 }
 
 fn App() -> Element {
-    use_provide_auth();
+    let auth = use_provide_auth();
+    use_provide_notifications(auth);
+    use_provide_user_relations(auth);
     use_provide_theme();
+    use_hook(register_service_worker);
     
     rsx! {
         Router::<Route> {}
@@ -83,6 +88,9 @@ pub enum Route {
     #[route("/publications/:slug")]
     PublicationDetail { slug: String },
     
+    #[route("/publications/:slug/review")]
+    PublicationReview { slug: String },
+    
     #[route("/domain-management/:publication_id")]
     DomainManagement { publication_id: String },
     
@@ -95,6 +103,21 @@ pub enum Route {
     #[route("/earnings")]
     Earnings {},
     
+    #[route("/reading-history")]
+    ReadingHistory {},
+    
+    #[route("/offline")]
+    Offline {},
+    
+    #[route("/comments/moderation")]
+    CommentModeration {},
+    
+    #[route("/notes")]
+    PrivateNotes {},
+    
+    #[route("/notifications")]
+    Notifications {},
+    
     #[route("/:..route")]
     NotFound { route: Vec<String> },
 }
@@ -228,6 +251,15 @@ fn PublicationDetail(slug: String) -> Element {
     }
 }
 
+#[component]
+fn PublicationReview(slug: String) -> Element {
+    rsx! {
+        ProtectedRoute {
+            PublicationReviewPage { slug }
+        }
+    }
+}
+
 #[component]
 fn DomainManagement(publication_id: String) -> Element {
     rsx! {
@@ -256,6 +288,47 @@ fn Earnings() -> Element {
     }
 }
 
+#[component]
+fn ReadingHistory() -> Element {
+    rsx! {
+        ReadingHistoryPage {}
+    }
+}
+
+#[component]
+fn Offline() -> Element {
+    rsx! {
+        OfflinePage {}
+    }
+}
+
+#[component]
+fn CommentModeration() -> Element {
+    rsx! {
+        ProtectedRoute {
+            CommentModerationPage {}
+        }
+    }
+}
+
+#[component]
+fn PrivateNotes() -> Element {
+    rsx! {
+        ProtectedRoute {
+            PrivateNotesPage {}
+        }
+    }
+}
+
+#[component]
+fn Notifications() -> Element {
+    rsx! {
+        ProtectedRoute {
+            NotificationsPage {}
+        }
+    }
+}
+
 #[component]
 fn NotFound(route: Vec<String>) -> Element {
     rsx! {
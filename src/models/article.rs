@@ -41,6 +41,9 @@ pub struct Article {
     pub is_clapped: Option<bool>,
     #[serde(default)]
     pub user_clap_count: Option<i32>,
+    // 保存时确认存在的被提及用户名，正文中只有这些 @ 会链接到个人主页
+    #[serde(default)]
+    pub mentions: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -114,6 +117,9 @@ pub struct CreateArticleRequest {
     pub seo_description: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub seo_keywords: Option<Vec<String>>,
+    // 文中 @提及的用户名，发布时由服务端通知
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mentions: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -128,6 +134,8 @@ pub struct UpdateArticleRequest {
     pub series_order: Option<i32>,
     pub is_paid_content: Option<bool>,
     pub tags: Option<Vec<String>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub mentions: Vec<String>,
 }
 
 /// 处理 SurrealDB Thing ID 的反序列化
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
-use crate::models::user::User;
+use crate::{collab::anchor::TextAnchor, models::user::User};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Comment {
@@ -10,6 +10,18 @@ pub struct Comment {
     pub parent_id: Option<String>,
     pub content: String,
     pub is_author_response: bool,
+    // 作者置顶的评论排在最前面
+    #[serde(default)]
+    pub is_pinned: bool,
+    // 被作者隐藏的评论只有作者和评论者本人能看到
+    #[serde(default)]
+    pub is_hidden: bool,
+    // 针对正文某一段的评论，用与高亮相同的原文引用锚点定位
+    #[serde(default)]
+    pub anchor: Option<TextAnchor>,
+    // 发表时确认存在的被提及用户名，只有这些 @ 会链接到个人主页
+    #[serde(default)]
+    pub mentions: Vec<String>,
     pub clap_count: i64,
     pub is_edited: bool,
     pub is_deleted: bool,
@@ -27,6 +39,52 @@ pub struct CommentWithAuthor {
     pub author_avatar: Option<String>,
     pub user_has_clapped: bool,
     pub replies: Vec<CommentWithAuthor>,
+    // 直接回复的总数，replies 只包含已加载的部分
+    #[serde(default)]
+    pub reply_count: i64,
+}
+
+impl CommentWithAuthor {
+    /// 还没加载的直接回复数
+    pub fn remaining_replies(&self) -> i64 {
+        (self.reply_count - self.replies.len() as i64).max(0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CommentSort {
+    Top,
+    Newest,
+    Oldest,
+    // 作者回复的评论排在前面
+    AuthorFirst,
+}
+
+impl CommentSort {
+    pub const ALL: [CommentSort; 4] = [
+        CommentSort::Top,
+        CommentSort::Newest,
+        CommentSort::Oldest,
+        CommentSort::AuthorFirst,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommentSort::Top => "top",
+            CommentSort::Newest => "newest",
+            CommentSort::Oldest => "oldest",
+            CommentSort::AuthorFirst => "author",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CommentSort::Top => "热门",
+            CommentSort::Newest => "最新",
+            CommentSort::Oldest => "最早",
+            CommentSort::AuthorFirst => "作者回复优先",
+        }
+    }
 }
 
 impl Default for Comment {
@@ -38,6 +96,10 @@ impl Default for Comment {
             parent_id: None,
             content: String::new(),
             is_author_response: false,
+            is_pinned: false,
+            is_hidden: false,
+            anchor: None,
+            mentions: Vec::new(),
             clap_count: 0,
             is_edited: false,
             is_deleted: false,
@@ -46,4 +108,130 @@ impl Default for Comment {
             deleted_at: None,
         }
     }
-}
\ No newline at end of file
+}
+
+/// 评论实时推送的事件
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CommentEvent {
+    Created { comment: CommentWithAuthor },
+    Updated { comment: Comment },
+    Deleted { comment_id: String },
+    Clapped { comment_id: String, clap_count: i64 },
+}
+
+/// 在评论树中查找评论
+pub fn find_comment_mut<'a>(
+    comments: &'a mut [CommentWithAuthor],
+    comment_id: &str,
+) -> Option<&'a mut CommentWithAuthor> {
+    for comment in comments.iter_mut() {
+        if comment.comment.id == comment_id {
+            return Some(comment);
+        }
+        if let Some(found) = find_comment_mut(&mut comment.replies, comment_id) {
+            return Some(found);
+        }
+    }
+    None
+}
+
+pub fn contains_comment(comments: &[CommentWithAuthor], comment_id: &str) -> bool {
+    comments
+        .iter()
+        .any(|c| c.comment.id == comment_id || contains_comment(&c.replies, comment_id))
+}
+
+/// 从顶级评论到该评论的 id 路径（含自身）
+pub fn comment_path(comments: &[CommentWithAuthor], comment_id: &str) -> Option<Vec<String>> {
+    for comment in comments {
+        if comment.comment.id == comment_id {
+            return Some(vec![comment.comment.id.clone()]);
+        }
+        if let Some(mut path) = comment_path(&comment.replies, comment_id) {
+            path.insert(0, comment.comment.id.clone());
+            return Some(path);
+        }
+    }
+    None
+}
+
+/// 把新评论放进评论树：顶级评论放在最前（at_end 时放在最后），回复放在父评论回复的末尾；
+/// 找不到父评论时返回 false
+pub fn insert_comment(comments: &mut Vec<CommentWithAuthor>, comment: CommentWithAuthor, at_end: bool) -> bool {
+    if contains_comment(comments, &comment.comment.id) {
+        return true;
+    }
+    match comment.comment.parent_id.clone() {
+        None if at_end => {
+            comments.push(comment);
+            true
+        }
+        None => {
+            comments.insert(0, comment);
+            true
+        }
+        Some(parent_id) => match find_comment_mut(comments, &parent_id) {
+            Some(parent) => {
+                parent.replies.push(comment);
+                parent.reply_count = (parent.reply_count + 1).max(parent.replies.len() as i64);
+                true
+            }
+            None => false,
+        },
+    }
+}
+
+/// 追加一页评论，跳过已经显示的
+pub fn append_comments(comments: &mut Vec<CommentWithAuthor>, page: Vec<CommentWithAuthor>) {
+    for comment in page {
+        if !contains_comment(comments, &comment.comment.id) {
+            comments.push(comment);
+        }
+    }
+}
+
+/// 删除评论：有回复时保留占位，否则从树中移除
+pub fn remove_comment(comments: &mut Vec<CommentWithAuthor>, comment_id: &str) {
+    if let Some(index) = comments.iter().position(|c| c.comment.id == comment_id) {
+        if comments[index].replies.is_empty() {
+            comments.remove(index);
+        } else {
+            let comment = &mut comments[index].comment;
+            comment.is_deleted = true;
+            comment.content.clear();
+        }
+        return;
+    }
+    for comment in comments.iter_mut() {
+        remove_comment(&mut comment.replies, comment_id);
+    }
+}
+
+/// 评论树中不在 known 里的评论（不含回复），按发布时间排序
+pub fn unknown_comments(comments: &[CommentWithAuthor], known: &[CommentWithAuthor]) -> Vec<CommentWithAuthor> {
+    fn collect(comments: &[CommentWithAuthor], known: &[CommentWithAuthor], out: &mut Vec<CommentWithAuthor>) {
+        for comment in comments {
+            if !contains_comment(known, &comment.comment.id) {
+                out.push(CommentWithAuthor { replies: Vec::new(), ..comment.clone() });
+            }
+            collect(&comment.replies, known, out);
+        }
+    }
+    let mut out = Vec::new();
+    collect(comments, known, &mut out);
+    out.sort_by_key(|c| c.comment.created_at);
+    out
+}
+
+/// 用重新拉取的评论更新已显示评论的内容和点赞数，不改变树的结构
+pub fn merge_updates(comments: &mut [CommentWithAuthor], fetched: &[CommentWithAuthor]) {
+    for comment in fetched {
+        if let Some(existing) = find_comment_mut(comments, &comment.comment.id) {
+            existing.comment = comment.comment.clone();
+            existing.user_has_clapped = comment.user_has_clapped;
+            existing.reply_count = existing.reply_count.max(comment.reply_count);
+        }
+        merge_updates(comments, &comment.replies);
+    }
+}
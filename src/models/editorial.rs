@@ -0,0 +1,73 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use crate::collab::anchor::TextAnchor;
+use crate::models::article::Author;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum SuggestionStatus {
+    Pending,
+    Accepted,
+    Rejected,
+}
+
+/// 建议模式下的修改：把锚点处的原文替换为 replacement（插入时原文为空，删除时替换为空）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Suggestion {
+    pub id: String,
+    pub article_id: String,
+    pub author: Author,
+    pub anchor: TextAnchor,
+    pub replacement: String,
+    pub status: SuggestionStatus,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateSuggestionRequest {
+    pub anchor: TextAnchor,
+    pub replacement: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateSuggestionRequest {
+    pub status: SuggestionStatus,
+}
+
+/// 草稿上的编辑批注，锚定在一段文字上，仅出版物成员可见
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DraftComment {
+    pub id: String,
+    pub article_id: String,
+    pub author: Author,
+    pub anchor: TextAnchor,
+    pub body: String,
+    #[serde(default)]
+    pub replies: Vec<DraftCommentReply>,
+    pub resolved: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DraftCommentReply {
+    pub id: String,
+    pub author: Author,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateDraftCommentRequest {
+    pub anchor: TextAnchor,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplyDraftCommentRequest {
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResolveDraftCommentRequest {
+    pub resolved: bool,
+}
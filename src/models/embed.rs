@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+
+// 后端抓取的 OpenGraph 元数据，图片地址由后端代理以免泄露读者信息
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LinkPreview {
+    pub url: String,
+    pub title: Option<String>,
+    pub description: Option<String>,
+    pub image_url: Option<String>,
+    pub site_name: Option<String>,
+    pub favicon_url: Option<String>,
+}
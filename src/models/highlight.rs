@@ -1,6 +1,9 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use crate::collab::anchor::TextAnchor;
 
+/// 读者的高亮。content 与前后文一起构成原文引用选择器，
+/// start_offset/end_offset 是在正文纯文本中的字符位置，两者结合定位高亮
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Highlight {
     pub id: String,
@@ -12,11 +15,61 @@ pub struct Highlight {
     pub end_offset: usize,
     pub start_container_path: String,
     pub end_container_path: String,
+    #[serde(default)]
+    pub prefix: String,
+    #[serde(default)]
+    pub suffix: String,
+    // 文章修改后无法再定位到原文
+    #[serde(default)]
+    pub orphaned: bool,
     pub color: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
 
+impl Highlight {
+    pub fn anchor(&self) -> TextAnchor {
+        TextAnchor {
+            start: self.start_offset,
+            exact: self.content.clone(),
+            prefix: self.prefix.clone(),
+            suffix: self.suffix.clone(),
+        }
+    }
+}
+
+/// 多位读者高亮过的同一段文字，按人数聚合
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PopularHighlight {
+    pub content: String,
+    #[serde(default)]
+    pub prefix: String,
+    #[serde(default)]
+    pub suffix: String,
+    pub start_offset: usize,
+    pub end_offset: usize,
+    pub count: i64,
+}
+
+impl PopularHighlight {
+    pub fn anchor(&self) -> TextAnchor {
+        TextAnchor {
+            start: self.start_offset,
+            exact: self.content.clone(),
+            prefix: self.prefix.clone(),
+            suffix: self.suffix.clone(),
+        }
+    }
+}
+
+/// 作者查看的文章高亮统计
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HighlightReport {
+    pub total_highlights: i64,
+    pub readers: i64,
+    pub passages: Vec<PopularHighlight>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateHighlightRequest {
     pub article_id: String,
@@ -26,17 +79,31 @@ pub struct CreateHighlightRequest {
     pub end_offset: usize,
     pub start_container_path: String,
     pub end_container_path: String,
+    pub prefix: String,
+    pub suffix: String,
     pub color: Option<String>,
+    // 迁移旧的本地高亮时，正文中已找不到的原文仍上传，但标记为失效
+    #[serde(default)]
+    pub orphaned: bool,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct UpdateHighlightRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub note: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub color: Option<String>,
+    // 文章修改后重新定位的结果
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_offset: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_offset: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub orphaned: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct HighlightListResponse {
     pub highlights: Vec<Highlight>,
     pub total: i64,
-}
\ No newline at end of file
+}
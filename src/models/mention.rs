@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+
+// @ 后最多匹配这么多字符
+const MAX_USERNAME_LEN: usize = 30;
+
+/// @提及的候选用户
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MentionCandidate {
+    pub id: String,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+    // 对方关闭了提及、屏蔽了你或被你屏蔽时为 false，仍可输入但不会收到提醒
+    #[serde(default = "default_true")]
+    pub can_mention: bool,
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl MentionCandidate {
+    pub fn name(&self) -> &str {
+        self.display_name.as_deref().unwrap_or(&self.username)
+    }
+
+    /// 不能提及时显示给用户的原因
+    pub fn unavailable_reason(&self) -> &str {
+        self.reason.as_deref().unwrap_or("对方不接收提及")
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ResolveMentionsRequest {
+    pub usernames: Vec<String>,
+}
+
+fn is_username_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+/// 文本中所有 @username（去重，保持出现顺序）。@ 前面是字母数字时（如邮箱）不算提及
+pub fn extract_mentions(text: &str) -> Vec<String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut mentions: Vec<String> = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] == '@' && (i == 0 || !is_username_char(chars[i - 1])) {
+            let name: String = chars[i + 1..]
+                .iter()
+                .take_while(|c| is_username_char(**c))
+                .collect();
+            if !name.is_empty() && name.len() <= MAX_USERNAME_LEN {
+                if !mentions.iter().any(|m| m.eq_ignore_ascii_case(&name)) {
+                    mentions.push(name.clone());
+                }
+                i += name.len();
+            }
+        }
+        i += 1;
+    }
+    mentions
+}
+
+/// 光标（字符下标）正在输入的 @提及：返回 @ 的位置和已输入的用户名前缀
+pub fn mention_query_at(text: &str, cursor: usize) -> Option<(usize, String)> {
+    let chars: Vec<char> = text.chars().collect();
+    let cursor = cursor.min(chars.len());
+    let mut start = cursor;
+    while start > 0 && is_username_char(chars[start - 1]) {
+        start -= 1;
+    }
+    if start == 0 || chars[start - 1] != '@' {
+        return None;
+    }
+    let at = start - 1;
+    if at > 0 && is_username_char(chars[at - 1]) {
+        return None;
+    }
+    if cursor - start > MAX_USERNAME_LEN {
+        return None;
+    }
+    Some((at, chars[start..cursor].iter().collect()))
+}
+
+/// 用选中的用户名替换 [at, cursor) 之间的 @前缀，返回新文本和插入后的光标位置（字符下标）
+pub fn insert_mention(text: &str, at: usize, cursor: usize, username: &str) -> (String, usize) {
+    let chars: Vec<char> = text.chars().collect();
+    let cursor = cursor.min(chars.len());
+    // 光标后紧跟的用户名字符一并替换
+    let mut end = cursor;
+    while end < chars.len() && is_username_char(chars[end]) {
+        end += 1;
+    }
+    let before: String = chars[..at].iter().collect();
+    let after: String = chars[end..].iter().collect();
+    let inserted = format!("@{} ", username);
+    let caret = at + inserted.chars().count();
+    let after = after.strip_prefix(' ').unwrap_or(&after).to_string();
+    (format!("{}{}{}", before, inserted, after), caret)
+}
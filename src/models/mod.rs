@@ -10,3 +10,14 @@ pub mod publication;
 pub mod domain;
 pub mod subscription;
 pub mod bookmark;
+pub mod embed;
+pub mod editorial;
+pub mod submission;
+pub mod reading;
+pub mod preferences;
+pub mod offline;
+pub mod mention;
+pub mod moderation;
+pub mod private_note;
+pub mod notification;
+pub mod privacy;
@@ -0,0 +1,203 @@
+use serde::{Deserialize, Serialize};
+use crate::models::comment::Comment;
+
+/// 评论管理页的筛选条件
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ModerationFilter {
+    All,
+    Reported,
+    // 作者上次查看管理页之后的新评论
+    New,
+    NonFollowers,
+    Hidden,
+}
+
+impl ModerationFilter {
+    pub const ALL: [ModerationFilter; 5] = [
+        ModerationFilter::All,
+        ModerationFilter::Reported,
+        ModerationFilter::New,
+        ModerationFilter::NonFollowers,
+        ModerationFilter::Hidden,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ModerationFilter::All => "all",
+            ModerationFilter::Reported => "reported",
+            ModerationFilter::New => "new",
+            ModerationFilter::NonFollowers => "non_followers",
+            ModerationFilter::Hidden => "hidden",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ModerationFilter::All => "全部",
+            ModerationFilter::Reported => "被举报",
+            ModerationFilter::New => "新评论",
+            ModerationFilter::NonFollowers => "非关注者",
+            ModerationFilter::Hidden => "已隐藏",
+        }
+    }
+}
+
+/// 作者文章下的一条评论，附带文章和举报信息
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct ModerationComment {
+    pub comment: Comment,
+    pub author_name: String,
+    pub author_username: String,
+    pub author_avatar: Option<String>,
+    pub article_title: String,
+    pub article_slug: String,
+    #[serde(default)]
+    pub report_count: i64,
+    #[serde(default)]
+    pub report_reasons: Vec<String>,
+    // 评论者是否关注了作者
+    #[serde(default)]
+    pub is_follower: bool,
+    #[serde(default)]
+    pub is_new: bool,
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+pub struct ModerationListResponse {
+    pub comments: Vec<ModerationComment>,
+    pub total: i64,
+    pub page: i32,
+    pub per_page: i32,
+    pub has_next: bool,
+}
+
+/// 批量管理操作
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ModerationAction {
+    Hide,
+    Unhide,
+    Delete,
+    Pin,
+    Unpin,
+    MarkAuthorResponse,
+    UnmarkAuthorResponse,
+    BlockCommenter,
+}
+
+impl ModerationAction {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ModerationAction::Hide => "隐藏",
+            ModerationAction::Unhide => "取消隐藏",
+            ModerationAction::Delete => "删除",
+            ModerationAction::Pin => "置顶",
+            ModerationAction::Unpin => "取消置顶",
+            ModerationAction::MarkAuthorResponse => "标为作者回应",
+            ModerationAction::UnmarkAuthorResponse => "取消作者回应",
+            ModerationAction::BlockCommenter => "屏蔽评论者",
+        }
+    }
+
+    /// 执行前需要确认的操作
+    pub fn confirm_message(&self, count: usize) -> Option<String> {
+        match self {
+            ModerationAction::Delete => Some(format!("确定删除选中的 {} 条评论吗？删除后无法恢复。", count)),
+            ModerationAction::BlockCommenter => Some(format!(
+                "确定屏蔽选中的 {} 条评论的作者吗？他们将无法再评论你的文章。",
+                count
+            )),
+            _ => None,
+        }
+    }
+
+    /// 在本地评论上应用操作；返回 false 表示评论应从列表中移除
+    pub fn apply(&self, comment: &mut Comment) -> bool {
+        match self {
+            ModerationAction::Hide => comment.is_hidden = true,
+            ModerationAction::Unhide => comment.is_hidden = false,
+            ModerationAction::Pin => comment.is_pinned = true,
+            ModerationAction::Unpin => comment.is_pinned = false,
+            ModerationAction::MarkAuthorResponse => comment.is_author_response = true,
+            ModerationAction::UnmarkAuthorResponse => comment.is_author_response = false,
+            ModerationAction::Delete | ModerationAction::BlockCommenter => return false,
+        }
+        true
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BulkModerationRequest {
+    pub comment_ids: Vec<String>,
+    pub action: ModerationAction,
+}
+
+/// 谁可以评论一篇文章
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum CommentPermission {
+    #[default]
+    Everyone,
+    Followers,
+    Subscribers,
+    Closed,
+}
+
+impl CommentPermission {
+    pub const ALL: [CommentPermission; 4] = [
+        CommentPermission::Everyone,
+        CommentPermission::Followers,
+        CommentPermission::Subscribers,
+        CommentPermission::Closed,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CommentPermission::Everyone => "everyone",
+            CommentPermission::Followers => "followers",
+            CommentPermission::Subscribers => "subscribers",
+            CommentPermission::Closed => "closed",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CommentPermission::Everyone => "所有人",
+            CommentPermission::Followers => "仅关注者",
+            CommentPermission::Subscribers => "仅付费订阅者",
+            CommentPermission::Closed => "关闭评论",
+        }
+    }
+
+    /// 读者没有评论权限时显示的说明
+    pub fn restriction_notice(&self) -> &'static str {
+        match self {
+            CommentPermission::Everyone => "",
+            CommentPermission::Followers => "作者只允许关注者评论这篇文章",
+            CommentPermission::Subscribers => "作者只允许付费订阅者评论这篇文章",
+            CommentPermission::Closed => "作者已关闭这篇文章的评论",
+        }
+    }
+}
+
+/// 文章的评论设置
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CommentSettings {
+    pub permission: CommentPermission,
+    // 当前用户能否评论，由服务端根据关注和订阅关系计算
+    #[serde(default = "default_true", skip_serializing)]
+    pub viewer_can_comment: bool,
+}
+
+impl Default for CommentSettings {
+    fn default() -> Self {
+        Self {
+            permission: CommentPermission::Everyone,
+            viewer_can_comment: true,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
@@ -0,0 +1,166 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use crate::models::article::Author;
+
+/// 通知类型
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationType {
+    Clap,
+    Follow,
+    Reply,
+    Mention,
+    PublicationInvite,
+    Subscription,
+}
+
+impl NotificationType {
+    pub const ALL: [NotificationType; 6] = [
+        NotificationType::Clap,
+        NotificationType::Follow,
+        NotificationType::Reply,
+        NotificationType::Mention,
+        NotificationType::PublicationInvite,
+        NotificationType::Subscription,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationType::Clap => "clap",
+            NotificationType::Follow => "follow",
+            NotificationType::Reply => "reply",
+            NotificationType::Mention => "mention",
+            NotificationType::PublicationInvite => "publication_invite",
+            NotificationType::Subscription => "subscription",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            NotificationType::Clap => "点赞",
+            NotificationType::Follow => "关注",
+            NotificationType::Reply => "回复",
+            NotificationType::Mention => "提及",
+            NotificationType::PublicationInvite => "出版物邀请",
+            NotificationType::Subscription => "订阅",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            NotificationType::Clap => "👏",
+            NotificationType::Follow => "👤",
+            NotificationType::Reply => "💬",
+            NotificationType::Mention => "@",
+            NotificationType::PublicationInvite => "📨",
+            NotificationType::Subscription => "⭐",
+        }
+    }
+
+    // 同一对象上的这些事件会合并成一条通知
+    fn groupable(&self) -> bool {
+        matches!(
+            self,
+            NotificationType::Clap | NotificationType::Follow | NotificationType::Subscription
+        )
+    }
+}
+
+/// 一条通知；点赞、关注等同类事件由服务端按 group_key 合并，actors 只包含最近的几位
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Notification {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub kind: NotificationType,
+    pub actors: Vec<Author>,
+    // 合并后的总人数，可能大于 actors 的长度
+    pub actor_count: i64,
+    pub group_key: Option<String>,
+    // 文章类通知的文章标题和 slug，出版物邀请时为出版物
+    pub target_title: Option<String>,
+    pub target_slug: Option<String>,
+    // 回复和提及所在的评论
+    pub comment_id: Option<String>,
+    // 回复或评论内容摘要
+    pub excerpt: Option<String>,
+    pub is_read: bool,
+    // 最近一次事件的时间
+    pub created_at: DateTime<Utc>,
+}
+
+impl Notification {
+    /// 例如「Alice 和其他 12 人赞了你的文章《标题》」
+    pub fn summary(&self) -> String {
+        let actors = match (self.actors.first(), self.actor_count) {
+            (None, _) => "有人".to_string(),
+            (Some(first), count) if count <= 1 => first.display_name.clone(),
+            (Some(first), 2) => match self.actors.get(1) {
+                Some(second) => format!("{} 和 {}", first.display_name, second.display_name),
+                None => format!("{} 和其他 1 人", first.display_name),
+            },
+            (Some(first), count) => format!("{} 和其他 {} 人", first.display_name, count - 1),
+        };
+        let title = self
+            .target_title
+            .as_ref()
+            .map(|t| format!("《{}》", t))
+            .unwrap_or_default();
+        match self.kind {
+            NotificationType::Clap => format!("{} 赞了你的文章{}", actors, title),
+            NotificationType::Follow => format!("{} 关注了你", actors),
+            NotificationType::Reply => format!("{} 回复了你在{}下的评论", actors, title),
+            NotificationType::Mention => format!("{} 在{}中提到了你", actors, title),
+            NotificationType::PublicationInvite => format!("{} 邀请你加入出版物{}", actors, title),
+            NotificationType::Subscription => format!("{} 付费订阅了你", actors),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct NotificationListResponse {
+    pub notifications: Vec<Notification>,
+    pub total: i64,
+    pub page: i32,
+    pub per_page: i32,
+    pub has_next: bool,
+    // 所有未读通知数，不受类型筛选影响
+    #[serde(default)]
+    pub unread_count: i64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct UnreadCountResponse {
+    pub unread_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MarkReadRequest {
+    pub notification_ids: Vec<String>,
+}
+
+/// 通知实时推送的事件，unread_count 为服务端计算的最新未读数
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum NotificationEvent {
+    Created { notification: Notification, unread_count: i64 },
+    Read { notification_ids: Vec<String>, unread_count: i64 },
+    AllRead,
+}
+
+/// 把推送来的通知合并进列表：同组的通知替换旧的一条并移到最前
+pub fn merge_notification(list: &mut Vec<Notification>, incoming: Notification) {
+    list.retain(|n| {
+        n.id != incoming.id
+            && !(incoming.kind.groupable()
+                && n.kind == incoming.kind
+                && n.group_key.is_some()
+                && n.group_key == incoming.group_key)
+    });
+    list.insert(0, incoming);
+}
+
+pub fn mark_read_local(list: &mut [Notification], ids: &[String]) {
+    for notification in list.iter_mut().filter(|n| ids.contains(&n.id)) {
+        notification.is_read = true;
+    }
+}
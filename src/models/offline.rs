@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use super::article::Article;
+
+/// 已离线保存的文章，文章 JSON 和图片本身存放在 Cache Storage 中
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OfflineArticle {
+    pub article_id: String,
+    pub slug: String,
+    pub title: String,
+    pub excerpt: Option<String>,
+    pub cover_image_url: Option<String>,
+    pub author_name: String,
+    pub reading_time: i32,
+    // 缓存的图片地址，删除时一并清理
+    pub images: Vec<String>,
+    // 文章 JSON 与可计量图片的大致字节数
+    pub size_bytes: u64,
+    pub saved_at: DateTime<Utc>,
+    // 超出存储上限时先清理最久没有打开的文章
+    pub last_opened_at: DateTime<Utc>,
+}
+
+impl OfflineArticle {
+    pub fn from_article(article: &Article, images: Vec<String>, size_bytes: u64) -> Self {
+        let now = Utc::now();
+        Self {
+            article_id: article.id.clone(),
+            slug: article.slug.clone(),
+            title: article.title.clone(),
+            excerpt: article.excerpt.clone(),
+            cover_image_url: article.cover_image_url.clone(),
+            author_name: if article.author.display_name.is_empty() {
+                article.author.username.clone()
+            } else {
+                article.author.display_name.clone()
+            },
+            reading_time: article.reading_time,
+            images,
+            size_bytes,
+            saved_at: now,
+            last_opened_at: now,
+        }
+    }
+}
+
+/// 以 MB 为单位的可选存储上限
+pub const STORAGE_LIMITS_MB: [u64; 4] = [50, 100, 200, 500];
+
+pub fn format_bytes(bytes: f64) -> String {
+    if bytes < 1024.0 {
+        format!("{} B", bytes as u64)
+    } else if bytes < 1024.0 * 1024.0 {
+        format!("{:.1} KB", bytes / 1024.0)
+    } else if bytes < 1024.0 * 1024.0 * 1024.0 {
+        format!("{:.1} MB", bytes / 1024.0 / 1024.0)
+    } else {
+        format!("{:.1} GB", bytes / 1024.0 / 1024.0 / 1024.0)
+    }
+}
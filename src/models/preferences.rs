@@ -0,0 +1,332 @@
+use serde::{Deserialize, Serialize};
+
+/// 正文字体
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ReaderFont {
+    #[default]
+    Serif,
+    Sans,
+    Mono,
+}
+
+impl ReaderFont {
+    pub const ALL: [ReaderFont; 3] = [ReaderFont::Serif, ReaderFont::Sans, ReaderFont::Mono];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReaderFont::Serif => "衬线",
+            ReaderFont::Sans => "无衬线",
+            ReaderFont::Mono => "等宽",
+        }
+    }
+
+    pub fn font_family(&self) -> &'static str {
+        match self {
+            ReaderFont::Serif => "Merriweather, Georgia, serif",
+            ReaderFont::Sans => "Inter, system-ui, sans-serif",
+            ReaderFont::Mono => "ui-monospace, SFMono-Regular, Menlo, monospace",
+        }
+    }
+}
+
+/// 正文栏宽
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ReaderWidth {
+    Narrow,
+    #[default]
+    Standard,
+    Wide,
+}
+
+impl ReaderWidth {
+    pub const ALL: [ReaderWidth; 3] = [ReaderWidth::Narrow, ReaderWidth::Standard, ReaderWidth::Wide];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReaderWidth::Narrow => "窄",
+            ReaderWidth::Standard => "标准",
+            ReaderWidth::Wide => "宽",
+        }
+    }
+
+    pub fn max_width_class(&self) -> &'static str {
+        match self {
+            ReaderWidth::Narrow => "max-w-2xl",
+            ReaderWidth::Standard => "max-w-3xl",
+            ReaderWidth::Wide => "max-w-5xl",
+        }
+    }
+}
+
+// 字号倍率和行高的可选档位
+pub const FONT_SCALES: [f64; 5] = [0.875, 1.0, 1.125, 1.25, 1.5];
+pub const LINE_HEIGHTS: [f64; 3] = [1.5, 1.75, 2.0];
+
+/// 文章页的排版偏好
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ReaderPreferences {
+    pub font: ReaderFont,
+    pub font_scale: f64,
+    pub line_height: f64,
+    pub width: ReaderWidth,
+}
+
+impl Default for ReaderPreferences {
+    fn default() -> Self {
+        Self {
+            font: ReaderFont::default(),
+            font_scale: 1.0,
+            line_height: 1.75,
+            width: ReaderWidth::default(),
+        }
+    }
+}
+
+impl ReaderPreferences {
+    /// 正文容器上的 CSS 变量，由 prose.css 中的 .reader-content 规则使用
+    pub fn css_variables(&self) -> String {
+        format!(
+            "--reader-font: {}; --reader-scale: {}; --reader-line-height: {};",
+            self.font.font_family(),
+            self.font_scale,
+            self.line_height
+        )
+    }
+
+    /// 在档位间切换字号，step 为 -1 或 1
+    pub fn step_font_scale(&self, step: i32) -> f64 {
+        let current = FONT_SCALES
+            .iter()
+            .position(|s| (s - self.font_scale).abs() < f64::EPSILON)
+            .unwrap_or(1) as i32;
+        let index = (current + step).clamp(0, FONT_SCALES.len() as i32 - 1);
+        FONT_SCALES[index as usize]
+    }
+}
+
+/// 同步到个人资料的显示偏好：主题和文章排版
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(default)]
+pub struct DisplayPreferences {
+    // light / dark / system / sepia / high-contrast
+    pub theme: String,
+    pub reader: ReaderPreferences,
+}
+
+/// 可以单独设置通知方式的事件
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NotificationTopic {
+    CommentReply,
+    Mention,
+    Follower,
+    ClapMilestone,
+    PublicationActivity,
+    Subscription,
+}
+
+impl NotificationTopic {
+    pub const ALL: [NotificationTopic; 6] = [
+        NotificationTopic::CommentReply,
+        NotificationTopic::Mention,
+        NotificationTopic::Follower,
+        NotificationTopic::ClapMilestone,
+        NotificationTopic::PublicationActivity,
+        NotificationTopic::Subscription,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationTopic::CommentReply => "comment_reply",
+            NotificationTopic::Mention => "mention",
+            NotificationTopic::Follower => "follower",
+            NotificationTopic::ClapMilestone => "clap_milestone",
+            NotificationTopic::PublicationActivity => "publication_activity",
+            NotificationTopic::Subscription => "subscription",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            NotificationTopic::CommentReply => "评论回复",
+            NotificationTopic::Mention => "提及",
+            NotificationTopic::Follower => "新关注者",
+            NotificationTopic::ClapMilestone => "点赞里程碑",
+            NotificationTopic::PublicationActivity => "出版物动态",
+            NotificationTopic::Subscription => "订阅",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            NotificationTopic::CommentReply => "有人回复你的评论或评论你的文章",
+            NotificationTopic::Mention => "有人在评论或文章中 @ 你",
+            NotificationTopic::Follower => "有人关注了你",
+            NotificationTopic::ClapMilestone => "文章的点赞数达到 10、100、1000 等里程碑",
+            NotificationTopic::PublicationActivity => "出版物邀请、投稿审核结果和出版物新文章",
+            NotificationTopic::Subscription => "有人付费订阅你，或你的订阅即将续费",
+        }
+    }
+}
+
+/// 通知渠道
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NotificationChannel {
+    // 站内通知中心
+    InApp,
+    Email,
+    // 浏览器推送
+    Push,
+}
+
+impl NotificationChannel {
+    pub const ALL: [NotificationChannel; 3] = [
+        NotificationChannel::InApp,
+        NotificationChannel::Email,
+        NotificationChannel::Push,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationChannel::InApp => "in_app",
+            NotificationChannel::Email => "email",
+            NotificationChannel::Push => "push",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            NotificationChannel::InApp => "站内",
+            NotificationChannel::Email => "邮件",
+            NotificationChannel::Push => "浏览器推送",
+        }
+    }
+}
+
+/// 一个事件在各渠道是否通知
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct ChannelPreferences {
+    pub in_app: bool,
+    pub email: bool,
+    pub push: bool,
+}
+
+impl Default for ChannelPreferences {
+    fn default() -> Self {
+        Self {
+            in_app: true,
+            email: true,
+            push: false,
+        }
+    }
+}
+
+impl ChannelPreferences {
+    pub fn get(&self, channel: NotificationChannel) -> bool {
+        match channel {
+            NotificationChannel::InApp => self.in_app,
+            NotificationChannel::Email => self.email,
+            NotificationChannel::Push => self.push,
+        }
+    }
+
+    pub fn set(&mut self, channel: NotificationChannel, enabled: bool) {
+        match channel {
+            NotificationChannel::InApp => self.in_app = enabled,
+            NotificationChannel::Email => self.email = enabled,
+            NotificationChannel::Push => self.push = enabled,
+        }
+    }
+}
+
+/// 邮件摘要频率
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum DigestFrequency {
+    Never,
+    Daily,
+    #[default]
+    Weekly,
+}
+
+impl DigestFrequency {
+    pub const ALL: [DigestFrequency; 3] = [
+        DigestFrequency::Never,
+        DigestFrequency::Daily,
+        DigestFrequency::Weekly,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DigestFrequency::Never => "never",
+            DigestFrequency::Daily => "daily",
+            DigestFrequency::Weekly => "weekly",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DigestFrequency::Never => "不接收",
+            DigestFrequency::Daily => "每天",
+            DigestFrequency::Weekly => "每周",
+        }
+    }
+
+    pub fn from_str(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|f| f.as_str() == value)
+    }
+}
+
+/// 同步到账号的通知偏好：每个事件在各渠道是否通知，以及邮件摘要频率
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(default)]
+pub struct NotificationPreferences {
+    pub comment_reply: ChannelPreferences,
+    pub mention: ChannelPreferences,
+    pub follower: ChannelPreferences,
+    pub clap_milestone: ChannelPreferences,
+    pub publication_activity: ChannelPreferences,
+    pub subscription: ChannelPreferences,
+    pub digest: DigestFrequency,
+}
+
+impl NotificationPreferences {
+    pub fn topic(&self, topic: NotificationTopic) -> ChannelPreferences {
+        match topic {
+            NotificationTopic::CommentReply => self.comment_reply,
+            NotificationTopic::Mention => self.mention,
+            NotificationTopic::Follower => self.follower,
+            NotificationTopic::ClapMilestone => self.clap_milestone,
+            NotificationTopic::PublicationActivity => self.publication_activity,
+            NotificationTopic::Subscription => self.subscription,
+        }
+    }
+
+    pub fn topic_mut(&mut self, topic: NotificationTopic) -> &mut ChannelPreferences {
+        match topic {
+            NotificationTopic::CommentReply => &mut self.comment_reply,
+            NotificationTopic::Mention => &mut self.mention,
+            NotificationTopic::Follower => &mut self.follower,
+            NotificationTopic::ClapMilestone => &mut self.clap_milestone,
+            NotificationTopic::PublicationActivity => &mut self.publication_activity,
+            NotificationTopic::Subscription => &mut self.subscription,
+        }
+    }
+
+    /// 是否有事件开启了浏览器推送
+    pub fn wants_push(&self) -> bool {
+        NotificationTopic::ALL.iter().any(|t| self.topic(*t).push)
+    }
+
+    /// 邮件和推送是否已全部关闭
+    pub fn is_unsubscribed(&self) -> bool {
+        self.digest == DigestFrequency::Never
+            && NotificationTopic::ALL.iter().all(|t| {
+                let channels = self.topic(*t);
+                !channels.email && !channels.push
+            })
+    }
+}
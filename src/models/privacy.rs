@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use crate::models::{article::Author, moderation::CommentPermission};
+
+/// 谁可以 @ 我
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MentionPermission {
+    #[default]
+    Everyone,
+    // 只有我关注的人
+    Following,
+    Nobody,
+}
+
+impl MentionPermission {
+    pub const ALL: [MentionPermission; 3] = [
+        MentionPermission::Everyone,
+        MentionPermission::Following,
+        MentionPermission::Nobody,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MentionPermission::Everyone => "everyone",
+            MentionPermission::Following => "following",
+            MentionPermission::Nobody => "nobody",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MentionPermission::Everyone => "所有人",
+            MentionPermission::Following => "我关注的人",
+            MentionPermission::Nobody => "没有人",
+        }
+    }
+
+    pub fn from_str(value: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.as_str() == value)
+    }
+}
+
+/// 隐私与可发现性设置
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(default)]
+pub struct PrivacySettings {
+    // 在个人主页和文章页加 noindex
+    pub hide_from_search_engines: bool,
+    // 不出现在站内搜索和 @ 候选中
+    pub hide_from_site_search: bool,
+    pub hide_follow_lists: bool,
+    pub hide_reading_activity: bool,
+    // 新文章的默认评论权限，单篇文章可在评论管理中另行设置
+    pub default_comment_permission: CommentPermission,
+    pub mention_permission: MentionPermission,
+}
+
+/// 屏蔽或静音
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RelationKind {
+    // 对方无法关注、评论、提及或私信我，双方互相看不到评论
+    Block,
+    // 只在我这边隐藏对方的文章和评论，对方不会察觉
+    Mute,
+}
+
+impl RelationKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RelationKind::Block => "block",
+            RelationKind::Mute => "mute",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            RelationKind::Block => "屏蔽",
+            RelationKind::Mute => "静音",
+        }
+    }
+
+    pub fn undo_label(&self) -> &'static str {
+        match self {
+            RelationKind::Block => "取消屏蔽",
+            RelationKind::Mute => "取消静音",
+        }
+    }
+
+    pub fn confirm_message(&self, username: &str) -> String {
+        match self {
+            RelationKind::Block => format!(
+                "确定屏蔽 @{} 吗？对方将无法关注你、评论你的文章或提及你，你们也会互相看不到对方的评论。",
+                username
+            ),
+            RelationKind::Mute => format!(
+                "确定静音 @{} 吗？你将不再在首页和评论中看到对方的内容，对方不会收到通知。",
+                username
+            ),
+        }
+    }
+}
+
+/// 屏蔽或静音列表中的用户
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RelatedUser {
+    pub user: Author,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RelatedUserListResponse {
+    pub users: Vec<RelatedUser>,
+    pub total: i64,
+    pub has_next: bool,
+}
+
+/// 屏蔽和静音的用户 id，用于在客户端过滤内容
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(default)]
+pub struct UserRelationIds {
+    pub blocked: Vec<String>,
+    pub muted: Vec<String>,
+}
@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use crate::{collab::anchor::TextAnchor, models::article::Author};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum NoteStatus {
+    Open,
+    Resolved,
+}
+
+impl NoteStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NoteStatus::Open => "open",
+            NoteStatus::Resolved => "resolved",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            NoteStatus::Open => "待处理",
+            NoteStatus::Resolved => "已处理",
+        }
+    }
+}
+
+/// 读者针对正文中一段文字私下发给作者的留言（如错别字、事实错误）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PrivateNote {
+    pub id: String,
+    pub article_id: String,
+    pub article_title: String,
+    pub article_slug: String,
+    pub sender: Author,
+    pub anchor: TextAnchor,
+    pub message: String,
+    pub status: NoteStatus,
+    // 作者的回复只有发送者能看到
+    pub reply: Option<String>,
+    pub replied_at: Option<DateTime<Utc>>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PrivateNoteListResponse {
+    pub notes: Vec<PrivateNote>,
+    pub total: i64,
+    // 所有待处理的留言数，不受筛选条件影响
+    #[serde(default)]
+    pub open_count: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CreatePrivateNoteRequest {
+    pub article_id: String,
+    pub anchor: TextAnchor,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdatePrivateNoteRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<NoteStatus>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reply: Option<String>,
+}
@@ -101,3 +101,26 @@ pub struct MemberListResponse {
     pub page: i32,
     pub total_pages: i32,
 }
+
+impl MemberRole {
+    /// 可以直接发布到出版物、处理投稿的角色
+    pub fn can_publish(&self) -> bool {
+        matches!(self, MemberRole::Owner | MemberRole::Editor)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            MemberRole::Owner => "所有者",
+            MemberRole::Editor => "编辑",
+            MemberRole::Writer => "作者",
+            MemberRole::Contributor => "投稿人",
+        }
+    }
+}
+
+/// 当前用户加入的出版物及其角色
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PublicationMembership {
+    pub publication: Publication,
+    pub role: MemberRole,
+}
@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use super::article::Article;
+
+// 读到这个比例以上视为已经开始阅读
+const STARTED_PROGRESS: f64 = 0.05;
+// 读到这个比例以上视为读完
+const FINISHED_PROGRESS: f64 = 0.95;
+
+/// 一篇文章的阅读记录：阅读进度，以及书架和历史列表展示用的文章信息
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReadingRecord {
+    pub article_id: String,
+    pub article_title: String,
+    pub article_slug: String,
+    pub article_excerpt: Option<String>,
+    pub article_cover_image: Option<String>,
+    pub article_reading_time: i32,
+    pub author_name: String,
+    // 已读比例 0.0 - 1.0，同时用于恢复阅读位置
+    pub progress: f64,
+    pub last_read_at: DateTime<Utc>,
+}
+
+impl ReadingRecord {
+    pub fn from_article(article: &Article, progress: f64) -> Self {
+        Self {
+            article_id: article.id.clone(),
+            article_title: article.title.clone(),
+            article_slug: article.slug.clone(),
+            article_excerpt: article.excerpt.clone(),
+            article_cover_image: article.cover_image_url.clone(),
+            article_reading_time: article.reading_time,
+            author_name: article.author.display_name.clone(),
+            progress,
+            last_read_at: Utc::now(),
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.progress >= FINISHED_PROGRESS
+    }
+
+    /// 读了一部分但还没读完，可以从上次的位置继续
+    pub fn can_resume(&self) -> bool {
+        self.progress >= STARTED_PROGRESS && !self.is_finished()
+    }
+
+    pub fn minutes_left(&self) -> i32 {
+        ((1.0 - self.progress) * self.article_reading_time as f64).ceil().max(1.0) as i32
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateReadingProgressRequest {
+    pub progress: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReadingHistoryResponse {
+    pub records: Vec<ReadingRecord>,
+    pub total: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ReadingHistorySettings {
+    // 暂停后不再记录阅读历史和进度
+    pub paused: bool,
+}
@@ -27,6 +27,20 @@ pub struct SeriesWithArticles {
     pub articles: Vec<SeriesArticle>,
 }
 
+impl SeriesWithArticles {
+    /// 按系列顺序排列的文章
+    pub fn parts(&self) -> Vec<&SeriesArticle> {
+        let mut parts: Vec<&SeriesArticle> = self.articles.iter().collect();
+        parts.sort_by_key(|part| part.order);
+        parts
+    }
+
+    /// 文章在系列中的位置（从 0 开始）
+    pub fn position(&self, article_id: &str) -> Option<usize> {
+        self.parts().iter().position(|part| part.article.id == article_id)
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct CreateSeriesRequest {
     pub title: String,
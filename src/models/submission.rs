@@ -0,0 +1,143 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+use crate::models::article::Author;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SubmissionStatus {
+    Submitted,
+    InReview,
+    ChangesRequested,
+    Approved,
+    Scheduled,
+    Rejected,
+}
+
+impl SubmissionStatus {
+    pub const ALL: [SubmissionStatus; 6] = [
+        SubmissionStatus::Submitted,
+        SubmissionStatus::InReview,
+        SubmissionStatus::ChangesRequested,
+        SubmissionStatus::Approved,
+        SubmissionStatus::Scheduled,
+        SubmissionStatus::Rejected,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SubmissionStatus::Submitted => "submitted",
+            SubmissionStatus::InReview => "in_review",
+            SubmissionStatus::ChangesRequested => "changes_requested",
+            SubmissionStatus::Approved => "approved",
+            SubmissionStatus::Scheduled => "scheduled",
+            SubmissionStatus::Rejected => "rejected",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SubmissionStatus::Submitted => "已投稿",
+            SubmissionStatus::InReview => "审核中",
+            SubmissionStatus::ChangesRequested => "需要修改",
+            SubmissionStatus::Approved => "已通过",
+            SubmissionStatus::Scheduled => "已排期",
+            SubmissionStatus::Rejected => "已拒绝",
+        }
+    }
+
+    pub fn badge_class(&self) -> &'static str {
+        match self {
+            SubmissionStatus::Submitted => "bg-blue-100 text-blue-800",
+            SubmissionStatus::InReview => "bg-yellow-100 text-yellow-800",
+            SubmissionStatus::ChangesRequested => "bg-orange-100 text-orange-800",
+            SubmissionStatus::Approved => "bg-green-100 text-green-800",
+            SubmissionStatus::Scheduled => "bg-purple-100 text-purple-800",
+            SubmissionStatus::Rejected => "bg-gray-200 text-gray-700",
+        }
+    }
+
+    /// 编辑在该状态下可以把投稿转到的状态
+    pub fn editor_transitions(&self) -> &'static [SubmissionStatus] {
+        match self {
+            SubmissionStatus::Submitted => &[SubmissionStatus::InReview, SubmissionStatus::Rejected],
+            SubmissionStatus::InReview => &[
+                SubmissionStatus::ChangesRequested,
+                SubmissionStatus::Approved,
+                SubmissionStatus::Rejected,
+            ],
+            SubmissionStatus::ChangesRequested => &[SubmissionStatus::Rejected],
+            SubmissionStatus::Approved => &[SubmissionStatus::Scheduled],
+            SubmissionStatus::Scheduled => &[SubmissionStatus::Approved],
+            SubmissionStatus::Rejected => &[],
+        }
+    }
+
+    /// 已通过或已排期的投稿可以由编辑直接发布
+    pub fn can_publish(&self) -> bool {
+        matches!(self, SubmissionStatus::Approved | SubmissionStatus::Scheduled)
+    }
+
+    /// 作者在需要修改后可以重新投稿
+    pub fn can_resubmit(&self) -> bool {
+        matches!(self, SubmissionStatus::ChangesRequested)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SubmissionArticle {
+    pub id: String,
+    pub title: String,
+    pub slug: String,
+    pub excerpt: Option<String>,
+}
+
+/// 投稿的一次状态变化，附带编辑或作者的备注
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SubmissionEvent {
+    pub actor: Author,
+    pub status: SubmissionStatus,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Submission {
+    pub id: String,
+    pub publication_id: String,
+    pub article: SubmissionArticle,
+    pub author: Author,
+    pub status: SubmissionStatus,
+    pub scheduled_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub history: Vec<SubmissionEvent>,
+    pub submitted_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Submission {
+    /// 最近一条带备注的记录
+    pub fn latest_note(&self) -> Option<&SubmissionEvent> {
+        self.history.iter().rev().find(|e| e.note.as_deref().is_some_and(|n| !n.is_empty()))
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateSubmissionRequest {
+    pub article_id: String,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateSubmissionRequest {
+    pub status: SubmissionStatus,
+    pub note: Option<String>,
+    pub scheduled_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmissionListResponse {
+    pub submissions: Vec<Submission>,
+    pub total: i64,
+    pub page: i32,
+    pub total_pages: i32,
+}
@@ -81,6 +81,37 @@ pub struct UserSubscriptionsResponse {
     pub total_pages: i32,
 }
 
+// 非订阅用户每月可以免费阅读的会员文章数
+pub const FREE_MEMBER_STORIES_PER_MONTH: i32 = 3;
+
+/// 会员文章的每月免费阅读额度
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReadingMeter {
+    pub limit: i32,
+    pub used: i32,
+    // 本月已用额度解锁的文章，重复打开不再计数
+    #[serde(default)]
+    pub unlocked_article_ids: Vec<String>,
+    // 额度重置的月份，如 "2026-10"
+    pub period: String,
+}
+
+impl ReadingMeter {
+    pub fn remaining(&self) -> i32 {
+        (self.limit - self.used).max(0)
+    }
+
+    /// 额度接口使用不带 "article:" 前缀的文章 id，比较和提交前统一去掉前缀
+    pub fn article_key(article_id: &str) -> &str {
+        article_id.trim_start_matches("article:")
+    }
+
+    pub fn has_unlocked(&self, article_id: &str) -> bool {
+        let key = Self::article_key(article_id);
+        self.unlocked_article_ids.iter().any(|id| Self::article_key(id) == key)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PaymentMethod {
     pub id: String,
@@ -54,6 +54,9 @@ pub struct UserProfile {
     pub is_verified: bool,
     #[serde(default)]
     pub is_suspended: bool,
+    // 用户在隐私设置中隐藏了关注者和正在关注列表
+    #[serde(default)]
+    pub hide_follow_lists: bool,
     pub created_at: DateTime<Utc>,
 }
 
@@ -16,9 +16,34 @@ pub struct ArticleVersion {
     pub author_id: String,
     pub author_name: String,
     pub change_summary: Option<String>,
+    // 作者为版本起的名字，如“已发给编辑”“正式发布 v1”
+    #[serde(default)]
+    pub label: Option<String>,
+    // 固定的版本不会被保留策略清理
+    #[serde(default)]
+    pub pinned: bool,
+    // 保存草稿时自动创建的快照
+    #[serde(default)]
+    pub is_auto: bool,
+    // 读者当前看到的已发布版本
+    #[serde(default)]
+    pub is_live: bool,
     pub created_at: DateTime<Utc>,
 }
 
+impl ArticleVersion {
+    /// 早期的自动快照没有 is_auto 字段，只能靠变更说明识别
+    pub fn is_automatic(&self) -> bool {
+        self.is_auto || self.change_summary.as_deref() == Some(AUTO_SAVE_SUMMARY)
+    }
+
+    pub fn is_labeled(&self) -> bool {
+        self.label.as_deref().is_some_and(|label| !label.trim().is_empty())
+    }
+}
+
+pub const AUTO_SAVE_SUMMARY: &str = "自动保存";
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct VersionDiff {
     pub field: String,
@@ -37,10 +62,93 @@ pub struct ArticleVersionComparison {
 pub struct CreateVersionRequest {
     pub article_id: String,
     pub change_summary: Option<String>,
+    pub label: Option<String>,
+    // 服务器创建自动快照时按保留策略清理旧快照：一小时内全部保留，一天内每小时保留最新一个，
+    // 更早的每天保留最新一个；命名、固定、线上版本以及最新的版本始终保留
+    pub is_auto: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateVersionRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pinned: Option<bool>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RestoreVersionRequest {
     pub article_id: String,
     pub version_id: String,
-}
\ No newline at end of file
+}
+
+/// 可以从历史版本单独恢复的字段
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VersionField {
+    Title,
+    Subtitle,
+    Excerpt,
+    CoverImage,
+    Tags,
+    Content,
+}
+
+impl VersionField {
+    /// 除正文外逐项比较的字段，正文单独按行比较
+    pub const METADATA: [VersionField; 5] = [
+        VersionField::Title,
+        VersionField::Subtitle,
+        VersionField::Excerpt,
+        VersionField::CoverImage,
+        VersionField::Tags,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            VersionField::Title => "标题",
+            VersionField::Subtitle => "副标题",
+            VersionField::Excerpt => "摘要",
+            VersionField::CoverImage => "封面图",
+            VersionField::Tags => "标签",
+            VersionField::Content => "正文",
+        }
+    }
+}
+
+/// 参与比较的一组字段：可以来自历史版本，也可以是编辑器中尚未保存的草稿
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct VersionSnapshot {
+    pub title: String,
+    pub subtitle: String,
+    pub excerpt: String,
+    pub cover_image_url: String,
+    // 与编辑器标签输入框一致，以逗号分隔
+    pub tags: String,
+    pub content: String,
+}
+
+impl VersionSnapshot {
+    pub fn field(&self, field: VersionField) -> &str {
+        match field {
+            VersionField::Title => &self.title,
+            VersionField::Subtitle => &self.subtitle,
+            VersionField::Excerpt => &self.excerpt,
+            VersionField::CoverImage => &self.cover_image_url,
+            VersionField::Tags => &self.tags,
+            VersionField::Content => &self.content,
+        }
+    }
+}
+
+impl From<&ArticleVersion> for VersionSnapshot {
+    fn from(version: &ArticleVersion) -> Self {
+        Self {
+            title: version.title.clone(),
+            subtitle: version.subtitle.clone().unwrap_or_default(),
+            excerpt: version.excerpt.clone(),
+            cover_image_url: version.cover_image_url.clone().unwrap_or_default(),
+            tags: version.tags.join(", "),
+            content: version.content.clone(),
+        }
+    }
+}
@@ -1,10 +1,12 @@
 use dioxus::prelude::*;
 use dioxus_router::prelude::*;
 use crate::{
-    api::articles::{ArticleService, ClapResponse},
-    models::article::Article,
-    hooks::use_auth,
-    components::{CommentSection, ShareModal, HighlightSystem, RelatedArticles},
+    api::articles::ArticleService,
+    models::{article::{Article, Author}, subscription::FREE_MEMBER_STORIES_PER_MONTH, user::User},
+    hooks::{use_article_access, use_auth, use_embeds, use_online, use_reading_progress, use_series, use_theme, ArticleAccess, OfflineStore},
+    components::{ClapButton, CommentSection, ShareModal, HighlightSystem, HighlightReportPanel, InlineComments, NotificationBell, PrivateNoteInbox, OfflineSaveButton, ReaderSettingsPanel, RelatedArticles, SeriesBanner, SeriesPager, SubscriptionWidget, TableOfContents, HIGHLIGHT_ROOT_ID},
+    collab::anchor::TextAnchor,
+    rendering,
     Route,
 };
 
@@ -13,12 +15,35 @@ pub fn ArticlePage(slug: String) -> Element {
     let mut article = use_signal(|| None::<Article>);
     let mut loading = use_signal(|| true);
     let mut error = use_signal(|| None::<String>);
-    let mut is_clapping = use_signal(|| false);
     let mut is_bookmarking = use_signal(|| false);
     let mut show_share = use_signal(|| false);
+    let mut show_reader_settings = use_signal(|| false);
+    // 高亮工具栏中选中文字后发起的段落评论
+    let mut inline_draft = use_signal(|| None::<TextAnchor>);
+    
+    let mut read_offline = use_signal(|| false);
     
     let auth = use_auth();
+    let theme = use_theme();
+    let online = use_online();
     let navigator = use_navigator();
+    let mut access = use_article_access(article);
+    let series_id = use_memo(move || article().and_then(|art| art.series.map(|s| s.id)));
+    let series = use_series(series_id);
+    let is_author = use_memo(move || {
+        let author_id = article.read().as_ref().map(|art| art.author.id.clone());
+        author_id.is_some() && auth.read().user.as_ref().map(|u| u.id.clone()) == author_id
+    });
+    
+    // 渲染公式与图表、生成标题锚点后的正文及目录；会员文章未解锁时服务器只返回试读部分
+    let rendered = use_memo(move || {
+        article()
+            .map(|art| rendering::render_article(&art.content_html, &art.mentions))
+            .unwrap_or_default()
+    });
+    let content_html = use_memo(move || rendered().0);
+    use_embeds(content_html);
+    let mut reading = use_reading_progress(article, HIGHLIGHT_ROOT_ID);
     
     // 加载文章详情
     use_effect(move || {
@@ -40,7 +65,14 @@ pub fn ArticlePage(slug: String) -> Element {
                     article.set(Some(article_data));
                 }
                 Err(e) => {
-                    error.set(Some(e.message));
+                    // 网络不可用时读取离线保存的版本
+                    match OfflineStore::load(&slug).await {
+                        Some(saved) => {
+                            read_offline.set(true);
+                            article.set(Some(saved));
+                        }
+                        None => error.set(Some(e.message)),
+                    }
                 }
             }
             
@@ -48,32 +80,17 @@ pub fn ArticlePage(slug: String) -> Element {
         });
     });
     
-    // 处理点赞
-    let handle_clap = move |_| {
-        if !auth.read().is_authenticated {
-            navigator.push(Route::Login {});
-            return;
-        }
-        
-        if let Some(ref art) = article() {
-            let article_id = art.id.clone();
-            spawn(async move {
-                is_clapping.set(true);
-                
-                match ArticleService::clap_article(&article_id, 1).await {
-                    Ok(ClapResponse { user_clap_count, total_claps }) => {
-                        article.write().as_mut().unwrap().user_clap_count = Some(user_clap_count);
-                        article.write().as_mut().unwrap().clap_count = total_claps as i32;
-                        article.write().as_mut().unwrap().is_clapped = Some(true);
-                    }
-                    Err(e) => {
-                        web_sys::console::error_1(&format!("Failed to clap article: {:?}", e).into());
-                    }
+    // 订阅成功后直接解锁全文，不刷新页面
+    let handle_subscribed = move |_| {
+        access.set(ArticleAccess::Full);
+        let Some(slug) = article.peek().as_ref().map(|art| art.slug.clone()) else { return };
+        spawn(async move {
+            if let Ok(full) = ArticleService::get_article(&slug).await {
+                if let Some(art) = article.write().as_mut() {
+                    art.content_html = full.content_html;
                 }
-                
-                is_clapping.set(false);
-            });
-        }
+            }
+        });
     };
     
     // 处理收藏
@@ -114,6 +131,32 @@ pub fn ArticlePage(slug: String) -> Element {
         div {
             class: "min-h-screen bg-white",
             
+            // 阅读进度条
+            if article().is_some() {
+                div {
+                    class: "fixed top-0 left-0 z-50 h-1 bg-green-600 transition-[width] duration-150",
+                    style: format!("width: {}%", (reading.progress)() * 100.0)
+                }
+            }
+            
+            // 从上次的位置继续阅读
+            if let Some(resume_at) = (reading.resume_at)() {
+                div {
+                    class: "fixed bottom-6 left-1/2 -translate-x-1/2 z-40 flex items-center space-x-4 px-5 py-3 bg-gray-900 text-white text-sm rounded-full shadow-lg",
+                    span { {format!("上次读到 {:.0}%", resume_at * 100.0)} }
+                    button {
+                        class: "font-medium text-green-400 hover:text-green-300",
+                        onclick: move |_| reading.resume(),
+                        "继续阅读"
+                    }
+                    button {
+                        class: "text-gray-400 hover:text-white",
+                        onclick: move |_| reading.dismiss(),
+                        "✕"
+                    }
+                }
+            }
+            
             // 导航栏
             nav {
                 class: "border-b border-gray-100 sticky top-0 bg-white z-10",
@@ -129,6 +172,22 @@ pub fn ArticlePage(slug: String) -> Element {
                         
                         div {
                             class: "flex items-center space-x-4",
+                            // 主题与排版设置
+                            div {
+                                class: "relative",
+                                button {
+                                    class: "px-2 py-1 text-sm font-serif text-gray-700 hover:text-gray-900",
+                                    title: "主题与排版",
+                                    onclick: move |_| show_reader_settings.toggle(),
+                                    "Aa"
+                                }
+                                if show_reader_settings() {
+                                    div {
+                                        class: "absolute right-0 mt-2 w-72 p-4 bg-white dark:bg-gray-800 border border-gray-200 dark:border-gray-700 rounded-lg shadow-lg z-20",
+                                        ReaderSettingsPanel {}
+                                    }
+                                }
+                            }
                             // 出版物入口
                             Link {
                                 to: Route::Publications {},
@@ -136,6 +195,7 @@ pub fn ArticlePage(slug: String) -> Element {
                                 "出版物"
                             }
                             if auth.read().is_authenticated {
+                                NotificationBell {}
                                 button {
                                     class: "px-4 py-2 text-sm font-medium text-white bg-gray-900 rounded-full hover:bg-gray-800",
                                     "写文章"
@@ -167,10 +227,25 @@ pub fn ArticlePage(slug: String) -> Element {
                         class: "bg-red-50 border border-red-200 text-red-700 px-4 py-3 rounded",
                         {err}
                     }
+                    if !online() {
+                        Link {
+                            to: Route::Offline {},
+                            class: "inline-block mt-4 text-sm text-green-600 hover:text-green-700",
+                            "当前没有网络，查看已离线保存的文章 →"
+                        }
+                    }
                 }
             } else if let Some(art) = article() {
                 article {
-                    class: "max-w-3xl mx-auto px-4 sm:px-6 lg:px-8 py-4 sm:py-8",
+                    class: format!("{} mx-auto px-4 sm:px-6 lg:px-8 py-4 sm:py-8 reader-content", theme.reader().width.max_width_class()),
+                    style: theme.reader().css_variables(),
+                    
+                    if read_offline() {
+                        div {
+                            class: "mb-6 bg-yellow-50 border border-yellow-200 text-yellow-800 px-4 py-3 rounded text-sm",
+                            "正在阅读离线保存的版本，评论和点赞需要联网"
+                        }
+                    }
                     
                     // 标题部分
                     header {
@@ -243,19 +318,99 @@ pub fn ArticlePage(slug: String) -> Element {
                         }
                     }
                     
-                    // 文章内容（带高亮系统）
-                    if auth.read().is_authenticated {
+                    // 系列导航
+                    if art.series.is_some() {
+                        SeriesBanner { series, article_id: art.id.clone() }
+                    }
+                    
+                    // 目录
+                    TableOfContents { entries: rendered().1 }
+                    
+                    // 使用免费额度阅读的会员文章
+                    if let ArticleAccess::Metered(meter) = access() {
+                        div {
+                            class: "mb-8 flex items-center justify-between px-4 py-3 bg-yellow-50 dark:bg-yellow-900/20 border border-yellow-200 dark:border-yellow-800 rounded-lg text-sm text-yellow-800 dark:text-yellow-300",
+                            span {
+                                {format!("★ 会员专享文章 · 本月还可免费阅读 {} 篇（共 {} 篇）", meter.remaining(), meter.limit)}
+                            }
+                        }
+                    }
+                    
+                    // 文章内容（带高亮系统，未登录读者只能看到热门高亮）
+                    div {
+                        class: if access().is_locked() { "relative max-h-[60vh] overflow-hidden" } else { "relative" },
                         HighlightSystem {
                             article_id: art.id.clone(),
-                            article_html: art.content_html.clone()
+                            article_html: content_html(),
+                            can_highlight: auth.read().is_authenticated && !access().is_locked(),
+                            on_comment: move |anchor| inline_draft.set(Some(anchor)),
+                            note_recipient: (!is_author()).then(|| art.author.display_name.clone())
                         }
-                    } else {
+                        // 段落评论气泡（试读时不显示）
+                        if !access().is_locked() {
+                            InlineComments {
+                                article_id: art.id.clone(),
+                                article_html: content_html(),
+                                draft: inline_draft
+                            }
+                        }
+                        // 试读部分末尾淡出
+                        if access().is_locked() {
+                            div {
+                                class: "absolute inset-x-0 bottom-0 h-40 bg-gradient-to-b from-transparent to-white dark:to-gray-900 pointer-events-none"
+                            }
+                        }
+                    }
+                    
+                    // 未登录时引导登录使用免费额度
+                    if access() == ArticleAccess::SignInRequired {
                         div {
-                            class: "prose prose-lg max-w-none mb-12",
-                            dangerous_inner_html: "{art.content_html}"
+                            class: "mt-6 text-sm text-center text-gray-500 dark:text-gray-400",
+                            {format!("登录后每月可免费阅读 {} 篇会员文章", FREE_MEMBER_STORIES_PER_MONTH)}
+                            div {
+                                class: "mt-3 space-x-3",
+                                Link {
+                                    to: Route::Login {},
+                                    class: "inline-block px-4 py-2 bg-gray-900 text-white rounded-full font-medium hover:bg-gray-800",
+                                    "登录"
+                                }
+                                Link {
+                                    to: Route::Register {},
+                                    class: "inline-block px-4 py-2 border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-300 rounded-full font-medium hover:bg-gray-50 dark:hover:bg-gray-800",
+                                    "注册成为会员"
+                                }
+                            }
+                        }
+                    }
+
+                    // 免费额度用完或暂时查询不到时引导订阅作者
+                    if matches!(access(), ArticleAccess::Locked(_) | ArticleAccess::MeterUnavailable) {
+                        p {
+                            class: "mt-6 text-sm text-center text-gray-500 dark:text-gray-400",
+                            if let ArticleAccess::Locked(meter) = access() {
+                                {format!("本月 {} 篇免费会员文章已读完，订阅作者即可继续阅读", meter.limit)}
+                            } else {
+                                "暂时无法查询本月免费额度，请稍后刷新重试，或订阅作者继续阅读"
+                            }
+                        }
+                        SubscriptionWidget {
+                            creator: author_as_user(&art.author),
+                            is_premium_content: Some(true),
+                            on_subscribed: handle_subscribed
                         }
                     }
                     
+                    // 作者可以看到读者高亮最多的段落和读者私信
+                    if is_author() {
+                        HighlightReportPanel { article_id: art.id.clone() }
+                        PrivateNoteInbox { article_id: Some(art.id.clone()) }
+                    }
+                    
+                    // 系列的上一篇 / 下一篇
+                    if art.series.is_some() {
+                        SeriesPager { series, article_id: art.id.clone() }
+                    }
+                    
                     // 标签
                     if !art.tags.is_empty() {
                         div {
@@ -281,33 +436,15 @@ pub fn ArticlePage(slug: String) -> Element {
                                 class: "flex items-center space-x-4",
                                 
                                 // 点赞按钮
-                                button {
-                                    class: "flex items-center space-x-2 px-4 py-2 rounded-full hover:bg-gray-100 transition-colors",
-                                    onclick: handle_clap,
-                                    disabled: is_clapping(),
-                                    
-                                    svg {
-                                        class: if art.is_clapped.unwrap_or(false) { "w-6 h-6 text-gray-900" } else { "w-6 h-6 text-gray-500" },
-                                        fill: if art.is_clapped.unwrap_or(false) { "currentColor" } else { "none" },
-                                        stroke: "currentColor",
-                                        view_box: "0 0 24 24",
-                                        path {
-                                            stroke_linecap: "round",
-                                            stroke_linejoin: "round",
-                                            stroke_width: "2",
-                                            d: "M14 10h4.764a2 2 0 011.789 2.894l-3.5 7A2 2 0 0115.263 21h-4.017c-.163 0-.326-.02-.485-.06L7 20m7-10V5a2 2 0 00-2-2h-.095c-.5 0-.905.405-.905.905 0 .714-.211 1.412-.608 2.006L7 11v9m7-10h-2M7 20H5a2 2 0 01-2-2v-6a2 2 0 012-2h2.5"
-                                        }
-                                    }
-                                    
-                                    span {
-                                        class: "text-sm font-medium",
-                                        {art.clap_count.to_string()}
-                                    }
-                                    
-                                    if art.user_clap_count.unwrap_or(0) > 0 {
-                                        span {
-                                            class: "text-xs text-gray-500",
-                                            "(+{art.user_clap_count.unwrap_or(0)})"
+                                ClapButton {
+                                    article_id: art.id.clone(),
+                                    clap_count: art.clap_count,
+                                    user_clap_count: art.user_clap_count.unwrap_or(0),
+                                    on_change: move |(user_claps, total_claps): (i32, i32)| {
+                                        if let Some(art) = article.write().as_mut() {
+                                            art.user_clap_count = Some(user_claps);
+                                            art.clap_count = total_claps;
+                                            art.is_clapped = Some(user_claps > 0);
                                         }
                                     }
                                 }
@@ -338,6 +475,12 @@ pub fn ArticlePage(slug: String) -> Element {
                             div {
                                 class: "flex items-center space-x-2",
                                 
+                                // 收藏的文章可以离线保存
+                                // 未解锁的会员文章只有试读部分，不提供离线保存
+                                if (art.is_bookmarked.unwrap_or(false) || OfflineStore::is_saved(&art.id)) && !access().is_locked() {
+                                    OfflineSaveButton { article_id: art.id.clone(), slug: art.slug.clone() }
+                                }
+                                
                                 // 收藏按钮
                                 button {
                                     class: "p-2 rounded-full hover:bg-gray-100 transition-colors",
@@ -458,3 +601,15 @@ pub fn ArticlePage(slug: String) -> Element {
         }
     }
 }
+
+// 订阅组件需要完整的用户信息，文章里只有作者摘要
+fn author_as_user(author: &Author) -> User {
+    User {
+        id: author.id.clone(),
+        username: author.username.clone(),
+        display_name: Some(author.display_name.clone()),
+        avatar_url: author.avatar_url.clone(),
+        is_verified: author.is_verified,
+        ..Default::default()
+    }
+}
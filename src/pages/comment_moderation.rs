@@ -0,0 +1,510 @@
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+use std::collections::HashSet;
+use crate::{
+    api::{comments::CommentService, users::UserService},
+    components::ProtectedRoute,
+    hooks::{use_auth, use_user_relations},
+    models::{
+        article::Article,
+        moderation::{
+            BulkModerationRequest, CommentPermission, CommentSettings, ModerationAction,
+            ModerationComment, ModerationFilter,
+        },
+        privacy::RelationKind,
+    },
+    Route,
+};
+
+const PAGE_SIZE: i32 = 30;
+
+// 批量操作栏中的按钮
+const BULK_ACTIONS: [ModerationAction; 8] = [
+    ModerationAction::Hide,
+    ModerationAction::Unhide,
+    ModerationAction::Pin,
+    ModerationAction::Unpin,
+    ModerationAction::MarkAuthorResponse,
+    ModerationAction::UnmarkAuthorResponse,
+    ModerationAction::Delete,
+    ModerationAction::BlockCommenter,
+];
+
+#[component]
+pub fn CommentModerationPage() -> Element {
+    rsx! {
+        ProtectedRoute {
+            CommentModerationContent {}
+        }
+    }
+}
+
+#[component]
+fn CommentModerationContent() -> Element {
+    let auth = use_auth();
+    let relations = use_user_relations();
+    let mut filter = use_signal(|| ModerationFilter::All);
+    // None 表示所有文章
+    let mut article_filter = use_signal(|| None::<String>);
+    let mut articles = use_signal(Vec::<Article>::new);
+    let mut comments = use_signal(Vec::<ModerationComment>::new);
+    let mut selected = use_signal(HashSet::<String>::new);
+    let mut page = use_signal(|| 1);
+    let mut has_next = use_signal(|| false);
+    let mut total = use_signal(|| 0i64);
+    let mut loading = use_signal(|| true);
+    let mut busy = use_signal(|| false);
+    let mut error = use_signal(|| None::<String>);
+    let mut settings = use_signal(|| None::<CommentSettings>);
+    let mut settings_saved = use_signal(|| false);
+    // 每次切换筛选条件递增，丢弃较慢返回的旧请求结果
+    let mut list_generation = use_hook(|| CopyValue::new(0u64));
+
+    // 我的文章，用于按文章筛选和评论设置
+    use_effect(move || {
+        let Some(username) = auth.read().user.as_ref().map(|u| u.username.clone()) else {
+            return;
+        };
+        spawn(async move {
+            if let Ok(resp) = UserService::get_user_articles(&username, Some(1), Some(100)).await {
+                articles.set(resp.articles);
+            }
+        });
+    });
+
+    // 筛选条件变化时重新加载第一页
+    use_effect(move || {
+        let current_filter = filter();
+        let article_id = article_filter();
+        let generation = *list_generation.peek() + 1;
+        list_generation.set(generation);
+        spawn(async move {
+            loading.set(true);
+            error.set(None);
+            selected.write().clear();
+            let result = CommentService::get_moderation_comments(current_filter, article_id.as_deref(), Some(1), Some(PAGE_SIZE)).await;
+            if *list_generation.peek() != generation {
+                return;
+            }
+            match result {
+                Ok(resp) => {
+                    comments.set(resp.comments);
+                    has_next.set(resp.has_next);
+                    total.set(resp.total);
+                    page.set(1);
+                }
+                Err(e) => error.set(Some(format!("加载评论失败: {}", e.message))),
+            }
+            loading.set(false);
+        });
+    });
+
+    // 选中文章时加载它的评论设置
+    use_effect(move || {
+        settings.set(None);
+        settings_saved.set(false);
+        let Some(article_id) = article_filter() else {
+            return;
+        };
+        spawn(async move {
+            match CommentService::get_comment_settings(&article_id).await {
+                Ok(data) => settings.set(Some(data)),
+                Err(e) => error.set(Some(format!("加载评论设置失败: {}", e.message))),
+            }
+        });
+    });
+
+    let load_more = move |_| {
+        let next_page = page() + 1;
+        let current_filter = filter();
+        let article_id = article_filter();
+        let generation = *list_generation.peek();
+        spawn(async move {
+            loading.set(true);
+            let result = CommentService::get_moderation_comments(current_filter, article_id.as_deref(), Some(next_page), Some(PAGE_SIZE)).await;
+            if *list_generation.peek() != generation {
+                return;
+            }
+            match result {
+                Ok(resp) => {
+                    comments.with_mut(|list| {
+                        for item in resp.comments {
+                            if !list.iter().any(|c| c.comment.id == item.comment.id) {
+                                list.push(item);
+                            }
+                        }
+                    });
+                    has_next.set(resp.has_next);
+                    page.set(next_page);
+                }
+                Err(e) => error.set(Some(format!("加载评论失败: {}", e.message))),
+            }
+            loading.set(false);
+        });
+    };
+
+    let run_action = move |action: ModerationAction| {
+        let ids: Vec<String> = selected.read().iter().cloned().collect();
+        if ids.is_empty() {
+            return;
+        }
+        if let Some(message) = action.confirm_message(ids.len()) {
+            let confirmed = web_sys::window()
+                .and_then(|w| w.confirm_with_message(&message).ok())
+                .unwrap_or(false);
+            if !confirmed {
+                return;
+            }
+        }
+        let request = BulkModerationRequest { comment_ids: ids.clone(), action };
+        let current_filter = filter();
+        // 屏蔽评论者与个人主页上的屏蔽是同一件事，走用户关系接口，屏蔽列表也随之更新
+        let authors: HashSet<String> = if action == ModerationAction::BlockCommenter {
+            let own_id = auth.read().user.as_ref().map(|u| u.id.clone());
+            comments
+                .read()
+                .iter()
+                .filter(|c| ids.contains(&c.comment.id) && Some(&c.comment.author_id) != own_id.as_ref())
+                .map(|c| c.comment.author_id.clone())
+                .collect()
+        } else {
+            HashSet::new()
+        };
+        spawn(async move {
+            busy.set(true);
+            error.set(None);
+            if action == ModerationAction::BlockCommenter {
+                // 逐个屏蔽，成功一位就移除这位用户的所有评论
+                for author_id in &authors {
+                    if let Err(e) = relations.set(RelationKind::Block, author_id, true).await {
+                        error.set(Some(format!("操作失败: {}", e.message)));
+                        break;
+                    }
+                    let before = comments.read().len();
+                    comments.write().retain(|item| item.comment.author_id != *author_id);
+                    let removed = before - comments.read().len();
+                    total.set((total() - removed as i64).max(0));
+                }
+                let remaining: HashSet<String> = comments.read().iter().map(|c| c.comment.id.clone()).collect();
+                selected.write().retain(|id| remaining.contains(id));
+                busy.set(false);
+                return;
+            }
+            match CommentService::moderate_comments(&request).await {
+                Ok(_) => {
+                    let before = comments.read().len();
+                    comments.with_mut(|list| {
+                        list.retain_mut(|item| {
+                            if !ids.contains(&item.comment.id) {
+                                return true;
+                            }
+                            action.apply(&mut item.comment)
+                                && (current_filter != ModerationFilter::Hidden || item.comment.is_hidden)
+                        });
+                    });
+                    let removed = before - comments.read().len();
+                    total.set((total() - removed as i64).max(0));
+                    selected.write().clear();
+                }
+                Err(e) => error.set(Some(format!("操作失败: {}", e.message))),
+            }
+            busy.set(false);
+        });
+    };
+
+    let save_permission = move |permission: CommentPermission| {
+        let Some(article_id) = article_filter() else {
+            return;
+        };
+        let request = CommentSettings { permission, ..settings().unwrap_or_default() };
+        spawn(async move {
+            settings_saved.set(false);
+            match CommentService::update_comment_settings(&article_id, &request).await {
+                Ok(data) => {
+                    settings.set(Some(data));
+                    settings_saved.set(true);
+                }
+                Err(e) => error.set(Some(format!("保存评论设置失败: {}", e.message))),
+            }
+        });
+    };
+
+    let all_selected = !comments().is_empty() && selected.read().len() == comments().len();
+
+    rsx! {
+        div {
+            class: "min-h-screen bg-white dark:bg-gray-900",
+
+            // 顶部导航
+            nav {
+                class: "border-b border-gray-200 dark:border-gray-700",
+                div {
+                    class: "max-w-7xl mx-auto px-4 sm:px-6 lg:px-8",
+                    div {
+                        class: "flex items-center justify-between h-16",
+                        Link {
+                            to: Route::Home {},
+                            class: "text-2xl font-serif font-bold text-gray-900 dark:text-white",
+                            "Rainbow Blog"
+                        }
+                        Link {
+                            to: Route::Settings {},
+                            class: "text-sm text-gray-600 dark:text-gray-400 hover:text-gray-900 dark:hover:text-white",
+                            "← 返回设置"
+                        }
+                    }
+                }
+            }
+
+            div {
+                class: "max-w-4xl mx-auto px-4 py-8",
+                h1 {
+                    class: "text-3xl font-serif font-bold text-gray-900 dark:text-white mb-2",
+                    "评论管理"
+                }
+                p {
+                    class: "text-gray-600 dark:text-gray-400 mb-6",
+                    {format!("你的文章下共有 {} 条符合条件的评论", total())}
+                }
+
+                // 筛选
+                div {
+                    class: "flex flex-wrap items-center gap-2 mb-4",
+                    for option in ModerationFilter::ALL {
+                        button {
+                            key: "{option.as_str()}",
+                            class: if filter() == option {
+                                "px-3 py-1 text-sm rounded-full bg-gray-900 text-white"
+                            } else {
+                                "px-3 py-1 text-sm rounded-full border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-300"
+                            },
+                            onclick: move |_| filter.set(option),
+                            {option.label()}
+                        }
+                    }
+                    select {
+                        class: "ml-auto px-3 py-1 text-sm border border-gray-300 dark:border-gray-600 rounded-lg bg-transparent text-gray-700 dark:text-gray-300 max-w-xs",
+                        value: article_filter().unwrap_or_default(),
+                        onchange: move |e| {
+                            let value = e.value();
+                            article_filter.set(if value.is_empty() { None } else { Some(value) });
+                        },
+                        option { value: "", "全部文章" }
+                        for article in articles() {
+                            option {
+                                key: "{article.id}",
+                                value: "{article.id}",
+                                {article.title.clone()}
+                            }
+                        }
+                    }
+                }
+
+                // 选中文章的评论设置
+                if let Some(current) = settings() {
+                    div {
+                        class: "mb-6 p-4 border border-gray-200 dark:border-gray-700 rounded-lg",
+                        div {
+                            class: "flex items-center justify-between mb-3",
+                            h2 {
+                                class: "text-sm font-medium text-gray-900 dark:text-white",
+                                "谁可以评论这篇文章"
+                            }
+                            if settings_saved() {
+                                span { class: "text-xs text-green-600", "已保存" }
+                            }
+                        }
+                        div {
+                            class: "flex flex-wrap gap-2",
+                            for permission in CommentPermission::ALL {
+                                button {
+                                    key: "{permission.as_str()}",
+                                    class: if current.permission == permission {
+                                        "px-3 py-1.5 text-sm rounded-full bg-green-600 text-white"
+                                    } else {
+                                        "px-3 py-1.5 text-sm rounded-full border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-300 hover:border-gray-500"
+                                    },
+                                    onclick: move |_| save_permission(permission),
+                                    {permission.label()}
+                                }
+                            }
+                        }
+                        if current.permission != CommentPermission::Everyone {
+                            p {
+                                class: "mt-2 text-xs text-gray-500 dark:text-gray-400",
+                                {format!("读者会看到：“{}”。已有评论不受影响。", current.permission.restriction_notice())}
+                            }
+                        }
+                    }
+                }
+
+                if let Some(err) = error() {
+                    div {
+                        class: "mb-4 p-3 rounded bg-red-50 text-red-700 border border-red-200",
+                        {err}
+                    }
+                }
+
+                // 批量操作
+                if !comments().is_empty() {
+                    div {
+                        class: "sticky top-0 z-10 flex flex-wrap items-center gap-2 py-3 mb-2 bg-white dark:bg-gray-900 border-b border-gray-200 dark:border-gray-700",
+                        label {
+                            class: "flex items-center text-sm text-gray-700 dark:text-gray-300 mr-2",
+                            input {
+                                r#type: "checkbox",
+                                class: "mr-2",
+                                checked: all_selected,
+                                onchange: move |_| {
+                                    if all_selected {
+                                        selected.write().clear();
+                                    } else {
+                                        let ids = comments.read().iter().map(|c| c.comment.id.clone()).collect();
+                                        selected.set(ids);
+                                    }
+                                }
+                            }
+                            if selected.read().is_empty() {
+                                "全选"
+                            } else {
+                                {format!("已选 {} 条", selected.read().len())}
+                            }
+                        }
+                        for action in BULK_ACTIONS {
+                            button {
+                                class: match action {
+                                    ModerationAction::Delete | ModerationAction::BlockCommenter => "px-3 py-1 text-sm rounded-full border border-red-300 text-red-700 dark:text-red-400 hover:bg-red-50 dark:hover:bg-red-900/20 disabled:opacity-50",
+                                    _ => "px-3 py-1 text-sm rounded-full border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-300 hover:border-gray-500 disabled:opacity-50",
+                                },
+                                disabled: busy() || selected.read().is_empty(),
+                                onclick: move |_| run_action(action),
+                                {action.label()}
+                            }
+                        }
+                    }
+                }
+
+                if loading() && comments().is_empty() {
+                    div {
+                        class: "flex justify-center py-12",
+                        div { class: "animate-spin rounded-full h-8 w-8 border-b-2 border-gray-900 dark:border-white" }
+                    }
+                } else if comments().is_empty() {
+                    p {
+                        class: "text-center py-12 text-gray-500 dark:text-gray-400",
+                        "没有符合条件的评论"
+                    }
+                } else {
+                    div {
+                        class: "divide-y divide-gray-200 dark:divide-gray-700",
+                        for item in comments() {
+                            ModerationRow {
+                                key: "{item.comment.id}",
+                                checked: selected.read().contains(&item.comment.id),
+                                on_toggle: {
+                                    let id = item.comment.id.clone();
+                                    move |_| {
+                                        let mut set = selected.write();
+                                        if !set.remove(&id) {
+                                            set.insert(id.clone());
+                                        }
+                                    }
+                                },
+                                item: item.clone()
+                            }
+                        }
+                    }
+                    if has_next() {
+                        div {
+                            class: "text-center mt-6",
+                            button {
+                                class: "px-4 py-2 text-sm border border-gray-300 dark:border-gray-600 rounded-full text-gray-700 dark:text-gray-300 hover:border-gray-500 disabled:opacity-50",
+                                disabled: loading(),
+                                onclick: load_more,
+                                if loading() { "加载中..." } else { "加载更多" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn ModerationRow(item: ModerationComment, checked: bool, on_toggle: EventHandler<()>) -> Element {
+    let comment = &item.comment;
+
+    rsx! {
+        div {
+            class: if comment.is_hidden { "flex gap-3 py-4 opacity-60" } else { "flex gap-3 py-4" },
+            input {
+                r#type: "checkbox",
+                class: "mt-1",
+                checked,
+                onchange: move |_| on_toggle.call(())
+            }
+            if let Some(avatar_url) = &item.author_avatar {
+                img {
+                    src: "{avatar_url}",
+                    alt: "{item.author_username}",
+                    class: "w-9 h-9 rounded-full flex-shrink-0"
+                }
+            } else {
+                div { class: "w-9 h-9 rounded-full bg-gray-200 dark:bg-gray-600 flex-shrink-0" }
+            }
+            div {
+                class: "flex-1 min-w-0",
+                div {
+                    class: "flex flex-wrap items-center gap-x-2 gap-y-1 text-sm",
+                    Link {
+                        to: Route::Profile { username: item.author_username.clone() },
+                        class: "font-medium text-gray-900 dark:text-white hover:underline",
+                        {item.author_name.clone()}
+                    }
+                    span {
+                        class: "text-gray-500 dark:text-gray-400",
+                        {comment.created_at.format("%Y-%m-%d %H:%M").to_string()}
+                    }
+                    if item.is_new {
+                        span { class: "px-1.5 py-0.5 rounded text-xs bg-blue-100 text-blue-800", "新" }
+                    }
+                    if !item.is_follower {
+                        span { class: "px-1.5 py-0.5 rounded text-xs bg-gray-100 dark:bg-gray-700 text-gray-600 dark:text-gray-300", "未关注你" }
+                    }
+                    if comment.is_pinned {
+                        span { class: "px-1.5 py-0.5 rounded text-xs bg-blue-50 dark:bg-blue-900/30 text-blue-700 dark:text-blue-300", "已置顶" }
+                    }
+                    if comment.is_author_response {
+                        span { class: "px-1.5 py-0.5 rounded text-xs bg-green-50 dark:bg-green-900/30 text-green-700 dark:text-green-300", "作者回应" }
+                    }
+                    if comment.is_hidden {
+                        span { class: "px-1.5 py-0.5 rounded text-xs bg-gray-200 text-gray-700", "已隐藏" }
+                    }
+                    if item.report_count > 0 {
+                        span {
+                            class: "px-1.5 py-0.5 rounded text-xs bg-red-100 text-red-800",
+                            title: item.report_reasons.join("\n"),
+                            {format!("被举报 {} 次", item.report_count)}
+                        }
+                    }
+                }
+                p {
+                    class: "mt-1 text-gray-800 dark:text-gray-200 whitespace-pre-wrap break-words line-clamp-4",
+                    {comment.content.clone()}
+                }
+                if !item.report_reasons.is_empty() {
+                    p {
+                        class: "mt-1 text-xs text-red-700 dark:text-red-400",
+                        {format!("举报理由：{}", item.report_reasons.join("；"))}
+                    }
+                }
+                a {
+                    href: "/article/{item.article_slug}#comment-{comment.id}",
+                    class: "mt-1 inline-block text-xs text-gray-500 dark:text-gray-400 hover:underline",
+                    {format!("来自《{}》", item.article_title)}
+                }
+            }
+        }
+    }
+}
@@ -1,13 +1,16 @@
 use dioxus::prelude::*;
 use dioxus_router::prelude::*;
 use crate::{
-    api::{articles::ArticleService, versions::VersionService, client::API_BASE_URL},
+    api::{articles::ArticleService, users::UserService, versions::VersionService, submissions::SubmissionService, client::API_BASE_URL},
     models::{
         article::{Article, CreateArticleRequest, UpdateArticleRequest},
-        version::{ArticleVersion, CreateVersionRequest},
+        mention::{extract_mentions, MentionCandidate},
+        submission::{Submission, SubmissionStatus, UpdateSubmissionRequest},
+        version::{ArticleVersion, CreateVersionRequest, VersionField, VersionSnapshot, AUTO_SAVE_SUMMARY},
     },
-    components::{VersionHistory, ImageDropZone},
-    hooks::use_auth,
+    components::{VersionHistory, ImageDropZone, CollabPresence, EditorialPanel, SubmissionBanner, SubmitToPublicationModal, MentionMenu},
+    hooks::{use_auth, use_collab, use_embeds, use_mentions, use_publication_role, COLLAB_TEXTAREA_ID},
+    rendering,
     Route,
 };
 use gloo_timers::future::TimeoutFuture;
@@ -27,10 +30,113 @@ pub fn EditorV2Page(slug: Option<String>) -> Element {
     let mut show_versions = use_signal(|| false);
     let mut auto_save_enabled = use_signal(|| true);
     let mut last_saved = use_signal(|| None::<String>);
+    // 建议模式：(进入模式时的正文, 工作副本)，修改以建议形式提交而不直接写入正文
+    let mut suggest_draft = use_signal(|| None::<(String, String)>);
+    let mut submission = use_signal(|| None::<Submission>);
+    let mut show_submit = use_signal(|| false);
+    // 最近一次创建版本时的内容，没有变化的保存不再产生重复的自动快照
+    let mut last_versioned = use_signal(|| None::<VersionSnapshot>);
     
     let auth = use_auth();
     let navigator = use_navigator();
     
+    // 预览内容，仅在预览模式下渲染
+    let preview_html = use_memo(move || {
+        if preview_mode() {
+            // 只链接上次保存时确认存在的用户，新输入的 @ 保存后才会变成链接
+            let mentions = article().map(|art| art.mentions).unwrap_or_default();
+            rendering::link_mentions(&rendering::render_markdown(&content()), &mentions)
+        } else {
+            String::new()
+        }
+    });
+    use_embeds(preview_html);
+    
+    let draft_snapshot = move || VersionSnapshot {
+        title: title(),
+        subtitle: subtitle(),
+        excerpt: excerpt(),
+        cover_image_url: cover_image_url(),
+        tags: tags_input(),
+        content: content(),
+    };
+    
+    // 出版物文章启用实时协作，本地修改经由 CRDT 同步给其他成员
+    let mut collab = use_collab(article, content);
+    let mut set_content = move |updated: String| {
+        collab.local_edit(&updated);
+        content.set(updated);
+    };
+    
+    let mut mentions = use_mentions(COLLAB_TEXTAREA_ID.to_string());
+    // 正文输入框当前显示的文本：修改建议模式下是建议稿
+    let editor_text = move || suggest_draft.peek().clone().map(|(_, working)| working).unwrap_or_else(|| content.peek().clone());
+    let mut set_editor_text = move |updated: String| {
+        let draft = suggest_draft.peek().clone();
+        match draft {
+            Some((base, _)) => suggest_draft.set(Some((base, updated))),
+            None => set_content(updated),
+        }
+    };
+    
+    // 出版物成员（及作者本人）可以使用修改建议与批注
+    let publication_id = use_memo(move || article().and_then(|a| a.publication.map(|p| p.id)));
+    let member_role = use_publication_role(publication_id);
+    let is_author = use_memo(move || {
+        match (article(), auth().user) {
+            (Some(art), Some(user)) => art.author.id == user.id,
+            _ => false,
+        }
+    });
+    let can_review = use_memo(move || {
+        publication_id().is_some() && (member_role().is_some() || is_author())
+    });
+    // 出版物文章只有所有者和编辑可以直接发布，其他成员需要投稿审核
+    let can_publish = use_memo(move || {
+        publication_id().is_none() || member_role().is_some_and(|role| role.can_publish())
+    });
+    
+    // 加载文章的投稿状态
+    use_effect(move || {
+        let Some(article_id) = article().map(|a| a.id) else {
+            return;
+        };
+        spawn(async move {
+            submission.set(SubmissionService::get_article_submission(&article_id).await.ok());
+        });
+    });
+    
+    // 投稿成功后重新加载文章，以获取所属出版物
+    let handle_submitted = move |created: Submission| {
+        submission.set(Some(created));
+        show_submit.set(false);
+        if let Some(art) = article() {
+            spawn(async move {
+                if let Ok(updated) = ArticleService::get_article(&art.slug).await {
+                    article.set(Some(updated));
+                }
+            });
+        }
+    };
+    
+    // 需要修改的投稿重新提交审核
+    let handle_resubmit = move |note: String| {
+        let Some(current) = submission() else {
+            return;
+        };
+        spawn(async move {
+            let request = UpdateSubmissionRequest {
+                status: SubmissionStatus::Submitted,
+                note: if note.trim().is_empty() { None } else { Some(note.trim().to_string()) },
+                scheduled_at: None,
+            };
+            match SubmissionService::update_status(&current.publication_id, &current.id, &request).await {
+                Ok(updated) => submission.set(Some(updated)),
+                Err(e) => error.set(Some(format!("重新投稿失败: {}", e.message))),
+            }
+        });
+    };
+    
     // 加载现有文章
     use_effect(move || {
         if let Some(slug) = &slug {
@@ -47,6 +153,7 @@ pub fn EditorV2Page(slug: Option<String>) -> Element {
                         // 设置标签
                         let tags: Vec<String> = art.tags.iter().map(|t| t.name.clone()).collect();
                         tags_input.set(tags.join(", "));
+                        last_versioned.set(Some(draft_snapshot()));
                         
                         article.set(Some(art));
                     }
@@ -101,18 +208,26 @@ pub fn EditorV2Page(slug: Option<String>) -> Element {
                     series_id: None,
                     publication_id: None,
                     series_order: None,
+                    mentions: resolved_mentions(&content()).await,
                 };
                 
                 match ArticleService::update_article(&art.id, &request).await {
                     Ok(_) => {
                         last_saved.set(Some("已保存".to_string()));
                         
-                        // 创建版本
-                        let version_request = CreateVersionRequest {
-                            article_id: art.id.clone(),
-                            change_summary: Some("自动保存".to_string()),
-                        };
-                        let _ = VersionService::create_version(&version_request).await;
+                        // 内容有变化时创建自动快照，旧快照由服务器按保留策略清理
+                        let snapshot = draft_snapshot();
+                        if last_versioned.peek().as_ref() != Some(&snapshot) {
+                            let version_request = CreateVersionRequest {
+                                article_id: art.id.clone(),
+                                change_summary: Some(AUTO_SAVE_SUMMARY.to_string()),
+                                label: None,
+                                is_auto: true,
+                            };
+                            if VersionService::create_version(&version_request).await.is_ok() {
+                                last_versioned.set(Some(snapshot));
+                            }
+                        }
                         
                         spawn(async move {
                             TimeoutFuture::new(3000).await;
@@ -158,6 +273,7 @@ pub fn EditorV2Page(slug: Option<String>) -> Element {
                     seo_title: None,
                     seo_description: None,
                     seo_keywords: None,
+                    mentions: resolved_mentions(&content()).await,
                 };
                 
                 match ArticleService::create_article(&request).await {
@@ -236,6 +352,7 @@ pub fn EditorV2Page(slug: Option<String>) -> Element {
                     series_id: None,
                     publication_id: None,
                     series_order: None,
+                    mentions: resolved_mentions(&content()).await,
                 };
                 
                 match ArticleService::update_article(&art.id, &update_request).await {
@@ -247,6 +364,8 @@ pub fn EditorV2Page(slug: Option<String>) -> Element {
                                 let version_request = CreateVersionRequest {
                                     article_id: art.id.clone(),
                                     change_summary: Some("发布文章".to_string()),
+                                    label: None,
+                                    is_auto: false,
                                 };
                                 let _ = VersionService::create_version(&version_request).await;
                                 
@@ -301,6 +420,7 @@ pub fn EditorV2Page(slug: Option<String>) -> Element {
                     seo_title: None,
                     seo_description: None,
                     seo_keywords: None,
+                    mentions: resolved_mentions(&content()).await,
                 };
                 
                 match ArticleService::create_article(&request).await {
@@ -336,7 +456,7 @@ pub fn EditorV2Page(slug: Option<String>) -> Element {
     let handle_restore_version = move |version: ArticleVersion| {
         title.set(version.title);
         subtitle.set(version.subtitle.unwrap_or_default());
-        content.set(version.content);
+        set_content(version.content);
         excerpt.set(version.excerpt);
         cover_image_url.set(version.cover_image_url.unwrap_or_default());
         tags_input.set(version.tags.join(", "));
@@ -346,6 +466,18 @@ pub fn EditorV2Page(slug: Option<String>) -> Element {
         save_draft();
     };
     
+    // 只恢复历史版本中的某个字段或正文的一处修改，保留在草稿中由作者继续编辑
+    let handle_restore_field = move |(field, value): (VersionField, String)| {
+        match field {
+            VersionField::Title => title.set(value),
+            VersionField::Subtitle => subtitle.set(value),
+            VersionField::Excerpt => excerpt.set(value),
+            VersionField::CoverImage => cover_image_url.set(value),
+            VersionField::Tags => tags_input.set(value),
+            VersionField::Content => set_content(value),
+        }
+    };
+    
     rsx! {
         div {
             class: "min-h-screen bg-white dark:bg-gray-900",
@@ -397,6 +529,35 @@ pub fn EditorV2Page(slug: Option<String>) -> Element {
                                 }
                             }
                             
+                            // 编辑 / 建议模式切换
+                            if can_review() && !preview_mode() {
+                                div {
+                                    class: "flex text-sm border border-gray-300 dark:border-gray-600 rounded-full overflow-hidden",
+                                    button {
+                                        class: if suggest_draft().is_none() {
+                                            "px-3 py-1 bg-gray-900 text-white"
+                                        } else {
+                                            "px-3 py-1 text-gray-600 dark:text-gray-400"
+                                        },
+                                        onclick: move |_| suggest_draft.set(None),
+                                        "编辑"
+                                    }
+                                    button {
+                                        class: if suggest_draft().is_some() {
+                                            "px-3 py-1 bg-green-600 text-white"
+                                        } else {
+                                            "px-3 py-1 text-gray-600 dark:text-gray-400"
+                                        },
+                                        onclick: move |_| {
+                                            if suggest_draft().is_none() {
+                                                suggest_draft.set(Some((content(), content())));
+                                            }
+                                        },
+                                        "建议"
+                                    }
+                                }
+                            }
+                            
                             // 预览切换
                             button {
                                 class: "text-sm text-gray-600 dark:text-gray-400 hover:text-gray-900 dark:hover:text-white",
@@ -412,12 +573,26 @@ pub fn EditorV2Page(slug: Option<String>) -> Element {
                                 if is_saving() { "保存中..." } else { "保存草稿" }
                             }
                             
+                            // 投稿按钮：尚未投稿或投稿被拒绝时可以投稿，出版物的编辑直接发布即可
+                            if article().is_some()
+                                && !(publication_id().is_some() && can_publish())
+                                && submission().is_none_or(|s| s.status == SubmissionStatus::Rejected)
+                            {
+                                button {
+                                    class: "px-4 py-2 text-sm text-gray-700 dark:text-gray-300 border border-gray-300 dark:border-gray-600 rounded-full hover:border-gray-500",
+                                    onclick: move |_| show_submit.set(true),
+                                    "投稿到出版物"
+                                }
+                            }
+                            
                             // 发布按钮
-                            button {
-                                class: "px-4 py-2 text-sm text-white bg-green-600 rounded-full hover:bg-green-700 disabled:opacity-50",
-                                disabled: is_saving() || title().is_empty() || content().is_empty(),
-                                onclick: move |_| publish(),
-                                "发布"
+                            if can_publish() {
+                                button {
+                                    class: "px-4 py-2 text-sm text-white bg-green-600 rounded-full hover:bg-green-700 disabled:opacity-50",
+                                    disabled: is_saving() || title().is_empty() || content().is_empty(),
+                                    onclick: move |_| publish(),
+                                    "发布"
+                                }
                             }
                         }
                     }
@@ -428,6 +603,14 @@ pub fn EditorV2Page(slug: Option<String>) -> Element {
             div {
                 class: "max-w-4xl mx-auto px-4 py-8",
                 
+                // 投稿状态
+                if let Some(current) = submission() {
+                    SubmissionBanner {
+                        submission: current,
+                        on_resubmit: handle_resubmit
+                    }
+                }
+                
                 // 错误提示
                 if let Some(err) = error() {
                     div {
@@ -474,13 +657,50 @@ pub fn EditorV2Page(slug: Option<String>) -> Element {
                             }
                         }
                         
+                        // 协作者在线状态
+                        CollabPresence { collab }
+                        
                         // 文章内容
-                        textarea {
-                            placeholder: "开始写作...",
-                            value: "{content}",
-                            oninput: move |evt| content.set(evt.value()),
-                            class: "w-full min-h-[400px] text-lg leading-relaxed placeholder-gray-400 dark:placeholder-gray-600 bg-transparent text-gray-900 dark:text-white border-0 outline-none resize-none mb-6",
-                            style: "font-family: 'Georgia', serif;"
+                        div {
+                            class: "relative mb-6",
+                            textarea {
+                                id: COLLAB_TEXTAREA_ID,
+                                placeholder: "开始写作...",
+                                value: suggest_draft().map(|(_, working)| working).unwrap_or_else(&*content),
+                                oninput: move |evt| {
+                                    let value = evt.value();
+                                    mentions.update(&value);
+                                    match suggest_draft() {
+                                        Some((base, _)) => suggest_draft.set(Some((base, value))),
+                                        None => set_content(value),
+                                    }
+                                },
+                                onkeydown: move |evt| {
+                                    if let Some(updated) = mentions.on_keydown(&evt, &editor_text()) {
+                                        set_editor_text(updated);
+                                    }
+                                },
+                                onblur: move |_| mentions.close(),
+                                onselect: move |_| collab.update_cursor(),
+                                onkeyup: move |_| collab.update_cursor(),
+                                onclick: move |_| collab.update_cursor(),
+                                class: "w-full min-h-[400px] text-lg leading-relaxed placeholder-gray-400 dark:placeholder-gray-600 bg-transparent text-gray-900 dark:text-white border-0 outline-none resize-none",
+                                style: "font-family: 'Georgia', serif;"
+                            }
+                            MentionMenu {
+                                mentions,
+                                on_select: move |candidate: MentionCandidate| {
+                                    if let Some(updated) = mentions.select(&candidate, &editor_text()) {
+                                        set_editor_text(updated);
+                                    }
+                                }
+                            }
+                        }
+                        
+                        // 独占一行的链接：卡片 / 普通链接切换
+                        LinkEmbedPanel {
+                            content: content(),
+                            on_change: move |updated: String| set_content(updated)
                         }
                         
                         // 摘要
@@ -499,6 +719,19 @@ pub fn EditorV2Page(slug: Option<String>) -> Element {
                             oninput: move |evt| tags_input.set(evt.value()),
                             class: "w-full text-sm text-gray-600 dark:text-gray-400 placeholder-gray-400 dark:placeholder-gray-600 bg-transparent border border-gray-300 dark:border-gray-700 rounded-lg p-3 outline-none"
                         }
+                        
+                        // 修改建议与批注
+                        if let (true, Some(art)) = (can_review(), article()) {
+                            EditorialPanel {
+                                article_id: art.id.clone(),
+                                content: content(),
+                                draft: suggest_draft(),
+                                is_author: is_author(),
+                                on_apply: move |updated: String| set_content(updated),
+                                on_draft_change: move |working: String| set_editor_text(working),
+                                on_draft_submitted: move |_| suggest_draft.set(None)
+                            }
+                        }
                     }
                 } else {
                     // 预览模式
@@ -525,9 +758,9 @@ pub fn EditorV2Page(slug: Option<String>) -> Element {
                             }
                         }
                         
+                        // 与文章页使用同一渲染管线（含公式与图表）
                         div {
-                            class: "whitespace-pre-wrap",
-                            {content()}
+                            dangerous_inner_html: "{preview_html}"
                         }
                         
                         if !tags_input().is_empty() {
@@ -545,15 +778,91 @@ pub fn EditorV2Page(slug: Option<String>) -> Element {
                 }
             }
             
+            // 投稿对话框
+            if let (true, Some(art)) = (show_submit(), article()) {
+                SubmitToPublicationModal {
+                    article_id: art.id.clone(),
+                    publication_id: art.publication.as_ref().map(|p| p.id.clone()),
+                    on_close: move |_| show_submit.set(false),
+                    on_submitted: handle_submitted
+                }
+            }
+            
             // 版本历史侧边栏
             if let Some(art) = article() {
                 VersionHistory {
                     article_id: art.id.clone(),
                     show: show_versions(),
+                    draft: draft_snapshot(),
                     on_close: move |_| show_versions.set(false),
-                    on_restore: handle_restore_version
+                    on_restore: handle_restore_version,
+                    on_restore_field: handle_restore_field
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn LinkEmbedPanel(content: String, on_change: EventHandler<String>) -> Element {
+    let links = rendering::embed::standalone_links(&content);
+    
+    if links.is_empty() {
+        return rsx! {};
+    }
+    
+    rsx! {
+        div {
+            class: "mb-6 border border-gray-200 dark:border-gray-700 rounded-lg p-3",
+            h3 {
+                class: "text-sm font-medium text-gray-700 dark:text-gray-300 mb-2",
+                "链接嵌入"
+            }
+            p {
+                class: "text-xs text-gray-500 mb-3",
+                "独占一行的链接会显示为卡片，视频等第三方内容在读者点击后才会加载"
+            }
+            div {
+                class: "space-y-2",
+                // 同一链接可能嵌入多次，key 需带上序号
+                for (index, (url, is_card)) in links.into_iter().enumerate() {
+                    div {
+                        key: "{index}-{url}",
+                        class: "flex items-center justify-between gap-3",
+                        span {
+                            class: "text-sm text-gray-600 dark:text-gray-400 truncate",
+                            {rendering::embed::detect(&url).map(|e| e.provider()).unwrap_or("链接")}
+                            " · "
+                            {url.clone()}
+                        }
+                        button {
+                            class: if is_card {
+                                "shrink-0 px-3 py-1 text-xs rounded-full bg-gray-900 text-white"
+                            } else {
+                                "shrink-0 px-3 py-1 text-xs rounded-full border border-gray-300 text-gray-700 dark:text-gray-300"
+                            },
+                            onclick: {
+                                let content = content.clone();
+                                let url = url.clone();
+                                move |_| on_change.call(rendering::embed::toggle_link_card(&content, &url, !is_card))
+                            },
+                            if is_card { "卡片" } else { "普通链接" }
+                        }
+                    }
                 }
             }
         }
     }
-}
\ No newline at end of file
+}
+
+// 文中 @提及的用户名中确实存在的那些，只有它们会在正文中链接到个人主页；查询失败时交给服务端判断
+async fn resolved_mentions(content: &str) -> Vec<String> {
+    let usernames = extract_mentions(content);
+    if usernames.is_empty() {
+        return usernames;
+    }
+    match UserService::resolve_mentions(&usernames).await {
+        Ok(candidates) => candidates.into_iter().map(|c| c.username).collect(),
+        Err(_) => usernames,
+    }
+}
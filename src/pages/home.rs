@@ -4,9 +4,9 @@ use std::rc::Rc;
 use dioxus_router::prelude::*;
 use crate::{
     api::{articles::ArticleService, tags::TagService},
-    components::{ArticleCard, PersonalizedRecommendations, TrendingArticles},
+    components::{ArticleCard, ContinueReadingShelf, NotificationBell, PersonalizedRecommendations, TrendingArticles},
     models::{article::{Article, ArticleListResponse}, tag::Tag},
-    hooks::use_auth,
+    hooks::{use_auth, use_user_relations},
     Route,
 };
 
@@ -20,6 +20,7 @@ pub fn HomePage() -> Element {
     let mut selected_sort = use_signal(|| "newest");
     let mut tags = use_signal(|| Vec::<Tag>::new());
     let auth = use_auth();
+    let relations = use_user_relations();
     
     // 加载文章
     use_effect(move || {
@@ -170,6 +171,7 @@ pub fn HomePage() -> Element {
                                 "出版物"
                             }
                             if auth.read().is_authenticated {
+                                NotificationBell {}
                                 Link {
                                     to: Route::Write {},
                                     class: "text-sm text-gray-700 hover:text-gray-900",
@@ -273,6 +275,8 @@ pub fn HomePage() -> Element {
                     main {
                         class: "flex-1 lg:max-w-3xl",
                         
+                        ContinueReadingShelf {}
+                        
                         // 错误信息
                         if let Some(err) = error() {
                             div {
@@ -287,7 +291,8 @@ pub fn HomePage() -> Element {
                         } else {
                             div {
                                 class: "divide-y divide-gray-100",
-                                for article in articles() {
+                                // 屏蔽和静音的作者的文章只在客户端隐藏，不影响分页
+                                for article in articles().into_iter().filter(|a| !relations.is_hidden(&a.author.id)) {
                                     ArticleCard { article }
                                 }
                             }
@@ -11,11 +11,17 @@ pub mod series;
 pub mod series_detail;
 pub mod publications;
 pub mod publication_detail;
+pub mod publication_review;
 pub mod create_publication;
 pub mod domain_management;
 pub mod subscription_plans;
 pub mod my_subscriptions;
 pub mod earnings;
+pub mod reading_history;
+pub mod offline;
+pub mod comment_moderation;
+pub mod private_notes;
+pub mod notifications;
 
 pub use login::LoginPage;
 pub use register::RegisterPage;
@@ -30,7 +36,13 @@ pub use series::SeriesManagePage;
 pub use series_detail::SeriesDetailPage;
 pub use publications::PublicationsPage;
 pub use publication_detail::PublicationDetailPage;
+pub use publication_review::PublicationReviewPage;
 pub use domain_management::DomainManagementPage;
 pub use subscription_plans::SubscriptionPlansPage;
 pub use my_subscriptions::MySubscriptionsPage;
 pub use earnings::EarningsPage;
+pub use reading_history::ReadingHistoryPage;
+pub use offline::OfflinePage;
+pub use comment_moderation::CommentModerationPage;
+pub use private_notes::PrivateNotesPage;
+pub use notifications::NotificationsPage;
@@ -0,0 +1,233 @@
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+use crate::{
+    api::notifications::NotificationService,
+    components::{NotificationItem, ProtectedRoute},
+    hooks::{use_notifications, LiveStatus},
+    models::notification::{mark_read_local, merge_notification, Notification, NotificationType},
+    Route,
+};
+
+const PAGE_SIZE: i32 = 20;
+
+#[component]
+pub fn NotificationsPage() -> Element {
+    rsx! {
+        ProtectedRoute {
+            NotificationsContent {}
+        }
+    }
+}
+
+#[component]
+fn NotificationsContent() -> Element {
+    let notifications = use_notifications();
+    // None 表示所有类型
+    let mut kind = use_signal(|| None::<NotificationType>);
+    let mut unread_only = use_signal(|| false);
+    let mut list = use_signal(Vec::<Notification>::new);
+    let mut page = use_signal(|| 1);
+    let mut has_next = use_signal(|| false);
+    let mut loading = use_signal(|| true);
+    let mut error = use_signal(|| None::<String>);
+
+    // 筛选条件变化时重新加载第一页
+    use_effect(move || {
+        let current_kind = kind();
+        let current_unread_only = unread_only();
+        spawn(async move {
+            loading.set(true);
+            error.set(None);
+            match NotificationService::get_notifications(current_kind, current_unread_only, Some(1), Some(PAGE_SIZE)).await {
+                Ok(resp) => {
+                    list.set(resp.notifications);
+                    has_next.set(resp.has_next);
+                    page.set(1);
+                }
+                Err(e) => error.set(Some(format!("加载通知失败: {}", e.message))),
+            }
+            loading.set(false);
+        });
+    });
+
+    // 实时推送的新通知符合筛选条件时插入列表顶部
+    use_effect(move || {
+        let Some(incoming) = (notifications.latest)() else {
+            return;
+        };
+        if kind.peek().is_none_or(|k| k == incoming.kind) {
+            merge_notification(&mut list.write(), incoming);
+        }
+    });
+
+    let load_more = move |_| {
+        let next_page = page() + 1;
+        let current_kind = kind();
+        let current_unread_only = unread_only();
+        spawn(async move {
+            loading.set(true);
+            match NotificationService::get_notifications(current_kind, current_unread_only, Some(next_page), Some(PAGE_SIZE)).await {
+                Ok(resp) => {
+                    list.with_mut(|items| {
+                        for item in resp.notifications {
+                            if !items.iter().any(|n| n.id == item.id) {
+                                items.push(item);
+                            }
+                        }
+                    });
+                    has_next.set(resp.has_next);
+                    page.set(next_page);
+                }
+                Err(e) => error.set(Some(format!("加载通知失败: {}", e.message))),
+            }
+            loading.set(false);
+        });
+    };
+
+    let mark_all_read = move |_| {
+        notifications.mark_all_read();
+        for item in list.write().iter_mut() {
+            item.is_read = true;
+        }
+    };
+
+    let unread = (notifications.unread)();
+
+    rsx! {
+        div {
+            class: "min-h-screen bg-white dark:bg-gray-900",
+
+            // 顶部导航
+            nav {
+                class: "border-b border-gray-200 dark:border-gray-700",
+                div {
+                    class: "max-w-7xl mx-auto px-4 sm:px-6 lg:px-8",
+                    div {
+                        class: "flex items-center justify-between h-16",
+                        Link {
+                            to: Route::Home {},
+                            class: "text-2xl font-serif font-bold text-gray-900 dark:text-white",
+                            "Rainbow Blog"
+                        }
+                        Link {
+                            to: Route::Settings {},
+                            class: "text-sm text-gray-600 dark:text-gray-400 hover:text-gray-900 dark:hover:text-white",
+                            "← 返回设置"
+                        }
+                    }
+                }
+            }
+
+            div {
+                class: "max-w-4xl mx-auto px-4 py-8",
+                div {
+                    class: "flex flex-wrap items-end justify-between gap-4 mb-6",
+                    div {
+                        h1 {
+                            class: "text-3xl font-serif font-bold text-gray-900 dark:text-white mb-2",
+                            "通知"
+                        }
+                        p {
+                            class: "text-gray-600 dark:text-gray-400",
+                            if unread > 0 {
+                                {format!("{} 条未读", unread)}
+                            } else {
+                                "没有未读通知"
+                            }
+                            if (notifications.status)() == LiveStatus::Polling {
+                                span {
+                                    class: "ml-2 text-xs text-gray-400",
+                                    "（实时推送不可用，每 30 秒刷新）"
+                                }
+                            }
+                        }
+                    }
+                    button {
+                        class: "px-4 py-2 text-sm border border-gray-300 dark:border-gray-600 rounded-full text-gray-700 dark:text-gray-300 hover:border-gray-500 disabled:opacity-50",
+                        disabled: unread == 0,
+                        onclick: mark_all_read,
+                        "全部标为已读"
+                    }
+                }
+
+                // 筛选
+                div {
+                    class: "flex flex-wrap items-center gap-2 mb-4",
+                    button {
+                        class: if kind().is_none() {
+                            "px-3 py-1 text-sm rounded-full bg-gray-900 text-white"
+                        } else {
+                            "px-3 py-1 text-sm rounded-full border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-300"
+                        },
+                        onclick: move |_| kind.set(None),
+                        "全部"
+                    }
+                    for option in NotificationType::ALL {
+                        button {
+                            key: "{option.as_str()}",
+                            class: if kind() == Some(option) {
+                                "px-3 py-1 text-sm rounded-full bg-gray-900 text-white"
+                            } else {
+                                "px-3 py-1 text-sm rounded-full border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-300"
+                            },
+                            onclick: move |_| kind.set(Some(option)),
+                            {option.label()}
+                        }
+                    }
+                    label {
+                        class: "ml-auto flex items-center gap-2 text-sm text-gray-700 dark:text-gray-300",
+                        input {
+                            r#type: "checkbox",
+                            checked: unread_only(),
+                            onchange: move |e| unread_only.set(e.checked()),
+                        }
+                        "仅未读"
+                    }
+                }
+
+                if let Some(err) = error() {
+                    div {
+                        class: "mb-4 p-3 text-sm text-red-700 bg-red-50 dark:bg-red-900/20 rounded-lg",
+                        "{err}"
+                    }
+                }
+
+                if loading() && list().is_empty() {
+                    div {
+                        class: "flex justify-center py-12",
+                        div {
+                            class: "animate-spin rounded-full h-8 w-8 border-b-2 border-gray-900 dark:border-white"
+                        }
+                    }
+                } else if list().is_empty() {
+                    p {
+                        class: "py-12 text-center text-gray-500 dark:text-gray-400",
+                        "没有符合条件的通知"
+                    }
+                } else {
+                    div {
+                        class: "border border-gray-200 dark:border-gray-700 rounded-lg divide-y divide-gray-100 dark:divide-gray-700 overflow-hidden",
+                        for notification in list() {
+                            NotificationItem {
+                                key: "{notification.id}",
+                                notification: notification.clone(),
+                                on_open: move |id: String| mark_read_local(&mut list.write(), &[id]),
+                            }
+                        }
+                    }
+                    if has_next() {
+                        div {
+                            class: "text-center mt-6",
+                            button {
+                                class: "px-4 py-2 text-sm border border-gray-300 dark:border-gray-600 rounded-full text-gray-700 dark:text-gray-300 hover:border-gray-500 disabled:opacity-50",
+                                disabled: loading(),
+                                onclick: load_more,
+                                if loading() { "加载中..." } else { "加载更多" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
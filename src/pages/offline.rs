@@ -0,0 +1,158 @@
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+use crate::{
+    hooks::{use_online, OfflineStore},
+    models::offline::{format_bytes, OfflineArticle},
+    Route,
+};
+
+/// 离线可读的文章列表，没有网络时也能打开
+#[component]
+pub fn OfflinePage() -> Element {
+    let online = use_online();
+    let mut articles = use_signal(OfflineStore::list);
+    let mut error = use_signal(|| None::<String>);
+
+    let remove = move |article_id: String| {
+        spawn(async move {
+            match OfflineStore::remove(&article_id).await {
+                Ok(_) => articles.set(OfflineStore::list()),
+                Err(e) => error.set(Some(format!("移除失败: {}", e))),
+            }
+        });
+    };
+
+    rsx! {
+        div {
+            class: "min-h-screen bg-white dark:bg-gray-900",
+
+            // 顶部导航
+            nav {
+                class: "border-b border-gray-200 dark:border-gray-700",
+                div {
+                    class: "max-w-7xl mx-auto px-4 sm:px-6 lg:px-8",
+                    div {
+                        class: "flex items-center justify-between h-16",
+                        Link {
+                            to: Route::Home {},
+                            class: "text-2xl font-serif font-bold text-gray-900 dark:text-white",
+                            "Rainbow Blog"
+                        }
+                        span {
+                            class: if online() {
+                                "text-sm text-green-600"
+                            } else {
+                                "text-sm text-yellow-600"
+                            },
+                            if online() { "● 已联网" } else { "● 离线" }
+                        }
+                    }
+                }
+            }
+
+            div {
+                class: "max-w-3xl mx-auto px-4 sm:px-6 lg:px-8 py-8",
+
+                div {
+                    class: "mb-6",
+                    h1 {
+                        class: "text-3xl font-bold text-gray-900 dark:text-white",
+                        "离线阅读"
+                    }
+                    p {
+                        class: "text-gray-600 dark:text-gray-400 mt-1",
+                        {format!("{} 篇文章 · 共 {}", articles().len(), format_bytes(OfflineStore::total_bytes() as f64))}
+                    }
+                }
+
+                if let Some(error_msg) = error() {
+                    div {
+                        class: "mb-6 bg-red-50 dark:bg-red-900/20 border border-red-200 dark:border-red-800 text-red-700 dark:text-red-400 px-4 py-3 rounded-lg",
+                        {error_msg}
+                    }
+                }
+
+                if articles().is_empty() {
+                    div {
+                        class: "text-center py-12 bg-gray-50 dark:bg-gray-800 rounded-lg text-gray-500 dark:text-gray-400",
+                        p { "还没有离线保存的文章" }
+                        p {
+                            class: "text-sm mt-2",
+                            "在收藏的文章上点击“离线保存”，没有网络时也能阅读"
+                        }
+                    }
+                } else {
+                    div {
+                        class: "divide-y divide-gray-100 dark:divide-gray-800",
+                        for entry in articles() {
+                            OfflineArticleRow {
+                                key: "{entry.article_id}",
+                                entry: entry.clone(),
+                                on_remove: move |id: String| remove(id)
+                            }
+                        }
+                    }
+                }
+
+                div {
+                    class: "mt-8 text-sm text-gray-500 dark:text-gray-400",
+                    "存储上限和清理可在 "
+                    Link {
+                        to: Route::Settings {},
+                        class: "text-green-600 hover:text-green-700",
+                        "设置 → 离线阅读"
+                    }
+                    " 中调整"
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn OfflineArticleRow(entry: OfflineArticle, on_remove: EventHandler<String>) -> Element {
+    rsx! {
+        div {
+            class: "flex items-start py-4",
+            if let Some(cover) = &entry.cover_image_url {
+                img {
+                    src: "{cover}",
+                    alt: "{entry.title}",
+                    class: "w-20 h-16 object-cover rounded mr-4 shrink-0"
+                }
+            }
+            div {
+                class: "flex-1 min-w-0",
+                Link {
+                    to: Route::Article { slug: entry.slug.clone() },
+                    class: "block text-lg font-semibold text-gray-900 dark:text-white hover:underline",
+                    {entry.title.clone()}
+                }
+                if let Some(excerpt) = &entry.excerpt {
+                    p {
+                        class: "text-sm text-gray-600 dark:text-gray-400 mt-1 line-clamp-2",
+                        {excerpt.clone()}
+                    }
+                }
+                div {
+                    class: "text-xs text-gray-500 dark:text-gray-400 mt-2",
+                    {format!(
+                        "{} · {} 分钟 · {} · 保存于 {}",
+                        entry.author_name,
+                        entry.reading_time,
+                        format_bytes(entry.size_bytes as f64),
+                        entry.saved_at.format("%Y-%m-%d")
+                    )}
+                }
+            }
+            button {
+                class: "ml-4 text-sm text-gray-400 hover:text-red-600",
+                onclick: {
+                    let article_id = entry.article_id.clone();
+                    move |_| on_remove.call(article_id.clone())
+                },
+                "移除"
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+use crate::{
+    api::users::UserService,
+    components::{PrivateNoteInbox, ProtectedRoute},
+    hooks::use_auth,
+    models::article::Article,
+    Route,
+};
+
+#[component]
+pub fn PrivateNotesPage() -> Element {
+    rsx! {
+        ProtectedRoute {
+            PrivateNotesContent {}
+        }
+    }
+}
+
+#[component]
+fn PrivateNotesContent() -> Element {
+    let auth = use_auth();
+    let mut articles = use_signal(Vec::<Article>::new);
+    // None 表示所有文章
+    let mut article_filter = use_signal(|| None::<String>);
+
+    use_effect(move || {
+        let Some(username) = auth.read().user.as_ref().map(|u| u.username.clone()) else {
+            return;
+        };
+        spawn(async move {
+            if let Ok(resp) = UserService::get_user_articles(&username, Some(1), Some(100)).await {
+                articles.set(resp.articles);
+            }
+        });
+    });
+
+    rsx! {
+        div {
+            class: "min-h-screen bg-white dark:bg-gray-900",
+
+            // 顶部导航
+            nav {
+                class: "border-b border-gray-200 dark:border-gray-700",
+                div {
+                    class: "max-w-7xl mx-auto px-4 sm:px-6 lg:px-8",
+                    div {
+                        class: "flex items-center justify-between h-16",
+                        Link {
+                            to: Route::Home {},
+                            class: "text-2xl font-serif font-bold text-gray-900 dark:text-white",
+                            "Rainbow Blog"
+                        }
+                        Link {
+                            to: Route::Settings {},
+                            class: "text-sm text-gray-600 dark:text-gray-400 hover:text-gray-900 dark:hover:text-white",
+                            "← 返回设置"
+                        }
+                    }
+                }
+            }
+
+            div {
+                class: "max-w-4xl mx-auto px-4 py-8",
+                div {
+                    class: "flex flex-wrap items-end justify-between gap-4 mb-6",
+                    div {
+                        h1 {
+                            class: "text-3xl font-serif font-bold text-gray-900 dark:text-white mb-2",
+                            "读者私信"
+                        }
+                        p {
+                            class: "text-gray-600 dark:text-gray-400",
+                            "读者在正文中选中文字后私下发给你的留言，例如错别字和事实错误"
+                        }
+                    }
+                    select {
+                        class: "px-3 py-1 text-sm border border-gray-300 dark:border-gray-600 rounded-lg bg-transparent text-gray-700 dark:text-gray-300 max-w-xs",
+                        value: article_filter().unwrap_or_default(),
+                        onchange: move |e| {
+                            let value = e.value();
+                            article_filter.set(if value.is_empty() { None } else { Some(value) });
+                        },
+                        option { value: "", "全部文章" }
+                        for article in articles() {
+                            option {
+                                key: "{article.id}",
+                                value: "{article.id}",
+                                {article.title.clone()}
+                            }
+                        }
+                    }
+                }
+
+                PrivateNoteInbox { article_id: article_filter() }
+            }
+        }
+    }
+}
@@ -2,9 +2,9 @@ use dioxus::prelude::*;
 use dioxus_router::prelude::*;
 use crate::{
     api::{users::UserService, articles::ArticleService, bookmarks::BookmarkService},
-    components::ArticleCard,
+    components::{ArticleCard, OfflineSaveButton, UserRelationMenu},
     models::{user::UserProfile, article::Article, bookmark::BookmarkItem},
-    hooks::use_auth,
+    hooks::{use_auth, use_user_relations},
     Route,
 };
 
@@ -20,6 +20,7 @@ pub fn ProfilePage(username: String) -> Element {
     let mut is_loading_follow = use_signal(|| false);
     
     let auth = use_auth();
+    let relations = use_user_relations();
     let navigator = use_navigator();
     
     // 加载用户资料和文章
@@ -108,6 +109,12 @@ pub fn ProfilePage(username: String) -> Element {
         }
     };
     
+    // 对方在隐私设置中隐藏了关注列表时不显示关注数
+    let hide_follow_lists = profile().map(|p| {
+        p.hide_follow_lists && auth.read().user.as_ref().map(|u| u.id != p.user_id).unwrap_or(true)
+    }).unwrap_or(false);
+    let blocked = profile().map(|p| relations.is_blocked(&p.user_id)).unwrap_or(false);
+    
     rsx! {
         div {
             class: "min-h-screen bg-gray-50",
@@ -307,15 +314,17 @@ pub fn ProfilePage(username: String) -> Element {
                                                         "文章" 
                                                     }
                                                 }
-                                                div {
-                                                    class: "flex items-center gap-2",
-                                                    span { 
-                                                        class: "text-2xl font-bold text-gray-900", 
-                                                        {user_profile.follower_count.to_string()} 
-                                                    }
-                                                    span { 
-                                                        class: "text-gray-600 font-medium", 
-                                                        "关注者" 
+                                                if !hide_follow_lists {
+                                                    div {
+                                                        class: "flex items-center gap-2",
+                                                        span { 
+                                                            class: "text-2xl font-bold text-gray-900", 
+                                                            {user_profile.follower_count.to_string()} 
+                                                        }
+                                                        span { 
+                                                            class: "text-gray-600 font-medium", 
+                                                            "关注者" 
+                                                        }
                                                     }
                                                 }
                                                 div {
@@ -338,64 +347,75 @@ pub fn ProfilePage(username: String) -> Element {
                                         class: "flex items-center gap-4 pt-6",
                                         if auth.read().is_authenticated {
                                             if auth.read().user.as_ref().map(|u| u.id != user_profile.user_id).unwrap_or(true) {
-                                                button {
-                                                    class: if is_following() {
-                                                        "flex items-center gap-2 px-6 py-3 border-2 border-gray-300 text-gray-700 rounded-full font-medium hover:bg-gray-50 transition-all duration-200"
-                                                    } else {
-                                                        "flex items-center gap-2 px-6 py-3 bg-green-600 text-white rounded-full font-medium hover:bg-green-700 transition-all duration-200 shadow-sm"
-                                                    },
-                                                    onclick: handle_follow,
-                                                    disabled: is_loading_follow(),
-                                                    if is_loading_follow() {
-                                                        svg {
-                                                            class: "animate-spin w-4 h-4",
-                                                            fill: "none",
-                                                            view_box: "0 0 24 24",
-                                                            circle {
-                                                                class: "opacity-25",
-                                                                cx: "12",
-                                                                cy: "12",
-                                                                r: "10",
-                                                                stroke: "currentColor",
-                                                                stroke_width: "4"
-                                                            }
-                                                            path {
-                                                                class: "opacity-75",
-                                                                fill: "currentColor",
-                                                                d: "M4 12a8 8 0 018-8V0C5.373 0 0 5.373 0 12h4zm2 5.291A7.962 7.962 0 014 12H0c0 3.042 1.135 5.824 3 7.938l3-2.647z"
+                                                if blocked {
+                                                    span {
+                                                        class: "px-6 py-3 text-sm text-gray-500",
+                                                        "你已屏蔽该用户"
+                                                    }
+                                                } else {
+                                                    button {
+                                                        class: if is_following() {
+                                                            "flex items-center gap-2 px-6 py-3 border-2 border-gray-300 text-gray-700 rounded-full font-medium hover:bg-gray-50 transition-all duration-200"
+                                                        } else {
+                                                            "flex items-center gap-2 px-6 py-3 bg-green-600 text-white rounded-full font-medium hover:bg-green-700 transition-all duration-200 shadow-sm"
+                                                        },
+                                                        onclick: handle_follow,
+                                                        disabled: is_loading_follow(),
+                                                        if is_loading_follow() {
+                                                            svg {
+                                                                class: "animate-spin w-4 h-4",
+                                                                fill: "none",
+                                                                view_box: "0 0 24 24",
+                                                                circle {
+                                                                    class: "opacity-25",
+                                                                    cx: "12",
+                                                                    cy: "12",
+                                                                    r: "10",
+                                                                    stroke: "currentColor",
+                                                                    stroke_width: "4"
+                                                                }
+                                                                path {
+                                                                    class: "opacity-75",
+                                                                    fill: "currentColor",
+                                                                    d: "M4 12a8 8 0 018-8V0C5.373 0 0 5.373 0 12h4zm2 5.291A7.962 7.962 0 014 12H0c0 3.042 1.135 5.824 3 7.938l3-2.647z"
+                                                                }
                                                             }
-                                                        }
-                                                        "处理中"
-                                                    } else if is_following() {
-                                                        svg {
-                                                            class: "w-4 h-4",
-                                                            fill: "none",
-                                                            stroke: "currentColor",
-                                                            view_box: "0 0 24 24",
-                                                            path {
-                                                                stroke_linecap: "round",
-                                                                stroke_linejoin: "round",
-                                                                stroke_width: "2",
-                                                                d: "M5 13l4 4L19 7"
+                                                            "处理中"
+                                                        } else if is_following() {
+                                                            svg {
+                                                                class: "w-4 h-4",
+                                                                fill: "none",
+                                                                stroke: "currentColor",
+                                                                view_box: "0 0 24 24",
+                                                                path {
+                                                                    stroke_linecap: "round",
+                                                                    stroke_linejoin: "round",
+                                                                    stroke_width: "2",
+                                                                    d: "M5 13l4 4L19 7"
+                                                                }
                                                             }
-                                                        }
-                                                        "已关注"
-                                                    } else {
-                                                        svg {
-                                                            class: "w-4 h-4",
-                                                            fill: "none",
-                                                            stroke: "currentColor",
-                                                            view_box: "0 0 24 24",
-                                                            path {
-                                                                stroke_linecap: "round",
-                                                                stroke_linejoin: "round",
-                                                                stroke_width: "2",
-                                                                d: "M12 4v16m8-8H4"
+                                                            "已关注"
+                                                        } else {
+                                                            svg {
+                                                                class: "w-4 h-4",
+                                                                fill: "none",
+                                                                stroke: "currentColor",
+                                                                view_box: "0 0 24 24",
+                                                                path {
+                                                                    stroke_linecap: "round",
+                                                                    stroke_linejoin: "round",
+                                                                    stroke_width: "2",
+                                                                    d: "M12 4v16m8-8H4"
+                                                                }
                                                             }
+                                                            "关注"
                                                         }
-                                                        "关注"
                                                     }
                                                 }
+                                                UserRelationMenu {
+                                                    user_id: user_profile.user_id.clone(),
+                                                    username: user_profile.username.clone(),
+                                                }
                                             } else {
                                                 Link {
                                                     to: Route::Home {},
@@ -582,26 +602,28 @@ pub fn ProfilePage(username: String) -> Element {
                                                 "发布文章"
                                             }
                                         }
-                                        div {
-                                            class: "text-center",
+                                        if !hide_follow_lists {
                                             div {
-                                                class: "text-3xl font-bold text-blue-600 mb-1",
-                                                {user_profile.follower_count.to_string()}
-                                            }
-                                            div {
-                                                class: "text-sm text-gray-600 font-medium",
-                                                "关注者"
-                                            }
-                                        }
-                                        div {
-                                            class: "text-center",
-                                            div {
-                                                class: "text-3xl font-bold text-purple-600 mb-1",
-                                                {user_profile.following_count.to_string()}
+                                                class: "text-center",
+                                                div {
+                                                    class: "text-3xl font-bold text-blue-600 mb-1",
+                                                    {user_profile.follower_count.to_string()}
+                                                }
+                                                div {
+                                                    class: "text-sm text-gray-600 font-medium",
+                                                    "关注者"
+                                                }
                                             }
                                             div {
-                                                class: "text-sm text-gray-600 font-medium",
-                                                "正在关注"
+                                                class: "text-center",
+                                                div {
+                                                    class: "text-3xl font-bold text-purple-600 mb-1",
+                                                    {user_profile.following_count.to_string()}
+                                                }
+                                                div {
+                                                    class: "text-sm text-gray-600 font-medium",
+                                                    "正在关注"
+                                                }
                                             }
                                         }
                                         div {
@@ -659,6 +681,7 @@ pub fn ProfileByIdPage(user_id: String) -> Element {
     let mut is_loading_follow = use_signal(|| false);
     
     let auth = use_auth();
+    let relations = use_user_relations();
     let navigator = use_navigator();
     
     // 加载用户资料和文章
@@ -806,6 +829,8 @@ pub fn ProfileByIdPage(user_id: String) -> Element {
     let auth_state = auth.read();
     let is_own_profile = auth_state.is_authenticated && 
                         auth_state.user.as_ref().map(|u| u.id == user_profile.user_id).unwrap_or(false);
+    let hide_follow_lists = user_profile.hide_follow_lists && !is_own_profile;
+    let blocked = relations.is_blocked(&user_profile.user_id);
     
     rsx! {
         div {
@@ -868,15 +893,17 @@ pub fn ProfileByIdPage(user_id: String) -> Element {
                                     span { class: "text-2xl font-bold text-green-600", {user_profile.article_count.to_string()} }
                                     span { class: "text-gray-600", "篇文章" }
                                 }
-                                div {
-                                    class: "flex items-center gap-1",
-                                    span { class: "text-2xl font-bold text-blue-600", {user_profile.follower_count.to_string()} }
-                                    span { class: "text-gray-600", "关注者" }
-                                }
-                                div {
-                                    class: "flex items-center gap-1",
-                                    span { class: "text-2xl font-bold text-purple-600", {user_profile.following_count.to_string()} }
-                                    span { class: "text-gray-600", "正在关注" }
+                                if !hide_follow_lists {
+                                    div {
+                                        class: "flex items-center gap-1",
+                                        span { class: "text-2xl font-bold text-blue-600", {user_profile.follower_count.to_string()} }
+                                        span { class: "text-gray-600", "关注者" }
+                                    }
+                                    div {
+                                        class: "flex items-center gap-1",
+                                        span { class: "text-2xl font-bold text-purple-600", {user_profile.following_count.to_string()} }
+                                        span { class: "text-gray-600", "正在关注" }
+                                    }
                                 }
                                 div {
                                     class: "flex items-center gap-1",
@@ -895,22 +922,33 @@ pub fn ProfileByIdPage(user_id: String) -> Element {
                                         "编辑个人资料"
                                     }
                                 } else if auth_state.is_authenticated {
-                                    button {
-                                        onclick: handle_follow,
-                                        disabled: is_loading_follow(),
-                                        class: if is_following() {
-                                            "px-6 py-2 border border-gray-300 text-gray-700 rounded-full font-medium hover:bg-gray-50 transition-colors"
-                                        } else {
-                                            "px-6 py-2 bg-green-600 text-white rounded-full font-medium hover:bg-green-700 transition-colors"
-                                        },
-                                        if is_loading_follow() {
-                                            "处理中..."
-                                        } else if is_following() {
-                                            "已关注"
-                                        } else {
-                                            "关注"
+                                    if blocked {
+                                        span {
+                                            class: "px-6 py-2 text-sm text-gray-500",
+                                            "你已屏蔽该用户"
+                                        }
+                                    } else {
+                                        button {
+                                            onclick: handle_follow,
+                                            disabled: is_loading_follow(),
+                                            class: if is_following() {
+                                                "px-6 py-2 border border-gray-300 text-gray-700 rounded-full font-medium hover:bg-gray-50 transition-colors"
+                                            } else {
+                                                "px-6 py-2 bg-green-600 text-white rounded-full font-medium hover:bg-green-700 transition-colors"
+                                            },
+                                            if is_loading_follow() {
+                                                "处理中..."
+                                            } else if is_following() {
+                                                "已关注"
+                                            } else {
+                                                "关注"
+                                            }
                                         }
                                     }
+                                    UserRelationMenu {
+                                        user_id: user_profile.user_id.clone(),
+                                        username: user_profile.username.clone(),
+                                    }
                                 }
                             }
                         }
@@ -1056,6 +1094,7 @@ pub fn ProfileByIdPage(user_id: String) -> Element {
                                         }
                                         // actions
                                         div { class: "flex flex-col items-end gap-2",
+                                            OfflineSaveButton { article_id: b.article_id.clone(), slug: b.article_slug.clone() }
                                             // 编辑备注
                                             button {
                                                 class: "px-3 py-1 text-sm border border-gray-300 rounded hover:bg-gray-100",
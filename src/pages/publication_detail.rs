@@ -4,7 +4,7 @@ use crate::{
     api::{publications::PublicationService, articles::ArticleService, domains::DomainService},
     models::{publication::Publication, article::Article, domain::PublicationDomain},
     components::ArticleCard,
-    hooks::use_auth,
+    hooks::{use_auth, use_publication_role},
     Route,
 };
 
@@ -22,6 +22,8 @@ pub fn PublicationDetailPage(slug: String) -> Element {
     let mut add_role = use_signal(|| String::from("writer"));
     let mut members_error = use_signal(|| None::<String>);
     let auth = use_auth();
+    let publication_id = use_memo(move || publication().map(|p| p.id));
+    let member_role = use_publication_role(publication_id);
     
     // 加载出版物详情
     use_effect(move || {
@@ -304,6 +306,14 @@ pub fn PublicationDetailPage(slug: String) -> Element {
                                 onclick: move |_| active_tab.set("members"),
                                 "成员"
                             }
+                            // 所有者与编辑可以进入审核队列
+                            if member_role().is_some_and(|role| role.can_publish()) {
+                                Link {
+                                    to: Route::PublicationReview { slug: pub_data.slug.clone() },
+                                    class: "py-4 px-1 text-sm text-gray-500 hover:text-gray-700 dark:hover:text-gray-300",
+                                    "审核队列"
+                                }
+                            }
                             if auth.read().is_authenticated {
                                 button {
                                     class: if active_tab() == "domains" {
@@ -0,0 +1,389 @@
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+use chrono::{Local, NaiveDateTime, TimeZone, Utc};
+use std::collections::HashMap;
+use crate::{
+    api::{publications::PublicationService, submissions::SubmissionService},
+    hooks::use_publication_role,
+    models::{
+        publication::Publication,
+        submission::{Submission, SubmissionStatus, UpdateSubmissionRequest},
+    },
+    Route,
+};
+
+#[component]
+pub fn PublicationReviewPage(slug: String) -> Element {
+    let mut publication = use_signal(|| None::<Publication>);
+    let mut submissions = use_signal(Vec::<Submission>::new);
+    let mut loading = use_signal(|| true);
+    let mut error = use_signal(|| None::<String>);
+    // None 表示所有未结束的投稿
+    let mut status_filter = use_signal(|| None::<SubmissionStatus>);
+    let mut expanded = use_signal(|| None::<String>);
+    let mut notes = use_signal(HashMap::<String, String>::new);
+    let mut schedule_times = use_signal(HashMap::<String, String>::new);
+    let mut busy = use_signal(|| false);
+
+    let publication_id = use_memo(move || publication().map(|p| p.id));
+    let role = use_publication_role(publication_id);
+    let can_review = use_memo(move || role().is_some_and(|r| r.can_publish()));
+
+    // 加载出版物
+    use_effect(move || {
+        let slug = slug.clone();
+        spawn(async move {
+            match PublicationService::get_publication(&slug).await {
+                Ok(data) => publication.set(Some(data)),
+                Err(e) => {
+                    error.set(Some(format!("加载出版物失败: {}", e.message)));
+                    loading.set(false);
+                }
+            }
+        });
+    });
+
+    // 加载审核队列
+    use_effect(move || {
+        let (Some(pub_id), true) = (publication_id(), can_review()) else {
+            return;
+        };
+        let filter = status_filter();
+        spawn(async move {
+            loading.set(true);
+            match SubmissionService::get_submissions(&pub_id, filter, Some(1), Some(50)).await {
+                Ok(resp) => {
+                    let list = resp
+                        .submissions
+                        .into_iter()
+                        .filter(|s| filter.is_some() || !matches!(s.status, SubmissionStatus::Rejected))
+                        .collect();
+                    submissions.set(list);
+                }
+                Err(e) => error.set(Some(format!("加载投稿失败: {}", e.message))),
+            }
+            loading.set(false);
+        });
+    });
+
+    let replace_submission = move |updated: Submission| {
+        if let Some(item) = submissions.write().iter_mut().find(|s| s.id == updated.id) {
+            *item = updated;
+        }
+    };
+
+    // 变更投稿状态（附备注）
+    let mut change_status = move |current: Submission, status: SubmissionStatus| {
+        let note = notes.read().get(&current.id).cloned().unwrap_or_default();
+        let scheduled_at = if status == SubmissionStatus::Scheduled {
+            let raw = schedule_times.read().get(&current.id).cloned().unwrap_or_default();
+            match NaiveDateTime::parse_from_str(&raw, "%Y-%m-%dT%H:%M")
+                .ok()
+                .and_then(|naive| Local.from_local_datetime(&naive).single())
+            {
+                Some(local) if local.with_timezone(&Utc) > Utc::now() => Some(local.with_timezone(&Utc)),
+                _ => {
+                    error.set(Some("请选择一个将来的发布时间".to_string()));
+                    return;
+                }
+            }
+        } else {
+            None
+        };
+        if status == SubmissionStatus::ChangesRequested && note.trim().is_empty() {
+            error.set(Some("要求修改时请写明需要修改的地方".to_string()));
+            return;
+        }
+
+        let request = UpdateSubmissionRequest {
+            status,
+            note: if note.trim().is_empty() { None } else { Some(note.trim().to_string()) },
+            scheduled_at,
+        };
+        let mut replace_submission = replace_submission;
+        spawn(async move {
+            busy.set(true);
+            error.set(None);
+            match SubmissionService::update_status(&current.publication_id, &current.id, &request).await {
+                Ok(updated) => {
+                    notes.write().remove(&updated.id);
+                    replace_submission(updated);
+                }
+                Err(e) => error.set(Some(format!("操作失败: {}", e.message))),
+            }
+            busy.set(false);
+        });
+    };
+
+    let publish_now = move |current: Submission| {
+        let mut replace_submission = replace_submission;
+        spawn(async move {
+            busy.set(true);
+            error.set(None);
+            match SubmissionService::publish(&current.publication_id, &current.id).await {
+                Ok(updated) => replace_submission(updated),
+                Err(e) => error.set(Some(format!("发布失败: {}", e.message))),
+            }
+            busy.set(false);
+        });
+    };
+
+    let Some(pub_data) = publication() else {
+        return rsx! {
+            div {
+                class: "min-h-screen flex items-center justify-center",
+                if let Some(err) = error() {
+                    p { class: "text-gray-500", {err} }
+                } else {
+                    div { class: "animate-spin rounded-full h-8 w-8 border-b-2 border-gray-900 dark:border-white" }
+                }
+            }
+        };
+    };
+
+    rsx! {
+        div {
+            class: "min-h-screen bg-white dark:bg-gray-900",
+
+            // 顶部导航
+            nav {
+                class: "border-b border-gray-200 dark:border-gray-700",
+                div {
+                    class: "max-w-7xl mx-auto px-4 sm:px-6 lg:px-8",
+                    div {
+                        class: "flex items-center justify-between h-16",
+                        Link {
+                            to: Route::Home {},
+                            class: "text-2xl font-serif font-bold text-gray-900 dark:text-white",
+                            "Rainbow Blog"
+                        }
+                        Link {
+                            to: Route::PublicationDetail { slug: pub_data.slug.clone() },
+                            class: "text-sm text-gray-600 dark:text-gray-400 hover:text-gray-900 dark:hover:text-white",
+                            "← 返回 {pub_data.name}"
+                        }
+                    }
+                }
+            }
+
+            div {
+                class: "max-w-4xl mx-auto px-4 py-8",
+                h1 {
+                    class: "text-3xl font-serif font-bold text-gray-900 dark:text-white mb-6",
+                    "审核队列"
+                }
+
+                if !can_review() {
+                    p {
+                        class: "text-gray-500 dark:text-gray-400",
+                        "只有出版物的所有者和编辑可以审核投稿"
+                    }
+                } else {
+                    // 状态筛选
+                    div {
+                        class: "flex flex-wrap gap-2 mb-6",
+                        button {
+                            class: if status_filter().is_none() {
+                                "px-3 py-1 text-sm rounded-full bg-gray-900 text-white"
+                            } else {
+                                "px-3 py-1 text-sm rounded-full border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-300"
+                            },
+                            onclick: move |_| status_filter.set(None),
+                            "待处理"
+                        }
+                        for status in SubmissionStatus::ALL {
+                            button {
+                                key: "{status.as_str()}",
+                                class: if status_filter() == Some(status) {
+                                    "px-3 py-1 text-sm rounded-full bg-gray-900 text-white"
+                                } else {
+                                    "px-3 py-1 text-sm rounded-full border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-300"
+                                },
+                                onclick: move |_| status_filter.set(Some(status)),
+                                {status.label()}
+                            }
+                        }
+                    }
+
+                    if let Some(err) = error() {
+                        div {
+                            class: "mb-4 p-3 rounded bg-red-50 text-red-700 border border-red-200",
+                            {err}
+                        }
+                    }
+
+                    if loading() {
+                        div {
+                            class: "flex justify-center py-12",
+                            div { class: "animate-spin rounded-full h-8 w-8 border-b-2 border-gray-900 dark:border-white" }
+                        }
+                    } else if submissions().is_empty() {
+                        p {
+                            class: "text-center py-12 text-gray-500 dark:text-gray-400",
+                            "没有符合条件的投稿"
+                        }
+                    } else {
+                        div {
+                            class: "divide-y divide-gray-200 dark:divide-gray-700 border border-gray-200 dark:border-gray-700 rounded-lg",
+                            for item in submissions() {
+                                div {
+                                    key: "{item.id}",
+                                    class: "p-4 space-y-3",
+
+                                    div {
+                                        class: "flex items-start justify-between gap-4",
+                                        div {
+                                            class: "min-w-0",
+                                            Link {
+                                                to: Route::Edit { slug: item.article.slug.clone() },
+                                                class: "text-lg font-medium text-gray-900 dark:text-white hover:underline",
+                                                {item.article.title.clone()}
+                                            }
+                                            p {
+                                                class: "text-sm text-gray-500 dark:text-gray-400",
+                                                {format!("{} · 投稿于 {}", item.author.display_name, item.submitted_at.format("%Y-%m-%d %H:%M"))}
+                                            }
+                                            if let Some(excerpt) = &item.article.excerpt {
+                                                p {
+                                                    class: "mt-1 text-sm text-gray-600 dark:text-gray-300 line-clamp-2",
+                                                    {excerpt.clone()}
+                                                }
+                                            }
+                                        }
+                                        div {
+                                            class: "flex flex-col items-end space-y-1 shrink-0",
+                                            span {
+                                                class: "px-2 py-0.5 rounded-full text-xs font-medium {item.status.badge_class()}",
+                                                {item.status.label()}
+                                            }
+                                            if let Some(at) = item.scheduled_at {
+                                                span {
+                                                    class: "text-xs text-gray-500",
+                                                    {format!("{} 发布", at.with_timezone(&Local).format("%m-%d %H:%M"))}
+                                                }
+                                            }
+                                            button {
+                                                class: "text-xs text-gray-500 hover:text-gray-900 dark:hover:text-white",
+                                                onclick: {
+                                                    let id = item.id.clone();
+                                                    move |_| {
+                                                        if expanded().as_deref() == Some(id.as_str()) {
+                                                            expanded.set(None);
+                                                        } else {
+                                                            expanded.set(Some(id.clone()));
+                                                        }
+                                                    }
+                                                },
+                                                if expanded().as_deref() == Some(item.id.as_str()) { "收起" } else { "处理" }
+                                            }
+                                        }
+                                    }
+
+                                    if expanded().as_deref() == Some(item.id.as_str()) {
+                                        // 处理记录
+                                        if !item.history.is_empty() {
+                                            ol {
+                                                class: "space-y-2 border-l border-gray-200 dark:border-gray-700 pl-4 text-sm",
+                                                for (i, event) in item.history.iter().enumerate() {
+                                                    li {
+                                                        key: "{i}",
+                                                        div {
+                                                            class: "text-gray-500",
+                                                            {format!("{} · {} · {}", event.created_at.format("%Y-%m-%d %H:%M"), event.actor.display_name, event.status.label())}
+                                                        }
+                                                        if let Some(note) = &event.note {
+                                                            p {
+                                                                class: "text-gray-800 dark:text-gray-200 whitespace-pre-wrap",
+                                                                {note.clone()}
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+                                        }
+
+                                        if !item.status.editor_transitions().is_empty() || item.status.can_publish() {
+                                            textarea {
+                                                class: "w-full h-20 p-3 text-sm bg-transparent border border-gray-300 dark:border-gray-600 rounded-lg outline-none resize-none text-gray-900 dark:text-white",
+                                                placeholder: "给作者的备注（要求修改时必填）",
+                                                value: notes.read().get(&item.id).cloned().unwrap_or_default(),
+                                                oninput: {
+                                                    let id = item.id.clone();
+                                                    move |e: FormEvent| {
+                                                        notes.write().insert(id.clone(), e.value());
+                                                    }
+                                                }
+                                            }
+
+                                            if item.status.editor_transitions().contains(&SubmissionStatus::Scheduled) {
+                                                div {
+                                                    class: "flex items-center space-x-2 text-sm",
+                                                    span { class: "text-gray-600 dark:text-gray-400", "定时发布" }
+                                                    input {
+                                                        r#type: "datetime-local",
+                                                        class: "px-2 py-1 border border-gray-300 dark:border-gray-600 rounded bg-transparent text-gray-900 dark:text-white",
+                                                        value: schedule_times.read().get(&item.id).cloned().unwrap_or_default(),
+                                                        oninput: {
+                                                            let id = item.id.clone();
+                                                            move |e: FormEvent| {
+                                                                schedule_times.write().insert(id.clone(), e.value());
+                                                            }
+                                                        }
+                                                    }
+                                                }
+                                            }
+
+                                            div {
+                                                class: "flex flex-wrap gap-2",
+                                                for status in item.status.editor_transitions().iter().copied() {
+                                                    button {
+                                                        key: "{status.as_str()}",
+                                                        class: match status {
+                                                            SubmissionStatus::Rejected => "px-3 py-1.5 text-sm rounded-full border border-red-300 text-red-700 hover:bg-red-50 disabled:opacity-50",
+                                                            SubmissionStatus::Approved => "px-3 py-1.5 text-sm rounded-full bg-green-600 text-white hover:bg-green-700 disabled:opacity-50",
+                                                            _ => "px-3 py-1.5 text-sm rounded-full border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-300 hover:border-gray-500 disabled:opacity-50",
+                                                        },
+                                                        disabled: busy(),
+                                                        onclick: {
+                                                            let item = item.clone();
+                                                            move |_| change_status(item.clone(), status)
+                                                        },
+                                                        {transition_label(item.status, status)}
+                                                    }
+                                                }
+                                                if item.status.can_publish() {
+                                                    button {
+                                                        class: "px-3 py-1.5 text-sm rounded-full bg-gray-900 text-white hover:bg-gray-700 disabled:opacity-50",
+                                                        disabled: busy(),
+                                                        onclick: {
+                                                            let item = item.clone();
+                                                            move |_| publish_now(item.clone())
+                                                        },
+                                                        "立即发布"
+                                                    }
+                                                }
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// 操作按钮文案
+fn transition_label(from: SubmissionStatus, to: SubmissionStatus) -> &'static str {
+    match (from, to) {
+        (_, SubmissionStatus::InReview) => "开始审核",
+        (_, SubmissionStatus::ChangesRequested) => "要求修改",
+        (SubmissionStatus::Scheduled, SubmissionStatus::Approved) => "取消排期",
+        (_, SubmissionStatus::Approved) => "通过",
+        (_, SubmissionStatus::Scheduled) => "定时发布",
+        (_, SubmissionStatus::Rejected) => "拒绝",
+        (_, SubmissionStatus::Submitted) => "退回待审",
+    }
+}
@@ -2,6 +2,7 @@ use dioxus::prelude::*;
 use dioxus_router::prelude::*;
 use crate::{
     api::publications::PublicationService,
+    components::NotificationBell,
     models::publication::Publication,
     hooks::use_auth,
     Route,
@@ -90,6 +91,7 @@ pub fn PublicationsPage() -> Element {
                             }
                             
                             if auth.read().is_authenticated {
+                                NotificationBell {}
                                 Link {
                                     to: Route::CreatePublication {},
                                     class: "px-4 py-2 bg-green-600 text-white rounded-full text-sm hover:bg-green-700",
@@ -0,0 +1,301 @@
+use dioxus::prelude::*;
+use dioxus_router::prelude::*;
+use chrono::{DateTime, Utc};
+use crate::{
+    hooks::{use_auth, ReadingStore},
+    models::reading::ReadingRecord,
+    Route,
+};
+
+// 未登录也可以查看，记录保存在本地
+#[component]
+pub fn ReadingHistoryPage() -> Element {
+    let auth = use_auth();
+    let mut records = use_signal(Vec::<ReadingRecord>::new);
+    let mut loading = use_signal(|| true);
+    let mut error = use_signal(|| None::<String>);
+    let mut query = use_signal(String::new);
+    let mut paused = use_signal(ReadingStore::paused);
+    let mut page = use_signal(|| 1);
+    let mut has_next = use_signal(|| false);
+    // 每次搜索词或登录状态变化时递增，丢弃较慢返回的旧请求结果
+    let mut list_generation = use_hook(|| CopyValue::new(0u64));
+
+    // 搜索词或登录状态变化时重新加载第一页
+    use_effect(move || {
+        let authenticated = auth.read().is_authenticated;
+        let query = query();
+        let generation = *list_generation.peek() + 1;
+        list_generation.set(generation);
+        spawn(async move {
+            loading.set(true);
+            let result = ReadingStore::history(&query, 1, authenticated).await;
+            if *list_generation.peek() != generation {
+                return;
+            }
+            match result {
+                Ok((list, next)) => {
+                    records.set(list);
+                    has_next.set(next);
+                    page.set(1);
+                }
+                Err(e) => error.set(Some(format!("加载阅读历史失败: {}", e.message))),
+            }
+            loading.set(false);
+        });
+    });
+
+    let load_more = move |_| {
+        let next_page = page() + 1;
+        let query = query();
+        let authenticated = auth.peek().is_authenticated;
+        let generation = *list_generation.peek();
+        spawn(async move {
+            loading.set(true);
+            let result = ReadingStore::history(&query, next_page, authenticated).await;
+            if *list_generation.peek() != generation {
+                return;
+            }
+            match result {
+                Ok((list, next)) => {
+                    records.with_mut(|current| {
+                        for record in list {
+                            if !current.iter().any(|r| r.article_id == record.article_id) {
+                                current.push(record);
+                            }
+                        }
+                    });
+                    has_next.set(next);
+                    page.set(next_page);
+                }
+                Err(e) => error.set(Some(format!("加载阅读历史失败: {}", e.message))),
+            }
+            loading.set(false);
+        });
+    };
+
+    use_effect(move || {
+        let authenticated = auth.read().is_authenticated;
+        spawn(async move {
+            paused.set(ReadingStore::refresh_paused(authenticated).await);
+        });
+    });
+
+    let remove = move |article_id: String| {
+        let authenticated = auth.peek().is_authenticated;
+        spawn(async move {
+            match ReadingStore::remove(&article_id, authenticated).await {
+                Ok(_) => records.with_mut(|list| list.retain(|r| r.article_id != article_id)),
+                Err(e) => error.set(Some(format!("移除失败: {}", e.message))),
+            }
+        });
+    };
+
+    let clear = move |_| {
+        let confirmed = web_sys::window()
+            .and_then(|w| w.confirm_with_message("确定要清空全部阅读历史吗？").ok())
+            .unwrap_or(false);
+        if !confirmed {
+            return;
+        }
+        let authenticated = auth.peek().is_authenticated;
+        spawn(async move {
+            match ReadingStore::clear(authenticated).await {
+                Ok(_) => {
+                    records.set(Vec::new());
+                    has_next.set(false);
+                }
+                Err(e) => error.set(Some(format!("清空失败: {}", e.message))),
+            }
+        });
+    };
+
+    let toggle_paused = move |_| {
+        let next = !paused();
+        let authenticated = auth.peek().is_authenticated;
+        spawn(async move {
+            match ReadingStore::set_paused(next, authenticated).await {
+                Ok(_) => paused.set(next),
+                Err(e) => error.set(Some(format!("保存设置失败: {}", e.message))),
+            }
+        });
+    };
+
+    rsx! {
+        div {
+            class: "min-h-screen bg-white dark:bg-gray-900",
+
+            // 顶部导航
+            nav {
+                class: "border-b border-gray-200 dark:border-gray-700",
+                div {
+                    class: "max-w-7xl mx-auto px-4 sm:px-6 lg:px-8",
+                    div {
+                        class: "flex items-center justify-between h-16",
+                        Link {
+                            to: Route::Home {},
+                            class: "text-2xl font-serif font-bold text-gray-900 dark:text-white",
+                            "Rainbow Blog"
+                        }
+                        Link {
+                            to: Route::Home {},
+                            class: "text-sm text-gray-600 dark:text-gray-400 hover:text-gray-900 dark:hover:text-white",
+                            "← 返回首页"
+                        }
+                    }
+                }
+            }
+
+            div {
+                class: "max-w-3xl mx-auto px-4 sm:px-6 lg:px-8 py-8",
+
+                div {
+                    class: "flex items-center justify-between mb-6",
+                    div {
+                        h1 {
+                            class: "text-3xl font-bold text-gray-900 dark:text-white",
+                            "阅读历史"
+                        }
+                        p {
+                            class: "text-gray-600 dark:text-gray-400 mt-1",
+                            if auth.read().is_authenticated {
+                                "你读过的文章及阅读进度，在所有设备间同步"
+                            } else {
+                                "你读过的文章及阅读进度，仅保存在当前浏览器"
+                            }
+                        }
+                    }
+                    div {
+                        class: "flex items-center space-x-3",
+                        button {
+                            class: "px-3 py-1.5 text-sm border border-gray-300 dark:border-gray-600 text-gray-700 dark:text-gray-300 rounded-lg hover:bg-gray-50 dark:hover:bg-gray-800",
+                            onclick: toggle_paused,
+                            if paused() { "恢复记录" } else { "暂停记录" }
+                        }
+                        button {
+                            class: "px-3 py-1.5 text-sm text-red-600 hover:text-red-700 disabled:opacity-50",
+                            disabled: records().is_empty(),
+                            onclick: clear,
+                            "清空历史"
+                        }
+                    }
+                }
+
+                if paused() {
+                    div {
+                        class: "mb-6 bg-yellow-50 dark:bg-yellow-900/20 border border-yellow-200 dark:border-yellow-800 text-yellow-800 dark:text-yellow-300 px-4 py-3 rounded-lg text-sm",
+                        "阅读历史已暂停，新的阅读进度不会被记录"
+                    }
+                }
+
+                input {
+                    r#type: "search",
+                    class: "w-full mb-6 px-4 py-2 border border-gray-300 dark:border-gray-600 dark:bg-gray-800 dark:text-white rounded-lg focus:outline-none focus:ring-2 focus:ring-green-500",
+                    placeholder: "按标题或作者搜索...",
+                    value: "{query}",
+                    oninput: move |e| query.set(e.value())
+                }
+
+                if let Some(error_msg) = error() {
+                    div {
+                        class: "mb-6 bg-red-50 dark:bg-red-900/20 border border-red-200 dark:border-red-800 text-red-700 dark:text-red-400 px-4 py-3 rounded-lg",
+                        {error_msg}
+                    }
+                }
+
+                if loading() && records().is_empty() {
+                    div {
+                        class: "flex justify-center py-12",
+                        div {
+                            class: "animate-spin rounded-full h-8 w-8 border-b-2 border-gray-900 dark:border-white"
+                        }
+                    }
+                } else if records().is_empty() {
+                    div {
+                        class: "text-center py-12 bg-gray-50 dark:bg-gray-800 rounded-lg text-gray-500 dark:text-gray-400",
+                        if query().is_empty() { "还没有阅读记录" } else { "没有找到匹配的文章" }
+                    }
+                } else {
+                    div {
+                        class: "divide-y divide-gray-100 dark:divide-gray-800",
+                        for record in records() {
+                            div {
+                                key: "{record.article_id}",
+                                class: "flex items-start py-4",
+                                div {
+                                    class: "flex-1 min-w-0",
+                                    Link {
+                                        to: Route::Article { slug: record.article_slug.clone() },
+                                        class: "block text-lg font-semibold text-gray-900 dark:text-white hover:underline",
+                                        {record.article_title.clone()}
+                                    }
+                                    if let Some(excerpt) = &record.article_excerpt {
+                                        p {
+                                            class: "text-sm text-gray-600 dark:text-gray-400 mt-1 line-clamp-2",
+                                            {excerpt.clone()}
+                                        }
+                                    }
+                                    div {
+                                        class: "flex items-center text-xs text-gray-500 dark:text-gray-400 mt-2 space-x-3",
+                                        span { {record.author_name.clone()} }
+                                        span { {format_relative_time(&record.last_read_at)} }
+                                        span {
+                                            if record.is_finished() {
+                                                "已读完"
+                                            } else {
+                                                {format!("已读 {}% · 还剩 {} 分钟", (record.progress * 100.0).round(), record.minutes_left())}
+                                            }
+                                        }
+                                    }
+                                    div {
+                                        class: "h-1 mt-2 w-40 bg-gray-100 dark:bg-gray-800 rounded-full overflow-hidden",
+                                        div {
+                                            class: "h-full bg-green-600",
+                                            style: format!("width: {}%", record.progress * 100.0)
+                                        }
+                                    }
+                                }
+                                button {
+                                    class: "ml-4 text-sm text-gray-400 hover:text-red-600",
+                                    title: "从阅读历史中移除",
+                                    onclick: {
+                                        let article_id = record.article_id.clone();
+                                        move |_| remove(article_id.clone())
+                                    },
+                                    "移除"
+                                }
+                            }
+                        }
+                    }
+                    if has_next() {
+                        div {
+                            class: "text-center mt-6",
+                            button {
+                                class: "px-4 py-2 text-sm border border-gray-300 dark:border-gray-600 rounded-full text-gray-700 dark:text-gray-300 hover:border-gray-500 disabled:opacity-50",
+                                disabled: loading(),
+                                onclick: load_more,
+                                if loading() { "加载中..." } else { "加载更多" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn format_relative_time(datetime: &DateTime<Utc>) -> String {
+    let duration = Utc::now().signed_duration_since(*datetime);
+
+    if duration.num_seconds() < 60 {
+        "刚刚".to_string()
+    } else if duration.num_minutes() < 60 {
+        format!("{} 分钟前", duration.num_minutes())
+    } else if duration.num_hours() < 24 {
+        format!("{} 小时前", duration.num_hours())
+    } else if duration.num_days() < 7 {
+        format!("{} 天前", duration.num_days())
+    } else {
+        datetime.format("%Y-%m-%d %H:%M").to_string()
+    }
+}
@@ -1,12 +1,12 @@
 use dioxus::prelude::*;
 use dioxus_router::prelude::*;
 use crate::{
-    api::{series::SeriesService, articles::ArticleService},
+    api::{series::SeriesService, articles::ArticleService, reading::ReadingService},
     models::{
         series::{SeriesWithArticles, SeriesArticle},
         article::Article,
     },
-    hooks::use_auth,
+    hooks::{next_unfinished, use_auth},
     Route,
 };
 
@@ -18,6 +18,8 @@ pub fn SeriesDetailPage(slug: String) -> Element {
     let mut show_add_article = use_signal(|| false);
     let mut available_articles = use_signal(|| Vec::<Article>::new());
     let mut is_owner = use_signal(|| false);
+    // 登录读者已读完的文章
+    let mut finished_ids = use_signal(Vec::<String>::new);
     
     let auth = use_auth();
     let slug_for_effect = slug.clone();
@@ -37,7 +39,19 @@ pub fn SeriesDetailPage(slug: String) -> Element {
                     if let Some(user) = &auth.read().user {
                         is_owner.set(data.series.author_id == user.id);
                     }
+                    let series_id = data.series.id.clone();
                     series_data.set(Some(data));
+                    if auth.read().is_authenticated {
+                        if let Ok(records) = ReadingService::get_series_progress(&series_id).await {
+                            finished_ids.set(
+                                records
+                                    .into_iter()
+                                    .filter(|r| r.is_finished())
+                                    .map(|r| r.article_id)
+                                    .collect(),
+                            );
+                        }
+                    }
                 }
                 Err(e) => {
                     error.set(Some(e.message));
@@ -179,6 +193,12 @@ pub fn SeriesDetailPage(slug: String) -> Element {
                                             "{data.series.article_count} 篇文章"
                                         }
                                         
+                                        if !finished_ids().is_empty() {
+                                            span {
+                                                {format!("已读完 {} / {} 篇", data.articles.iter().filter(|sa| finished_ids().contains(&sa.article.id)).count(), data.articles.len())}
+                                            }
+                                        }
+                                        
                                         if data.series.is_completed {
                                             span {
                                                 class: "px-2 py-1 bg-green-100 dark:bg-green-900 text-green-800 dark:text-green-200 rounded",
@@ -193,16 +213,37 @@ pub fn SeriesDetailPage(slug: String) -> Element {
                                     }
                                 }
                                 
-                                // 操作按钮
-                                if is_owner() {
-                                    button {
-                                        class: "px-4 py-2 bg-blue-600 text-white rounded-md hover:bg-blue-700",
-                                        onclick: move |_| {
-                                            load_available_articles();
-                                            show_add_article.set(true);
-                                        },
-                                        "添加文章"
+                                // 从头阅读，或从第一篇没读完的文章继续
+                                div {
+                                    class: "flex items-center space-x-3",
+                                    if let Some(first) = data.parts().first() {
+                                        Link {
+                                            to: Route::Article { slug: first.article.slug.clone() },
+                                            class: "px-4 py-2 bg-green-600 text-white rounded-md hover:bg-green-700",
+                                            "从头开始阅读"
+                                        }
                                     }
+                                    if !finished_ids().is_empty() {
+                                        if let Some(next) = next_unfinished(&data, &finished_ids()) {
+                                            Link {
+                                                to: Route::Article { slug: next.slug.clone() },
+                                                class: "px-4 py-2 border border-green-600 text-green-700 dark:text-green-400 rounded-md hover:bg-green-50 dark:hover:bg-green-900/20",
+                                                "继续阅读"
+                                            }
+                                        }
+                                    }
+                                    
+                                    // 操作按钮
+                                    if is_owner() {
+                                        button {
+                                            class: "px-4 py-2 bg-blue-600 text-white rounded-md hover:bg-blue-700",
+                                            onclick: move |_| {
+                                                load_available_articles();
+                                                show_add_article.set(true);
+                                            },
+                                            "添加文章"
+                                        }
+                                }
                                 }
                             }
                         }
@@ -223,10 +264,11 @@ pub fn SeriesDetailPage(slug: String) -> Element {
                         } else {
                             div {
                                 class: "space-y-6",
-                                for (index, series_article) in data.articles.iter().enumerate() {
+                                for (index, series_article) in data.parts().into_iter().enumerate() {
                                     SeriesArticleItem {
                                         series_article: series_article.clone(),
                                         index: index + 1,
+                                        finished: finished_ids().contains(&series_article.article.id),
                                         is_owner: is_owner(),
                                         on_remove: {
                                             let article_id = series_article.article.id.clone();
@@ -297,6 +339,7 @@ pub fn SeriesDetailPage(slug: String) -> Element {
 fn SeriesArticleItem(
     series_article: SeriesArticle,
     index: usize,
+    finished: bool,
     is_owner: bool,
     on_remove: EventHandler<String>,
 ) -> Element {
@@ -346,6 +389,12 @@ fn SeriesArticleItem(
                     span {
                         {article.published_at.map(|d| d.format("%Y-%m-%d").to_string()).unwrap_or("草稿".to_string())}
                     }
+                    if finished {
+                        span {
+                            class: "ml-2 text-green-600",
+                            "· ✓ 已读完"
+                        }
+                    }
                 }
             }
             
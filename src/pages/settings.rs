@@ -1,10 +1,16 @@
 use dioxus::prelude::*;
 use dioxus_router::prelude::*;
 use crate::{
-    api::users::UserService,
-    models::user::{UpdateProfileRequest, UserProfile},
-    hooks::use_auth,
-    components::{ProtectedRoute, ImageDropZone},
+    api::{privacy::PrivacyService, users::UserService},
+    models::{
+        user::{UpdateProfileRequest, UserProfile},
+        offline::{format_bytes, STORAGE_LIMITS_MB},
+        preferences::{DigestFrequency, NotificationChannel, NotificationPreferences, NotificationTopic},
+        privacy::{MentionPermission, PrivacySettings, RelatedUser, RelationKind},
+        moderation::CommentPermission,
+    },
+    hooks::{use_auth, use_user_relations, OfflineStore},
+    components::{ProtectedRoute, ImageDropZone, ReaderSettingsPanel, others_highlights_hidden, set_others_highlights_hidden},
     Route,
 };
 use gloo_timers::future::TimeoutFuture;
@@ -182,6 +188,24 @@ fn SettingsContent() -> Element {
                                 onclick: move |_| active_tab.set("privacy"),
                                 "隐私设置"
                             }
+                            button {
+                                class: if active_tab() == "reading" {
+                                    "w-full text-left px-4 py-2 text-sm font-medium text-gray-900 dark:text-white bg-gray-100 dark:bg-gray-800 rounded-md"
+                                } else {
+                                    "w-full text-left px-4 py-2 text-sm text-gray-600 dark:text-gray-400 hover:text-gray-900 dark:hover:text-white hover:bg-gray-50 dark:hover:bg-gray-800 rounded-md"
+                                },
+                                onclick: move |_| active_tab.set("reading"),
+                                "阅读设置"
+                            }
+                            button {
+                                class: if active_tab() == "offline" {
+                                    "w-full text-left px-4 py-2 text-sm font-medium text-gray-900 dark:text-white bg-gray-100 dark:bg-gray-800 rounded-md"
+                                } else {
+                                    "w-full text-left px-4 py-2 text-sm text-gray-600 dark:text-gray-400 hover:text-gray-900 dark:hover:text-white hover:bg-gray-50 dark:hover:bg-gray-800 rounded-md"
+                                },
+                                onclick: move |_| active_tab.set("offline"),
+                                "离线阅读"
+                            }
                             
                             // 订阅管理相关链接
                             div {
@@ -241,6 +265,57 @@ fn SettingsContent() -> Element {
                                     }
                                     "收益统计"
                                 }
+                                Link {
+                                    to: Route::CommentModeration {},
+                                    class: "flex items-center w-full px-4 py-2 text-sm text-gray-600 dark:text-gray-400 hover:text-gray-900 dark:hover:text-white hover:bg-gray-50 dark:hover:bg-gray-800 rounded-md",
+                                    svg {
+                                        class: "w-4 h-4 mr-3",
+                                        fill: "none",
+                                        stroke: "currentColor",
+                                        view_box: "0 0 24 24",
+                                        path {
+                                            stroke_linecap: "round",
+                                            stroke_linejoin: "round",
+                                            stroke_width: "2",
+                                            d: "M8 10h.01M12 10h.01M16 10h.01M9 16H5a2 2 0 01-2-2V6a2 2 0 012-2h14a2 2 0 012 2v8a2 2 0 01-2 2h-5l-5 5v-5z"
+                                        }
+                                    }
+                                    "评论管理"
+                                }
+                                Link {
+                                    to: Route::PrivateNotes {},
+                                    class: "flex items-center w-full px-4 py-2 text-sm text-gray-600 dark:text-gray-400 hover:text-gray-900 dark:hover:text-white hover:bg-gray-50 dark:hover:bg-gray-800 rounded-md",
+                                    svg {
+                                        class: "w-4 h-4 mr-3",
+                                        fill: "none",
+                                        stroke: "currentColor",
+                                        view_box: "0 0 24 24",
+                                        path {
+                                            stroke_linecap: "round",
+                                            stroke_linejoin: "round",
+                                            stroke_width: "2",
+                                            d: "M3 8l7.89 5.26a2 2 0 002.22 0L21 8M5 19h14a2 2 0 002-2V7a2 2 0 00-2-2H5a2 2 0 00-2 2v10a2 2 0 002 2z"
+                                        }
+                                    }
+                                    "读者私信"
+                                }
+                                Link {
+                                    to: Route::Notifications {},
+                                    class: "flex items-center w-full px-4 py-2 text-sm text-gray-600 dark:text-gray-400 hover:text-gray-900 dark:hover:text-white hover:bg-gray-50 dark:hover:bg-gray-800 rounded-md",
+                                    svg {
+                                        class: "w-4 h-4 mr-3",
+                                        fill: "none",
+                                        stroke: "currentColor",
+                                        view_box: "0 0 24 24",
+                                        path {
+                                            stroke_linecap: "round",
+                                            stroke_linejoin: "round",
+                                            stroke_width: "2",
+                                            d: "M15 17h5l-1.405-1.405A2.032 2.032 0 0118 14.158V11a6.002 6.002 0 00-4-5.659V5a2 2 0 10-4 0v.341C7.67 6.165 6 8.388 6 11v3.159c0 .538-.214 1.055-.595 1.436L4 17h5m6 0v1a3 3 0 11-6 0v-1m6 0H9"
+                                        }
+                                    }
+                                    "通知"
+                                }
                             }
                         }
                     }
@@ -511,79 +586,421 @@ fn SettingsContent() -> Element {
                             
                             // 通知设置
                             if active_tab() == "notifications" {
+                                NotificationPreferencesSettings {}
+                            }
+                            
+                            // 隐私设置
+                            if active_tab() == "privacy" {
+                                PrivacySettingsPanel {}
+                            }
+                            
+                            // 阅读设置
+                            if active_tab() == "reading" {
                                 div {
                                     class: "bg-white dark:bg-gray-800 shadow rounded-lg p-6",
                                     
                                     h2 {
                                         class: "text-lg font-medium text-gray-900 dark:text-white mb-6",
-                                        "通知设置"
+                                        "阅读设置"
                                     }
                                     
                                     div {
                                         class: "space-y-4",
                                         
-                                        NotificationToggle {
-                                            title: "新关注者",
-                                            description: "当有人关注您时收到通知",
-                                            checked: true
-                                        }
-                                        
-                                        NotificationToggle {
-                                            title: "评论回复",
-                                            description: "当有人回复您的评论时收到通知",
-                                            checked: true
+                                        div {
+                                            class: "pb-4 border-b border-gray-100 dark:border-gray-700",
+                                            ReaderSettingsPanel {}
                                         }
                                         
-                                        NotificationToggle {
-                                            title: "文章互动",
-                                            description: "当有人点赞或评论您的文章时收到通知",
-                                            checked: false
+                                        ReadingToggle {
+                                            title: "显示其他读者的热门高亮",
+                                            description: "在文章中标出被很多读者高亮过的段落及人数",
+                                            checked: !others_highlights_hidden(),
+                                            on_change: move |checked: bool| set_others_highlights_hidden(!checked)
                                         }
                                         
-                                        NotificationToggle {
-                                            title: "每周摘要",
-                                            description: "接收每周的统计数据和热门内容",
-                                            checked: true
+                                        div {
+                                            class: "flex items-center justify-between pt-4 border-t border-gray-100 dark:border-gray-700",
+                                            div {
+                                                h3 {
+                                                    class: "text-sm font-medium text-gray-900 dark:text-white",
+                                                    "阅读历史"
+                                                }
+                                                p {
+                                                    class: "text-sm text-gray-500 dark:text-gray-400",
+                                                    "查看、搜索或清除阅读记录，也可以暂停记录"
+                                                }
+                                            }
+                                            Link {
+                                                to: Route::ReadingHistory {},
+                                                class: "text-sm text-green-600 hover:text-green-700",
+                                                "管理 →"
+                                            }
                                         }
                                     }
                                 }
                             }
                             
-                            // 隐私设置
-                            if active_tab() == "privacy" {
-                                div {
-                                    class: "bg-white dark:bg-gray-800 shadow rounded-lg p-6",
-                                    
-                                    h2 {
-                                        class: "text-lg font-medium text-gray-900 dark:text-white mb-6",
-                                        "隐私设置"
+                            // 离线阅读
+                            if active_tab() == "offline" {
+                                OfflineStorageSettings {}
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn OfflineStorageSettings() -> Element {
+    let mut articles = use_signal(OfflineStore::list);
+    let mut limit_mb = use_signal(OfflineStore::storage_limit_mb);
+    let mut estimate = use_signal(|| None::<(f64, f64)>);
+    let mut error = use_signal(|| None::<String>);
+    
+    let mut refresh = move || {
+        articles.set(OfflineStore::list());
+        spawn(async move {
+            estimate.set(OfflineStore::storage_estimate().await);
+        });
+    };
+    
+    use_hook(refresh);
+    
+    let change_limit = move |e: Event<FormData>| {
+        let Ok(limit) = e.value().parse::<u64>() else { return };
+        limit_mb.set(limit);
+        spawn(async move {
+            if let Err(e) = OfflineStore::set_storage_limit_mb(limit).await {
+                error.set(Some(format!("清理失败: {}", e)));
+            }
+            refresh();
+        });
+    };
+    
+    let remove = move |article_id: String| {
+        spawn(async move {
+            if let Err(e) = OfflineStore::remove(&article_id).await {
+                error.set(Some(format!("移除失败: {}", e)));
+            }
+            refresh();
+        });
+    };
+    
+    let clear_all = move |_| {
+        let confirmed = web_sys::window()
+            .and_then(|w| w.confirm_with_message("确定要删除所有离线保存的文章吗？").ok())
+            .unwrap_or(false);
+        if !confirmed {
+            return;
+        }
+        spawn(async move {
+            if let Err(e) = OfflineStore::clear().await {
+                error.set(Some(format!("清除失败: {}", e)));
+            }
+            refresh();
+        });
+    };
+    
+    let saved_bytes = articles().iter().map(|a| a.size_bytes).sum::<u64>() as f64;
+    let limit_bytes = (limit_mb() * 1024 * 1024) as f64;
+    
+    rsx! {
+        div {
+            class: "bg-white dark:bg-gray-800 shadow rounded-lg p-6",
+            
+            h2 {
+                class: "text-lg font-medium text-gray-900 dark:text-white mb-6",
+                "离线阅读"
+            }
+            
+            if let Some(error_msg) = error() {
+                div {
+                    class: "mb-4 bg-red-50 dark:bg-red-900/20 border border-red-200 dark:border-red-800 text-red-700 dark:text-red-400 px-4 py-3 rounded-lg text-sm",
+                    {error_msg}
+                }
+            }
+            
+            // 存储用量
+            div {
+                class: "mb-6",
+                div {
+                    class: "flex justify-between text-sm text-gray-600 dark:text-gray-400 mb-2",
+                    span { {format!("离线文章 {} 篇，占用 {}", articles().len(), format_bytes(saved_bytes))} }
+                    span { {format!("上限 {}", format_bytes(limit_bytes))} }
+                }
+                div {
+                    class: "h-2 bg-gray-100 dark:bg-gray-700 rounded-full overflow-hidden",
+                    div {
+                        class: "h-full bg-green-600",
+                        style: format!("width: {}%", (saved_bytes / limit_bytes * 100.0).min(100.0))
+                    }
+                }
+                if let Some((usage, quota)) = estimate() {
+                    p {
+                        class: "text-xs text-gray-500 dark:text-gray-400 mt-2",
+                        {format!("本站在浏览器中共使用 {}，可用配额 {}", format_bytes(usage), format_bytes(quota))}
+                    }
+                }
+            }
+            
+            // 存储上限
+            div {
+                class: "flex items-center justify-between py-4 border-t border-gray-100 dark:border-gray-700",
+                div {
+                    h3 {
+                        class: "text-sm font-medium text-gray-900 dark:text-white",
+                        "存储上限"
+                    }
+                    p {
+                        class: "text-sm text-gray-600 dark:text-gray-400",
+                        "超出上限时自动删除最久没有打开的离线文章"
+                    }
+                }
+                select {
+                    class: "px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg text-sm",
+                    value: "{limit_mb}",
+                    onchange: change_limit,
+                    for limit in STORAGE_LIMITS_MB {
+                        option { value: "{limit}", selected: limit == limit_mb(), {format!("{} MB", limit)} }
+                    }
+                }
+            }
+            
+            // 已保存的文章
+            div {
+                class: "py-4 border-t border-gray-100 dark:border-gray-700",
+                div {
+                    class: "flex items-center justify-between mb-3",
+                    h3 {
+                        class: "text-sm font-medium text-gray-900 dark:text-white",
+                        "已保存的文章"
+                    }
+                    div {
+                        class: "flex items-center space-x-4",
+                        Link {
+                            to: Route::Offline {},
+                            class: "text-sm text-green-600 hover:text-green-700",
+                            "打开离线列表"
+                        }
+                        button {
+                            class: "text-sm text-red-600 hover:text-red-700 disabled:opacity-50",
+                            disabled: articles().is_empty(),
+                            onclick: clear_all,
+                            "全部删除"
+                        }
+                    }
+                }
+                if articles().is_empty() {
+                    p {
+                        class: "text-sm text-gray-500 dark:text-gray-400",
+                        "还没有离线保存的文章"
+                    }
+                }
+                for entry in articles() {
+                    div {
+                        key: "{entry.article_id}",
+                        class: "flex items-center justify-between py-2 text-sm",
+                        span {
+                            class: "truncate text-gray-700 dark:text-gray-300",
+                            {entry.title.clone()}
+                        }
+                        div {
+                            class: "flex items-center space-x-4 shrink-0 ml-4",
+                            span {
+                                class: "text-gray-500 dark:text-gray-400",
+                                {format_bytes(entry.size_bytes as f64)}
+                            }
+                            button {
+                                class: "text-gray-400 hover:text-red-600",
+                                onclick: {
+                                    let article_id = entry.article_id.clone();
+                                    move |_| remove(article_id.clone())
+                                },
+                                "删除"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn NotificationPreferencesSettings() -> Element {
+    let mut preferences = use_signal(|| None::<NotificationPreferences>);
+    let mut error = use_signal(|| None::<String>);
+    let mut saved = use_signal(|| false);
+    // 服务器最近确认的设置，保存失败时恢复到这里
+    let mut server_preferences = use_hook(|| CopyValue::new(None::<NotificationPreferences>));
+    // 保存期间的新修改只记下最新的设置，等上一个请求完成后再发，避免旧请求的结果覆盖新修改
+    let mut queued = use_hook(|| CopyValue::new(None::<NotificationPreferences>));
+    let mut saving = use_hook(|| CopyValue::new(false));
+    
+    use_hook(move || {
+        spawn(async move {
+            match UserService::get_notification_preferences().await {
+                Ok(data) => {
+                    server_preferences.set(Some(data.clone()));
+                    preferences.set(Some(data));
+                }
+                Err(e) => error.set(Some(format!("加载通知设置失败: {}", e.message))),
+            }
+        });
+    });
+    
+    // 先更新界面再依次保存，最后一次保存失败时恢复到服务器上的设置
+    let mut save = move |next: NotificationPreferences| {
+        preferences.set(Some(next.clone()));
+        queued.set(Some(next));
+        if *saving.peek() {
+            return;
+        }
+        saving.set(true);
+        spawn(async move {
+            saved.set(false);
+            error.set(None);
+            let mut failure = None;
+            loop {
+                let next = queued.write().take();
+                let Some(next) = next else { break };
+                match UserService::update_notification_preferences(&next).await {
+                    Ok(data) => {
+                        server_preferences.set(Some(data));
+                        failure = None;
+                    }
+                    Err(e) => failure = Some(e.message),
+                }
+            }
+            saving.set(false);
+            preferences.set(server_preferences.peek().clone());
+            match failure {
+                Some(message) => error.set(Some(format!("保存通知设置失败: {}", message))),
+                None => saved.set(true),
+            }
+        });
+    };
+    
+    let mut toggle = move |topic: NotificationTopic, channel: NotificationChannel, enabled: bool| {
+        let Some(mut next) = preferences() else { return };
+        next.topic_mut(topic).set(channel, enabled);
+        // 开启浏览器推送前先请求通知权限
+        if channel == NotificationChannel::Push && enabled && !push_permission_granted() {
+            spawn(async move {
+                if request_push_permission().await {
+                    save(next);
+                } else {
+                    error.set(Some("浏览器没有授予通知权限，无法开启浏览器推送".to_string()));
+                }
+            });
+            return;
+        }
+        save(next);
+    };
+    
+    let change_digest = move |e: Event<FormData>| {
+        let (Some(mut next), Some(digest)) = (preferences(), DigestFrequency::from_str(&e.value())) else {
+            return;
+        };
+        next.digest = digest;
+        save(next);
+    };
+    
+    let unsubscribe_all = move |_| {
+        let confirmed = web_sys::window()
+            .and_then(|w| w.confirm_with_message("确定关闭所有邮件、浏览器推送和摘要吗？站内通知不受影响。").ok())
+            .unwrap_or(false);
+        if !confirmed {
+            return;
+        }
+        spawn(async move {
+            saved.set(false);
+            error.set(None);
+            match UserService::unsubscribe_all_notifications().await {
+                Ok(data) => {
+                    server_preferences.set(Some(data.clone()));
+                    preferences.set(Some(data));
+                    saved.set(true);
+                }
+                Err(e) => error.set(Some(format!("退订失败: {}", e.message))),
+            }
+        });
+    };
+    
+    rsx! {
+        div {
+            class: "bg-white dark:bg-gray-800 shadow rounded-lg p-6",
+            
+            div {
+                class: "flex items-center justify-between mb-6",
+                h2 {
+                    class: "text-lg font-medium text-gray-900 dark:text-white",
+                    "通知设置"
+                }
+                if saved() {
+                    span { class: "text-xs text-green-600", "已保存" }
+                }
+            }
+            
+            if let Some(error_msg) = error() {
+                div {
+                    class: "mb-4 bg-red-50 dark:bg-red-900/20 border border-red-200 dark:border-red-800 text-red-700 dark:text-red-400 px-4 py-3 rounded-lg text-sm",
+                    {error_msg}
+                }
+            }
+            
+            if let Some(current) = preferences() {
+                if current.wants_push() && !push_permission_granted() {
+                    div {
+                        class: "mb-4 bg-yellow-50 dark:bg-yellow-900/20 border border-yellow-200 dark:border-yellow-800 text-yellow-800 dark:text-yellow-300 px-4 py-3 rounded-lg text-sm",
+                        "当前浏览器还没有允许通知，在这台设备上不会收到浏览器推送"
+                    }
+                }
+                
+                // 事件 × 渠道
+                table {
+                    class: "w-full text-sm",
+                    thead {
+                        tr {
+                            class: "text-left text-gray-500 dark:text-gray-400",
+                            th { class: "pb-3 font-normal", "事件" }
+                            for channel in NotificationChannel::ALL {
+                                th {
+                                    key: "{channel.as_str()}",
+                                    class: "pb-3 px-2 font-normal text-center whitespace-nowrap",
+                                    {channel.label()}
+                                }
+                            }
+                        }
+                    }
+                    tbody {
+                        for topic in NotificationTopic::ALL {
+                            tr {
+                                key: "{topic.as_str()}",
+                                class: "border-t border-gray-100 dark:border-gray-700",
+                                td {
+                                    class: "py-3 pr-4",
+                                    h3 {
+                                        class: "font-medium text-gray-900 dark:text-white",
+                                        {topic.label()}
                                     }
-                                    
-                                    div {
-                                        class: "space-y-4",
-                                        
-                                        PrivacyToggle {
-                                            title: "公开个人资料",
-                                            description: "其他用户可以查看您的个人资料页面",
-                                            checked: true
-                                        }
-                                        
-                                        PrivacyToggle {
-                                            title: "显示邮箱地址",
-                                            description: "在您的个人资料页显示邮箱地址",
-                                            checked: false
-                                        }
-                                        
-                                        PrivacyToggle {
-                                            title: "允许被搜索引擎索引",
-                                            description: "您的个人资料和文章可以被搜索引擎收录",
-                                            checked: true
-                                        }
-                                        
-                                        PrivacyToggle {
-                                            title: "显示阅读历史",
-                                            description: "其他用户可以看到您的阅读历史",
-                                            checked: false
+                                    p {
+                                        class: "text-gray-600 dark:text-gray-400",
+                                        {topic.description()}
+                                    }
+                                }
+                                for channel in NotificationChannel::ALL {
+                                    td {
+                                        key: "{channel.as_str()}",
+                                        class: "py-3 px-2 text-center",
+                                        input {
+                                            r#type: "checkbox",
+                                            class: "w-4 h-4",
+                                            checked: current.topic(topic).get(channel),
+                                            onchange: move |e| toggle(topic, channel, e.checked()),
                                         }
                                     }
                                 }
@@ -591,13 +1008,392 @@ fn SettingsContent() -> Element {
                         }
                     }
                 }
+                
+                // 邮件摘要
+                div {
+                    class: "flex items-center justify-between py-4 mt-2 border-t border-gray-100 dark:border-gray-700",
+                    div {
+                        h3 {
+                            class: "text-sm font-medium text-gray-900 dark:text-white",
+                            "邮件摘要"
+                        }
+                        p {
+                            class: "text-sm text-gray-600 dark:text-gray-400",
+                            "定期接收你的文章数据和关注的作者的热门内容"
+                        }
+                    }
+                    select {
+                        class: "px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg text-sm",
+                        value: current.digest.as_str(),
+                        onchange: change_digest,
+                        for frequency in DigestFrequency::ALL {
+                            option {
+                                value: frequency.as_str(),
+                                selected: frequency == current.digest,
+                                {frequency.label()}
+                            }
+                        }
+                    }
+                }
+                
+                // 全部退订
+                div {
+                    class: "flex items-center justify-between pt-4 border-t border-gray-100 dark:border-gray-700",
+                    div {
+                        h3 {
+                            class: "text-sm font-medium text-gray-900 dark:text-white",
+                            "全部退订"
+                        }
+                        p {
+                            class: "text-sm text-gray-600 dark:text-gray-400",
+                            if current.is_unsubscribed() {
+                                "已关闭所有邮件和浏览器推送，只保留站内通知"
+                            } else {
+                                "一键关闭所有邮件、浏览器推送和摘要，站内通知不受影响"
+                            }
+                        }
+                    }
+                    button {
+                        class: "text-sm text-red-600 hover:text-red-700 disabled:opacity-50",
+                        disabled: current.is_unsubscribed(),
+                        onclick: unsubscribe_all,
+                        "全部退订"
+                    }
+                }
+            } else if error().is_none() {
+                div {
+                    class: "flex justify-center py-8",
+                    div {
+                        class: "animate-spin rounded-full h-8 w-8 border-b-2 border-gray-900 dark:border-white"
+                    }
+                }
             }
         }
     }
 }
 
+fn push_permission_granted() -> bool {
+    web_sys::Notification::permission() == web_sys::NotificationPermission::Granted
+}
+
+// 请求浏览器通知权限，返回是否已授予
+async fn request_push_permission() -> bool {
+    let Ok(promise) = web_sys::Notification::request_permission() else {
+        return false;
+    };
+    wasm_bindgen_futures::JsFuture::from(promise)
+        .await
+        .ok()
+        .and_then(|value| value.as_string())
+        .as_deref()
+        == Some("granted")
+}
+
 #[component]
-fn NotificationToggle(title: String, description: String, checked: bool) -> Element {
+fn PrivacySettingsPanel() -> Element {
+    let mut settings = use_signal(|| None::<PrivacySettings>);
+    let mut error = use_signal(|| None::<String>);
+    let mut saved = use_signal(|| false);
+    
+    use_hook(move || {
+        spawn(async move {
+            match PrivacyService::get_settings().await {
+                Ok(data) => settings.set(Some(data)),
+                Err(e) => error.set(Some(format!("加载隐私设置失败: {}", e.message))),
+            }
+        });
+    });
+    
+    // 先更新界面再保存，失败时恢复
+    let mut save = move |next: PrivacySettings| {
+        let previous = settings();
+        settings.set(Some(next));
+        spawn(async move {
+            saved.set(false);
+            error.set(None);
+            match PrivacyService::update_settings(&next).await {
+                Ok(data) => {
+                    settings.set(Some(data));
+                    saved.set(true);
+                }
+                Err(e) => {
+                    settings.set(previous);
+                    error.set(Some(format!("保存隐私设置失败: {}", e.message)));
+                }
+            }
+        });
+    };
+    
+    rsx! {
+        div {
+            class: "space-y-6",
+            
+            div {
+                class: "bg-white dark:bg-gray-800 shadow rounded-lg p-6",
+                
+                div {
+                    class: "flex items-center justify-between mb-6",
+                    h2 {
+                        class: "text-lg font-medium text-gray-900 dark:text-white",
+                        "隐私设置"
+                    }
+                    if saved() {
+                        span { class: "text-xs text-green-600", "已保存" }
+                    }
+                }
+                
+                if let Some(error_msg) = error() {
+                    div {
+                        class: "mb-4 bg-red-50 dark:bg-red-900/20 border border-red-200 dark:border-red-800 text-red-700 dark:text-red-400 px-4 py-3 rounded-lg text-sm",
+                        {error_msg}
+                    }
+                }
+                
+                if let Some(current) = settings() {
+                    div {
+                        class: "space-y-4",
+                        
+                        PrivacyToggle {
+                            title: "不被搜索引擎收录",
+                            description: "个人主页和文章不会出现在 Google 等搜索引擎的结果中",
+                            checked: current.hide_from_search_engines,
+                            on_change: move |checked: bool| save(PrivacySettings { hide_from_search_engines: checked, ..current })
+                        }
+                        
+                        PrivacyToggle {
+                            title: "不出现在站内搜索中",
+                            description: "其他用户无法通过站内搜索或 @ 提及找到你，文章仍会出现在搜索结果中",
+                            checked: current.hide_from_site_search,
+                            on_change: move |checked: bool| save(PrivacySettings { hide_from_site_search: checked, ..current })
+                        }
+                        
+                        PrivacyToggle {
+                            title: "隐藏关注者和正在关注",
+                            description: "其他用户在你的个人主页上看不到关注数和关注列表",
+                            checked: current.hide_follow_lists,
+                            on_change: move |checked: bool| save(PrivacySettings { hide_follow_lists: checked, ..current })
+                        }
+                        
+                        PrivacyToggle {
+                            title: "隐藏阅读动态",
+                            description: "你的点赞、高亮和阅读记录不会出现在关注者的动态和热门高亮人数中",
+                            checked: current.hide_reading_activity,
+                            on_change: move |checked: bool| save(PrivacySettings { hide_reading_activity: checked, ..current })
+                        }
+                        
+                        div {
+                            class: "flex items-center justify-between py-4 border-t border-gray-100 dark:border-gray-700",
+                            div {
+                                h3 {
+                                    class: "text-sm font-medium text-gray-900 dark:text-white",
+                                    "谁可以评论我的文章"
+                                }
+                                p {
+                                    class: "text-sm text-gray-600 dark:text-gray-400",
+                                    "新文章的默认设置，单篇文章可在评论管理中另行设置"
+                                }
+                            }
+                            select {
+                                class: "px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg text-sm",
+                                value: current.default_comment_permission.as_str(),
+                                onchange: move |e: Event<FormData>| {
+                                    if let Some(permission) = CommentPermission::ALL.into_iter().find(|p| p.as_str() == e.value()) {
+                                        save(PrivacySettings { default_comment_permission: permission, ..current });
+                                    }
+                                },
+                                for permission in CommentPermission::ALL {
+                                    option {
+                                        value: permission.as_str(),
+                                        selected: permission == current.default_comment_permission,
+                                        {permission.label()}
+                                    }
+                                }
+                            }
+                        }
+                        
+                        div {
+                            class: "flex items-center justify-between py-4 border-t border-gray-100 dark:border-gray-700",
+                            div {
+                                h3 {
+                                    class: "text-sm font-medium text-gray-900 dark:text-white",
+                                    "谁可以 @ 我"
+                                }
+                                p {
+                                    class: "text-sm text-gray-600 dark:text-gray-400",
+                                    "其他人提及你时，不在范围内的提及不会通知你"
+                                }
+                            }
+                            select {
+                                class: "px-3 py-2 border border-gray-300 dark:border-gray-600 rounded-lg text-sm",
+                                value: current.mention_permission.as_str(),
+                                onchange: move |e: Event<FormData>| {
+                                    if let Some(permission) = MentionPermission::from_str(&e.value()) {
+                                        save(PrivacySettings { mention_permission: permission, ..current });
+                                    }
+                                },
+                                for permission in MentionPermission::ALL {
+                                    option {
+                                        value: permission.as_str(),
+                                        selected: permission == current.mention_permission,
+                                        {permission.label()}
+                                    }
+                                }
+                            }
+                        }
+                    }
+                } else if error().is_none() {
+                    div {
+                        class: "flex justify-center py-8",
+                        div {
+                            class: "animate-spin rounded-full h-8 w-8 border-b-2 border-gray-900 dark:border-white"
+                        }
+                    }
+                }
+            }
+            
+            RelationList { kind: RelationKind::Block }
+            RelationList { kind: RelationKind::Mute }
+        }
+    }
+}
+
+#[component]
+fn RelationList(kind: RelationKind) -> Element {
+    let relations = use_user_relations();
+    let mut users = use_signal(Vec::<RelatedUser>::new);
+    let mut page = use_signal(|| 1);
+    let mut has_next = use_signal(|| false);
+    let mut loading = use_signal(|| true);
+    let mut error = use_signal(|| None::<String>);
+    
+    let load_page = move |next_page: i32| {
+        spawn(async move {
+            loading.set(true);
+            match PrivacyService::list(kind, Some(next_page), Some(20)).await {
+                Ok(resp) => {
+                    if next_page == 1 {
+                        users.set(resp.users);
+                    } else {
+                        users.write().extend(resp.users);
+                    }
+                    has_next.set(resp.has_next);
+                    page.set(next_page);
+                }
+                Err(e) => error.set(Some(format!("加载失败: {}", e.message))),
+            }
+            loading.set(false);
+        });
+    };
+    
+    use_hook(move || load_page(1));
+    
+    let undo = move |user_id: String| {
+        spawn(async move {
+            error.set(None);
+            match relations.set(kind, &user_id, false).await {
+                Ok(()) => users.write().retain(|u| u.user.id != user_id),
+                Err(e) => error.set(Some(format!("{}失败: {}", kind.undo_label(), e.message))),
+            }
+        });
+    };
+    
+    let (title, description, empty) = match kind {
+        RelationKind::Block => (
+            "屏蔽的用户",
+            "他们无法关注你、评论你的文章、提及你或给你发私信，你们互相看不到对方的评论",
+            "没有屏蔽任何用户",
+        ),
+        RelationKind::Mute => (
+            "静音的用户",
+            "他们的文章和评论不会出现在你的首页和评论区中，对方不会知道被静音",
+            "没有静音任何用户",
+        ),
+    };
+    
+    rsx! {
+        div {
+            class: "bg-white dark:bg-gray-800 shadow rounded-lg p-6",
+            
+            h2 {
+                class: "text-lg font-medium text-gray-900 dark:text-white mb-1",
+                {title}
+            }
+            p {
+                class: "text-sm text-gray-600 dark:text-gray-400 mb-4",
+                {description}
+            }
+            
+            if let Some(error_msg) = error() {
+                div {
+                    class: "mb-4 bg-red-50 dark:bg-red-900/20 border border-red-200 dark:border-red-800 text-red-700 dark:text-red-400 px-4 py-3 rounded-lg text-sm",
+                    {error_msg}
+                }
+            }
+            
+            if users().is_empty() && !loading() {
+                p {
+                    class: "text-sm text-gray-500 dark:text-gray-400",
+                    {empty}
+                }
+            }
+            for related in users() {
+                div {
+                    key: "{related.user.id}",
+                    class: "flex items-center justify-between py-2 text-sm",
+                    Link {
+                        to: Route::Profile { username: related.user.username.clone() },
+                        class: "flex items-center min-w-0",
+                        if let Some(avatar_url) = &related.user.avatar_url {
+                            img {
+                                src: "{avatar_url}",
+                                alt: "{related.user.username}",
+                                class: "w-8 h-8 rounded-full flex-shrink-0"
+                            }
+                        } else {
+                            div {
+                                class: "w-8 h-8 rounded-full bg-gray-200 dark:bg-gray-600 flex-shrink-0"
+                            }
+                        }
+                        span {
+                            class: "ml-3 truncate text-gray-900 dark:text-white",
+                            {related.user.display_name.clone()}
+                            span {
+                                class: "ml-1 text-gray-500 dark:text-gray-400",
+                                "@{related.user.username}"
+                            }
+                        }
+                    }
+                    div {
+                        class: "flex items-center space-x-4 shrink-0 ml-4",
+                        span {
+                            class: "text-gray-500 dark:text-gray-400",
+                            {related.created_at.format("%Y-%m-%d").to_string()}
+                        }
+                        button {
+                            class: "text-gray-600 dark:text-gray-400 hover:text-gray-900 dark:hover:text-white",
+                            onclick: {
+                                let user_id = related.user.id.clone();
+                                move |_| undo(user_id.clone())
+                            },
+                            {kind.undo_label()}
+                        }
+                    }
+                }
+            }
+            if has_next() {
+                button {
+                    class: "mt-2 text-sm text-green-600 hover:text-green-700 disabled:opacity-50",
+                    disabled: loading(),
+                    onclick: move |_| load_page(page() + 1),
+                    if loading() { "加载中..." } else { "加载更多" }
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn PrivacyToggle(title: String, description: String, checked: bool, on_change: EventHandler<bool>) -> Element {
     let mut is_checked = use_signal(|| checked);
     
     rsx! {
@@ -620,7 +1416,11 @@ fn NotificationToggle(title: String, description: String, checked: bool) -> Elem
                     r#type: "checkbox",
                     class: "sr-only peer",
                     checked: is_checked(),
-                    onchange: move |_| is_checked.set(!is_checked())
+                    onchange: move |_| {
+                        let checked = !is_checked();
+                        is_checked.set(checked);
+                        on_change.call(checked);
+                    }
                 }
                 div {
                     class: "w-11 h-6 bg-gray-200 peer-focus:outline-none peer-focus:ring-4 peer-focus:ring-blue-300 dark:peer-focus:ring-blue-800 rounded-full peer dark:bg-gray-700 peer-checked:after:translate-x-full peer-checked:after:border-white after:content-[''] after:absolute after:top-[2px] after:left-[2px] after:bg-white after:border-gray-300 after:border after:rounded-full after:h-5 after:w-5 after:transition-all dark:border-gray-600 peer-checked:bg-blue-600"
@@ -631,7 +1431,7 @@ fn NotificationToggle(title: String, description: String, checked: bool) -> Elem
 }
 
 #[component]
-fn PrivacyToggle(title: String, description: String, checked: bool) -> Element {
+fn ReadingToggle(title: String, description: String, checked: bool, on_change: EventHandler<bool>) -> Element {
     let mut is_checked = use_signal(|| checked);
     
     rsx! {
@@ -654,7 +1454,11 @@ fn PrivacyToggle(title: String, description: String, checked: bool) -> Element {
                     r#type: "checkbox",
                     class: "sr-only peer",
                     checked: is_checked(),
-                    onchange: move |_| is_checked.set(!is_checked())
+                    onchange: move |_| {
+                        let checked = !is_checked();
+                        is_checked.set(checked);
+                        on_change.call(checked);
+                    }
                 }
                 div {
                     class: "w-11 h-6 bg-gray-200 peer-focus:outline-none peer-focus:ring-4 peer-focus:ring-blue-300 dark:peer-focus:ring-blue-800 rounded-full peer dark:bg-gray-700 peer-checked:after:translate-x-full peer-checked:after:border-white after:content-[''] after:absolute after:top-[2px] after:left-[2px] after:bg-white after:border-gray-300 after:border after:rounded-full after:h-5 after:w-5 after:transition-all dark:border-gray-600 peer-checked:bg-blue-600"
@@ -662,4 +1466,4 @@ fn PrivacyToggle(title: String, description: String, checked: bool) -> Element {
             }
         }
     }
-}
\ No newline at end of file
+}
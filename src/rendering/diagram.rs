@@ -0,0 +1,899 @@
+// mermaid 语法子集（flowchart / sequenceDiagram）-> 内联 SVG
+
+use super::math::escape_xml;
+
+const FONT_SIZE: f64 = 14.0;
+const NODE_HEIGHT: f64 = 40.0;
+const RANK_GAP: f64 = 56.0;
+const NODE_GAP: f64 = 32.0;
+const PADDING: f64 = 16.0;
+
+/// 渲染图表源码，不支持的语法返回错误信息
+pub fn render_diagram(source: &str) -> Result<String, String> {
+    let lines: Vec<&str> = source
+        .lines()
+        .map(|l| l.trim())
+        .filter(|l| !l.is_empty() && !l.starts_with("%%"))
+        .collect();
+
+    let header = lines.first().ok_or_else(|| "图表内容为空".to_string())?;
+    let keyword = header.split_whitespace().next().unwrap_or_default();
+
+    // 用源码哈希区分同一页面中多个图表的 marker id
+    let id = format!("dg{:x}", hash(source));
+
+    match keyword {
+        "graph" | "flowchart" => {
+            let direction = header.split_whitespace().nth(1).unwrap_or("TD");
+            let horizontal = match direction.trim_end_matches(';') {
+                "TD" | "TB" | "BT" => false,
+                "LR" | "RL" => true,
+                other => return Err(format!("不支持的方向 {}", other)),
+            };
+            let flowchart = parse_flowchart(&lines[1..])?;
+            Ok(render_flowchart(&flowchart, horizontal, &id))
+        }
+        "sequenceDiagram" => {
+            let sequence = parse_sequence(&lines[1..])?;
+            Ok(render_sequence(&sequence, &id))
+        }
+        other => Err(format!("不支持的图表类型 {}", other)),
+    }
+}
+
+fn hash(text: &str) -> u64 {
+    // FNV-1a
+    text.bytes().fold(0xcbf29ce484222325u64, |h, b| {
+        (h ^ b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+fn text_width(text: &str) -> f64 {
+    text.chars()
+        .map(|c| if c.is_ascii() { FONT_SIZE * 0.6 } else { FONT_SIZE })
+        .sum()
+}
+
+// ---------- 流程图 ----------
+
+#[derive(Clone, Copy, PartialEq)]
+enum Shape {
+    Rect,
+    Round,
+    Circle,
+    Diamond,
+}
+
+struct FlowNode {
+    id: String,
+    label: String,
+    shape: Shape,
+}
+
+struct FlowEdge {
+    from: usize,
+    to: usize,
+    label: Option<String>,
+    arrow: bool,
+    dotted: bool,
+    thick: bool,
+}
+
+struct Flowchart {
+    nodes: Vec<FlowNode>,
+    edges: Vec<FlowEdge>,
+}
+
+fn parse_flowchart(lines: &[&str]) -> Result<Flowchart, String> {
+    let mut chart = Flowchart { nodes: Vec::new(), edges: Vec::new() };
+
+    for line in lines {
+        for statement in line.split(';').map(|s| s.trim()).filter(|s| !s.is_empty()) {
+            let keyword = statement.split_whitespace().next().unwrap_or_default();
+            // 样式类语句与子图分组在子集中忽略
+            if matches!(keyword, "classDef" | "class" | "style" | "linkStyle" | "click" | "subgraph" | "end" | "direction") {
+                continue;
+            }
+            parse_flow_statement(statement, &mut chart)?;
+        }
+    }
+
+    if chart.nodes.is_empty() {
+        return Err("流程图中没有节点".to_string());
+    }
+    Ok(chart)
+}
+
+fn parse_flow_statement(statement: &str, chart: &mut Flowchart) -> Result<(), String> {
+    let chars: Vec<char> = statement.chars().collect();
+    let mut pos = 0;
+
+    let mut previous = parse_flow_node(&chars, &mut pos, chart)?;
+    loop {
+        skip_spaces(&chars, &mut pos);
+        if pos >= chars.len() {
+            return Ok(());
+        }
+
+        let (arrow, dotted, thick, mut label) = parse_flow_link(&chars, &mut pos)?;
+        skip_spaces(&chars, &mut pos);
+        if chars.get(pos) == Some(&'|') {
+            let end = chars[pos + 1..]
+                .iter()
+                .position(|c| *c == '|')
+                .ok_or_else(|| "连线标签缺少结束的 '|'".to_string())?;
+            label = Some(chars[pos + 1..pos + 1 + end].iter().collect::<String>().trim().to_string());
+            pos += end + 2;
+        }
+
+        let next = parse_flow_node(&chars, &mut pos, chart)?;
+        chart.edges.push(FlowEdge {
+            from: previous,
+            to: next,
+            label: label.filter(|l| !l.is_empty()),
+            arrow,
+            dotted,
+            thick,
+        });
+        previous = next;
+    }
+}
+
+fn skip_spaces(chars: &[char], pos: &mut usize) {
+    while chars.get(*pos).map(|c| c.is_whitespace()).unwrap_or(false) {
+        *pos += 1;
+    }
+}
+
+fn parse_flow_node(chars: &[char], pos: &mut usize, chart: &mut Flowchart) -> Result<usize, String> {
+    skip_spaces(chars, pos);
+    let start = *pos;
+    while let Some(c) = chars.get(*pos) {
+        if c.is_alphanumeric() || *c == '_' {
+            *pos += 1;
+        } else {
+            break;
+        }
+    }
+    if *pos == start {
+        return Err(format!("无法识别的节点: {}", chars[start..].iter().collect::<String>()));
+    }
+    let id: String = chars[start..*pos].iter().collect();
+
+    // 形状与标签
+    let rest: String = chars[*pos..].iter().collect();
+    let shapes: [(&str, &str, Shape); 7] = [
+        ("((", "))", Shape::Circle),
+        ("([", "])", Shape::Round),
+        ("[[", "]]", Shape::Rect),
+        ("[", "]", Shape::Rect),
+        ("(", ")", Shape::Round),
+        ("{", "}", Shape::Diamond),
+        (">", "]", Shape::Rect),
+    ];
+    let mut shape_label = None;
+    for (open, close, shape) in shapes {
+        if rest.starts_with(open) {
+            // '>' 开头可能是箭头的一部分，仅在后面有 ']' 时视为旗形节点
+            let inner_start = open.chars().count();
+            let inner: Vec<char> = rest.chars().skip(inner_start).collect();
+            let inner_str: String = inner.iter().collect();
+            if let Some(end) = inner_str.find(close) {
+                let label = inner_str[..end].trim().trim_matches('"').to_string();
+                *pos += inner_start + inner_str[..end].chars().count() + close.chars().count();
+                shape_label = Some((shape, label));
+                break;
+            } else if open != ">" {
+                return Err(format!("节点 {} 缺少结束的 '{}'", id, close));
+            }
+        }
+    }
+
+    if let Some(index) = chart.nodes.iter().position(|n| n.id == id) {
+        if let Some((shape, label)) = shape_label {
+            chart.nodes[index].shape = shape;
+            chart.nodes[index].label = label;
+        }
+        return Ok(index);
+    }
+
+    let (shape, label) = shape_label.unwrap_or((Shape::Rect, id.clone()));
+    chart.nodes.push(FlowNode { id, label, shape });
+    Ok(chart.nodes.len() - 1)
+}
+
+// 返回 (是否有箭头, 虚线, 粗线, "-- 文字 -->" 形式的标签)
+fn parse_flow_link(chars: &[char], pos: &mut usize) -> Result<(bool, bool, bool, Option<String>), String> {
+    let rest: String = chars[*pos..].iter().collect();
+    const LINKS: [&str; 7] = ["-.->", "-.-", "==>", "===", "-->", "---", "--"];
+
+    for link in LINKS {
+        if !rest.starts_with(link) {
+            continue;
+        }
+        *pos += link.chars().count();
+
+        if link == "--" {
+            // "A -- 文字 --> B"
+            let after: String = chars[*pos..].iter().collect();
+            let (end, arrow) = match (after.find("-->"), after.find("---")) {
+                (Some(a), Some(b)) if b < a => (b, false),
+                (Some(a), _) => (a, true),
+                (None, Some(b)) => (b, false),
+                (None, None) => return Err("连线缺少结束符号".to_string()),
+            };
+            let label = after[..end].trim().to_string();
+            *pos += after[..end].chars().count() + 3;
+            return Ok((arrow, false, false, Some(label)));
+        }
+
+        return Ok((link.ends_with('>'), link.contains('.'), link.contains('='), None));
+    }
+
+    Err(format!("无法识别的连线: {}", rest))
+}
+
+struct Placed {
+    x: f64,
+    y: f64,
+    w: f64,
+    h: f64,
+}
+
+fn node_size(node: &FlowNode) -> (f64, f64) {
+    let w = (text_width(&node.label) + 28.0).max(64.0);
+    match node.shape {
+        Shape::Diamond => (w * 1.3, NODE_HEIGHT * 1.5),
+        Shape::Circle => {
+            let d = w.max(NODE_HEIGHT);
+            (d, d)
+        }
+        _ => (w, NODE_HEIGHT),
+    }
+}
+
+// 按最长路径分层，回边（环）在分层时忽略
+fn assign_ranks(chart: &Flowchart) -> Vec<usize> {
+    let n = chart.nodes.len();
+    let mut adjacency = vec![Vec::new(); n];
+    for edge in &chart.edges {
+        adjacency[edge.from].push(edge.to);
+    }
+
+    // 0 = 未访问, 1 = 访问中, 2 = 已完成
+    let mut state = vec![0u8; n];
+    let mut order = Vec::with_capacity(n);
+    let mut forward = vec![Vec::new(); n];
+
+    // 用显式栈做深度优先遍历，很长的链也不会耗尽 wasm 调用栈
+    for root in 0..n {
+        if state[root] != 0 {
+            continue;
+        }
+        state[root] = 1;
+        let mut stack = vec![(root, 0usize)];
+        while let Some(top) = stack.last_mut() {
+            let (node, index) = *top;
+            match adjacency[node].get(index) {
+                Some(&next) => {
+                    top.1 += 1;
+                    match state[next] {
+                        0 => {
+                            forward[node].push(next);
+                            state[next] = 1;
+                            stack.push((next, 0));
+                        }
+                        2 => forward[node].push(next),
+                        // 回边，忽略以打破环
+                        _ => {}
+                    }
+                }
+                None => {
+                    state[node] = 2;
+                    order.push(node);
+                    stack.pop();
+                }
+            }
+        }
+    }
+
+    let mut rank = vec![0usize; n];
+    for &node in order.iter().rev() {
+        for &next in &forward[node] {
+            rank[next] = rank[next].max(rank[node] + 1);
+        }
+    }
+    rank
+}
+
+fn render_flowchart(chart: &Flowchart, horizontal: bool, id: &str) -> String {
+    let ranks = assign_ranks(chart);
+    let rank_count = ranks.iter().max().map(|r| r + 1).unwrap_or(1);
+
+    let mut layers: Vec<Vec<usize>> = vec![Vec::new(); rank_count];
+    for (index, rank) in ranks.iter().enumerate() {
+        layers[*rank].push(index);
+    }
+
+    let sizes: Vec<(f64, f64)> = chart.nodes.iter().map(node_size).collect();
+    // main: 分层方向上的尺寸, cross: 同层排列方向上的尺寸
+    let main_of = |s: (f64, f64)| if horizontal { s.0 } else { s.1 };
+    let cross_of = |s: (f64, f64)| if horizontal { s.1 } else { s.0 };
+
+    let layer_main: Vec<f64> = layers
+        .iter()
+        .map(|layer| layer.iter().map(|i| main_of(sizes[*i])).fold(0.0, f64::max))
+        .collect();
+    let layer_cross: Vec<f64> = layers
+        .iter()
+        .map(|layer| {
+            layer.iter().map(|i| cross_of(sizes[*i])).sum::<f64>()
+                + NODE_GAP * (layer.len().saturating_sub(1)) as f64
+        })
+        .collect();
+    let total_cross = layer_cross.iter().cloned().fold(0.0, f64::max);
+
+    let mut placed: Vec<Placed> = (0..chart.nodes.len())
+        .map(|_| Placed { x: 0.0, y: 0.0, w: 0.0, h: 0.0 })
+        .collect();
+
+    let mut main_offset = PADDING;
+    for (rank, layer) in layers.iter().enumerate() {
+        let mut cross_offset = PADDING + (total_cross - layer_cross[rank]) / 2.0;
+        for &index in layer {
+            let (w, h) = sizes[index];
+            let main_center = main_offset + layer_main[rank] / 2.0;
+            let cross_center = cross_offset + cross_of(sizes[index]) / 2.0;
+            let (x, y) = if horizontal {
+                (main_center, cross_center)
+            } else {
+                (cross_center, main_center)
+            };
+            placed[index] = Placed { x, y, w, h };
+            cross_offset += cross_of(sizes[index]) + NODE_GAP;
+        }
+        main_offset += layer_main[rank] + RANK_GAP;
+    }
+
+    let total_main = main_offset - RANK_GAP + PADDING;
+    let (width, height) = if horizontal {
+        (total_main, total_cross + PADDING * 2.0)
+    } else {
+        (total_cross + PADDING * 2.0, total_main)
+    };
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" class=\"diagram-svg\" viewBox=\"0 0 {w:.0} {h:.0}\" width=\"{w:.0}\" height=\"{h:.0}\" font-size=\"{fs}\" role=\"img\">",
+        w = width,
+        h = height,
+        fs = FONT_SIZE
+    );
+    svg.push_str(&format!(
+        "<defs><marker id=\"{id}-arrow\" viewBox=\"0 0 10 10\" refX=\"9\" refY=\"5\" markerWidth=\"7\" markerHeight=\"7\" orient=\"auto-start-reverse\"><path d=\"M0,0 L10,5 L0,10 z\" fill=\"#4b5563\"/></marker></defs>"
+    ));
+
+    for edge in &chart.edges {
+        let from = &placed[edge.from];
+        let to = &placed[edge.to];
+        let (x1, y1) = clip_to_boundary(from, to.x, to.y, chart.nodes[edge.from].shape);
+        let (x2, y2) = clip_to_boundary(to, from.x, from.y, chart.nodes[edge.to].shape);
+
+        let mut attrs = String::from("stroke=\"#4b5563\" fill=\"none\"");
+        attrs.push_str(if edge.thick { " stroke-width=\"3\"" } else { " stroke-width=\"1.5\"" });
+        if edge.dotted {
+            attrs.push_str(" stroke-dasharray=\"4 3\"");
+        }
+        if edge.arrow {
+            attrs.push_str(&format!(" marker-end=\"url(#{}-arrow)\"", id));
+        }
+        svg.push_str(&format!(
+            "<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" {}/>",
+            x1, y1, x2, y2, attrs
+        ));
+
+        if let Some(label) = &edge.label {
+            let mx = (x1 + x2) / 2.0;
+            let my = (y1 + y2) / 2.0;
+            let lw = text_width(label) + 8.0;
+            svg.push_str(&format!(
+                "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"20\" fill=\"#ffffff\"/><text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" dominant-baseline=\"central\" fill=\"#374151\">{}</text>",
+                mx - lw / 2.0,
+                my - 10.0,
+                lw,
+                mx,
+                my,
+                escape_xml(label)
+            ));
+        }
+    }
+
+    for (node, place) in chart.nodes.iter().zip(placed.iter()) {
+        let style = "fill=\"#f9fafb\" stroke=\"#6b7280\" stroke-width=\"1.5\"";
+        let shape = match node.shape {
+            Shape::Rect => format!(
+                "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" rx=\"3\" {}/>",
+                place.x - place.w / 2.0, place.y - place.h / 2.0, place.w, place.h, style
+            ),
+            Shape::Round => format!(
+                "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" rx=\"{:.1}\" {}/>",
+                place.x - place.w / 2.0, place.y - place.h / 2.0, place.w, place.h, place.h / 2.0, style
+            ),
+            Shape::Circle => format!(
+                "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" {}/>",
+                place.x, place.y, place.w / 2.0, style
+            ),
+            Shape::Diamond => format!(
+                "<polygon points=\"{:.1},{:.1} {:.1},{:.1} {:.1},{:.1} {:.1},{:.1}\" {}/>",
+                place.x, place.y - place.h / 2.0,
+                place.x + place.w / 2.0, place.y,
+                place.x, place.y + place.h / 2.0,
+                place.x - place.w / 2.0, place.y,
+                style
+            ),
+        };
+        svg.push_str(&shape);
+        svg.push_str(&format!(
+            "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" dominant-baseline=\"central\" fill=\"#111827\">{}</text>",
+            place.x, place.y, escape_xml(&node.label)
+        ));
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+// 计算从节点中心指向 (tx, ty) 的射线与节点边界的交点
+fn clip_to_boundary(node: &Placed, tx: f64, ty: f64, shape: Shape) -> (f64, f64) {
+    let dx = tx - node.x;
+    let dy = ty - node.y;
+    if dx == 0.0 && dy == 0.0 {
+        return (node.x, node.y);
+    }
+    let hw = node.w / 2.0;
+    let hh = node.h / 2.0;
+
+    let t = match shape {
+        Shape::Diamond => 1.0 / (dx.abs() / hw + dy.abs() / hh),
+        Shape::Circle => hw / (dx * dx + dy * dy).sqrt(),
+        _ => {
+            let tx = if dx == 0.0 { f64::INFINITY } else { hw / dx.abs() };
+            let ty = if dy == 0.0 { f64::INFINITY } else { hh / dy.abs() };
+            tx.min(ty)
+        }
+    };
+    (node.x + dx * t, node.y + dy * t)
+}
+
+// ---------- 时序图 ----------
+
+#[derive(Clone, Copy, PartialEq)]
+enum ArrowHead {
+    None,
+    Filled,
+    Cross,
+    Open,
+}
+
+enum SeqItem {
+    Message {
+        from: usize,
+        to: usize,
+        text: String,
+        dashed: bool,
+        head: ArrowHead,
+    },
+    Note {
+        from: usize,
+        to: usize,
+        placement: NotePlacement,
+        text: String,
+    },
+    // 分组（loop / alt / opt / par）的起始或分支标题行
+    Section,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum NotePlacement {
+    Over,
+    Left,
+    Right,
+}
+
+struct Frame {
+    kind: String,
+    label: String,
+    start: usize,
+    end: usize,
+    depth: usize,
+    // 分支（else / and）所在的行
+    dividers: Vec<(usize, String)>,
+}
+
+struct Sequence {
+    participants: Vec<(String, String)>,
+    items: Vec<SeqItem>,
+    frames: Vec<Frame>,
+    autonumber: bool,
+}
+
+impl Sequence {
+    fn participant(&mut self, id: &str) -> usize {
+        let id = id.trim();
+        if let Some(index) = self.participants.iter().position(|(pid, _)| pid == id) {
+            return index;
+        }
+        self.participants.push((id.to_string(), id.to_string()));
+        self.participants.len() - 1
+    }
+}
+
+fn parse_sequence(lines: &[&str]) -> Result<Sequence, String> {
+    let mut seq = Sequence {
+        participants: Vec::new(),
+        items: Vec::new(),
+        frames: Vec::new(),
+        autonumber: false,
+    };
+    let mut open_frames: Vec<Frame> = Vec::new();
+
+    for line in lines {
+        let (keyword, rest) = match line.split_once(char::is_whitespace) {
+            Some((k, r)) => (k, r.trim()),
+            None => (*line, ""),
+        };
+
+        match keyword {
+            "participant" | "actor" => {
+                let (id, alias) = match rest.split_once(" as ") {
+                    Some((id, alias)) => (id.trim(), alias.trim()),
+                    None => (rest, rest),
+                };
+                if id.is_empty() {
+                    return Err("参与者缺少名称".to_string());
+                }
+                let index = seq.participant(id);
+                seq.participants[index].1 = alias.to_string();
+            }
+            "autonumber" => seq.autonumber = true,
+            "activate" | "deactivate" => {}
+            "loop" | "alt" | "opt" | "par" | "critical" | "break" | "rect" => {
+                open_frames.push(Frame {
+                    kind: keyword.to_string(),
+                    label: rest.to_string(),
+                    start: seq.items.len(),
+                    end: 0,
+                    depth: open_frames.len(),
+                    dividers: Vec::new(),
+                });
+                seq.items.push(SeqItem::Section);
+            }
+            "else" | "and" | "option" => {
+                let frame = open_frames
+                    .last_mut()
+                    .ok_or_else(|| format!("{} 不在任何分组中", keyword))?;
+                frame.dividers.push((seq.items.len(), rest.to_string()));
+                seq.items.push(SeqItem::Section);
+            }
+            "end" => {
+                let mut frame = open_frames.pop().ok_or_else(|| "多余的 end".to_string())?;
+                frame.end = seq.items.len();
+                seq.frames.push(frame);
+            }
+            "Note" | "note" => {
+                let (target, text) = rest
+                    .split_once(':')
+                    .ok_or_else(|| "注释缺少 ':'".to_string())?;
+                let target = target.trim();
+                let (placement, names) = if let Some(names) = target.strip_prefix("over ") {
+                    (NotePlacement::Over, names)
+                } else if let Some(names) = target.strip_prefix("left of ") {
+                    (NotePlacement::Left, names)
+                } else if let Some(names) = target.strip_prefix("right of ") {
+                    (NotePlacement::Right, names)
+                } else {
+                    return Err(format!("无法识别的注释位置: {}", target));
+                };
+                let mut names = names.split(',');
+                let from = seq.participant(names.next().unwrap_or_default());
+                let to = names.next().map(|n| seq.participant(n)).unwrap_or(from);
+                seq.items.push(SeqItem::Note {
+                    from,
+                    to,
+                    placement,
+                    text: text.trim().to_string(),
+                });
+            }
+            _ => {
+                let item = parse_message(line, &mut seq)?;
+                seq.items.push(item);
+            }
+        }
+    }
+
+    if !open_frames.is_empty() {
+        return Err("分组缺少 end".to_string());
+    }
+    if seq.participants.is_empty() {
+        return Err("时序图中没有参与者".to_string());
+    }
+    Ok(seq)
+}
+
+fn parse_message(line: &str, seq: &mut Sequence) -> Result<SeqItem, String> {
+    const ARROWS: [(&str, bool, ArrowHead); 8] = [
+        ("-->>", true, ArrowHead::Filled),
+        ("->>", false, ArrowHead::Filled),
+        ("--x", true, ArrowHead::Cross),
+        ("-x", false, ArrowHead::Cross),
+        ("--)", true, ArrowHead::Open),
+        ("-)", false, ArrowHead::Open),
+        ("-->", true, ArrowHead::None),
+        ("->", false, ArrowHead::None),
+    ];
+
+    let (head_part, text) = match line.split_once(':') {
+        Some((h, t)) => (h, t.trim()),
+        None => (line, ""),
+    };
+
+    // 取最靠前的箭头，位置相同时取最长的
+    let found = ARROWS
+        .iter()
+        .filter_map(|(arrow, dashed, head)| head_part.find(arrow).map(|p| (p, arrow, *dashed, *head)))
+        .min_by(|a, b| a.0.cmp(&b.0).then(b.1.len().cmp(&a.1.len())));
+
+    let (position, arrow, dashed, head) = found.ok_or_else(|| format!("无法识别的语句: {}", line))?;
+    let from_name = head_part[..position].trim();
+    let to_name = head_part[position + arrow.len()..]
+        .trim()
+        .trim_start_matches(['+', '-'])
+        .trim();
+    if from_name.is_empty() || to_name.is_empty() {
+        return Err(format!("消息缺少参与者: {}", line));
+    }
+
+    Ok(SeqItem::Message {
+        from: seq.participant(from_name),
+        to: seq.participant(to_name),
+        text: text.to_string(),
+        dashed,
+        head,
+    })
+}
+
+fn render_sequence(seq: &Sequence, id: &str) -> String {
+    const ROW_HEIGHT: f64 = 40.0;
+    const HEADER_HEIGHT: f64 = 36.0;
+
+    let count = seq.participants.len();
+    let box_widths: Vec<f64> = seq
+        .participants
+        .iter()
+        .map(|(_, label)| (text_width(label) + 24.0).max(80.0))
+        .collect();
+
+    // 相邻参与者之间的间距需要容纳两者之间最长的消息文字
+    let mut gaps = vec![0.0f64; count.saturating_sub(1)];
+    for (i, gap) in gaps.iter_mut().enumerate() {
+        *gap = box_widths[i] / 2.0 + box_widths[i + 1] / 2.0 + 40.0;
+    }
+    for item in &seq.items {
+        if let SeqItem::Message { from, to, text, .. } = item {
+            let (lo, hi) = if from < to { (*from, *to) } else { (*to, *from) };
+            if hi > lo {
+                let needed = (text_width(text) + 32.0) / (hi - lo) as f64;
+                for gap in &mut gaps[lo..hi] {
+                    *gap = gap.max(needed);
+                }
+            }
+        }
+    }
+
+    let mut centers = Vec::with_capacity(count);
+    let mut x = PADDING + box_widths[0] / 2.0;
+    for i in 0..count {
+        centers.push(x);
+        if i < gaps.len() {
+            x += gaps[i];
+        }
+    }
+
+    // 自环消息和右侧注释需要额外的右边距
+    let right_extra = seq
+        .items
+        .iter()
+        .map(|item| match item {
+            SeqItem::Message { from, to, text, .. } if from == to && *from == count - 1 => {
+                text_width(text) + 48.0
+            }
+            SeqItem::Note { placement: NotePlacement::Right, text, from, .. } if *from == count - 1 => {
+                text_width(text) + 40.0
+            }
+            _ => 0.0,
+        })
+        .fold(box_widths[count - 1] / 2.0, f64::max);
+    let left_extra = seq
+        .items
+        .iter()
+        .map(|item| match item {
+            SeqItem::Note { placement: NotePlacement::Left, text, from, .. } if *from == 0 => {
+                (text_width(text) + 40.0 - box_widths[0] / 2.0).max(0.0)
+            }
+            _ => 0.0,
+        })
+        .fold(0.0, f64::max);
+    let centers: Vec<f64> = centers.iter().map(|c| c + left_extra).collect();
+
+    let width = centers[count - 1] + right_extra + PADDING;
+    let top = PADDING + HEADER_HEIGHT;
+    let height = top + ROW_HEIGHT * (seq.items.len() as f64 + 1.0) + PADDING;
+    let row_y = |row: usize| top + ROW_HEIGHT * (row as f64 + 0.75);
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" class=\"diagram-svg\" viewBox=\"0 0 {w:.0} {h:.0}\" width=\"{w:.0}\" height=\"{h:.0}\" font-size=\"{fs}\" role=\"img\">",
+        w = width,
+        h = height,
+        fs = FONT_SIZE
+    );
+    svg.push_str(&format!(
+        "<defs><marker id=\"{id}-filled\" viewBox=\"0 0 10 10\" refX=\"9\" refY=\"5\" markerWidth=\"7\" markerHeight=\"7\" orient=\"auto\"><path d=\"M0,0 L10,5 L0,10 z\" fill=\"#374151\"/></marker><marker id=\"{id}-open\" viewBox=\"0 0 10 10\" refX=\"9\" refY=\"5\" markerWidth=\"7\" markerHeight=\"7\" orient=\"auto\"><path d=\"M0,0 L10,5 L0,10\" fill=\"none\" stroke=\"#374151\"/></marker><marker id=\"{id}-cross\" viewBox=\"0 0 10 10\" refX=\"5\" refY=\"5\" markerWidth=\"8\" markerHeight=\"8\"><path d=\"M1,1 L9,9 M9,1 L1,9\" stroke=\"#374151\" stroke-width=\"2\"/></marker></defs>"
+    ));
+
+    // 分组框
+    for frame in &seq.frames {
+        let inset = frame.depth as f64 * 6.0;
+        let x1 = PADDING / 2.0 + inset;
+        let x2 = width - PADDING / 2.0 - inset;
+        let y1 = row_y(frame.start) - ROW_HEIGHT * 0.6;
+        let y2 = row_y(frame.end.max(frame.start + 1) - 1) + ROW_HEIGHT * 0.4;
+        svg.push_str(&format!(
+            "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" fill=\"none\" stroke=\"#9ca3af\" stroke-dasharray=\"4 2\"/>",
+            x1, y1, x2 - x1, y2 - y1
+        ));
+        svg.push_str(&format!(
+            "<text x=\"{:.1}\" y=\"{:.1}\" dominant-baseline=\"central\" fill=\"#4b5563\" font-weight=\"600\">{} {}</text>",
+            x1 + 6.0, row_y(frame.start) - ROW_HEIGHT * 0.2, escape_xml(&frame.kind), escape_xml(&frame.label)
+        ));
+        for (row, label) in &frame.dividers {
+            let y = row_y(*row) - ROW_HEIGHT * 0.6;
+            svg.push_str(&format!(
+                "<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"#9ca3af\" stroke-dasharray=\"4 2\"/><text x=\"{:.1}\" y=\"{:.1}\" dominant-baseline=\"central\" fill=\"#4b5563\">[{}]</text>",
+                x1, y, x2, y, x1 + 6.0, row_y(*row) - ROW_HEIGHT * 0.2, escape_xml(label)
+            ));
+        }
+    }
+
+    // 参与者与生命线
+    for (i, (_, label)) in seq.participants.iter().enumerate() {
+        let cx = centers[i];
+        svg.push_str(&format!(
+            "<line x1=\"{cx:.1}\" y1=\"{y1:.1}\" x2=\"{cx:.1}\" y2=\"{y2:.1}\" stroke=\"#9ca3af\" stroke-dasharray=\"3 3\"/>",
+            cx = cx,
+            y1 = top,
+            y2 = height - PADDING
+        ));
+        svg.push_str(&format!(
+            "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" rx=\"3\" fill=\"#f9fafb\" stroke=\"#6b7280\" stroke-width=\"1.5\"/><text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" dominant-baseline=\"central\" fill=\"#111827\">{}</text>",
+            cx - box_widths[i] / 2.0, PADDING, box_widths[i], HEADER_HEIGHT, cx, PADDING + HEADER_HEIGHT / 2.0, escape_xml(label)
+        ));
+    }
+
+    let mut number = 0;
+    for (row, item) in seq.items.iter().enumerate() {
+        let y = row_y(row);
+        match item {
+            SeqItem::Message { from, to, text, dashed, head } => {
+                number += 1;
+                let label = if seq.autonumber && !text.is_empty() {
+                    format!("{}. {}", number, text)
+                } else {
+                    text.clone()
+                };
+                let mut attrs = String::from("stroke=\"#374151\" stroke-width=\"1.5\" fill=\"none\"");
+                if *dashed {
+                    attrs.push_str(" stroke-dasharray=\"5 3\"");
+                }
+                match head {
+                    ArrowHead::Filled => attrs.push_str(&format!(" marker-end=\"url(#{}-filled)\"", id)),
+                    ArrowHead::Open => attrs.push_str(&format!(" marker-end=\"url(#{}-open)\"", id)),
+                    ArrowHead::Cross => attrs.push_str(&format!(" marker-end=\"url(#{}-cross)\"", id)),
+                    ArrowHead::None => {}
+                }
+
+                if from == to {
+                    let cx = centers[*from];
+                    svg.push_str(&format!(
+                        "<path d=\"M{:.1},{:.1} h32 v16 h-30\" {}/>",
+                        cx, y - 8.0, attrs
+                    ));
+                    svg.push_str(&format!(
+                        "<text x=\"{:.1}\" y=\"{:.1}\" dominant-baseline=\"central\" fill=\"#111827\">{}</text>",
+                        cx + 38.0, y, escape_xml(&label)
+                    ));
+                } else {
+                    let x1 = centers[*from];
+                    let x2 = centers[*to];
+                    svg.push_str(&format!(
+                        "<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" {}/>",
+                        x1, y, x2, y, attrs
+                    ));
+                    svg.push_str(&format!(
+                        "<text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" fill=\"#111827\">{}</text>",
+                        (x1 + x2) / 2.0, y - 6.0, escape_xml(&label)
+                    ));
+                }
+            }
+            SeqItem::Note { from, to, placement, text } => {
+                let w = text_width(text) + 20.0;
+                let (x1, x2) = match placement {
+                    NotePlacement::Over => {
+                        let (lo, hi) = (centers[*from].min(centers[*to]), centers[*from].max(centers[*to]));
+                        let w = w.max(hi - lo + 40.0);
+                        let mid = (lo + hi) / 2.0;
+                        (mid - w / 2.0, mid + w / 2.0)
+                    }
+                    NotePlacement::Left => (centers[*from] - 12.0 - w, centers[*from] - 12.0),
+                    NotePlacement::Right => (centers[*from] + 12.0, centers[*from] + 12.0 + w),
+                };
+                svg.push_str(&format!(
+                    "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"26\" fill=\"#fef9c3\" stroke=\"#ca8a04\"/><text x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\" dominant-baseline=\"central\" fill=\"#111827\">{}</text>",
+                    x1, y - 13.0, x2 - x1, (x1 + x2) / 2.0, y, escape_xml(text)
+                ));
+            }
+            // 分组标题由分组框绘制
+            SeqItem::Section => {}
+        }
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flowchart(source: &str) -> Flowchart {
+        let lines: Vec<&str> = source.lines().map(|l| l.trim()).filter(|l| !l.is_empty()).collect();
+        parse_flowchart(&lines).unwrap()
+    }
+
+    #[test]
+    fn ranks_follow_longest_path() {
+        let chart = flowchart("A --> B\nB --> C\nA --> C");
+        assert_eq!(assign_ranks(&chart), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn ignores_back_edges_in_cycles() {
+        let chart = flowchart("A --> B\nB --> C\nC --> A");
+        assert_eq!(assign_ranks(&chart), vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn long_chain_does_not_overflow() {
+        let source: String = (0..20_000).map(|i| format!("n{} --> n{}\n", i, i + 1)).collect();
+        let chart = flowchart(&source);
+        let ranks = assign_ranks(&chart);
+        assert_eq!(ranks.len(), 20_001);
+        assert_eq!(ranks[20_000], 20_000);
+    }
+
+    #[test]
+    fn renders_flowchart_and_sequence() {
+        let svg = render_diagram("graph LR\nA[开始] --> B{判断}\nB -->|是| C(结束)").unwrap();
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.contains("开始"));
+
+        let svg = render_diagram("sequenceDiagram\nAlice->>Bob: 你好\nBob-->>Alice: 收到").unwrap();
+        assert!(svg.contains("Alice"));
+        assert!(svg.contains("你好"));
+    }
+
+    #[test]
+    fn rejects_unsupported_input() {
+        assert!(render_diagram("").is_err());
+        assert!(render_diagram("pie\n\"a\": 1").is_err());
+        assert!(render_diagram("graph XY\nA --> B").is_err());
+    }
+}
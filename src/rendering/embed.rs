@@ -0,0 +1,343 @@
+// 链接嵌入：识别常见站点的 URL，渲染为点击后才加载第三方内容的卡片
+
+use super::math::escape_xml;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Embed {
+    YouTube { id: String, start: Option<u32> },
+    Vimeo { id: String },
+    CodePen { user: String, id: String },
+    Gist { user: String, id: String },
+    GitHubRepo { owner: String, repo: String },
+    // 其他链接：由后端返回 OpenGraph 元数据生成预览卡片
+    Link,
+}
+
+impl Embed {
+    pub fn provider(&self) -> &'static str {
+        match self {
+            Embed::YouTube { .. } => "YouTube",
+            Embed::Vimeo { .. } => "Vimeo",
+            Embed::CodePen { .. } => "CodePen",
+            Embed::Gist { .. } => "GitHub Gist",
+            Embed::GitHubRepo { .. } => "GitHub",
+            Embed::Link => "链接",
+        }
+    }
+
+    // 点击加载后使用的 iframe 地址，YouTube 使用无 Cookie 域名
+    fn iframe_src(&self) -> Option<String> {
+        match self {
+            Embed::YouTube { id, start } => Some(match start {
+                Some(s) => format!("https://www.youtube-nocookie.com/embed/{}?autoplay=1&start={}", id, s),
+                None => format!("https://www.youtube-nocookie.com/embed/{}?autoplay=1", id),
+            }),
+            Embed::Vimeo { id } => Some(format!("https://player.vimeo.com/video/{}?autoplay=1&dnt=1", id)),
+            Embed::CodePen { user, id } => Some(format!(
+                "https://codepen.io/{}/embed/{}?default-tab=result",
+                user, id
+            )),
+            _ => None,
+        }
+    }
+}
+
+struct ParsedUrl<'a> {
+    host: &'a str,
+    path: &'a str,
+    query: &'a str,
+}
+
+fn parse_url(url: &str) -> Option<ParsedUrl<'_>> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let (without_fragment, _) = rest.split_once('#').unwrap_or((rest, ""));
+    let (authority_path, query) = without_fragment.split_once('?').unwrap_or((without_fragment, ""));
+    let (host, path) = match authority_path.find('/') {
+        Some(i) => (&authority_path[..i], &authority_path[i..]),
+        None => (authority_path, "/"),
+    };
+    if host.is_empty() {
+        return None;
+    }
+    Some(ParsedUrl {
+        host: host.trim_start_matches("www.").trim_start_matches("m."),
+        path,
+        query,
+    })
+}
+
+fn query_param<'a>(query: &'a str, key: &str) -> Option<&'a str> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(k, _)| *k == key)
+        .map(|(_, v)| v)
+}
+
+fn is_token(s: &str) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+}
+
+// YouTube 的 t/start 参数，支持 "90" 与 "1m30s"
+fn parse_start(value: &str) -> Option<u32> {
+    if let Ok(seconds) = value.trim_end_matches('s').parse() {
+        return Some(seconds);
+    }
+    let mut total = 0;
+    let mut number = 0;
+    for c in value.chars() {
+        match c {
+            '0'..='9' => number = number * 10 + c.to_digit(10)?,
+            'h' => { total += number * 3600; number = 0; }
+            'm' => { total += number * 60; number = 0; }
+            's' => { total += number; number = 0; }
+            _ => return None,
+        }
+    }
+    Some(total + number)
+}
+
+/// 识别 URL 对应的嵌入类型，非 http(s) 链接返回 None
+pub fn detect(url: &str) -> Option<Embed> {
+    let url = url.trim();
+    if url.chars().any(|c| c.is_whitespace() || c == '<' || c == '>' || c == '"') {
+        return None;
+    }
+    let parsed = parse_url(url)?;
+    let segments: Vec<&str> = parsed.path.split('/').filter(|s| !s.is_empty()).collect();
+
+    let embed = match parsed.host {
+        "youtube.com" | "music.youtube.com" => {
+            let start = query_param(parsed.query, "t")
+                .or_else(|| query_param(parsed.query, "start"))
+                .and_then(parse_start);
+            match segments.as_slice() {
+                ["watch"] => query_param(parsed.query, "v")
+                    .filter(|id| is_token(id))
+                    .map(|id| Embed::YouTube { id: id.to_string(), start }),
+                ["shorts", id] | ["embed", id] | ["live", id] if is_token(id) => {
+                    Some(Embed::YouTube { id: id.to_string(), start })
+                }
+                _ => None,
+            }
+        }
+        "youtu.be" => match segments.as_slice() {
+            [id] if is_token(id) => Some(Embed::YouTube {
+                id: id.to_string(),
+                start: query_param(parsed.query, "t").and_then(parse_start),
+            }),
+            _ => None,
+        },
+        "vimeo.com" => match segments.as_slice() {
+            [id] if id.chars().all(|c| c.is_ascii_digit()) => Some(Embed::Vimeo { id: id.to_string() }),
+            _ => None,
+        },
+        "codepen.io" => match segments.as_slice() {
+            [user, "pen", id] | [user, "full", id] | [user, "details", id] if is_token(user) && is_token(id) => {
+                Some(Embed::CodePen { user: user.to_string(), id: id.to_string() })
+            }
+            _ => None,
+        },
+        "gist.github.com" => match segments.as_slice() {
+            [user, id] if is_token(user) && is_token(id) => {
+                Some(Embed::Gist { user: user.to_string(), id: id.to_string() })
+            }
+            _ => None,
+        },
+        "github.com" => match segments.as_slice() {
+            [owner, repo] if is_token(owner) && is_token(repo) => Some(Embed::GitHubRepo {
+                owner: owner.to_string(),
+                repo: repo.trim_end_matches(".git").to_string(),
+            }),
+            _ => None,
+        },
+        _ => None,
+    };
+
+    Some(embed.unwrap_or(Embed::Link))
+}
+
+fn display_host(url: &str) -> String {
+    parse_url(url).map(|p| p.host.to_string()).unwrap_or_default()
+}
+
+/// 渲染嵌入卡片。第三方内容只有在读者点击后才会加载
+pub fn render_card(url: &str, embed: &Embed) -> String {
+    let href = escape_xml(url);
+    let provider = embed.provider();
+
+    match embed {
+        Embed::YouTube { .. } | Embed::Vimeo { .. } | Embed::CodePen { .. } => {
+            let src = embed.iframe_src().unwrap_or_default();
+            let title = match embed {
+                Embed::CodePen { user, id } => format!("{} / {}", user, id),
+                _ => format!("{} 视频", provider),
+            };
+            format!(
+                "<div class=\"embed-card embed-media\" data-embed-src=\"{src}\" data-embed-provider=\"{provider}\"><button type=\"button\" class=\"embed-load\"><span class=\"embed-play\" aria-hidden=\"true\">▶</span><span class=\"embed-title\">{title}</span><span class=\"embed-hint\">点击加载 {provider} 内容（将连接到第三方服务）</span></button><a class=\"embed-source\" href=\"{href}\" target=\"_blank\" rel=\"noopener noreferrer nofollow\">{href}</a></div>",
+                src = escape_xml(&src),
+                provider = provider,
+                title = escape_xml(&title),
+                href = href,
+            )
+        }
+        Embed::Gist { user, id } => {
+            // gist 只提供脚本嵌入，放进 srcdoc 沙箱中执行
+            let srcdoc = format!(
+                "<base target=\"_blank\"><script src=\"https://gist.github.com/{}/{}.js\"></script>",
+                user, id
+            );
+            format!(
+                "<div class=\"embed-card embed-media embed-gist\" data-embed-srcdoc=\"{srcdoc}\" data-embed-provider=\"{provider}\"><button type=\"button\" class=\"embed-load\"><span class=\"embed-play\" aria-hidden=\"true\">{{ }}</span><span class=\"embed-title\">{user} / {id}</span><span class=\"embed-hint\">点击加载 Gist 代码（将连接到 GitHub）</span></button><a class=\"embed-source\" href=\"{href}\" target=\"_blank\" rel=\"noopener noreferrer nofollow\">{href}</a></div>",
+                srcdoc = escape_xml(&srcdoc),
+                provider = provider,
+                user = escape_xml(user),
+                id = escape_xml(id),
+                href = href,
+            )
+        }
+        Embed::GitHubRepo { owner, repo } => format!(
+            "<a class=\"embed-card link-card\" href=\"{href}\" target=\"_blank\" rel=\"noopener noreferrer nofollow\" data-unfurl-url=\"{href}\"><span class=\"link-card-body\"><span class=\"link-card-site\">GitHub</span><span class=\"link-card-title\">{owner}/{repo}</span><span class=\"link-card-description\"></span></span></a>",
+            href = href,
+            owner = escape_xml(owner),
+            repo = escape_xml(repo),
+        ),
+        Embed::Link => format!(
+            "<a class=\"embed-card link-card\" href=\"{href}\" target=\"_blank\" rel=\"noopener noreferrer nofollow\" data-unfurl-url=\"{href}\"><span class=\"link-card-body\"><span class=\"link-card-site\">{host}</span><span class=\"link-card-title\">{href}</span><span class=\"link-card-description\"></span></span></a>",
+            href = href,
+            host = escape_xml(&display_host(url)),
+        ),
+    }
+}
+
+/// 独占一行的 URL 渲染为卡片；写成 `<URL>` 自动链接的保持为普通链接
+pub fn standalone_url(text: &str) -> Option<&str> {
+    let text = text.trim();
+    if text.starts_with("http://") || text.starts_with("https://") {
+        detect(text).map(|_| text)
+    } else {
+        None
+    }
+}
+
+/// 编辑器中列出正文里所有独占一行的链接，返回 (URL, 是否为卡片)
+pub fn standalone_links(markdown: &str) -> Vec<(String, bool)> {
+    let mut links = Vec::new();
+    let mut in_code_block = false;
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+        if let Some(url) = standalone_url(trimmed) {
+            links.push((url.to_string(), true));
+        } else if let Some(url) = trimmed.strip_prefix('<').and_then(|s| s.strip_suffix('>')) {
+            if standalone_url(url).is_some() {
+                links.push((url.to_string(), false));
+            }
+        }
+    }
+    links
+}
+
+/// 在卡片与普通链接之间切换某个独占一行的链接
+pub fn toggle_link_card(markdown: &str, url: &str, as_card: bool) -> String {
+    let mut in_code_block = false;
+    let lines: Vec<String> = markdown
+        .split('\n')
+        .map(|line| {
+            let trimmed = line.trim();
+            if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+                in_code_block = !in_code_block;
+                return line.to_string();
+            }
+            if in_code_block {
+                return line.to_string();
+            }
+            let angle = format!("<{}>", url);
+            if as_card && trimmed == angle {
+                line.replace(&angle, url)
+            } else if !as_card && trimmed == url {
+                line.replace(url, &angle)
+            } else {
+                line.to_string()
+            }
+        })
+        .collect();
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_video_providers() {
+        assert_eq!(
+            detect("https://www.youtube.com/watch?v=dQw4w9WgXcQ&t=1m30s"),
+            Some(Embed::YouTube { id: "dQw4w9WgXcQ".to_string(), start: Some(90) })
+        );
+        assert_eq!(
+            detect("https://youtu.be/dQw4w9WgXcQ?t=42"),
+            Some(Embed::YouTube { id: "dQw4w9WgXcQ".to_string(), start: Some(42) })
+        );
+        assert_eq!(
+            detect("https://m.youtube.com/shorts/abc_123"),
+            Some(Embed::YouTube { id: "abc_123".to_string(), start: None })
+        );
+        assert_eq!(detect("https://vimeo.com/76979871"), Some(Embed::Vimeo { id: "76979871".to_string() }));
+        assert_eq!(detect("https://vimeo.com/channels/staffpicks"), Some(Embed::Link));
+    }
+
+    #[test]
+    fn detects_code_providers() {
+        assert_eq!(
+            detect("https://codepen.io/alice/pen/xyzAbc"),
+            Some(Embed::CodePen { user: "alice".to_string(), id: "xyzAbc".to_string() })
+        );
+        assert_eq!(
+            detect("https://gist.github.com/alice/0123abcd"),
+            Some(Embed::Gist { user: "alice".to_string(), id: "0123abcd".to_string() })
+        );
+        assert_eq!(
+            detect("https://github.com/rust-lang/rust.git"),
+            Some(Embed::GitHubRepo { owner: "rust-lang".to_string(), repo: "rust".to_string() })
+        );
+        assert_eq!(detect("https://github.com/rust-lang/rust/issues/1"), Some(Embed::Link));
+    }
+
+    #[test]
+    fn rejects_non_http_and_unsafe_urls() {
+        assert_eq!(detect("ftp://example.com/file"), None);
+        assert_eq!(detect("javascript:alert(1)"), None);
+        assert_eq!(detect("https://example.com/\"onmouseover"), None);
+        assert_eq!(detect("https://example.com/post"), Some(Embed::Link));
+    }
+
+    #[test]
+    fn lists_only_standalone_links() {
+        let markdown = "看这个 https://example.com/inline 链接\n\nhttps://youtu.be/abc\n\n<https://example.com/plain>\n\n```\nhttps://example.com/code\n```";
+        assert_eq!(
+            standalone_links(markdown),
+            vec![
+                ("https://youtu.be/abc".to_string(), true),
+                ("https://example.com/plain".to_string(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn toggles_card_back_and_forth() {
+        let markdown = "开头\n\nhttps://youtu.be/abc\n\n```\nhttps://youtu.be/abc\n```";
+        let plain = toggle_link_card(markdown, "https://youtu.be/abc", false);
+        assert_eq!(plain, "开头\n\n<https://youtu.be/abc>\n\n```\nhttps://youtu.be/abc\n```");
+        assert_eq!(standalone_links(&plain), vec![("https://youtu.be/abc".to_string(), false)]);
+        assert_eq!(toggle_link_card(&plain, "https://youtu.be/abc", true), markdown);
+    }
+}
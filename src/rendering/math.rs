@@ -0,0 +1,834 @@
+// TeX 数学公式子集 -> MathML 转换（纯 Rust 实现，不依赖 JS 渲染库）
+
+const MAX_DEPTH: usize = 64;
+
+struct Node {
+    xml: String,
+    // 大型运算符（求和、极限等），在行间公式中上下标放在正上/正下方
+    limits: bool,
+}
+
+impl Node {
+    fn new(xml: String) -> Self {
+        Self { xml, limits: false }
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    display: bool,
+    depth: usize,
+}
+
+/// 将 TeX 源码转换为 MathML，解析失败时返回错误信息
+pub fn tex_to_mathml(tex: &str, display: bool) -> Result<String, String> {
+    let mut parser = Parser {
+        chars: tex.chars().collect(),
+        pos: 0,
+        display,
+        depth: 0,
+    };
+
+    let row = parser.parse_row()?;
+    parser.skip_whitespace();
+    if let Some(c) = parser.peek() {
+        return Err(format!("位置 {} 处有多余的字符 '{}'", parser.pos, c));
+    }
+
+    Ok(format!(
+        "<math xmlns=\"http://www.w3.org/1998/Math/MathML\" display=\"{}\"><semantics><mrow>{}</mrow><annotation encoding=\"application/x-tex\">{}</annotation></semantics></math>",
+        if display { "block" } else { "inline" },
+        row.concat(),
+        escape_xml(tex.trim())
+    ))
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(c) if c.is_whitespace()) {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.pos += 1;
+                Ok(())
+            }
+            Some(c) => Err(format!("期望 '{}'，实际为 '{}'", expected, c)),
+            None => Err(format!("缺少 '{}'", expected)),
+        }
+    }
+
+    // 行的结束位置：'}'、'&'、'\\'、\right、\end 或输入结束，不消费结束标记
+    fn at_row_end(&self) -> bool {
+        match self.peek() {
+            None | Some('}') | Some('&') => true,
+            Some('\\') => {
+                if self.peek_at(1) == Some('\\') {
+                    return true;
+                }
+                let name = self.command_name_at(self.pos + 1);
+                name == "right" || name == "end"
+            }
+            _ => false,
+        }
+    }
+
+    fn command_name_at(&self, start: usize) -> String {
+        self.chars[start.min(self.chars.len())..]
+            .iter()
+            .take_while(|c| c.is_ascii_alphabetic())
+            .collect()
+    }
+
+    // 行和原子都计入嵌套深度：\sqrt\sqrt… 这类命令不经过 parse_row 也会递归
+    fn enter(&mut self) -> Result<(), String> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err("公式嵌套过深".to_string());
+        }
+        Ok(())
+    }
+
+    fn parse_row(&mut self) -> Result<Vec<String>, String> {
+        self.enter()?;
+
+        let mut nodes = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.at_row_end() {
+                break;
+            }
+            let node = self.parse_scripted()?;
+            nodes.push(node.xml);
+        }
+
+        self.depth -= 1;
+        Ok(nodes)
+    }
+
+    // 解析一个原子及其上下标
+    fn parse_scripted(&mut self) -> Result<Node, String> {
+        let base = match self.peek() {
+            Some('^') | Some('_') => Node::new("<mrow></mrow>".to_string()),
+            _ => self.parse_atom()?,
+        };
+
+        let mut sub = None;
+        let mut sup = None;
+        loop {
+            self.skip_whitespace();
+            match self.peek() {
+                Some('_') if sub.is_none() => {
+                    self.pos += 1;
+                    sub = Some(self.parse_arg()?);
+                }
+                Some('^') if sup.is_none() => {
+                    self.pos += 1;
+                    sup = Some(self.parse_arg()?);
+                }
+                Some('\'') if sup.is_none() => {
+                    let mut primes = String::new();
+                    while self.peek() == Some('\'') {
+                        self.pos += 1;
+                        primes.push('′');
+                    }
+                    sup = Some(format!("<mo>{}</mo>", primes));
+                }
+                Some('_') | Some('^') => return Err("重复的上标或下标".to_string()),
+                _ => break,
+            }
+        }
+
+        let under_over = base.limits && self.display;
+        let xml = match (sub, sup) {
+            (None, None) => return Ok(base),
+            (Some(b), None) if under_over => format!("<munder>{}{}</munder>", base.xml, b),
+            (None, Some(p)) if under_over => format!("<mover>{}{}</mover>", base.xml, p),
+            (Some(b), Some(p)) if under_over => {
+                format!("<munderover>{}{}{}</munderover>", base.xml, b, p)
+            }
+            (Some(b), None) => format!("<msub>{}{}</msub>", base.xml, b),
+            (None, Some(p)) => format!("<msup>{}{}</msup>", base.xml, p),
+            (Some(b), Some(p)) => format!("<msubsup>{}{}{}</msubsup>", base.xml, b, p),
+        };
+        Ok(Node::new(xml))
+    }
+
+    // 命令参数：花括号分组或单个原子
+    fn parse_arg(&mut self) -> Result<String, String> {
+        self.skip_whitespace();
+        match self.peek() {
+            None => Err("缺少参数".to_string()),
+            Some('{') => self.parse_group(),
+            Some('}') | Some('&') | Some('^') | Some('_') => Err("缺少参数".to_string()),
+            _ => Ok(self.parse_atom()?.xml),
+        }
+    }
+
+    fn parse_group(&mut self) -> Result<String, String> {
+        self.expect('{')?;
+        let row = self.parse_row()?;
+        self.expect('}')?;
+        Ok(format!("<mrow>{}</mrow>", row.concat()))
+    }
+
+    // 读取花括号内的原始文本（用于 \text 等）
+    fn read_raw_group(&mut self) -> Result<String, String> {
+        self.expect('{')?;
+        let mut depth = 1;
+        let mut text = String::new();
+        while let Some(c) = self.peek() {
+            self.pos += 1;
+            match c {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Ok(text);
+                    }
+                }
+                _ => {}
+            }
+            text.push(c);
+        }
+        Err("缺少 '}'".to_string())
+    }
+
+    fn parse_atom(&mut self) -> Result<Node, String> {
+        self.enter()?;
+        let node = self.parse_atom_inner();
+        self.depth -= 1;
+        node
+    }
+
+    fn parse_atom_inner(&mut self) -> Result<Node, String> {
+        self.skip_whitespace();
+        let c = self.peek().ok_or_else(|| "公式意外结束".to_string())?;
+
+        match c {
+            '{' => Ok(Node::new(self.parse_group()?)),
+            '\\' => self.parse_command(),
+            c if c.is_ascii_digit() || (c == '.' && matches!(self.peek_at(1), Some(d) if d.is_ascii_digit())) => {
+                let mut number = String::new();
+                while let Some(d) = self.peek() {
+                    if d.is_ascii_digit() || d == '.' {
+                        number.push(d);
+                        self.pos += 1;
+                    } else {
+                        break;
+                    }
+                }
+                Ok(Node::new(format!("<mn>{}</mn>", number)))
+            }
+            c if c.is_alphabetic() => {
+                self.pos += 1;
+                Ok(Node::new(format!("<mi>{}</mi>", c)))
+            }
+            '+' | '=' | '<' | '>' | '(' | ')' | '[' | ']' | '|' | ',' | ';' | ':' | '!' | '/' | '*' | '?' | '.' => {
+                self.pos += 1;
+                let op = match c {
+                    '*' => "∗".to_string(),
+                    _ => escape_xml(&c.to_string()),
+                };
+                Ok(Node::new(format!("<mo>{}</mo>", op)))
+            }
+            '-' => {
+                self.pos += 1;
+                Ok(Node::new("<mo>−</mo>".to_string()))
+            }
+            '~' => {
+                self.pos += 1;
+                Ok(Node::new("<mspace width=\"0.333em\"></mspace>".to_string()))
+            }
+            '$' | '#' | '%' => Err(format!("不支持的字符 '{}'", c)),
+            c => {
+                self.pos += 1;
+                Ok(Node::new(format!("<mo>{}</mo>", escape_xml(&c.to_string()))))
+            }
+        }
+    }
+
+    fn parse_command(&mut self) -> Result<Node, String> {
+        // 跳过反斜杠
+        self.pos += 1;
+        let c = self.peek().ok_or_else(|| "反斜杠后缺少命令".to_string())?;
+
+        if !c.is_ascii_alphabetic() {
+            self.pos += 1;
+            let xml = match c {
+                '{' => "<mo>{</mo>".to_string(),
+                '}' => "<mo>}</mo>".to_string(),
+                '|' => "<mo>‖</mo>".to_string(),
+                ',' => "<mspace width=\"0.167em\"></mspace>".to_string(),
+                ':' | '>' => "<mspace width=\"0.222em\"></mspace>".to_string(),
+                ';' => "<mspace width=\"0.278em\"></mspace>".to_string(),
+                ' ' => "<mspace width=\"0.333em\"></mspace>".to_string(),
+                '!' => "<mspace width=\"-0.167em\"></mspace>".to_string(),
+                '%' | '$' | '#' | '_' => format!("<mo>{}</mo>", c),
+                '&' => "<mo>&amp;</mo>".to_string(),
+                _ => return Err(format!("未知命令 \\{}", c)),
+            };
+            return Ok(Node::new(xml));
+        }
+
+        let name = self.command_name_at(self.pos);
+        self.pos += name.chars().count();
+
+        match name.as_str() {
+            "frac" | "dfrac" | "tfrac" => {
+                let num = self.parse_arg()?;
+                let den = self.parse_arg()?;
+                Ok(Node::new(format!("<mfrac>{}{}</mfrac>", num, den)))
+            }
+            "binom" => {
+                let top = self.parse_arg()?;
+                let bottom = self.parse_arg()?;
+                Ok(Node::new(format!(
+                    "<mrow><mo>(</mo><mfrac linethickness=\"0\">{}{}</mfrac><mo>)</mo></mrow>",
+                    top, bottom
+                )))
+            }
+            "sqrt" => {
+                self.skip_whitespace();
+                if self.peek() == Some('[') {
+                    self.pos += 1;
+                    let mut index = Vec::new();
+                    loop {
+                        self.skip_whitespace();
+                        match self.peek() {
+                            Some(']') => {
+                                self.pos += 1;
+                                break;
+                            }
+                            None => return Err("缺少 ']'".to_string()),
+                            _ => index.push(self.parse_scripted()?.xml),
+                        }
+                    }
+                    let radicand = self.parse_arg()?;
+                    Ok(Node::new(format!(
+                        "<mroot>{}<mrow>{}</mrow></mroot>",
+                        radicand,
+                        index.concat()
+                    )))
+                } else {
+                    let radicand = self.parse_arg()?;
+                    Ok(Node::new(format!("<msqrt>{}</msqrt>", radicand)))
+                }
+            }
+            "text" | "textrm" | "textnormal" | "mbox" => {
+                let text = self.read_raw_group()?;
+                Ok(Node::new(format!("<mtext>{}</mtext>", escape_xml(&text))))
+            }
+            "mathrm" | "operatorname" => {
+                let text = self.read_raw_group()?;
+                Ok(Node::new(format!(
+                    "<mi mathvariant=\"normal\">{}</mi>",
+                    escape_xml(text.trim())
+                )))
+            }
+            "mathbf" | "boldsymbol" | "mathbb" | "mathcal" | "mathscr" | "mathit" => {
+                let start = self.pos;
+                let text = self.read_raw_group()?;
+                if text.chars().all(|c| c.is_ascii_alphanumeric() || c == ' ') {
+                    let styled: String = text
+                        .chars()
+                        .filter(|c| *c != ' ')
+                        .map(|c| styled_char(&name, c))
+                        .collect();
+                    Ok(Node::new(format!("<mi>{}</mi>", styled)))
+                } else {
+                    // 复杂参数退回为普通分组
+                    self.pos = start;
+                    Ok(Node::new(self.parse_group()?))
+                }
+            }
+            "left" => self.parse_fenced(),
+            "begin" => self.parse_environment(),
+            "hat" | "widehat" | "bar" | "overline" | "vec" | "tilde" | "widetilde" | "dot" | "ddot" => {
+                let accent = match name.as_str() {
+                    "hat" | "widehat" => "^",
+                    "bar" | "overline" => "¯",
+                    "vec" => "→",
+                    "tilde" | "widetilde" => "~",
+                    "dot" => "˙",
+                    _ => "¨",
+                };
+                let arg = self.parse_arg()?;
+                Ok(Node::new(format!(
+                    "<mover accent=\"true\">{}<mo stretchy=\"true\">{}</mo></mover>",
+                    arg, accent
+                )))
+            }
+            "underline" => {
+                let arg = self.parse_arg()?;
+                Ok(Node::new(format!(
+                    "<munder accentunder=\"true\">{}<mo stretchy=\"true\">_</mo></munder>",
+                    arg
+                )))
+            }
+            "quad" => Ok(Node::new("<mspace width=\"1em\"></mspace>".to_string())),
+            "qquad" => Ok(Node::new("<mspace width=\"2em\"></mspace>".to_string())),
+            "displaystyle" | "textstyle" | "limits" | "nolimits" => Ok(Node::new(String::new())),
+            _ => {
+                if let Some(op) = large_operator(&name) {
+                    return Ok(Node {
+                        xml: format!("<mo largeop=\"true\" movablelimits=\"true\">{}</mo>", op),
+                        limits: !name.contains("int"),
+                    });
+                }
+                if let Some(limit_fn) = limit_function(&name) {
+                    return Ok(Node {
+                        xml: format!("<mo movablelimits=\"true\">{}</mo>", limit_fn),
+                        limits: true,
+                    });
+                }
+                if FUNCTION_NAMES.contains(&name.as_str()) {
+                    return Ok(Node::new(format!("<mi>{}</mi>", name)));
+                }
+                if let Some(letter) = greek_letter(&name) {
+                    let variant = if letter.chars().next().map(|c| c.is_uppercase()).unwrap_or(false) {
+                        " mathvariant=\"normal\""
+                    } else {
+                        ""
+                    };
+                    return Ok(Node::new(format!("<mi{}>{}</mi>", variant, letter)));
+                }
+                if let Some(symbol) = symbol(&name) {
+                    let xml = if matches!(name.as_str(), "infty" | "partial" | "nabla" | "emptyset" | "varnothing" | "hbar" | "ell" | "aleph" | "Re" | "Im") {
+                        format!("<mi>{}</mi>", symbol)
+                    } else {
+                        format!("<mo>{}</mo>", escape_xml(symbol))
+                    };
+                    return Ok(Node::new(xml));
+                }
+                Err(format!("未知命令 \\{}", name))
+            }
+        }
+    }
+
+    fn parse_delimiter(&mut self) -> Result<String, String> {
+        self.skip_whitespace();
+        let c = self.peek().ok_or_else(|| "缺少定界符".to_string())?;
+        self.pos += 1;
+        match c {
+            '.' => Ok(String::new()),
+            '(' | ')' | '[' | ']' | '|' | '/' => Ok(c.to_string()),
+            '<' => Ok("⟨".to_string()),
+            '>' => Ok("⟩".to_string()),
+            '\\' => {
+                let next = self.peek().ok_or_else(|| "缺少定界符".to_string())?;
+                if !next.is_ascii_alphabetic() {
+                    self.pos += 1;
+                    return match next {
+                        '{' => Ok("{".to_string()),
+                        '}' => Ok("}".to_string()),
+                        '|' => Ok("‖".to_string()),
+                        _ => Err(format!("无效的定界符 \\{}", next)),
+                    };
+                }
+                let name = self.command_name_at(self.pos);
+                self.pos += name.chars().count();
+                match name.as_str() {
+                    "langle" => Ok("⟨".to_string()),
+                    "rangle" => Ok("⟩".to_string()),
+                    "lfloor" => Ok("⌊".to_string()),
+                    "rfloor" => Ok("⌋".to_string()),
+                    "lceil" => Ok("⌈".to_string()),
+                    "rceil" => Ok("⌉".to_string()),
+                    "lvert" | "rvert" | "vert" => Ok("|".to_string()),
+                    "lVert" | "rVert" | "Vert" => Ok("‖".to_string()),
+                    _ => Err(format!("无效的定界符 \\{}", name)),
+                }
+            }
+            _ => Err(format!("无效的定界符 '{}'", c)),
+        }
+    }
+
+    fn parse_fenced(&mut self) -> Result<Node, String> {
+        let open = self.parse_delimiter()?;
+        let body = self.parse_row()?;
+
+        if self.peek() != Some('\\') || self.command_name_at(self.pos + 1) != "right" {
+            return Err("\\left 缺少对应的 \\right".to_string());
+        }
+        self.pos += "\\right".len();
+        let close = self.parse_delimiter()?;
+
+        Ok(Node::new(format!(
+            "<mrow>{}{}{}</mrow>",
+            fence(&open),
+            body.concat(),
+            fence(&close)
+        )))
+    }
+
+    fn parse_environment(&mut self) -> Result<Node, String> {
+        let env = self.read_raw_group()?;
+        let env = env.trim().to_string();
+
+        let (open, close, align) = match env.as_str() {
+            "matrix" | "smallmatrix" => ("", "", "center"),
+            "pmatrix" => ("(", ")", "center"),
+            "bmatrix" => ("[", "]", "center"),
+            "Bmatrix" => ("{", "}", "center"),
+            "vmatrix" => ("|", "|", "center"),
+            "Vmatrix" => ("‖", "‖", "center"),
+            "cases" => ("{", "", "left"),
+            "aligned" | "align" | "align*" | "gathered" | "array" => ("", "", "left"),
+            _ => return Err(format!("不支持的环境 {}", env)),
+        };
+
+        // array 的列格式参数直接忽略
+        if env == "array" {
+            self.read_raw_group()?;
+        }
+
+        let mut rows: Vec<Vec<String>> = Vec::new();
+        let mut cells: Vec<String> = Vec::new();
+        loop {
+            let cell = self.parse_row()?;
+            cells.push(cell.concat());
+
+            match self.peek() {
+                Some('&') => {
+                    self.pos += 1;
+                }
+                Some('\\') if self.peek_at(1) == Some('\\') => {
+                    self.pos += 2;
+                    rows.push(std::mem::take(&mut cells));
+                }
+                Some('\\') if self.command_name_at(self.pos + 1) == "end" => {
+                    self.pos += "\\end".len();
+                    let end_env = self.read_raw_group()?;
+                    if end_env.trim() != env {
+                        return Err(format!("环境 {} 与 \\end{{{}}} 不匹配", env, end_env.trim()));
+                    }
+                    // 忽略末尾的空行
+                    if !(cells.len() == 1 && cells[0].is_empty()) {
+                        rows.push(std::mem::take(&mut cells));
+                    }
+                    break;
+                }
+                _ => return Err(format!("环境 {} 缺少 \\end", env)),
+            }
+        }
+
+        let table: String = rows
+            .iter()
+            .map(|row| {
+                let cells: String = row
+                    .iter()
+                    .map(|cell| format!("<mtd><mrow>{}</mrow></mtd>", cell))
+                    .collect();
+                format!("<mtr>{}</mtr>", cells)
+            })
+            .collect();
+
+        Ok(Node::new(format!(
+            "<mrow>{}<mtable columnalign=\"{}\">{}</mtable>{}</mrow>",
+            fence(open),
+            align,
+            table,
+            fence(close)
+        )))
+    }
+}
+
+fn fence(delimiter: &str) -> String {
+    if delimiter.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "<mo fence=\"true\" stretchy=\"true\">{}</mo>",
+            escape_xml(delimiter)
+        )
+    }
+}
+
+const FUNCTION_NAMES: &[&str] = &[
+    "sin", "cos", "tan", "cot", "sec", "csc", "sinh", "cosh", "tanh", "coth", "arcsin",
+    "arccos", "arctan", "log", "ln", "lg", "exp", "det", "dim", "ker", "deg", "arg", "gcd",
+    "hom", "Pr",
+];
+
+fn large_operator(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "sum" => "∑",
+        "prod" => "∏",
+        "coprod" => "∐",
+        "int" => "∫",
+        "iint" => "∬",
+        "iiint" => "∭",
+        "oint" => "∮",
+        "bigcup" => "⋃",
+        "bigcap" => "⋂",
+        "bigoplus" => "⨁",
+        "bigotimes" => "⨂",
+        "bigvee" => "⋁",
+        "bigwedge" => "⋀",
+        _ => return None,
+    })
+}
+
+fn limit_function(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "lim" => "lim",
+        "limsup" => "lim sup",
+        "liminf" => "lim inf",
+        "max" => "max",
+        "min" => "min",
+        "sup" => "sup",
+        "inf" => "inf",
+        "argmax" => "arg max",
+        "argmin" => "arg min",
+        _ => return None,
+    })
+}
+
+fn greek_letter(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "alpha" => "α",
+        "beta" => "β",
+        "gamma" => "γ",
+        "delta" => "δ",
+        "epsilon" => "ϵ",
+        "varepsilon" => "ε",
+        "zeta" => "ζ",
+        "eta" => "η",
+        "theta" => "θ",
+        "vartheta" => "ϑ",
+        "iota" => "ι",
+        "kappa" => "κ",
+        "lambda" => "λ",
+        "mu" => "μ",
+        "nu" => "ν",
+        "xi" => "ξ",
+        "pi" => "π",
+        "varpi" => "ϖ",
+        "rho" => "ρ",
+        "varrho" => "ϱ",
+        "sigma" => "σ",
+        "varsigma" => "ς",
+        "tau" => "τ",
+        "upsilon" => "υ",
+        "phi" => "ϕ",
+        "varphi" => "φ",
+        "chi" => "χ",
+        "psi" => "ψ",
+        "omega" => "ω",
+        "Gamma" => "Γ",
+        "Delta" => "Δ",
+        "Theta" => "Θ",
+        "Lambda" => "Λ",
+        "Xi" => "Ξ",
+        "Pi" => "Π",
+        "Sigma" => "Σ",
+        "Upsilon" => "Υ",
+        "Phi" => "Φ",
+        "Psi" => "Ψ",
+        "Omega" => "Ω",
+        _ => return None,
+    })
+}
+
+fn symbol(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "cdot" => "·",
+        "times" => "×",
+        "div" => "÷",
+        "pm" => "±",
+        "mp" => "∓",
+        "ast" => "∗",
+        "star" => "⋆",
+        "circ" => "∘",
+        "bullet" => "∙",
+        "oplus" => "⊕",
+        "otimes" => "⊗",
+        "leq" | "le" => "≤",
+        "geq" | "ge" => "≥",
+        "neq" | "ne" => "≠",
+        "ll" => "≪",
+        "gg" => "≫",
+        "approx" => "≈",
+        "equiv" => "≡",
+        "sim" => "∼",
+        "simeq" => "≃",
+        "cong" => "≅",
+        "propto" => "∝",
+        "infty" => "∞",
+        "partial" => "∂",
+        "nabla" => "∇",
+        "forall" => "∀",
+        "exists" => "∃",
+        "nexists" => "∄",
+        "in" => "∈",
+        "notin" => "∉",
+        "ni" => "∋",
+        "subset" => "⊂",
+        "subseteq" => "⊆",
+        "supset" => "⊃",
+        "supseteq" => "⊇",
+        "cup" => "∪",
+        "cap" => "∩",
+        "setminus" => "∖",
+        "emptyset" | "varnothing" => "∅",
+        "to" | "rightarrow" => "→",
+        "leftarrow" | "gets" => "←",
+        "leftrightarrow" => "↔",
+        "Rightarrow" => "⇒",
+        "Leftarrow" => "⇐",
+        "Leftrightarrow" => "⇔",
+        "implies" | "Longrightarrow" => "⟹",
+        "impliedby" | "Longleftarrow" => "⟸",
+        "iff" | "Longleftrightarrow" => "⟺",
+        "mapsto" => "↦",
+        "uparrow" => "↑",
+        "downarrow" => "↓",
+        "ldots" | "dots" => "…",
+        "cdots" => "⋯",
+        "vdots" => "⋮",
+        "ddots" => "⋱",
+        "wedge" | "land" => "∧",
+        "vee" | "lor" => "∨",
+        "neg" | "lnot" => "¬",
+        "angle" => "∠",
+        "perp" | "bot" => "⊥",
+        "top" => "⊤",
+        "parallel" => "∥",
+        "mid" => "∣",
+        "langle" => "⟨",
+        "rangle" => "⟩",
+        "lfloor" => "⌊",
+        "rfloor" => "⌋",
+        "lceil" => "⌈",
+        "rceil" => "⌉",
+        "prime" => "′",
+        "hbar" => "ℏ",
+        "ell" => "ℓ",
+        "Re" => "ℜ",
+        "Im" => "ℑ",
+        "aleph" => "ℵ",
+        "triangle" => "△",
+        "therefore" => "∴",
+        "because" => "∵",
+        "colon" => ":",
+        "vert" => "|",
+        "Vert" => "‖",
+        _ => return None,
+    })
+}
+
+// 将 ASCII 字母映射到 Unicode 数学字母区
+fn styled_char(style: &str, c: char) -> char {
+    let (upper, lower, digit): (u32, Option<u32>, Option<u32>) = match style {
+        "mathbf" | "boldsymbol" => (0x1D400, Some(0x1D41A), Some(0x1D7CE)),
+        "mathbb" => {
+            match c {
+                'C' => return 'ℂ',
+                'H' => return 'ℍ',
+                'N' => return 'ℕ',
+                'P' => return 'ℙ',
+                'Q' => return 'ℚ',
+                'R' => return 'ℝ',
+                'Z' => return 'ℤ',
+                _ => {}
+            }
+            (0x1D538, Some(0x1D552), Some(0x1D7D8))
+        }
+        "mathcal" | "mathscr" => {
+            match c {
+                'B' => return 'ℬ',
+                'E' => return 'ℰ',
+                'F' => return 'ℱ',
+                'H' => return 'ℋ',
+                'I' => return 'ℐ',
+                'L' => return 'ℒ',
+                'M' => return 'ℳ',
+                'R' => return 'ℛ',
+                _ => {}
+            }
+            (0x1D49C, None, None)
+        }
+        "mathit" => {
+            if c == 'h' {
+                return 'ℎ';
+            }
+            (0x1D434, Some(0x1D44E), None)
+        }
+        _ => return c,
+    };
+
+    let code = if c.is_ascii_uppercase() {
+        Some(upper + (c as u32 - 'A' as u32))
+    } else if c.is_ascii_lowercase() {
+        lower.map(|base| base + (c as u32 - 'a' as u32))
+    } else if c.is_ascii_digit() {
+        digit.map(|base| base + (c as u32 - '0' as u32))
+    } else {
+        None
+    };
+
+    code.and_then(char::from_u32).unwrap_or(c)
+}
+
+pub fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_fraction_and_superscript() {
+        let xml = tex_to_mathml(r"\frac{a}{b} + x^2", false).unwrap();
+        assert!(xml.contains("<mfrac><mrow><mi>a</mi></mrow><mrow><mi>b</mi></mrow></mfrac>"));
+        assert!(xml.contains("<msup><mi>x</mi><mn>2</mn></msup>"));
+        assert!(xml.contains("display=\"inline\""));
+    }
+
+    #[test]
+    fn escapes_annotation_source() {
+        let xml = tex_to_mathml("a < b", true).unwrap();
+        assert!(xml.contains("<annotation encoding=\"application/x-tex\">a &lt; b</annotation>"));
+    }
+
+    #[test]
+    fn rejects_unbalanced_group() {
+        assert!(tex_to_mathml(r"\frac{a}{b", false).is_err());
+        assert!(tex_to_mathml("a}", false).is_err());
+    }
+
+    #[test]
+    fn rejects_deeply_nested_commands() {
+        // 命令参数不经过 parse_row，也必须受深度限制
+        for prefix in [r"\sqrt ", r"\hat ", r"\frac{x}"] {
+            let tex = format!("{}x", prefix.repeat(10_000));
+            assert_eq!(tex_to_mathml(&tex, false), Err("公式嵌套过深".to_string()), "{}", prefix);
+        }
+        let tex = format!("{}x{}", "{".repeat(10_000), "}".repeat(10_000));
+        assert_eq!(tex_to_mathml(&tex, false), Err("公式嵌套过深".to_string()));
+    }
+
+    #[test]
+    fn accepts_moderate_nesting() {
+        let tex = format!("{}x", r"\sqrt ".repeat(8));
+        assert_eq!(tex_to_mathml(&tex, false).unwrap().matches("<msqrt>").count(), 8);
+    }
+}
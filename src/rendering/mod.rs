@@ -0,0 +1,395 @@
+// 文章内容渲染管线：编辑器预览与文章页共用，保证两处显示一致
+pub mod math;
+pub mod diagram;
+pub mod embed;
+pub mod toc;
+
+use math::escape_xml;
+use pulldown_cmark::{html, CodeBlockKind, CowStr, Event, Options, Parser, Tag, TagEnd};
+
+/// 将 Markdown 源码渲染为 HTML（编辑器预览使用）
+pub fn render_markdown(source: &str) -> String {
+    let mut options = Options::empty();
+    options.insert(Options::ENABLE_TABLES);
+    options.insert(Options::ENABLE_STRIKETHROUGH);
+    options.insert(Options::ENABLE_TASKLISTS);
+    options.insert(Options::ENABLE_MATH);
+
+    let mut events = Vec::new();
+    let mut diagram_source: Option<String> = None;
+
+    for event in Parser::new_ext(source, options) {
+        match event {
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(ref lang))) if is_diagram_lang(lang) => {
+                diagram_source = Some(String::new());
+            }
+            Event::End(TagEnd::CodeBlock) if diagram_source.is_some() => {
+                let source = diagram_source.take().unwrap_or_default();
+                events.push(Event::Html(CowStr::from(render_diagram_block(&source))));
+            }
+            Event::Text(text) if diagram_source.is_some() => {
+                if let Some(buffer) = diagram_source.as_mut() {
+                    buffer.push_str(&text);
+                }
+            }
+            Event::InlineMath(tex) => {
+                events.push(Event::InlineHtml(CowStr::from(render_math(&tex, false))));
+            }
+            Event::DisplayMath(tex) => {
+                events.push(Event::InlineHtml(CowStr::from(render_math(&tex, true))));
+            }
+            // 原始 HTML 按文本转义输出：草稿由多人协作编辑，预览中不能执行他人写入的脚本
+            Event::Html(raw) | Event::InlineHtml(raw) => events.push(Event::Text(raw)),
+            other => events.push(other),
+        }
+    }
+
+    let events = replace_link_paragraphs(events);
+
+    let mut output = String::with_capacity(source.len() * 3 / 2);
+    html::push_html(&mut output, events.into_iter());
+    output
+}
+
+// 只包含一个 URL 的段落替换为嵌入卡片
+fn replace_link_paragraphs(events: Vec<Event<'_>>) -> Vec<Event<'_>> {
+    let mut output = Vec::with_capacity(events.len());
+    let mut i = 0;
+    while i < events.len() {
+        if let Event::Start(Tag::Paragraph) = events[i] {
+            let mut text = String::new();
+            let mut j = i + 1;
+            while let Some(Event::Text(t)) = events.get(j) {
+                text.push_str(t);
+                j += 1;
+            }
+            if j > i + 1 && matches!(events.get(j), Some(Event::End(TagEnd::Paragraph))) {
+                if let Some(url) = embed::standalone_url(&text) {
+                    if let Some(kind) = embed::detect(url) {
+                        output.push(Event::Html(CowStr::from(embed::render_card(url, &kind))));
+                        i = j + 1;
+                        continue;
+                    }
+                }
+            }
+        }
+        output.push(events[i].clone());
+        i += 1;
+    }
+    output
+}
+
+/// 文章页使用：二次处理正文并为标题生成锚点和目录，mentions 为保存时确认存在的被提及用户名
+pub fn render_article(html: &str, mentions: &[String]) -> (String, Vec<toc::TocEntry>) {
+    toc::add_heading_anchors(&link_mentions(&enhance_html(html), mentions))
+}
+
+/// 把 HTML 文本中属于 mentions 的 @username 转为个人主页链接，其余 @ 原样保留（如 @media、npm 包名）；
+/// 标签属性以及链接、代码等元素内的文本不处理
+pub fn link_mentions(html: &str, mentions: &[String]) -> String {
+    const SKIP_TAGS: [&str; 8] = ["a", "pre", "code", "script", "style", "math", "svg", "textarea"];
+
+    let mut output = String::with_capacity(html.len());
+    let mut rest = html;
+    // 当前所在的需要跳过的元素
+    let mut skipping: Option<String> = None;
+
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            output.push_str(&if skipping.is_some() { rest.to_string() } else { link_text_mentions(rest, mentions) });
+            break;
+        };
+        let text = &rest[..tag_start];
+        output.push_str(&if skipping.is_some() { text.to_string() } else { link_text_mentions(text, mentions) });
+        rest = &rest[tag_start..];
+
+        let tag_end = rest.find('>').map(|i| i + 1).unwrap_or(rest.len());
+        let tag = &rest[..tag_end];
+        let closing = tag.starts_with("</");
+        let name: String = tag
+            .trim_start_matches('<')
+            .trim_start_matches('/')
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase();
+        match &skipping {
+            Some(open) if closing && *open == name => skipping = None,
+            None if !closing && !tag.ends_with("/>") && SKIP_TAGS.contains(&name.as_str()) => {
+                skipping = Some(name)
+            }
+            _ => {}
+        }
+        output.push_str(tag);
+        rest = &rest[tag_end..];
+    }
+
+    output
+}
+
+fn link_text_mentions(text: &str, mentions: &[String]) -> String {
+    if mentions.is_empty() || !text.contains('@') {
+        return text.to_string();
+    }
+    let chars: Vec<char> = text.chars().collect();
+    let mut output = String::with_capacity(text.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let boundary = i == 0 || !(chars[i - 1].is_ascii_alphanumeric() || chars[i - 1] == '_');
+        if chars[i] == '@' && boundary {
+            let name: String = chars[i + 1..]
+                .iter()
+                .take_while(|c| c.is_ascii_alphanumeric() || **c == '_')
+                .collect();
+            if mentions.iter().any(|m| m.eq_ignore_ascii_case(&name)) {
+                output.push_str(&format!("<a href=\"/@{0}\" class=\"mention\">@{0}</a>", name));
+                i += 1 + name.chars().count();
+                continue;
+            }
+        }
+        output.push(chars[i]);
+        i += 1;
+    }
+    output
+}
+
+/// 对后端返回的 content_html 做二次处理：公式转 MathML、图表代码块转 SVG、独立链接转嵌入卡片
+pub fn enhance_html(html: &str) -> String {
+    let mut output = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            output.push_str(&render_text_math(rest));
+            break;
+        };
+
+        output.push_str(&render_text_math(&rest[..tag_start]));
+        rest = &rest[tag_start..];
+
+        // 图表代码块
+        if let Some((lang_end, close)) = diagram_code_block(rest) {
+            if let Some(end) = rest.find(close) {
+                let source = decode_entities(&rest[lang_end..end]);
+                output.push_str(&render_diagram_block(&source));
+                rest = &rest[end + close.len()..];
+                continue;
+            }
+        }
+
+        // 只包含一个 URL 的段落
+        if let Some(body) = rest.strip_prefix("<p>") {
+            if let Some(end) = body.find("</p>") {
+                let text = decode_entities(&body[..end]);
+                if !body[..end].contains('<') {
+                    if let Some(url) = embed::standalone_url(&text) {
+                        if let Some(kind) = embed::detect(url) {
+                            output.push_str(&embed::render_card(url, &kind));
+                            rest = &body[end + "</p>".len()..];
+                            continue;
+                        }
+                    }
+                }
+            }
+        }
+
+        // 后端 Markdown 渲染器输出的公式占位元素
+        if let Some((open, display)) = [
+            ("<span class=\"math math-inline\">", false),
+            ("<span class=\"math math-display\">", true),
+        ]
+        .into_iter()
+        .find(|(open, _)| rest.starts_with(open))
+        {
+            if let Some(end) = rest.find("</span>") {
+                let tex = decode_entities(&rest[open.len()..end]);
+                output.push_str(&render_math(&tex, display));
+                rest = &rest[end + "</span>".len()..];
+                continue;
+            }
+        }
+
+        let tag_end = rest.find('>').map(|i| i + 1).unwrap_or(rest.len());
+        let tag = &rest[..tag_end];
+        let name: String = tag
+            .trim_start_matches('<')
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase();
+
+        // 代码、脚本等元素内部的文本原样保留
+        if matches!(name.as_str(), "pre" | "code" | "script" | "style" | "math" | "svg" | "textarea") && !tag.ends_with("/>") {
+            let close = format!("</{}", name);
+            if let Some(close_start) = rest[tag_end..].find(&close) {
+                let close_start = tag_end + close_start;
+                let close_end = rest[close_start..].find('>').map(|i| close_start + i + 1).unwrap_or(rest.len());
+                output.push_str(&rest[..close_end]);
+                rest = &rest[close_end..];
+                continue;
+            }
+        }
+
+        output.push_str(tag);
+        rest = &rest[tag_end..];
+    }
+
+    output
+}
+
+fn is_diagram_lang(lang: &str) -> bool {
+    matches!(lang.trim(), "mermaid" | "diagram")
+}
+
+// 匹配 <pre><code class="language-mermaid">，返回 (代码起始位置, 结束标记)
+fn diagram_code_block(html: &str) -> Option<(usize, &'static str)> {
+    ["<pre><code class=\"language-mermaid\">", "<pre><code class=\"language-diagram\">"]
+        .into_iter()
+        .find(|open| html.starts_with(open))
+        .map(|open| (open.len(), "</code></pre>"))
+}
+
+fn render_math(tex: &str, display: bool) -> String {
+    match math::tex_to_mathml(tex, display) {
+        Ok(mathml) => {
+            let class = if display { "math-display" } else { "math-inline" };
+            format!("<span class=\"{}\">{}</span>", class, mathml)
+        }
+        Err(err) => {
+            // 解析失败时回退为原始源码
+            let delimiter = if display { "$$" } else { "$" };
+            format!(
+                "<code class=\"math-error\" title=\"{}\">{}{}{}</code>",
+                escape_xml(&err),
+                delimiter,
+                escape_xml(tex),
+                delimiter
+            )
+        }
+    }
+}
+
+fn render_diagram_block(source: &str) -> String {
+    match diagram::render_diagram(source) {
+        Ok(svg) => format!("<figure class=\"diagram\">{}</figure>", svg),
+        Err(err) => format!(
+            "<pre class=\"diagram-error\" title=\"{}\"><code class=\"language-mermaid\">{}</code></pre>",
+            escape_xml(&err),
+            escape_xml(source)
+        ),
+    }
+}
+
+// 在已转义的 HTML 文本片段中查找 $...$ 与 $$...$$
+fn render_text_math(text: &str) -> String {
+    if !text.contains('$') {
+        return text.to_string();
+    }
+
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('$') {
+        // 转义的 \$ 原样输出
+        if start > 0 && rest.as_bytes()[start - 1] == b'\\' {
+            output.push_str(&rest[..start - 1]);
+            output.push('$');
+            rest = &rest[start + 1..];
+            continue;
+        }
+
+        output.push_str(&rest[..start]);
+        let after = &rest[start..];
+
+        if let Some(body) = after.strip_prefix("$$") {
+            if let Some(end) = body.find("$$") {
+                let tex = decode_entities(&body[..end]);
+                if !tex.trim().is_empty() {
+                    output.push_str(&render_math(&tex, true));
+                    rest = &body[end + 2..];
+                    continue;
+                }
+            }
+            output.push_str("$$");
+            rest = body;
+            continue;
+        }
+
+        let body = &after[1..];
+        if let Some(end) = find_inline_close(body) {
+            let tex = decode_entities(&body[..end]);
+            output.push_str(&render_math(&tex, false));
+            rest = &body[end + 1..];
+        } else {
+            output.push('$');
+            rest = body;
+        }
+    }
+
+    output.push_str(rest);
+    output
+}
+
+// 行内公式的结束位置：开头不能是空白，结尾前不能是空白，结尾后不能紧跟数字（避免 "$5 和 $10"）
+fn find_inline_close(body: &str) -> Option<usize> {
+    let first = body.chars().next()?;
+    if first.is_whitespace() || first == '$' {
+        return None;
+    }
+
+    let bytes = body.as_bytes();
+    let mut search_from = 0;
+    while let Some(offset) = body[search_from..].find('$') {
+        let end = search_from + offset;
+        if body[..end].contains("\n\n") {
+            return None;
+        }
+        let before = body[..end].chars().last();
+        let after = body[end + 1..].chars().next();
+        let escaped = end > 0 && bytes[end - 1] == b'\\';
+        if end > 0
+            && !escaped
+            && !before.map(|c| c.is_whitespace()).unwrap_or(true)
+            && !after.map(|c| c.is_ascii_digit()).unwrap_or(false)
+        {
+            return Some(end);
+        }
+        search_from = end + 1;
+    }
+    None
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#x27;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_raw_html_blocks() {
+        let html = render_markdown("<script>alert(1)</script>\n\n正文");
+        assert!(!html.contains("<script>"));
+        assert!(html.contains("&lt;script&gt;alert(1)&lt;/script&gt;"));
+    }
+
+    #[test]
+    fn escapes_inline_html() {
+        let html = render_markdown("图片 <img src=x onerror=alert(1)> 结束");
+        assert!(!html.contains("<img"));
+        assert!(html.contains("&lt;img src=x onerror=alert(1)&gt;"));
+    }
+
+    #[test]
+    fn keeps_generated_math_markup() {
+        let html = render_markdown("公式 $x^2$");
+        assert!(html.contains("<math"));
+    }
+}
@@ -0,0 +1,186 @@
+// 目录：从正文标题生成稳定的锚点与目录项
+
+use super::math::escape_xml;
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TocEntry {
+    pub level: u8,
+    pub id: String,
+    pub text: String,
+}
+
+/// 由标题文字生成锚点，保留中日韩等 Unicode 字母数字
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len());
+    let mut pending_dash = false;
+
+    for c in text.trim().chars() {
+        if c.is_alphanumeric() {
+            if pending_dash && !slug.is_empty() {
+                slug.push('-');
+            }
+            pending_dash = false;
+            slug.extend(c.to_lowercase());
+        } else if c.is_whitespace() || c == '-' || c == '_' {
+            pending_dash = true;
+        }
+    }
+
+    if slug.is_empty() {
+        "section".to_string()
+    } else {
+        slug
+    }
+}
+
+/// 为 h2-h4 标题补充 id 与可分享的锚点链接，返回处理后的 HTML 与目录
+pub fn add_heading_anchors(html: &str) -> (String, Vec<TocEntry>) {
+    let mut output = String::with_capacity(html.len() + 256);
+    let mut entries = Vec::new();
+    let mut used: HashSet<String> = HashSet::new();
+    let mut rest = html;
+
+    while let Some(start) = find_heading(rest) {
+        output.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let level = rest.as_bytes()[2] - b'0';
+        let close_tag = format!("</h{}>", level);
+        let (Some(open_end), Some(close_start)) = (rest.find('>'), rest.find(&close_tag)) else {
+            break;
+        };
+        if close_start < open_end {
+            output.push_str(&rest[..open_end + 1]);
+            rest = &rest[open_end + 1..];
+            continue;
+        }
+
+        let open_tag = &rest[..open_end + 1];
+        let inner = &rest[open_end + 1..close_start];
+        let text = strip_tags(inner);
+
+        let id = match attribute(open_tag, "id") {
+            Some(existing) => existing,
+            None => {
+                // 递增后缀直到未被使用，避免 "A 1" 与第二个 "A" 都得到 a-1
+                let base = slugify(&text);
+                let mut id = base.clone();
+                let mut suffix = 1;
+                while used.contains(&id) {
+                    id = format!("{}-{}", base, suffix);
+                    suffix += 1;
+                }
+                id
+            }
+        };
+        used.insert(id.clone());
+
+        let open_with_id = if attribute(open_tag, "id").is_some() {
+            open_tag.to_string()
+        } else {
+            format!("<h{} id=\"{}\"{}", level, escape_xml(&id), &open_tag[3..])
+        };
+
+        output.push_str(&open_with_id);
+        output.push_str(inner);
+        output.push_str(&format!(
+            "<a class=\"heading-anchor\" href=\"#{}\" aria-label=\"链接到此标题\">#</a>",
+            escape_xml(&id)
+        ));
+        output.push_str(&close_tag);
+
+        if !text.trim().is_empty() {
+            entries.push(TocEntry { level, id, text: text.trim().to_string() });
+        }
+        rest = &rest[close_start + close_tag.len()..];
+    }
+
+    output.push_str(rest);
+    (output, entries)
+}
+
+fn find_heading(html: &str) -> Option<usize> {
+    let bytes = html.as_bytes();
+    let mut from = 0;
+    while let Some(offset) = html[from..].find("<h") {
+        let i = from + offset;
+        if let (Some(level), Some(next)) = (bytes.get(i + 2), bytes.get(i + 3)) {
+            if (b'2'..=b'4').contains(level) && (*next == b'>' || *next == b' ') {
+                return Some(i);
+            }
+        }
+        from = i + 2;
+    }
+    None
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let pattern = format!(" {}=\"", name);
+    let start = tag.find(&pattern)? + pattern.len();
+    let end = tag[start..].find('"')?;
+    Some(tag[start..start + end].to_string())
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+    // 公式的 TeX 源码注解与锚点符号不计入标题文字
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let skip_until = if rest.starts_with("<annotation") {
+            "</annotation>"
+        } else if rest.starts_with("<a class=\"heading-anchor\"") {
+            "</a>"
+        } else {
+            ">"
+        };
+        match rest.find(skip_until) {
+            Some(end) => rest = &rest[end + skip_until.len()..],
+            None => rest = "",
+        }
+    }
+    text.push_str(rest);
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(html: &str) -> Vec<String> {
+        add_heading_anchors(html).1.into_iter().map(|e| e.id).collect()
+    }
+
+    #[test]
+    fn slugify_keeps_cjk_text() {
+        assert_eq!(slugify("第一章 开始"), "第一章-开始");
+        assert_eq!(slugify("  Rust 与 WebAssembly！ "), "rust-与-webassembly");
+        assert_eq!(slugify("日本語の見出し"), "日本語の見出し");
+        assert_eq!(slugify("？！"), "section");
+    }
+
+    #[test]
+    fn anchors_are_stable_across_renders() {
+        let html = "<h2>简介</h2><p>正文</p><h3>安装 步骤</h3>";
+        assert_eq!(ids(html), vec!["简介", "安装-步骤"]);
+        assert_eq!(add_heading_anchors(html), add_heading_anchors(html));
+    }
+
+    #[test]
+    fn deduplicates_colliding_ids() {
+        assert_eq!(ids("<h2>A</h2><h2>A 1</h2><h2>A</h2><h2>A</h2>"), vec!["a", "a-1", "a-2", "a-3"]);
+        assert_eq!(ids("<h2>总结</h2><h2>总结</h2>"), vec!["总结", "总结-1"]);
+    }
+
+    #[test]
+    fn keeps_existing_ids_reserved() {
+        assert_eq!(ids("<h2 id=\"intro\">Intro</h2><h2>Intro</h2>"), vec!["intro", "intro-1"]);
+    }
+}
@@ -0,0 +1,77 @@
+// Rainbow Blog service worker
+// - 应用外壳（页面、样式、wasm/js）缓存后离线可用
+// - 离线保存的文章图片从 OFFLINE_CACHE 中读取（由 src/hooks/use_offline.rs 写入）
+
+const SHELL_CACHE = 'rainbow-shell-v1';
+const OFFLINE_CACHE = 'rainbow-offline-v1';
+const SHELL_URLS = ['/', '/index.html', '/style.css', '/src/styles/prose.css'];
+
+self.addEventListener('install', (event) => {
+    event.waitUntil(
+        caches.open(SHELL_CACHE)
+            .then((cache) => cache.addAll(SHELL_URLS))
+            .then(() => self.skipWaiting())
+    );
+});
+
+self.addEventListener('activate', (event) => {
+    // 清理旧版本的外壳缓存，离线文章缓存保留
+    event.waitUntil(
+        caches.keys()
+            .then((keys) => Promise.all(
+                keys
+                    .filter((key) => key.startsWith('rainbow-shell-') && key !== SHELL_CACHE)
+                    .map((key) => caches.delete(key))
+            ))
+            .then(() => self.clients.claim())
+    );
+});
+
+self.addEventListener('fetch', (event) => {
+    const request = event.request;
+    if (request.method !== 'GET') {
+        return;
+    }
+    const url = new URL(request.url);
+
+    // 接口请求不缓存，离线时由应用自己读取已保存的文章
+    if (url.pathname.startsWith('/api/')) {
+        return;
+    }
+
+    // 页面导航：优先联网，离线时返回缓存的应用外壳，由前端路由显示对应页面
+    if (request.mode === 'navigate') {
+        event.respondWith(
+            fetch(request).catch(() => caches.match('/', { cacheName: SHELL_CACHE }))
+        );
+        return;
+    }
+
+    // 图片：优先使用离线保存的版本
+    if (request.destination === 'image') {
+        event.respondWith(
+            caches.match(request, { cacheName: OFFLINE_CACHE })
+                .then((cached) => cached || fetch(request))
+        );
+        return;
+    }
+
+    // 样式、脚本和 wasm：先返回缓存，同时在后台更新
+    if (['style', 'script', 'font'].includes(request.destination) || url.pathname.endsWith('.wasm')) {
+        event.respondWith(
+            caches.open(SHELL_CACHE).then((cache) =>
+                cache.match(request).then((cached) => {
+                    const network = fetch(request)
+                        .then((response) => {
+                            if (response.ok || response.type === 'opaque') {
+                                cache.put(request, response.clone());
+                            }
+                            return response;
+                        })
+                        .catch(() => cached);
+                    return cached || network;
+                })
+            )
+        );
+    }
+});